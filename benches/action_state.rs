@@ -1,10 +1,7 @@
 use bevy::prelude::Reflect;
 use criterion::{criterion_group, criterion_main, Criterion};
 use leafwing_input_manager::{
-    action_state::{ActionData, Timing},
-    buttonlike::ButtonState,
-    prelude::ActionState,
-    Actionlike,
+    action_state::ActionData, buttonlike::ButtonState, prelude::ActionState, Actionlike,
 };
 
 #[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
@@ -57,8 +54,11 @@ fn criterion_benchmark(c: &mut Criterion) {
             state: ButtonState::JustPressed,
             value: 0.0,
             axis_pair: None,
-            timing: Timing::default(),
+            cursor_position: None,
             consumed: false,
+            disabled: false,
+            latched: false,
+            chord_progress: (0, 0),
         })
         .collect();
 
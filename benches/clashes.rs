@@ -0,0 +1,62 @@
+use bevy::prelude::Reflect;
+use bevy::{input::InputPlugin, prelude::App};
+use criterion::{criterion_group, criterion_main, Criterion};
+use leafwing_input_manager::{
+    bench_utils::{clashing_input_map, clashing_keys, press_keys},
+    input_map::WhichPressedBuffer,
+    input_streams::InputStreams,
+    prelude::ClashStrategy,
+    Actionlike,
+};
+
+#[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+enum TestAction {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let input_map = clashing_input_map::<TestAction>();
+
+    // Pressing every key of the longest chord presses every action, so each possible clash occurs
+    let mut app = App::new();
+    app.add_plugins(InputPlugin);
+    press_keys(&mut app.world, clashing_keys::<TestAction>());
+    app.update();
+
+    let input_streams = InputStreams::from_world(&app.world, None);
+    let mut buffer = WhichPressedBuffer::default();
+    input_map.which_pressed_into(&input_streams, ClashStrategy::PressAll, &mut buffer);
+    let unresolved = buffer.action_data().to_vec();
+
+    let mut handle_clashes_group = c.benchmark_group("handle_clashes");
+    for clash_strategy in ClashStrategy::variants() {
+        handle_clashes_group.bench_function(format!("{:?}", clash_strategy), |b| {
+            b.iter(|| {
+                let mut action_data = unresolved.clone();
+                input_map.handle_clashes(&mut action_data, &input_streams, *clash_strategy);
+                action_data
+            })
+        });
+    }
+    handle_clashes_group.finish();
+
+    let mut which_pressed_group = c.benchmark_group("which_pressed_clashing");
+    for clash_strategy in ClashStrategy::variants() {
+        which_pressed_group.bench_function(format!("{:?}", clash_strategy), |b| {
+            b.iter(|| input_map.which_pressed_into(&input_streams, *clash_strategy, &mut buffer))
+        });
+    }
+    which_pressed_group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -6,6 +6,8 @@ use bevy::{
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use leafwing_input_manager::{
     action_state::ActionData,
+    bench_utils::{input_map_with_bindings, press_keys},
+    input_map::WhichPressedBuffer,
     input_streams::InputStreams,
     prelude::{ClashStrategy, InputMap, MockInput},
     Actionlike,
@@ -86,6 +88,32 @@ pub fn criterion_benchmark(c: &mut Criterion) {
         });
     }
     which_pressed_group.finish();
+
+    let mut which_pressed_into_group = c.benchmark_group("which_pressed_into");
+    let input_map = construct_input_map_from_iter();
+    let mut buffer = WhichPressedBuffer::default();
+    for clash_strategy in ClashStrategy::variants() {
+        which_pressed_into_group.bench_function(format!("{:?}", clash_strategy), |b| {
+            b.iter(|| input_map.which_pressed_into(&input_streams, *clash_strategy, &mut buffer))
+        });
+    }
+    which_pressed_into_group.finish();
+
+    // Every action has several bindings, only some of which are pressed
+    let mut many_bindings_group = c.benchmark_group("which_pressed_many_bindings");
+    for bindings_per_action in [1, 3, 8] {
+        let input_map = input_map_with_bindings::<TestAction>(bindings_per_action);
+        press_keys(&mut app.world, (0..TestAction::n_variants()).map(|i| i * 2));
+        let input_streams = InputStreams::from_world(&app.world, None);
+        let mut buffer = WhichPressedBuffer::default();
+
+        many_bindings_group.bench_function(format!("{bindings_per_action}"), |b| {
+            b.iter(|| {
+                input_map.which_pressed_into(&input_streams, ClashStrategy::PressAll, &mut buffer)
+            })
+        });
+    }
+    many_bindings_group.finish();
 }
 
 criterion_group!(benches, criterion_benchmark);
@@ -0,0 +1,93 @@
+use bevy::input::InputPlugin;
+use bevy::prelude::*;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use leafwing_input_manager::{
+    action_state::ActionDiff,
+    bench_utils::{input_map_with_bindings, press_keys, release_keys},
+    prelude::*,
+    systems::generate_action_diffs,
+};
+
+#[derive(Actionlike, Debug, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+enum TestAction {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+}
+
+/// A stable identifier for each entity, as used when sending [`ActionDiff`]s over the network
+#[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+struct StableId(u64);
+
+const BINDINGS_PER_ACTION: usize = 3;
+
+/// Creates an app with `n_entities` entities that each have an input map with several bindings per action
+fn app_with_entities(n_entities: u64) -> App {
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<TestAction>::default())
+        .add_event::<ActionDiff<TestAction, StableId>>()
+        .add_systems(PostUpdate, generate_action_diffs::<TestAction, StableId>);
+
+    let input_map = input_map_with_bindings::<TestAction>(BINDINGS_PER_ACTION);
+    for id in 0..n_entities {
+        app.world.spawn((
+            InputManagerBundle {
+                action_state: ActionState::default(),
+                input_map: input_map.clone(),
+            },
+            StableId(id),
+        ));
+    }
+    app.update();
+    app
+}
+
+/// The first key bound to each action, so that pressing them presses every action
+fn first_keys() -> impl Iterator<Item = usize> {
+    (0..TestAction::n_variants()).map(|index| index * BINDINGS_PER_ACTION)
+}
+
+pub fn criterion_benchmark(c: &mut Criterion) {
+    let mut update_group = c.benchmark_group("update_action_state");
+    for n_entities in [1, 100, 1000] {
+        let mut app = app_with_entities(n_entities);
+        press_keys(&mut app.world, first_keys());
+
+        update_group.bench_function(BenchmarkId::from_parameter(n_entities), |b| {
+            b.iter(|| app.update())
+        });
+    }
+    update_group.finish();
+
+    // Toggling the keys every frame makes every action just pressed or just released, producing a diff for each
+    let mut diff_group = c.benchmark_group("generate_action_diffs");
+    for n_entities in [1, 100, 1000] {
+        let mut app = app_with_entities(n_entities);
+        let mut pressed = false;
+
+        diff_group.bench_function(BenchmarkId::from_parameter(n_entities), |b| {
+            b.iter(|| {
+                if pressed {
+                    release_keys(&mut app.world, first_keys());
+                } else {
+                    press_keys(&mut app.world, first_keys());
+                }
+                pressed = !pressed;
+                app.update();
+            })
+        });
+    }
+    diff_group.finish();
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,43 @@
+//! Minimal descriptions of changes to an action's state, for replication over the network
+
+/// Stores presses and releases of actions without timing information
+///
+/// This is the engine-independent counterpart of `leafwing_input_manager::action_state::ActionDiff`.
+/// The action is identified by its index, and `ID` is a stable identifier for the player or entity that owns the action,
+/// such as an id shared by the client and the server.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ActionDiff<ID> {
+    /// The action was pressed
+    Pressed {
+        /// The index of the action
+        action: usize,
+        /// The stable identifier of the owner of the action
+        id: ID,
+    },
+    /// The action was released
+    Released {
+        /// The index of the action
+        action: usize,
+        /// The stable identifier of the owner of the action
+        id: ID,
+    },
+}
+
+impl<ID> ActionDiff<ID> {
+    /// The index of the action that changed
+    #[must_use]
+    pub fn action(&self) -> usize {
+        match self {
+            ActionDiff::Pressed { action, .. } | ActionDiff::Released { action, .. } => *action,
+        }
+    }
+
+    /// The stable identifier of the owner of the action that changed
+    #[must_use]
+    pub fn id(&self) -> &ID {
+        match self {
+            ActionDiff::Pressed { id, .. } | ActionDiff::Released { id, .. } => id,
+        }
+    }
+}
@@ -0,0 +1,277 @@
+//! The state of every action of a player or entity, identified by index
+
+use crate::action_diff::ActionDiff;
+use crate::button_state::ButtonState;
+use crate::timing::Timing;
+
+use alloc::vec::Vec;
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Metadata about an action, stored in an [`ActionState`]
+///
+/// This is the engine-independent counterpart of `leafwing_input_manager::action_state::ActionData`,
+/// without the data that depends on Bevy's math types.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionData {
+    /// Is the action pressed or released?
+    pub state: ButtonState,
+    /// The "value" of the binding that triggered the action
+    ///
+    /// See `leafwing_input_manager::action_state::ActionData::value` for how this is computed.
+    pub value: f32,
+    /// How long the action has been pressed or released
+    pub timing: Timing,
+    /// Was this action consumed?
+    ///
+    /// Consumed actions can not be pressed again until they are released.
+    pub consumed: bool,
+    /// Is the action disabled?
+    ///
+    /// Disabled actions can not be pressed until they are enabled again.
+    pub disabled: bool,
+}
+
+/// Stores the canonical state of every action for a single player or entity
+///
+/// This is the engine-independent counterpart of `leafwing_input_manager::action_state::ActionState`.
+/// Actions are identified by their index, which corresponds to `Actionlike::index` in `leafwing-input-manager`.
+/// Indices that are out of range are ignored by every method, so that actions from a newer client can not crash an older server.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ActionState {
+    /// The [`ActionData`] of each action, indexed by action
+    action_data: Vec<ActionData>,
+}
+
+impl ActionState {
+    /// Creates an [`ActionState`] with `n_actions` released actions
+    #[must_use]
+    pub fn new(n_actions: usize) -> Self {
+        Self::from_action_data(alloc::vec![ActionData::default(); n_actions])
+    }
+
+    /// Creates an [`ActionState`] from the [`ActionData`] of each action, indexed by action
+    #[must_use]
+    pub fn from_action_data(action_data: Vec<ActionData>) -> Self {
+        Self { action_data }
+    }
+
+    /// The number of actions stored in this [`ActionState`]
+    #[must_use]
+    pub fn n_actions(&self) -> usize {
+        self.action_data.len()
+    }
+
+    /// The [`ActionData`] of every action, indexed by action
+    #[must_use]
+    pub fn all_action_data(&self) -> &[ActionData] {
+        &self.action_data
+    }
+
+    /// The [`ActionData`] of the `action`, or `None` if it is out of range
+    #[must_use]
+    pub fn action_data(&self, action: usize) -> Option<&ActionData> {
+        self.action_data.get(action)
+    }
+
+    /// A mutable reference to the [`ActionData`] of the `action`, or `None` if it is out of range
+    #[must_use]
+    pub fn action_data_mut(&mut self, action: usize) -> Option<&mut ActionData> {
+        self.action_data.get_mut(action)
+    }
+
+    /// Advances the time of every action by `delta`, and causes `just_pressed` and `just_released` to become false
+    ///
+    /// This should be called once at the start of each tick, before new input is applied.
+    pub fn tick(&mut self, delta: Duration) {
+        for action_data in &mut self.action_data {
+            action_data.state.tick();
+            action_data.timing.advance(delta);
+        }
+    }
+
+    /// Measures the time of every action from the `current_instant` and the `previous_instant` of the last tick,
+    /// and causes `just_pressed` and `just_released` to become false
+    ///
+    /// This mirrors `leafwing_input_manager::action_state::ActionState::tick`.
+    #[cfg(feature = "std")]
+    pub fn tick_instant(&mut self, current_instant: Instant, previous_instant: Instant) {
+        for action_data in &mut self.action_data {
+            action_data.state.tick();
+            action_data.timing.tick(current_instant, previous_instant);
+        }
+    }
+
+    /// Presses the `action`
+    ///
+    /// Consumed actions can not be pressed until they are released,
+    /// and disabled actions can not be pressed until they are enabled.
+    pub fn press(&mut self, action: usize) {
+        let Some(action_data) = self.action_data.get_mut(action) else {
+            return;
+        };
+        if action_data.consumed || action_data.disabled {
+            return;
+        }
+        if action_data.state.released() {
+            action_data.timing.flip();
+        }
+        action_data.state.press();
+    }
+
+    /// Releases the `action`
+    ///
+    /// Once released, consumed actions can be pressed again.
+    pub fn release(&mut self, action: usize) {
+        let Some(action_data) = self.action_data.get_mut(action) else {
+            return;
+        };
+        action_data.consumed = false;
+        if action_data.state.pressed() {
+            action_data.timing.flip();
+        }
+        action_data.state.release();
+    }
+
+    /// Consumes the `action`, releasing it until it would otherwise have been released
+    pub fn consume(&mut self, action: usize) {
+        self.release(action);
+        if let Some(action_data) = self.action_data.get_mut(action) {
+            action_data.consumed = true;
+        }
+    }
+
+    /// Disables the `action`, releasing it until it is enabled again
+    pub fn disable(&mut self, action: usize) {
+        self.release(action);
+        if let Some(action_data) = self.action_data.get_mut(action) {
+            action_data.disabled = true;
+            action_data.value = 0.0;
+        }
+    }
+
+    /// Enables the `action`, so that it can be pressed again
+    pub fn enable(&mut self, action: usize) {
+        if let Some(action_data) = self.action_data.get_mut(action) {
+            action_data.disabled = false;
+        }
+    }
+
+    /// Applies a press or release that was replicated as an [`ActionDiff`]
+    ///
+    /// Returns `false`, without changing anything, if the index of its action is out of range.
+    pub fn apply_diff<ID>(&mut self, diff: &ActionDiff<ID>) -> bool {
+        if diff.action() >= self.n_actions() {
+            return false;
+        }
+        match diff {
+            ActionDiff::Pressed { action, .. } => self.press(*action),
+            ActionDiff::Released { action, .. } => self.release(*action),
+        }
+        true
+    }
+
+    /// Is the `action` currently pressed?
+    #[must_use]
+    pub fn pressed(&self, action: usize) -> bool {
+        self.action_data(action)
+            .is_some_and(|action_data| action_data.state.pressed())
+    }
+
+    /// Was the `action` pressed since the last tick?
+    #[must_use]
+    pub fn just_pressed(&self, action: usize) -> bool {
+        self.action_data(action)
+            .is_some_and(|action_data| action_data.state.just_pressed())
+    }
+
+    /// Is the `action` currently released?
+    ///
+    /// Actions that are out of range are always released.
+    #[must_use]
+    pub fn released(&self, action: usize) -> bool {
+        self.action_data(action)
+            .map_or(true, |action_data| action_data.state.released())
+    }
+
+    /// Was the `action` released since the last tick?
+    #[must_use]
+    pub fn just_released(&self, action: usize) -> bool {
+        self.action_data(action)
+            .is_some_and(|action_data| action_data.state.just_released())
+    }
+
+    /// The value of the `action`, or `0.0` if it is out of range
+    #[must_use]
+    pub fn value(&self, action: usize) -> f32 {
+        self.action_data(action)
+            .map_or(0.0, |action_data| action_data.value)
+    }
+
+    /// How long the `action` has been pressed or released
+    #[must_use]
+    pub fn current_duration(&self, action: usize) -> Duration {
+        self.action_data(action)
+            .map_or(Duration::ZERO, |action_data| {
+                action_data.timing.current_duration
+            })
+    }
+
+    /// How long the `action` was pressed or released before its state last changed
+    #[must_use]
+    pub fn previous_duration(&self, action: usize) -> Duration {
+        self.action_data(action)
+            .map_or(Duration::ZERO, |action_data| {
+                action_data.timing.previous_duration
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::action_diff::ActionDiff;
+    use crate::action_state::ActionState;
+    use core::time::Duration;
+
+    #[test]
+    fn press_lifecycle() {
+        let mut action_state = ActionState::new(2);
+        let delta = Duration::from_millis(10);
+
+        action_state.press(0);
+        assert!(action_state.just_pressed(0));
+        assert!(action_state.released(1));
+
+        action_state.tick(delta);
+        action_state.tick(delta);
+        assert!(action_state.pressed(0));
+        assert!(!action_state.just_pressed(0));
+        assert_eq!(action_state.current_duration(0), 2 * delta);
+
+        action_state.release(0);
+        assert!(action_state.just_released(0));
+        assert_eq!(action_state.current_duration(0), Duration::ZERO);
+        assert_eq!(action_state.previous_duration(0), 2 * delta);
+
+        // Consumed actions stay released until they are released again
+        action_state.press(1);
+        action_state.consume(1);
+        action_state.press(1);
+        assert!(action_state.released(1));
+    }
+
+    #[test]
+    fn diffs_out_of_range_are_ignored() {
+        let mut action_state = ActionState::new(1);
+
+        assert!(action_state.apply_diff(&ActionDiff::Pressed { action: 0, id: 7 }));
+        assert!(action_state.pressed(0));
+        assert!(!action_state.apply_diff(&ActionDiff::Pressed { action: 1, id: 7 }));
+        assert!(!action_state.pressed(1));
+        assert!(action_state.apply_diff(&ActionDiff::Released { action: 0, id: 7 }));
+        assert!(action_state.just_released(0));
+    }
+}
@@ -0,0 +1,78 @@
+//! The pressed or released state of a single action
+
+/// The current state of a particular button, usually corresponding to a single action
+///
+/// This is the engine-independent counterpart of `leafwing_input_manager::buttonlike::ButtonState`.
+/// By default, buttons are [`ButtonState::Released`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ButtonState {
+    /// The button was pressed since the most recent tick
+    JustPressed,
+    /// This button is currently pressed (and was pressed before the most recent tick)
+    Pressed,
+    /// The button was released since the most recent tick
+    JustReleased,
+    /// This button is currently released (and was released before the most recent tick)
+    #[default]
+    Released,
+}
+
+impl ButtonState {
+    /// Causes [`just_pressed`](ButtonState::just_pressed) and [`just_released`](ButtonState::just_released) to become false
+    pub fn tick(&mut self) {
+        use ButtonState::*;
+        *self = match self {
+            JustPressed | Pressed => Pressed,
+            JustReleased | Released => Released,
+        }
+    }
+
+    /// Presses the button
+    ///
+    /// It will be [`JustPressed`](ButtonState::JustPressed), unless it was already [`Pressed`](ButtonState::Pressed)
+    #[inline]
+    pub fn press(&mut self) {
+        if *self != ButtonState::Pressed {
+            *self = ButtonState::JustPressed;
+        }
+    }
+
+    /// Releases the button
+    ///
+    /// It will be [`JustReleased`](ButtonState::JustReleased), unless it was already [`Released`](ButtonState::Released)
+    #[inline]
+    pub fn release(&mut self) {
+        if *self != ButtonState::Released {
+            *self = ButtonState::JustReleased;
+        }
+    }
+
+    /// Is the button currently pressed?
+    #[inline]
+    #[must_use]
+    pub fn pressed(&self) -> bool {
+        matches!(self, ButtonState::Pressed | ButtonState::JustPressed)
+    }
+
+    /// Is the button currently released?
+    #[inline]
+    #[must_use]
+    pub fn released(&self) -> bool {
+        matches!(self, ButtonState::Released | ButtonState::JustReleased)
+    }
+
+    /// Was the button pressed since the most recent tick?
+    #[inline]
+    #[must_use]
+    pub fn just_pressed(&self) -> bool {
+        *self == ButtonState::JustPressed
+    }
+
+    /// Was the button released since the most recent tick?
+    #[inline]
+    #[must_use]
+    pub fn just_released(&self) -> bool {
+        *self == ButtonState::JustReleased
+    }
+}
@@ -0,0 +1,29 @@
+//! The engine-independent action model of `leafwing-input-manager`
+//!
+//! This crate contains the state of each action, how long it has been pressed or released,
+//! and the diffs used to replicate its presses and releases, without depending on Bevy or the standard library.
+//! Dedicated servers and tools can use it to exchange actions with a game,
+//! and convert to and from the full types of `leafwing-input-manager` at the boundary.
+//!
+//! Actions are identified by their index, which corresponds to `Actionlike::index` in `leafwing-input-manager`.
+//!
+//! All features are disabled by default:
+//! - `std` links the standard library, and measures [`Timing`] from `Instant`s
+//! - `serde` implements `Serialize` and `Deserialize`
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![forbid(missing_docs)]
+#![forbid(unsafe_code)]
+#![warn(clippy::doc_markdown)]
+
+extern crate alloc;
+
+pub mod action_diff;
+pub mod action_state;
+pub mod button_state;
+pub mod timing;
+
+pub use action_diff::ActionDiff;
+pub use action_state::{ActionData, ActionState};
+pub use button_state::ButtonState;
+pub use timing::Timing;
@@ -0,0 +1,54 @@
+//! Tracking how long an action has been pressed or released
+
+use core::time::Duration;
+#[cfg(feature = "std")]
+use std::time::Instant;
+
+/// Stores how long an action has been pressed or released
+///
+/// This is the engine-independent counterpart of `leafwing_input_manager::timing::Timing`.
+/// Without the `std` feature, there is no clock to read, so the time that passed during each tick is supplied to [`Timing::advance`].
+/// With the `std` feature, [`Timing::tick`] measures it from the [`Instant`] of each tick instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Timing {
+    /// The [`Instant`] at which the action started being pressed or released, as of the start of the tick after it changed
+    ///
+    /// If this is none, [`Timing::tick`] has not been called since the action last changed.
+    #[cfg(feature = "std")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub instant_started: Option<Instant>,
+    /// The [`Duration`] for which the action has been pressed or released
+    pub current_duration: Duration,
+    /// The [`Duration`] for which the action was pressed or released before its state last changed
+    pub previous_duration: Duration,
+}
+
+impl Timing {
+    /// Advances the `current_duration` by the `delta` that passed during a tick
+    pub fn advance(&mut self, delta: Duration) {
+        self.current_duration = self.current_duration.saturating_add(delta);
+    }
+
+    /// Advances the `current_duration` to the time between the `instant_started` and the `current_instant`
+    ///
+    /// If the `instant_started` is none, it is set to the `previous_instant`,
+    /// so that the timing is always synchronized with the start of each tick.
+    #[cfg(feature = "std")]
+    pub fn tick(&mut self, current_instant: Instant, previous_instant: Instant) {
+        let instant_started = *self.instant_started.get_or_insert(previous_instant);
+        self.current_duration = current_instant.saturating_duration_since(instant_started);
+    }
+
+    /// Flips the metaphorical hourglass, storing `current_duration` in `previous_duration` and restarting the `current_duration`
+    ///
+    /// This should be called whenever the action is pressed or released.
+    pub fn flip(&mut self) {
+        self.previous_duration = self.current_duration;
+        self.current_duration = Duration::ZERO;
+        #[cfg(feature = "std")]
+        {
+            self.instant_started = None;
+        }
+    }
+}
@@ -14,7 +14,9 @@ use bevy_egui::{
     EguiContexts, EguiPlugin,
 };
 use derive_more::Display;
-use leafwing_input_manager::{prelude::*, user_input::InputKind};
+use leafwing_input_manager::{
+    binding_conflicts::ConflictSeverity, prelude::*, user_input::InputKind,
+};
 
 const UI_MARGIN: f32 = 10.0;
 
@@ -161,15 +163,13 @@ fn binding_window_system(
                 if ui_action_state.just_pressed(UiAction::Back) {
                     commands.remove_resource::<ActiveBinding>();
                 } else if let Some(input_button) = input_events.input_button() {
-                    let conflict_action =
-                        control_settings.input.iter().find_map(|(inputs, action)| {
-                            if action != active_binding.action
-                                && inputs.contains(&input_button.into())
-                            {
-                                return Some(action);
-                            }
-                            None
-                        });
+                    // Intentional overlaps, such as chords, are allowed without asking
+                    let conflict_action = control_settings
+                        .input
+                        .binding_conflicts(input_button, active_binding.action)
+                        .into_iter()
+                        .find(|conflict| conflict.kind.severity() == ConflictSeverity::Blocking)
+                        .map(|conflict| conflict.action);
                     if let Some(action) = conflict_action {
                         active_binding.conflict.replace(BindingConflict {
                             action,
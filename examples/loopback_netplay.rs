@@ -0,0 +1,42 @@
+//! Before wiring up a real network layer, the replication of actions can be checked in a single process.
+//!
+//! The [`NetplayHarness`] runs a client and a host [`App`] side by side.
+//! The client generates [`ActionDiff`](leafwing_input_manager::action_state::ActionDiff)s from its inputs,
+//! and sends them to the host over a loopback channel, which applies them to its own copy of the player.
+//!
+//! To test your own networking setup, add your game's systems to both apps with
+//! [`NetplayHarness::client_mut`] and [`NetplayHarness::host_mut`].
+
+use bevy::prelude::*;
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::testing::NetplayHarness;
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+enum FpsAction {
+    Jump,
+    Shoot,
+}
+
+fn main() {
+    let mut netplay = NetplayHarness::new(
+        InputMap::new([(KeyCode::Space, FpsAction::Jump)])
+            .insert(MouseButton::Left, FpsAction::Shoot)
+            .build(),
+    );
+
+    // Inputs are pressed on the client, and replicated to the host in the same update
+    netplay.press_input(KeyCode::Space).update();
+    assert!(netplay.client_action_state().pressed(FpsAction::Jump));
+    assert!(netplay.host_action_state().just_pressed(FpsAction::Jump));
+
+    netplay
+        .press_input(MouseButton::Left)
+        .advance_frames(10)
+        .assert_in_sync();
+    assert!(netplay.host_action_state().pressed(FpsAction::Shoot));
+
+    // Releases are replicated too
+    netplay.reset_inputs().update().assert_in_sync();
+    assert!(netplay.host_action_state().released(FpsAction::Jump));
+    assert!(netplay.host_action_state().released(FpsAction::Shoot));
+}
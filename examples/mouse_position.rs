@@ -39,10 +39,9 @@ fn setup(mut commands: Commands, window: Query<Entity, With<PrimaryWindow>>) {
         // Note: another entity will be driving this input
         .id();
 
-    commands.entity(window.single()).insert(ActionStateDriver {
-        action: BoxMovement::MousePosition,
-        targets: entity.into(),
-    });
+    commands
+        .entity(window.single())
+        .insert(ActionStateDriver::new(BoxMovement::MousePosition, entity));
 }
 
 fn update_cursor_state_from_window(
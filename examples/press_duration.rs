@@ -82,19 +82,30 @@ fn spawn_camera(mut commands: Commands) {
 }
 
 /// The longer you hold, the faster you dash when released!
-fn hold_dash(mut player_query: Query<(&ActionState<Action>, &mut Velocity), With<Player>>) {
+fn hold_dash(
+    mut player_query: Query<
+        (&ActionState<Action>, &ActionTimings<Action>, &mut Velocity),
+        With<Player>,
+    >,
+) {
     const VELOCITY_RATIO: f32 = 1000.0;
 
-    let (action_state, mut velocity) = player_query.single_mut();
+    let (action_state, timings, mut velocity) = player_query.single_mut();
 
     if action_state.just_released(Action::Left) {
         // Accelerate left
-        velocity.x -= VELOCITY_RATIO * action_state.previous_duration(Action::Left).as_secs_f32();
+        velocity.x -= VELOCITY_RATIO
+            * timings
+                .previous_duration(action_state, Action::Left)
+                .as_secs_f32();
     }
 
     if action_state.just_released(Action::Right) {
         // Accelerate right
-        velocity.x += VELOCITY_RATIO * action_state.previous_duration(Action::Right).as_secs_f32();
+        velocity.x += VELOCITY_RATIO
+            * timings
+                .previous_duration(action_state, Action::Right)
+                .as_secs_f32();
     }
 }
 
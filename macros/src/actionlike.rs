@@ -2,12 +2,11 @@ use proc_macro2::Span;
 use proc_macro2::TokenStream;
 use proc_macro_crate::{crate_name, FoundCrate};
 use quote::quote;
-use syn::{Data, DeriveInput, Ident};
+use syn::{Data, DeriveInput, Ident, LitInt, LitStr, Variant};
 
 /// This approach and implementation is inspired by the `strum` crate,
 /// Copyright (c) 2019 Peter Glotfelty
 /// available under the MIT License at <https://github.com/Peternator7/strum>
-
 pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
     // Splitting the abstract syntax tree
     let enum_name = &ast.ident;
@@ -42,6 +41,7 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
     // Populate the array
     let mut get_at_match_items = Vec::new();
     let mut index_match_items = Vec::new();
+    let mut info_match_items = Vec::new();
 
     for (index, variant) in variants.iter().enumerate() {
         // The name of the enum variant
@@ -92,11 +92,40 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
         index_match_items.push(quote! {
             #enum_name::#variant_identifier #index_params => #index,
         });
+
+        let info = match variant_info(variant) {
+            Ok(info) => info,
+            Err(error) => return error.to_compile_error(),
+        };
+        let VariantInfo {
+            name,
+            description,
+            category,
+            sort_order,
+        } = info;
+        let description = optional_string(description);
+        let category = optional_string(category);
+
+        info_match_items.push(quote! {
+            #enum_name::#variant_identifier #index_params => #crate_path::action_metadata::ActionInfo {
+                name: ::std::string::String::from(#name),
+                description: #description,
+                category: #category,
+                sort_order: #sort_order,
+            },
+        });
     }
 
     let n_variants = variants.iter().len();
 
+    let too_many_actions = format!(
+        "`{enum_name}` has {n_variants} variants, but `Actionlike` types can have at most `leafwing_input_manager::MAX_ACTIONS`"
+    );
+
     quote! {
+        // Action indices must fit in the encoding used for networking
+        const _: () = ::core::assert!(#n_variants <= #crate_path::MAX_ACTIONS, #too_many_actions);
+
         impl #impl_generics #crate_path::Actionlike for #enum_name #type_generics #where_clause {
             fn n_variants() -> usize {
                 #n_variants
@@ -115,6 +144,64 @@ pub(crate) fn actionlike_inner(ast: &DeriveInput) -> TokenStream {
                     _ => unreachable!()
                 }
             }
+
+            fn info(&self) -> #crate_path::action_metadata::ActionInfo {
+                match self {
+                    #(#info_match_items)*
+                    _ => unreachable!()
+                }
+            }
+        }
+    }
+}
+
+/// The metadata declared for a single variant using the `#[actionlike(...)]` attribute
+struct VariantInfo {
+    name: String,
+    description: Option<String>,
+    category: Option<String>,
+    sort_order: i32,
+}
+
+/// Parses the `#[actionlike(name = "..", description = "..", category = "..", order = ..)]` attributes of a variant
+fn variant_info(variant: &Variant) -> syn::Result<VariantInfo> {
+    let mut info = VariantInfo {
+        name: variant.ident.to_string(),
+        description: None,
+        category: None,
+        sort_order: 0,
+    };
+
+    for attribute in variant.attrs.iter() {
+        if !attribute.path().is_ident("actionlike") {
+            continue;
         }
+
+        attribute.parse_nested_meta(|meta| {
+            if meta.path.is_ident("name") {
+                info.name = meta.value()?.parse::<LitStr>()?.value();
+            } else if meta.path.is_ident("description") {
+                info.description = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("category") {
+                info.category = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("order") {
+                info.sort_order = meta.value()?.parse::<LitInt>()?.base10_parse()?;
+            } else {
+                return Err(meta.error(
+                    "unsupported `actionlike` attribute, expected `name`, `description`, `category` or `order`",
+                ));
+            }
+            Ok(())
+        })?;
+    }
+
+    Ok(info)
+}
+
+/// Converts an optional string into tokens that construct an `Option<String>`
+fn optional_string(value: Option<String>) -> TokenStream {
+    match value {
+        Some(value) => quote!(::core::option::Option::Some(::std::string::String::from(#value))),
+        None => quote!(::core::option::Option::None),
     }
 }
@@ -0,0 +1,149 @@
+//! Callbacks that run when specific actions are pressed or released
+//!
+//! Rather than writing a polling system for each action, hooks can be registered in an [`ActionHooks`]
+//! resource or component, stored alongside the [`ActionState`](crate::action_state::ActionState) that they watch.
+//! The [`run_action_hooks`](crate::systems::run_action_hooks) system calls each hook with access to [`Commands`]
+//! during the frame in which its action transitions.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::action_hooks::ActionHooks;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Jumping;
+//!
+//! let mut hooks = ActionHooks::<Action>::default();
+//! hooks
+//!     .on_pressed(Action::Jump, |context, commands| {
+//!         if let Some(entity) = context.entity {
+//!             commands.entity(entity).insert(Jumping);
+//!         }
+//!     })
+//!     .on_released(Action::Jump, |context, commands| {
+//!         if let Some(entity) = context.entity {
+//!             commands.entity(entity).remove::<Jumping>();
+//!         }
+//!     });
+//!
+//! let mut world = World::new();
+//! world.spawn((InputManagerBundle::<Action>::default(), hooks));
+//! ```
+
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Commands, Component, Entity, Resource};
+use std::fmt::Debug;
+
+/// The kind of state change that triggers a hook
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ActionTransition {
+    /// The action was just pressed
+    Pressed,
+    /// The action was just released
+    Released,
+}
+
+/// Information about the transition that triggered a hook
+#[derive(Debug, Clone, PartialEq)]
+pub struct ActionHookContext<A: Actionlike> {
+    /// The action that changed state
+    pub action: A,
+    /// How the action changed state
+    pub transition: ActionTransition,
+    /// The entity whose [`ActionState`](crate::action_state::ActionState) changed,
+    /// or [`None`] if the [`ActionState`](crate::action_state::ActionState) is a resource
+    pub entity: Option<Entity>,
+}
+
+/// A callback registered in [`ActionHooks`]
+pub type ActionHook<A> = Box<dyn Fn(&ActionHookContext<A>, &mut Commands) + Send + Sync>;
+
+/// Callbacks that run when actions of type `A` are pressed or released
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`](crate::action_state::ActionState) that it watches.
+/// Hooks are run by the [`run_action_hooks`](crate::systems::run_action_hooks) system, in the order that they were registered.
+#[derive(Resource, Component)]
+pub struct ActionHooks<A: Actionlike> {
+    hooks: Vec<(A, ActionTransition, ActionHook<A>)>,
+}
+
+impl<A: Actionlike> Default for ActionHooks<A> {
+    fn default() -> Self {
+        ActionHooks { hooks: Vec::new() }
+    }
+}
+
+impl<A: Actionlike + Debug> Debug for ActionHooks<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_list()
+            .entries(
+                self.hooks
+                    .iter()
+                    .map(|(action, transition, _)| (action, transition)),
+            )
+            .finish()
+    }
+}
+
+impl<A: Actionlike> ActionHooks<A> {
+    /// Registers a `hook` that runs whenever the `action` makes the provided `transition`
+    pub fn on(
+        &mut self,
+        action: A,
+        transition: ActionTransition,
+        hook: impl Fn(&ActionHookContext<A>, &mut Commands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.hooks.push((action, transition, Box::new(hook)));
+        self
+    }
+
+    /// Registers a `hook` that runs whenever the `action` is just pressed
+    pub fn on_pressed(
+        &mut self,
+        action: A,
+        hook: impl Fn(&ActionHookContext<A>, &mut Commands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on(action, ActionTransition::Pressed, hook)
+    }
+
+    /// Registers a `hook` that runs whenever the `action` is just released
+    pub fn on_released(
+        &mut self,
+        action: A,
+        hook: impl Fn(&ActionHookContext<A>, &mut Commands) + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.on(action, ActionTransition::Released, hook)
+    }
+
+    /// Removes all hooks registered for the `action`
+    pub fn clear(&mut self, action: A) -> &mut Self {
+        self.hooks
+            .retain(|(hooked_action, _, _)| hooked_action.index() != action.index());
+        self
+    }
+
+    /// The number of registered hooks
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.hooks.len()
+    }
+
+    /// Are there no registered hooks?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.hooks.is_empty()
+    }
+
+    /// Iterates over the registered hooks, along with the action and transition that trigger them
+    pub fn iter(&self) -> impl Iterator<Item = (&A, ActionTransition, &ActionHook<A>)> {
+        self.hooks
+            .iter()
+            .map(|(action, transition, hook)| (action, *transition, hook))
+    }
+}
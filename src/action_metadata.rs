@@ -0,0 +1,182 @@
+//! Human-readable metadata about actions, for use in settings menus and other user-facing tools
+//!
+//! Each action has an [`ActionInfo`], describing its display name, description, category and sort order.
+//! These are collected into the [`ActionMetadata`] registry, which can be edited at runtime.
+//!
+//! When deriving [`Actionlike`], this metadata can be declared using the `#[actionlike(...)]` attribute:
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use leafwing_input_manager::action_metadata::ActionMetadata;
+//! use leafwing_input_manager::Actionlike;
+//!
+//! #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Hash, Debug, Reflect)]
+//! enum PlayerAction {
+//!     #[actionlike(category = "Movement", order = 1)]
+//!     Run,
+//!     #[actionlike(description = "Leap into the air", category = "Movement", order = 0)]
+//!     Jump,
+//!     #[actionlike(name = "Ultimate Ability", category = "Combat")]
+//!     Ultimate,
+//! }
+//!
+//! let metadata = ActionMetadata::<PlayerAction>::default();
+//!
+//! // Names default to the name of the variant
+//! assert_eq!(metadata.name(PlayerAction::Run), "Run");
+//! assert_eq!(metadata.name(PlayerAction::Ultimate), "Ultimate Ability");
+//! assert_eq!(metadata.get(PlayerAction::Jump).description.as_deref(), Some("Leap into the air"));
+//!
+//! // Actions are sorted by category, and then by their sort order
+//! let sorted: Vec<PlayerAction> = metadata.iter_sorted().map(|(action, _info)| action).collect();
+//! assert_eq!(sorted, [PlayerAction::Ultimate, PlayerAction::Jump, PlayerAction::Run]);
+//! ```
+
+use crate::Actionlike;
+
+use bevy::ecs::system::Resource;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Human-readable metadata about a single action
+///
+/// Returned by [`Actionlike::info`], and stored in an [`ActionMetadata`] registry.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ActionInfo {
+    /// The name of the action, as displayed to players
+    pub name: String,
+    /// A longer description of what the action does
+    pub description: Option<String>,
+    /// The category that this action belongs to, used to group actions together in menus
+    pub category: Option<String>,
+    /// The position of this action within its category
+    ///
+    /// Actions with a lower sort order are displayed first.
+    /// Ties are broken by the order in which the actions were defined.
+    pub sort_order: i32,
+}
+
+impl ActionInfo {
+    /// Creates a new [`ActionInfo`] with the provided `name`, and no description or category
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> ActionInfo {
+        ActionInfo {
+            name: name.into(),
+            ..Default::default()
+        }
+    }
+
+    /// Returns this [`ActionInfo`] with the description set to the specified value
+    #[must_use]
+    pub fn with_description(mut self, description: impl Into<String>) -> ActionInfo {
+        self.description = Some(description.into());
+        self
+    }
+
+    /// Returns this [`ActionInfo`] with the category set to the specified value
+    #[must_use]
+    pub fn with_category(mut self, category: impl Into<String>) -> ActionInfo {
+        self.category = Some(category.into());
+        self
+    }
+
+    /// Returns this [`ActionInfo`] with the sort order set to the specified value
+    #[must_use]
+    pub fn with_sort_order(mut self, sort_order: i32) -> ActionInfo {
+        self.sort_order = sort_order;
+        self
+    }
+}
+
+/// A registry of the [`ActionInfo`] for each action of type `A`
+///
+/// The [`Default`] implementation is populated from [`Actionlike::info`].
+/// This resource is not added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin):
+/// initialize it using `app.init_resource::<ActionMetadata<A>>()` if you need it.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ActionMetadata<A: Actionlike> {
+    /// The [`ActionInfo`] of each action
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    info: Vec<ActionInfo>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for ActionMetadata<A> {
+    fn default() -> Self {
+        ActionMetadata {
+            info: A::variants().map(|action| action.info()).collect(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ActionMetadata<A> {
+    /// Returns the [`ActionInfo`] of the `action`
+    #[must_use]
+    pub fn get(&self, action: A) -> &ActionInfo {
+        &self.info[action.index()]
+    }
+
+    /// Returns a mutable reference to the [`ActionInfo`] of the `action`
+    #[must_use]
+    pub fn get_mut(&mut self, action: A) -> &mut ActionInfo {
+        &mut self.info[action.index()]
+    }
+
+    /// Replaces the [`ActionInfo`] of the `action`
+    pub fn set(&mut self, action: A, info: ActionInfo) -> &mut Self {
+        self.info[action.index()] = info;
+        self
+    }
+
+    /// Returns the display name of the `action`
+    #[must_use]
+    pub fn name(&self, action: A) -> &str {
+        &self.get(action).name
+    }
+
+    /// Iterates over all actions and their [`ActionInfo`], in the order they were defined
+    pub fn iter(&self) -> impl Iterator<Item = (A, &ActionInfo)> {
+        A::variants().zip(self.info.iter())
+    }
+
+    /// Iterates over all actions and their [`ActionInfo`], in the order they should be displayed
+    ///
+    /// Actions are grouped by category, with categories sorted alphabetically and uncategorized actions last.
+    /// Within each category, actions are sorted by [`ActionInfo::sort_order`].
+    pub fn iter_sorted(&self) -> impl Iterator<Item = (A, &ActionInfo)> {
+        let mut sorted: Vec<(A, &ActionInfo)> = self.iter().collect();
+        // Stable sorting preserves the definition order of ties
+        sorted.sort_by(|(_, a), (_, b)| {
+            (a.category.is_none(), &a.category, a.sort_order).cmp(&(
+                b.category.is_none(),
+                &b.category,
+                b.sort_order,
+            ))
+        });
+        sorted.into_iter()
+    }
+
+    /// Iterates over the actions in the provided `category`, in the order they should be displayed
+    pub fn iter_category<'a>(
+        &'a self,
+        category: &'a str,
+    ) -> impl Iterator<Item = (A, &'a ActionInfo)> + 'a {
+        self.iter_sorted()
+            .filter(move |(_, info)| info.category.as_deref() == Some(category))
+    }
+
+    /// Returns the distinct categories of the actions, in the order they should be displayed
+    #[must_use]
+    pub fn categories(&self) -> Vec<&str> {
+        let mut categories: Vec<&str> = self
+            .info
+            .iter()
+            .filter_map(|info| info.category.as_deref())
+            .collect();
+        categories.sort_unstable();
+        categories.dedup();
+        categories
+    }
+}
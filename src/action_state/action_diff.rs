@@ -0,0 +1,85 @@
+//! Compact records of presses and releases, for sending over the network
+
+use crate::stable_id::StableId;
+use crate::Actionlike;
+
+use bevy::prelude::Event;
+use leafwing_input_manager_core::ActionDiff as CoreActionDiff;
+use serde::{Deserialize, Serialize};
+
+/// Stores presses and releases of buttons without timing information
+///
+/// These are typically accessed using the `Events<ActionDiff>` resource.
+/// Uses a minimal storage format, in order to facilitate transport over the network.
+///
+/// `ID` is a [`StableId`] for the entity that stores the corresponding [`ActionState`](crate::action_state::ActionState),
+/// such as a `u64` shared by every peer.
+/// It can either be stored as a component on that entity, or mapped to it by a [`StableIdRegistry`](crate::stable_id::StableIdRegistry).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Event)]
+pub enum ActionDiff<A: Actionlike, ID: StableId> {
+    /// The action was pressed
+    Pressed {
+        /// The value of the action
+        action: A,
+        /// The stable identifier of the entity
+        id: ID,
+    },
+    /// The action was released
+    Released {
+        /// The value of the action
+        action: A,
+        /// The stable identifier of the entity
+        id: ID,
+    },
+}
+
+impl<A: Actionlike, ID: StableId> ActionDiff<A, ID> {
+    /// The action that was pressed or released
+    #[must_use]
+    pub fn action(&self) -> &A {
+        match self {
+            ActionDiff::Pressed { action, .. } | ActionDiff::Released { action, .. } => action,
+        }
+    }
+
+    /// The stable identifier of the entity whose action changed
+    #[must_use]
+    pub fn id(&self) -> &ID {
+        match self {
+            ActionDiff::Pressed { id, .. } | ActionDiff::Released { id, .. } => id,
+        }
+    }
+
+    /// Converts this into the engine-independent [`ActionDiff`](CoreActionDiff) of `leafwing_input_manager_core`,
+    /// which identifies the action by its [`Actionlike::index`]
+    #[must_use]
+    pub fn to_core(&self) -> CoreActionDiff<ID> {
+        match self {
+            ActionDiff::Pressed { action, id } => CoreActionDiff::Pressed {
+                action: action.index(),
+                id: id.clone(),
+            },
+            ActionDiff::Released { action, id } => CoreActionDiff::Released {
+                action: action.index(),
+                id: id.clone(),
+            },
+        }
+    }
+
+    /// Converts an engine-independent [`ActionDiff`](CoreActionDiff) of `leafwing_input_manager_core` back into an [`ActionDiff`]
+    ///
+    /// Returns `None` if the index of its action does not correspond to an action of type `A`.
+    #[must_use]
+    pub fn from_core(diff: CoreActionDiff<ID>) -> Option<Self> {
+        Some(match diff {
+            CoreActionDiff::Pressed { action, id } => ActionDiff::Pressed {
+                action: A::get_at(action)?,
+                id,
+            },
+            CoreActionDiff::Released { action, id } => ActionDiff::Released {
+                action: A::get_at(action)?,
+                id,
+            },
+        })
+    }
+}
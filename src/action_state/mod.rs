@@ -0,0 +1,1328 @@
+//! This module contains [`ActionState`] and its supporting methods and impls.
+//!
+//! The types used alongside it are defined in focused submodules, and re-exported here:
+//! [`ActionStateDriver`] and its targets in `driver`, and [`ActionDiff`] in `action_diff`.
+//! [`Timing`](crate::timing::Timing) lives in the [`timing`](crate::timing) module.
+
+mod action_diff;
+mod driver;
+
+pub use action_diff::ActionDiff;
+pub use driver::{
+    ActionStateDriver, ActionStateDriverTarget, DriverTargetDespawned, DriverTrigger,
+};
+
+/// Moved to the [`timing`](crate::timing) module
+///
+/// This is an alias rather than a re-export, as deprecating a `pub use` has no effect.
+#[deprecated(
+    since = "0.11.0",
+    note = "`Timing` has moved, use `leafwing_input_manager::timing::Timing` instead"
+)]
+pub type Timing = crate::timing::Timing;
+
+use crate::errors::{report, InputManagerError};
+use crate::hold_assist::HoldLatch;
+use crate::input_map::{BindingVec, HotSwap};
+use crate::timing::ActionTimings;
+use crate::user_input::UserInput;
+use crate::Actionlike;
+use crate::{axislike::DualAxisData, buttonlike::ButtonState};
+
+use bevy::ecs::component::Component;
+use bevy::math::Vec2;
+use bevy::prelude::Resource;
+use bevy::reflect::Reflect;
+use bevy::utils::{HashSet, Instant};
+use leafwing_input_manager_core::{
+    ActionData as CoreActionData, ActionState as CoreActionState, Timing as CoreTiming,
+};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Metadata about an [`Actionlike`] action
+///
+/// If a button is released, its `reasons_pressed` should be empty.
+#[derive(Debug, Default, Clone, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct ActionData {
+    /// Is the action pressed or released?
+    pub state: ButtonState,
+    /// The "value" of the binding that triggered the action.
+    ///
+    /// See [`ActionState::value`] for more details.
+    ///
+    /// **Warning:** this value may not be bounded as you might expect.
+    /// Consider clamping this to account for multiple triggering inputs.
+    pub value: f32,
+    /// The [`DualAxisData`] of the binding that triggered the action.
+    pub axis_pair: Option<DualAxisData>,
+    /// The position of the cursor in window coordinates when the action was last pressed by a mouse button.
+    ///
+    /// See [`ActionState::cursor_position`] for more details.
+    #[serde(default)]
+    pub cursor_position: Option<Vec2>,
+    /// Was this action consumed by [`ActionState::consume`]?
+    ///
+    /// Actions that are consumed cannot be pressed again until they are explicitly released.
+    /// This ensures that consumed actions are not immediately re-pressed by continued inputs.
+    pub consumed: bool,
+    /// Was this action disabled by [`ActionState::disable`]?
+    ///
+    /// Disabled actions are released, and cannot be pressed again until they are enabled.
+    #[serde(default)]
+    pub disabled: bool,
+    /// How many buttons of the most complete chord bound to this action are held, out of the buttons in that chord
+    ///
+    /// See [`ActionState::chord_progress`] for more details.
+    #[serde(default)]
+    pub chord_progress: (usize, usize),
+    /// Was this action pressed while [latched](crate::latching), without being consumed since?
+    ///
+    /// Latched presses are reported by [`ActionState::just_pressed`] until the action is consumed or reset.
+    #[serde(default)]
+    pub latched: bool,
+}
+
+/// Stores the canonical input-method-agnostic representation of the inputs received
+///
+/// Can be used as either a resource or as a [`Component`] on entities that you wish to control directly from player input.
+///
+/// # Example
+/// ```rust
+/// use bevy::reflect::Reflect;
+/// use leafwing_input_manager::prelude::*;
+/// use bevy::utils::Instant;
+///
+/// #[derive(Actionlike, PartialEq, Eq, Clone, Copy, Debug, Reflect)]
+/// enum Action {
+///     Left,
+///     Right,
+///     Jump,
+/// }
+///
+/// let mut action_state = ActionState::<Action>::default();
+///
+/// // Typically, this is done automatically by the `InputManagerPlugin` from user inputs
+/// // using the `ActionState::update` method
+/// action_state.press(Action::Jump);
+///
+/// assert!(action_state.pressed(Action::Jump));
+/// assert!(action_state.just_pressed(Action::Jump));
+/// assert!(action_state.released(Action::Left));
+///
+/// // Resets just_pressed and just_released
+/// let t0 = Instant::now();
+/// let t1 = Instant::now();
+///
+///  action_state.tick(t1, t0);
+/// assert!(action_state.pressed(Action::Jump));
+/// assert!(!action_state.just_pressed(Action::Jump));
+///
+/// action_state.release(Action::Jump);
+/// assert!(!action_state.pressed(Action::Jump));
+/// assert!(action_state.released(Action::Jump));
+/// assert!(action_state.just_released(Action::Jump));
+///
+/// let t2 = Instant::now();
+/// action_state.tick(t2, t1);
+/// assert!(action_state.released(Action::Jump));
+/// assert!(!action_state.just_released(Action::Jump));
+/// ```
+#[derive(Resource, Component, Clone, Debug, Serialize, Deserialize, Reflect)]
+pub struct ActionState<A: Actionlike> {
+    /// The [`ActionData`] of each action
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    action_data: Vec<ActionData>,
+    /// The bindings that are pressing each [`AggregationPolicy::LatestWins`](crate::input_map::AggregationPolicy::LatestWins) action,
+    /// ordered from least to most recently pressed
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub(crate) binding_recency: Vec<BindingVec<UserInput>>,
+    /// The toggle state of each action affected by a [`HoldAssist`](crate::hold_assist::HoldAssist)
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub(crate) hold_latches: Vec<HoldLatch>,
+    /// The progress of handing each action over to another device, see [`InputMap::set_device_hot_swap`](crate::input_map::InputMap::set_device_hot_swap)
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(skip)]
+    #[reflect(ignore)]
+    pub(crate) hot_swaps: Vec<HotSwap>,
+    /// Whether each action was pressed when [`ActionState::tick`] was last called
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    #[serde(skip)]
+    #[reflect(ignore)]
+    previously_pressed: Vec<bool>,
+    #[reflect(ignore)]
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> ActionState<A> {
+    /// Updates the [`ActionState`] based on a vector of [`ActionData`], ordered by [`Actionlike::id`](Actionlike).
+    ///
+    /// The `action_data` is typically constructed from [`InputMap::which_pressed`](crate::input_map::InputMap),
+    /// which reads from the assorted [`Input`](bevy::input::Input) resources.
+    pub fn update(&mut self, action_data: Vec<ActionData>) {
+        self.update_from_slice(&action_data);
+    }
+
+    /// Updates the [`ActionState`] based on a slice of [`ActionData`], ordered by [`Actionlike::id`](Actionlike).
+    ///
+    /// This behaves like [`ActionState::update`], but borrows the `action_data`,
+    /// so that it can be read from a reused [`WhichPressedBuffer`](crate::input_map::WhichPressedBuffer).
+    ///
+    /// If there is not exactly one [`ActionData`] per action, the update is skipped and an [`InputManagerError`] is [reported](crate::errors).
+    pub fn update_from_slice(&mut self, action_data: &[ActionData]) {
+        if let Err(error) = self.try_update_from_slice(action_data) {
+            report(error);
+        }
+    }
+
+    /// Updates the [`ActionState`] based on a slice of [`ActionData`], ordered by [`Actionlike::id`](Actionlike).
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InputManagerError::MismatchedActionCount`] without changing the state
+    /// if there is not exactly one [`ActionData`] per action.
+    pub fn try_update_from_slice(
+        &mut self,
+        action_data: &[ActionData],
+    ) -> Result<(), InputManagerError> {
+        if action_data.len() != A::n_variants() {
+            return Err(InputManagerError::MismatchedActionCount {
+                expected: A::n_variants(),
+                found: action_data.len(),
+            });
+        }
+
+        for (i, action) in A::variants().enumerate() {
+            // Disabled actions ignore their inputs entirely
+            if self.action_data[i].disabled {
+                continue;
+            }
+
+            if action_data[i].state.pressed() {
+                self.press(action.clone());
+            } else {
+                self.release(action.clone());
+            }
+
+            // Only record the cursor position at the moment of the press,
+            // so that it is not affected by later cursor movement
+            if self.action_data[i].state.just_pressed() {
+                self.action_data[i].cursor_position = action_data[i].cursor_position;
+            }
+
+            self.action_data[i].axis_pair = action_data[i].axis_pair;
+            self.action_data[i].value = action_data[i].value;
+            self.action_data[i].chord_progress = action_data[i].chord_progress;
+        }
+
+        Ok(())
+    }
+
+    /// Converts this into the engine-independent [`ActionState`](CoreActionState) of `leafwing_input_manager_core`,
+    /// which identifies each action by its [`Actionlike::index`]
+    ///
+    /// The durations of each action are copied from the `timings`, if they are supplied.
+    /// Data that depends on Bevy, such as axis pairs and cursor positions, is not converted.
+    #[must_use]
+    pub fn to_core(&self, timings: Option<&ActionTimings<A>>) -> CoreActionState {
+        CoreActionState::from_action_data(
+            A::variants()
+                .map(|action| {
+                    let action_data = self.action_data(action.clone());
+                    let mut timing = CoreTiming::default();
+                    if let Some(timings) = timings {
+                        let action_timing = timings.timing(self, action);
+                        timing.current_duration = action_timing.current_duration;
+                        timing.previous_duration = action_timing.previous_duration;
+                    }
+
+                    CoreActionData {
+                        state: action_data.state.into(),
+                        value: action_data.value,
+                        timing,
+                        consumed: action_data.consumed,
+                        disabled: action_data.disabled,
+                    }
+                })
+                .collect(),
+        )
+    }
+
+    /// Converts an engine-independent [`ActionState`](CoreActionState) of `leafwing_input_manager_core` back into an [`ActionState`]
+    ///
+    /// Timing is not converted: set it on the [`ActionTimings`] with [`ActionTimings::set_timing`] if it is needed.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InputManagerError::MismatchedActionCount`] if the `core` state does not have exactly one action per variant of `A`.
+    pub fn from_core(core: &CoreActionState) -> Result<Self, InputManagerError> {
+        if core.n_actions() != A::n_variants() {
+            return Err(InputManagerError::MismatchedActionCount {
+                expected: A::n_variants(),
+                found: core.n_actions(),
+            });
+        }
+
+        let mut action_state = Self::default();
+        for (action_data, core_data) in action_state
+            .action_data
+            .iter_mut()
+            .zip(core.all_action_data())
+        {
+            action_data.state = core_data.state.into();
+            action_data.value = core_data.value;
+            action_data.consumed = core_data.consumed;
+            action_data.disabled = core_data.disabled;
+        }
+        Ok(action_state)
+    }
+
+    /// Advances the time for all actions
+    ///
+    /// The underlying [`ButtonState`] of each action is advanced, clearing its `just_*` flags.
+    /// How long each action has been pressed or released is tracked separately,
+    /// by [ticking with timings](ActionState::tick_with_timings),
+    /// and the flags can be kept for longer with [`PersistentJustFlags::tick`](crate::just_persistence::PersistentJustFlags::tick).
+    /// The instants are not used here, but are taken so that each of these ticks is called the same way.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::buttonlike::ButtonState;
+    /// use bevy::utils::Instant;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    ///
+    /// // Actions start released
+    /// assert!(action_state.released(Action::Jump));
+    /// assert!(!action_state.just_released(Action::Run));
+    ///
+    /// // Ticking time moves causes buttons that were just released to no longer be just released
+    /// let t0 = Instant::now();
+    /// let t1 = Instant::now();
+    ///
+    /// action_state.tick(t1, t0);
+    /// assert!(action_state.released(Action::Jump));
+    /// assert!(!action_state.just_released(Action::Jump));
+    ///
+    /// action_state.press(Action::Jump);
+    /// assert!(action_state.just_pressed(Action::Jump));
+    ///
+    /// // Ticking time moves causes buttons that were just pressed to no longer be just pressed
+    /// let t2 = Instant::now();
+    ///
+    /// action_state.tick(t2, t1);
+    /// assert!(action_state.pressed(Action::Jump));
+    /// assert!(!action_state.just_pressed(Action::Jump));
+    /// ```
+    pub fn tick(&mut self, _current_instant: Instant, _previous_instant: Instant) {
+        // Snapshot the state that is ending, before it is advanced
+        self.previously_pressed.clear();
+        self.previously_pressed
+            .extend(self.action_data.iter().map(|ad| ad.state.pressed()));
+
+        // Advanced the ButtonState
+        self.action_data.iter_mut().for_each(|ad| ad.state.tick());
+    }
+
+    /// Advances the time for all actions, and the [`ActionTimings`] that tracks how long they have been pressed or released
+    ///
+    /// This is [`ActionTimings::tick`], followed by [`ActionState::tick`].
+    /// Features measured with these durations, such as [auto repeats](crate::auto_repeat) and [holds](crate::hold_for),
+    /// should be ticked afterwards, with the same instants.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::timing::ActionTimings;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let mut timings = ActionTimings::<Action>::default();
+    /// let t0 = Instant::now();
+    ///
+    /// action_state.press(Action::Jump);
+    /// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(100), t0);
+    ///
+    /// assert!(!action_state.just_pressed(Action::Jump));
+    /// assert_eq!(timings.current_duration(&action_state, Action::Jump), Duration::from_millis(100));
+    /// ```
+    pub fn tick_with_timings(
+        &mut self,
+        timings: &mut ActionTimings<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        timings.tick(self, current_instant, previous_instant);
+        self.tick(current_instant, previous_instant);
+    }
+
+    /// A reference to the [`ActionData`] of the corresponding `action`
+    ///
+    /// Generally, it'll be clearer to call `pressed` or so on directly on the [`ActionState`].
+    /// However, accessing the raw data directly allows you to examine detailed metadata holistically.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let run_data = action_state.action_data(Action::Run);
+    ///
+    /// dbg!(run_data);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn action_data(&self, action: A) -> &ActionData {
+        &self.action_data[action.index()]
+    }
+
+    /// A mutable reference of the [`ActionData`] of the corresponding `action`
+    ///
+    /// Generally, it'll be clearer to call `pressed` or so on directly on the [`ActionState`].
+    /// However, accessing the raw data directly allows you to examine detailed metadata holistically.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let mut run_data = action_state.action_data_mut(Action::Run);
+    /// run_data.axis_pair = None;
+    ///
+    /// dbg!(run_data);
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn action_data_mut(&mut self, action: A) -> &mut ActionData {
+        &mut self.action_data[action.index()]
+    }
+
+    /// Get the value associated with the corresponding `action`
+    ///
+    /// Different kinds of bindings have different ways of calculating the value:
+    ///
+    /// - Binary buttons will have a value of `0.0` when the button is not pressed, and a value of
+    ///   `1.0` when the button is pressed.
+    /// - Some axes, such as an analog stick, will have a value in the range `-1.0..=1.0`.
+    /// - Some axes, such as a variable trigger, will have a value in the range `0.0..=1.0`.
+    /// - Some buttons will also return a value in the range `0.0..=1.0`, such as analog gamepad
+    ///   triggers which may be tracked as buttons or axes. Examples of these include the Xbox LT/RT
+    ///   triggers and the Playstation L2/R2 triggers. See also the `axis_inputs` example in the
+    ///   repository.
+    /// - Dual axis inputs will return the magnitude of its [`DualAxisData`] and will be in the range
+    ///   `0.0..=1.0`.
+    /// - Chord inputs will return the value of its first input.
+    ///
+    /// If multiple inputs trigger the same game action at the same time, the value of each
+    /// triggering input will be added together.
+    ///
+    /// # Warning
+    ///
+    /// This value may not be bounded as you might expect.
+    /// Consider clamping this to account for multiple triggering inputs,
+    /// typically using the [`clamped_value`](Self::clamped_value) method instead.
+    pub fn value(&self, action: A) -> f32 {
+        self.action_data(action).value
+    }
+
+    /// Get the value associated with the corresponding `action`, clamped to `[-1.0, 1.0]`.
+    pub fn clamped_value(&self, action: A) -> f32 {
+        self.value(action).clamp(-1., 1.)
+    }
+
+    /// How many buttons of the chords bound to the `action` are held, as `(held, total)`
+    ///
+    /// The most complete chord is reported: the one with the most held buttons, or the fewest buttons in total if tied.
+    /// This allows UIs to show hints like "hold the other key too" while a chord is partially held.
+    /// Actions without any chords bound to them report `(0, 0)`.
+    ///
+    /// This is updated from the [`InputMap`](crate::input_map::InputMap) each frame,
+    /// and is not affected by [`ActionState::press`] or [`ActionState::release`].
+    #[inline]
+    #[must_use]
+    pub fn chord_progress(&self, action: A) -> (usize, usize) {
+        self.action_data[action.index()].chord_progress
+    }
+
+    /// Get the [`DualAxisData`] from the binding that triggered the corresponding `action`.
+    ///
+    /// Only certain events such as [`VirtualDPad`][crate::axislike::VirtualDPad] and
+    /// [`DualAxis`][crate::axislike::DualAxis] provide an [`DualAxisData`], and this
+    /// will return [`None`] for other events.
+    ///
+    /// Chord inputs will return the [`DualAxisData`] of it's first input.
+    ///
+    /// If multiple inputs with an axis pair trigger the same game action at the same time, the
+    /// value of each axis pair will be added together.
+    ///
+    /// # Warning
+    ///
+    /// These values may not be bounded as you might expect.
+    /// Consider clamping this to account for multiple triggering inputs,
+    /// typically using the [`clamped_axis_pair`](Self::clamped_axis_pair) method instead.
+    pub fn axis_pair(&self, action: A) -> Option<DualAxisData> {
+        self.action_data(action).axis_pair
+    }
+
+    /// Get the position of the cursor, in logical window coordinates, at the moment the `action` was last pressed.
+    ///
+    /// This is only recorded when the action was pressed by an input involving a [`MouseButton`](bevy::input::mouse::MouseButton),
+    /// and recording was enabled with [`InputMap::set_record_cursor_position`](crate::input_map::InputMap::set_record_cursor_position).
+    /// Otherwise, this returns [`None`].
+    /// The position is not updated while the action is held,
+    /// so abilities that target the cursor can read it later without racing against cursor movement.
+    ///
+    /// To convert this position into world coordinates, use [`ActionState::cursor_world_position`].
+    pub fn cursor_position(&self, action: A) -> Option<Vec2> {
+        self.action_data(action).cursor_position
+    }
+
+    /// Get the position of the cursor in 2D world coordinates at the moment the `action` was last pressed.
+    ///
+    /// The window coordinates returned by [`ActionState::cursor_position`] are projected through the provided `camera`.
+    /// Returns [`None`] if no cursor position was recorded, or if the projection failed.
+    #[cfg(feature = "ui")]
+    pub fn cursor_world_position(
+        &self,
+        action: A,
+        camera: &bevy::render::camera::Camera,
+        camera_transform: &bevy::transform::components::GlobalTransform,
+    ) -> Option<Vec2> {
+        let cursor_position = self.cursor_position(action)?;
+        camera.viewport_to_world_2d(camera_transform, cursor_position)
+    }
+
+    /// Get the [`DualAxisData`] associated with the corresponding `action`, clamped to `[-1.0, 1.0]`.
+    pub fn clamped_axis_pair(&self, action: A) -> Option<DualAxisData> {
+        self.axis_pair(action)
+            .map(|pair| DualAxisData::new(pair.x().clamp(-1.0, 1.0), pair.y().clamp(-1.0, 1.0)))
+    }
+
+    /// Manually sets the [`ActionData`] of the corresponding `action`
+    ///
+    /// You should almost always use more direct methods, as they are simpler and less error-prone.
+    ///
+    /// However, this method can be useful for testing,
+    /// or when transferring [`ActionData`] between action states.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum AbilitySlot {
+    ///     Slot1,
+    ///     Slot2,
+    /// }
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    ///
+    /// let mut ability_slot_state = ActionState::<AbilitySlot>::default();
+    /// let mut action_state = ActionState::<Action>::default();
+    ///
+    /// // Extract the state from the ability slot
+    /// let slot_1_state = ability_slot_state.action_data(AbilitySlot::Slot1);
+    ///
+    /// // And transfer it to the actual ability that we care about
+    /// action_state.set_action_data(Action::Run, slot_1_state.clone());
+    /// ```
+    ///
+    /// Timing information is stored separately, and can be transferred with [`ActionTimings::set_timing`].
+    #[inline]
+    pub fn set_action_data(&mut self, action: A, data: ActionData) {
+        self.action_data[action.index()] = data;
+    }
+
+    /// Press the `action`
+    ///
+    /// No initial instant or reasons why the button was pressed will be recorded
+    /// Instead, this is set through [`ActionState::tick()`]
+    #[inline]
+    pub fn press(&mut self, action: A) {
+        let index = action.index();
+        // Consumed actions cannot be pressed until they are released,
+        // and disabled actions cannot be pressed until they are enabled
+        if self.action_data[index].consumed || self.action_data[index].disabled {
+            return;
+        }
+
+        self.action_data[index].state.press();
+    }
+
+    /// Release the `action`
+    ///
+    /// No initial instant will be recorded
+    /// Instead, this is set through [`ActionState::tick()`]
+    #[inline]
+    pub fn release(&mut self, action: A) {
+        let index = action.index();
+        // Once released, consumed actions can be pressed again
+        self.action_data[index].consumed = false;
+
+        self.action_data[index].state.release();
+    }
+
+    /// Consumes the `action`
+    ///
+    /// The action will be released, and will not be able to be pressed again
+    /// until it would have otherwise been released by [`ActionState::release`],
+    /// [`ActionState::release_all`] or [`ActionState::update`].
+    ///
+    /// No initial instant will be recorded
+    /// Instead, this is set through [`ActionState::tick()`]
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Eat,
+    ///     Sleep,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    ///
+    /// action_state.press(Action::Eat);
+    /// assert!(action_state.pressed(Action::Eat));
+    ///
+    /// // Consuming actions releases them
+    /// action_state.consume(Action::Eat);
+    /// assert!(action_state.released(Action::Eat));
+    ///
+    /// // Doesn't work, as the action was consumed
+    /// action_state.press(Action::Eat);
+    /// assert!(action_state.released(Action::Eat));
+    ///
+    /// // Releasing consumed actions allows them to be pressed again
+    /// action_state.release(Action::Eat);
+    /// action_state.press(Action::Eat);
+    /// assert!(action_state.pressed(Action::Eat));
+    /// ```
+    #[inline]
+    pub fn consume(&mut self, action: A) {
+        let index = action.index();
+        // This is the only difference from action_state.release(action)
+        self.action_data[index].consumed = true;
+        self.action_data[index].state.release();
+        self.action_data[index].latched = false;
+    }
+
+    /// Consumes all actions
+    #[inline]
+    pub fn consume_all(&mut self) {
+        for action in A::variants() {
+            self.consume(action);
+        }
+    }
+
+    /// Releases all actions
+    pub fn release_all(&mut self) {
+        for action in A::variants() {
+            self.release(action);
+        }
+    }
+
+    /// Resets the `action` to its default state, clearing its value, consumption and [latched](crate::latching) press
+    ///
+    /// Its timing is stored separately, and is cleared by [`ActionTimings::reset`].
+    /// Whether the action is disabled is configuration, and is kept, as is everything stored in other components.
+    /// If the inputs of the action are still held, it will be pressed again during the next update:
+    /// use [`ActionState::consume`] instead to ignore them until they are released.
+    pub fn reset(&mut self, action: A) {
+        let index = action.index();
+        self.action_data[index] = ActionData {
+            disabled: self.action_data[index].disabled,
+            ..Default::default()
+        };
+
+        if let Some(binding_recency) = self.binding_recency.get_mut(index) {
+            binding_recency.clear();
+        }
+        if let Some(hold_latch) = self.hold_latches.get_mut(index) {
+            *hold_latch = HoldLatch::default();
+        }
+        if let Some(hot_swap) = self.hot_swaps.get_mut(index) {
+            *hot_swap = HotSwap::default();
+        }
+        if let Some(previously_pressed) = self.previously_pressed.get_mut(index) {
+            *previously_pressed = false;
+        }
+    }
+
+    /// Resets all actions to their default state
+    ///
+    /// This is useful to clean up between levels, so that nothing carries over from one to the next.
+    /// See [`ActionState::reset`] for what is kept.
+    pub fn reset_all(&mut self) {
+        for action in A::variants() {
+            self.reset(action);
+        }
+    }
+
+    /// Swaps the current state of actions `a` and `b`, including whether they are disabled
+    ///
+    /// Their timing is stored separately, and is swapped by [`ActionTimings::swap`].
+    /// Configuration stored in other components, such as their [`AutoRepeats`](crate::auto_repeat::AutoRepeats)
+    /// or [`NavigationDeadbands`](crate::navigation_deadband::NavigationDeadbands), is not swapped.
+    /// This only affects the current state, so inputs will keep updating each action as usual.
+    /// To swap which inputs trigger the actions, such as for a debuff that confuses the controls,
+    /// swap their bindings in the [`InputMap`](crate::input_map::InputMap) as well.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Left,
+    ///     Right,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.press(Action::Left);
+    ///
+    /// action_state.swap(Action::Left, Action::Right);
+    /// assert!(action_state.released(Action::Left));
+    /// assert!(action_state.just_pressed(Action::Right));
+    /// ```
+    pub fn swap(&mut self, a: A, b: A) {
+        let (a, b) = (a.index(), b.index());
+        self.action_data.swap(a, b);
+        swap_if_present(&mut self.binding_recency, a, b);
+        swap_if_present(&mut self.hold_latches, a, b);
+        swap_if_present(&mut self.hot_swaps, a, b);
+        swap_if_present(&mut self.previously_pressed, a, b);
+    }
+
+    /// Disables the `action`, releasing it
+    ///
+    /// Disabled actions cannot be pressed, by inputs or by [`ActionState::press`], until they are enabled again.
+    /// Their value and axis pair are no longer updated.
+    #[inline]
+    pub fn disable(&mut self, action: A) {
+        self.release(action.clone());
+        let action_data = &mut self.action_data[action.index()];
+        action_data.disabled = true;
+        action_data.value = 0.0;
+        action_data.axis_pair = None;
+    }
+
+    /// Disables all actions
+    #[inline]
+    pub fn disable_all(&mut self) {
+        for action in A::variants() {
+            self.disable(action);
+        }
+    }
+
+    /// Disables all actions except the provided `actions`, which are enabled
+    ///
+    /// This is the simplest way to pause a game:
+    /// the action used to unpause keeps working while every other action is frozen.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    ///     Pause,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// action_state.press(Action::Jump);
+    ///
+    /// action_state.disable_all_except([Action::Pause]);
+    /// assert!(action_state.released(Action::Jump));
+    ///
+    /// action_state.press(Action::Jump);
+    /// action_state.press(Action::Pause);
+    /// assert!(action_state.released(Action::Jump));
+    /// assert!(action_state.pressed(Action::Pause));
+    ///
+    /// // Unpausing
+    /// action_state.enable_all();
+    /// action_state.press(Action::Jump);
+    /// assert!(action_state.pressed(Action::Jump));
+    /// ```
+    pub fn disable_all_except(&mut self, actions: impl IntoIterator<Item = A>) {
+        let kept: HashSet<usize> = actions.into_iter().map(|action| action.index()).collect();
+
+        for action in A::variants() {
+            if kept.contains(&action.index()) {
+                self.enable(action);
+            } else {
+                self.disable(action);
+            }
+        }
+    }
+
+    /// Enables the `action`, allowing it to be pressed again
+    #[inline]
+    pub fn enable(&mut self, action: A) {
+        self.action_data[action.index()].disabled = false;
+    }
+
+    /// Enables all actions
+    #[inline]
+    pub fn enable_all(&mut self) {
+        for action in A::variants() {
+            self.enable(action);
+        }
+    }
+
+    /// Is this `action` currently disabled?
+    #[inline]
+    #[must_use]
+    pub fn disabled(&self, action: A) -> bool {
+        self.action_data[action.index()].disabled
+    }
+
+    /// Is this `action` currently pressed?
+    #[inline]
+    #[must_use]
+    pub fn pressed(&self, action: A) -> bool {
+        self.action_data[action.index()].state.pressed()
+    }
+
+    /// Was this `action` pressed since the last time [tick](ActionState::tick) was called?
+    ///
+    /// [Latched](crate::latching) presses stay just pressed until they are consumed, even once released.
+    #[inline]
+    #[must_use]
+    pub fn just_pressed(&self, action: A) -> bool {
+        let index = action.index();
+        self.action_data[index].state.just_pressed() || self.action_data[index].latched
+    }
+
+    /// Was this `action` pressed when [tick](ActionState::tick) was last called?
+    ///
+    /// This is the state of the action during the previous frame, and is unaffected by the `just_*` edge flags.
+    /// Systems that don't run every frame can compare it with [`ActionState::pressed`] to find their own edges,
+    /// or store their own snapshot of [`ActionState::pressed`] between runs.
+    /// This is false if the [`ActionState`] has never been ticked.
+    #[inline]
+    #[must_use]
+    pub fn was_pressed(&self, action: A) -> bool {
+        self.previously_pressed
+            .get(action.index())
+            .copied()
+            .unwrap_or(false)
+    }
+
+    /// Is this `action` currently released?
+    ///
+    /// This is always the logical negation of [pressed](ActionState::pressed)
+    #[inline]
+    #[must_use]
+    pub fn released(&self, action: A) -> bool {
+        self.action_data[action.index()].state.released()
+    }
+
+    /// Was this `action` released since the last time [tick](ActionState::tick) was called?
+    #[inline]
+    #[must_use]
+    pub fn just_released(&self, action: A) -> bool {
+        self.action_data[action.index()].state.just_released()
+    }
+
+    #[must_use]
+    /// Which actions are currently pressed?
+    pub fn get_pressed(&self) -> Vec<A> {
+        A::variants().filter(|a| self.pressed(a.clone())).collect()
+    }
+
+    #[must_use]
+    /// Which actions were just pressed?
+    pub fn get_just_pressed(&self) -> Vec<A> {
+        A::variants()
+            .filter(|a| self.just_pressed(a.clone()))
+            .collect()
+    }
+
+    #[must_use]
+    /// Which actions are currently released?
+    pub fn get_released(&self) -> Vec<A> {
+        A::variants().filter(|a| self.released(a.clone())).collect()
+    }
+
+    #[must_use]
+    /// Which actions were just released?
+    pub fn get_just_released(&self) -> Vec<A> {
+        A::variants()
+            .filter(|a| self.just_released(a.clone()))
+            .collect()
+    }
+
+    /// Is any action currently pressed?
+    ///
+    /// This is useful for "press any button to continue" screens, or to wake the game from idle.
+    /// Use [`ActionState::any_pressed_in`] to only consider some of the actions.
+    #[must_use]
+    pub fn any_pressed(&self) -> bool {
+        self.action_data.iter().any(|data| data.state.pressed())
+    }
+
+    /// Was any action pressed since the last time [tick](ActionState::tick) was called?
+    ///
+    /// Use [`ActionState::any_just_pressed_in`] to only consider some of the actions.
+    #[must_use]
+    pub fn any_just_pressed(&self) -> bool {
+        A::variants().any(|action| self.just_pressed(action))
+    }
+
+    /// Is any of the `actions` currently pressed?
+    #[must_use]
+    pub fn any_pressed_in(&self, actions: &[A]) -> bool {
+        actions.iter().any(|action| self.pressed(action.clone()))
+    }
+
+    /// Was any of the `actions` pressed since the last time [tick](ActionState::tick) was called?
+    #[must_use]
+    pub fn any_just_pressed_in(&self, actions: &[A]) -> bool {
+        actions
+            .iter()
+            .any(|action| self.just_pressed(action.clone()))
+    }
+
+    /// Are the same actions pressed in both [`ActionState`]s?
+    ///
+    /// Unlike [`PartialEq`], this ignores the timing, values and axis pairs of each action,
+    /// and does not distinguish between pressed and just pressed (or released and just released) actions.
+    /// This is useful in tests, and when checking for meaningful changes between two snapshots.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::Instant;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    ///
+    /// let mut a = ActionState::<Action>::default();
+    /// let mut b = ActionState::<Action>::default();
+    /// a.press(Action::Jump);
+    /// b.press(Action::Jump);
+    ///
+    /// // Ticking means that `b` is no longer just pressed, but doesn't change which actions are pressed
+    /// b.tick(Instant::now(), Instant::now());
+    /// assert_ne!(a, b);
+    /// assert!(a.eq_pressed_state(&b));
+    ///
+    /// b.press(Action::Run);
+    /// assert!(!a.eq_pressed_state(&b));
+    /// ```
+    #[must_use]
+    pub fn eq_pressed_state(&self, other: &ActionState<A>) -> bool {
+        self.action_data
+            .iter()
+            .zip(other.action_data.iter())
+            .all(|(a, b)| a.state.pressed() == b.state.pressed())
+    }
+}
+
+impl<A: Actionlike> Default for ActionState<A> {
+    fn default() -> ActionState<A> {
+        ActionState {
+            action_data: A::variants().map(|_| ActionData::default()).collect(),
+            binding_recency: Vec::new(),
+            hold_latches: Vec::new(),
+            hot_swaps: Vec::new(),
+            previously_pressed: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+// Implement manually to only compare the serialized state of the actions,
+// so that an `ActionState` is equal to itself after a round trip through serde
+impl<A: Actionlike> PartialEq for ActionState<A> {
+    fn eq(&self, other: &Self) -> bool {
+        self.action_data == other.action_data
+    }
+}
+
+/// Swaps the elements at indexes `a` and `b` of a lazily-sized per-action vector, if it is long enough to hold them
+///
+/// Missing elements are treated as their default value.
+fn swap_if_present<T: Default>(values: &mut Vec<T>, a: usize, b: usize) {
+    let len = a.max(b) + 1;
+    if values.len() < len {
+        if values.is_empty() {
+            return;
+        }
+        values.resize_with(len, T::default);
+    }
+    values.swap(a, b);
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as leafwing_input_manager;
+    use crate::input_mocking::MockInput;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Hide,
+    }
+
+    #[test]
+    fn press_lifecycle() {
+        use crate::action_state::ActionState;
+        use crate::clashing_inputs::ClashStrategy;
+        use crate::input_map::InputMap;
+        use crate::input_streams::InputStreams;
+        use bevy::input::InputPlugin;
+        use bevy::prelude::*;
+        use bevy::utils::{Duration, Instant};
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        // Action state
+        let mut action_state = ActionState::<Action>::default();
+
+        // Input map
+        let mut input_map = InputMap::default();
+        input_map.insert(KeyCode::R, Action::Run);
+
+        // Starting state
+        let input_streams = InputStreams::from_world(&app.world, None);
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+
+        assert!(!action_state.pressed(Action::Run));
+        assert!(!action_state.just_pressed(Action::Run));
+        assert!(action_state.released(Action::Run));
+        assert!(!action_state.just_released(Action::Run));
+
+        // Pressing
+        app.send_input(KeyCode::R);
+        // Process the input events into Input<KeyCode> data
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+
+        assert!(action_state.pressed(Action::Run));
+        assert!(action_state.just_pressed(Action::Run));
+        assert!(!action_state.released(Action::Run));
+        assert!(!action_state.just_released(Action::Run));
+
+        // Waiting
+        action_state.tick(Instant::now(), Instant::now() - Duration::from_micros(1));
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+
+        assert!(action_state.pressed(Action::Run));
+        assert!(!action_state.just_pressed(Action::Run));
+        assert!(!action_state.released(Action::Run));
+        assert!(!action_state.just_released(Action::Run));
+
+        // Releasing
+        app.release_input(KeyCode::R);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+
+        assert!(!action_state.pressed(Action::Run));
+        assert!(!action_state.just_pressed(Action::Run));
+        assert!(action_state.released(Action::Run));
+        assert!(action_state.just_released(Action::Run));
+
+        // Waiting
+        action_state.tick(Instant::now(), Instant::now() - Duration::from_micros(1));
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+
+        assert!(!action_state.pressed(Action::Run));
+        assert!(!action_state.just_pressed(Action::Run));
+        assert!(action_state.released(Action::Run));
+        assert!(!action_state.just_released(Action::Run));
+    }
+
+    #[test]
+    fn disabled_actions_ignore_inputs() {
+        use crate::action_state::ActionState;
+        use crate::clashing_inputs::ClashStrategy;
+        use crate::input_map::InputMap;
+        use crate::input_streams::InputStreams;
+        use bevy::input::InputPlugin;
+        use bevy::prelude::*;
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let mut action_state = ActionState::<Action>::default();
+        let input_map = InputMap::new([(KeyCode::R, Action::Run), (KeyCode::J, Action::Jump)]);
+
+        app.send_input(KeyCode::R);
+        app.send_input(KeyCode::J);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+        assert!(action_state.pressed(Action::Run));
+
+        // Disabling releases the action, even though its input is still held
+        action_state.disable_all_except([Action::Jump]);
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+        assert!(action_state.disabled(Action::Run));
+        assert!(action_state.released(Action::Run));
+        assert_eq!(action_state.value(Action::Run), 0.0);
+        assert!(!action_state.disabled(Action::Jump));
+        assert!(action_state.pressed(Action::Jump));
+
+        action_state.enable(Action::Run);
+        action_state.update(input_map.which_pressed(&input_streams, ClashStrategy::PressAll));
+        assert!(action_state.just_pressed(Action::Run));
+    }
+
+    #[test]
+    fn reset_and_swap() {
+        use crate::action_state::ActionState;
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Run);
+        action_state.press(Action::Jump);
+        action_state.disable(Action::Hide);
+
+        let t0 = Instant::now();
+        action_state.tick(t0 + Duration::from_millis(200), t0);
+
+        // Swapping exchanges state and configuration
+        action_state.swap(Action::Run, Action::Hide);
+        assert!(action_state.released(Action::Run));
+        assert!(action_state.disabled(Action::Run));
+        assert!(action_state.pressed(Action::Hide));
+        assert!(!action_state.disabled(Action::Hide));
+
+        // Resetting clears state but keeps configuration
+        action_state.consume(Action::Jump);
+        action_state.reset_all();
+        assert!(action_state.released(Action::Hide));
+        assert!(action_state.disabled(Action::Run));
+
+        // Reset actions are no longer consumed
+        action_state.press(Action::Jump);
+        assert!(action_state.just_pressed(Action::Jump));
+    }
+
+    #[test]
+    fn was_pressed() {
+        use crate::action_state::ActionState;
+        use bevy::utils::Instant;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Run);
+        assert!(!action_state.was_pressed(Action::Run));
+
+        action_state.tick(Instant::now(), Instant::now());
+        assert!(action_state.was_pressed(Action::Run));
+        assert!(!action_state.was_pressed(Action::Jump));
+
+        // Still true once the press is no longer just pressed
+        action_state.release(Action::Run);
+        assert!(action_state.was_pressed(Action::Run));
+        action_state.tick(Instant::now(), Instant::now());
+        assert!(!action_state.was_pressed(Action::Run));
+    }
+
+    #[test]
+    fn equality_ignores_bookkeeping() {
+        use crate::action_state::ActionState;
+        use crate::Actionlike;
+        use bevy::utils::Instant;
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Run);
+        action_state.tick(Instant::now(), Instant::now());
+
+        // Deserializing only restores the action data
+        let mut deserialized = ActionState::<Action>::default();
+        for action in Action::variants() {
+            deserialized.set_action_data(action, action_state.action_data(action).clone());
+        }
+        assert!(action_state.was_pressed(Action::Run));
+        assert!(!deserialized.was_pressed(Action::Run));
+        assert_eq!(action_state, deserialized);
+    }
+
+    #[test]
+    fn any_pressed() {
+        use crate::action_state::ActionState;
+        use bevy::utils::Instant;
+
+        let mut action_state = ActionState::<Action>::default();
+        assert!(!action_state.any_pressed());
+        assert!(!action_state.any_just_pressed());
+
+        action_state.press(Action::Hide);
+        assert!(action_state.any_pressed());
+        assert!(action_state.any_just_pressed());
+        assert!(action_state.any_just_pressed_in(&[Action::Jump, Action::Hide]));
+        assert!(!action_state.any_pressed_in(&[Action::Run, Action::Jump]));
+        assert!(!action_state.any_pressed_in(&[]));
+
+        action_state.tick(Instant::now(), Instant::now());
+        assert!(action_state.any_pressed());
+        assert!(!action_state.any_just_pressed());
+    }
+
+    #[test]
+    fn time_tick_ticks_away() {
+        use crate::action_state::ActionState;
+        use bevy::utils::{Duration, Instant};
+
+        let mut action_state = ActionState::<Action>::default();
+
+        // Actions start released (but not just released)
+        assert!(action_state.released(Action::Run));
+        assert!(!action_state.just_released(Action::Jump));
+
+        // Ticking causes buttons that were just released to no longer be just released
+        action_state.tick(Instant::now(), Instant::now() - Duration::from_micros(1));
+        assert!(action_state.released(Action::Jump));
+        assert!(!action_state.just_released(Action::Jump));
+        action_state.press(Action::Jump);
+        assert!(action_state.just_pressed(Action::Jump));
+
+        // Ticking causes buttons that were just pressed to no longer be just pressed
+        action_state.tick(Instant::now(), Instant::now() - Duration::from_micros(1));
+        assert!(action_state.pressed(Action::Jump));
+        assert!(!action_state.just_pressed(Action::Jump));
+    }
+
+    #[test]
+    fn core_diff_round_trip() {
+        use crate::action_state::ActionDiff;
+        use bevy::prelude::Component;
+
+        #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+        struct StableId(u64);
+
+        let diff = ActionDiff::Released {
+            action: Action::Hide,
+            id: StableId(3),
+        };
+        assert_eq!(ActionDiff::from_core(diff.to_core()), Some(diff));
+    }
+
+    #[test]
+    fn mismatched_action_data_is_rejected() {
+        use crate::action_state::{ActionData, ActionState};
+        use crate::buttonlike::ButtonState;
+        use crate::errors::InputManagerError;
+
+        let mut action_state = ActionState::<Action>::default();
+        let pressed = ActionData {
+            state: ButtonState::JustPressed,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            action_state.try_update_from_slice(&[pressed.clone(), pressed.clone()]),
+            Err(InputManagerError::MismatchedActionCount {
+                expected: 3,
+                found: 2
+            })
+        );
+        assert!(action_state.released(Action::Run));
+
+        assert_eq!(
+            action_state.try_update_from_slice(&[pressed.clone(), pressed.clone(), pressed]),
+            Ok(())
+        );
+        assert!(action_state.pressed(Action::Run));
+    }
+
+    #[test]
+    fn core_action_state_round_trip() {
+        use crate::action_state::ActionState;
+        use crate::errors::InputManagerError;
+        use crate::timing::ActionTimings;
+        use crate::Actionlike;
+        use bevy::utils::{Duration, Instant};
+        use leafwing_input_manager_core::ActionState as CoreActionState;
+
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let t0 = Instant::now();
+        action_state.press(Action::Run);
+        action_state.consume(Action::Jump);
+        action_state.tick_with_timings(&mut timings, t0 + Duration::from_secs(1), t0);
+
+        let core = action_state.to_core(Some(&timings));
+        assert!(core.pressed(Action::Run.index()));
+        assert_eq!(
+            core.current_duration(Action::Run.index()),
+            Duration::from_secs(1)
+        );
+
+        let converted = ActionState::<Action>::from_core(&core).unwrap();
+        assert!(converted.pressed(Action::Run));
+        assert!(converted.action_data(Action::Jump).consumed);
+        assert!(converted.released(Action::Hide));
+
+        assert_eq!(
+            ActionState::<Action>::from_core(&CoreActionState::new(2)),
+            Err(InputManagerError::MismatchedActionCount {
+                expected: 3,
+                found: 2
+            })
+        );
+    }
+}
@@ -0,0 +1,161 @@
+//! Collecting statistics about how each action is used, for telemetry, balancing and UX research
+//!
+//! Add an [`ActionStats`] resource or component alongside an [`ActionState`],
+//! and the [`record_action_stats`](crate::systems::record_action_stats) system will count the presses of each action,
+//! how long it was held for in total, and the largest value that it reached.
+//! The statistics cover a single session, which lasts until [`ActionStats::reset`] is called,
+//! and can be exported with [`serde`] to be analyzed elsewhere.
+//!
+//! This module requires the `stats` feature.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::action_stats::ActionStats;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Dash,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     commands
+//!         .spawn(InputManagerBundle::<Action>::default())
+//!         .insert(ActionStats::<Action>::default());
+//! }
+//!
+//! fn report_stats(query: Query<&ActionStats<Action>>) {
+//!     for stats in query.iter() {
+//!         let usage = stats.usage(Action::Dash);
+//!         println!("Dashed {} times in {:?}", usage.presses, stats.session_duration());
+//!     }
+//! }
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::utils::Duration;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// How a single action was used during a session
+#[derive(Debug, Default, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ActionUsage {
+    /// The number of times the action was pressed
+    pub presses: u32,
+    /// How long the action was held for, summed over every press
+    pub held_duration: Duration,
+    /// The largest absolute value that the action reached
+    pub peak_value: f32,
+}
+
+/// The [`ActionUsage`] of each action of type `A` during the current session
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it watches.
+/// Only the usage is serialized, so the statistics of a session can be exported as-is.
+#[derive(Resource, Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionStats<A: Actionlike> {
+    /// The usage of each action, indexed by [`Actionlike::index`]
+    usage: Vec<ActionUsage>,
+    /// How long the current session has lasted
+    session_duration: Duration,
+    #[serde(skip)]
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for ActionStats<A> {
+    fn default() -> Self {
+        Self {
+            usage: vec![ActionUsage::default(); A::n_variants()],
+            session_duration: Duration::ZERO,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ActionStats<A> {
+    /// Records a frame lasting `delta`, during which the actions were in the state of the `action_state`
+    pub fn record(&mut self, action_state: &ActionState<A>, delta: Duration) {
+        self.session_duration += delta;
+        for action in A::variants() {
+            let usage = &mut self.usage[action.index()];
+            if action_state.newly_pressed(action.clone()) {
+                usage.presses += 1;
+            }
+            if action_state.pressed(action.clone()) {
+                usage.held_duration += delta;
+            }
+            usage.peak_value = usage.peak_value.max(action_state.value(action).abs());
+        }
+    }
+
+    /// The usage of the `action` during the current session
+    #[must_use]
+    pub fn usage(&self, action: A) -> ActionUsage {
+        self.usage[action.index()]
+    }
+
+    /// Iterates over the usage of every action during the current session
+    pub fn iter(&self) -> impl Iterator<Item = (A, ActionUsage)> + '_ {
+        A::variants().map(|action| (action.clone(), self.usage(action)))
+    }
+
+    /// How long the current session has lasted
+    #[must_use]
+    pub fn session_duration(&self) -> Duration {
+        self.session_duration
+    }
+
+    /// Clears the statistics, starting a new session
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Throttle,
+    }
+
+    #[test]
+    fn usage_is_accumulated() {
+        let mut stats = ActionStats::<Action>::default();
+        let mut action_state = ActionState::<Action>::default();
+        let frame = Duration::from_millis(100);
+
+        action_state.press(Action::Jump);
+        action_state.action_data_mut(Action::Throttle).value = -0.8;
+        stats.record(&action_state, frame);
+
+        // Held presses are only counted once
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.action_data_mut(Action::Throttle).value = 0.5;
+        stats.record(&action_state, frame);
+
+        action_state.release(Action::Jump);
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.press(Action::Jump);
+        stats.record(&action_state, frame);
+
+        let jump = stats.usage(Action::Jump);
+        assert_eq!(jump.presses, 2);
+        assert_eq!(jump.held_duration, frame * 3);
+        assert_eq!(stats.usage(Action::Throttle).peak_value, 0.8);
+        assert_eq!(stats.session_duration(), frame * 3);
+
+        stats.reset();
+        assert_eq!(stats, ActionStats::default());
+    }
+}
@@ -0,0 +1,147 @@
+//! This module contains [`ActionWriter`] and [`ExternallyControlled`], which allow AI controllers to drive actions.
+//!
+//! Entities marked with [`ExternallyControlled`] do not read physical inputs from their [`InputMap`](crate::input_map::InputMap).
+//! Instead, their [`ActionState`](crate::action_state::ActionState) is set from their [`ActionWriter`]
+//! each time the [`apply_action_writers`](crate::systems::apply_action_writers) system runs.
+//!
+//! Unlike [`VirtualInput`](crate::virtual_input::VirtualInput), which is merged with physical input,
+//! an [`ActionWriter`] fully determines the state of its actions.
+//! This makes it well-suited to bots and AI-controlled characters that use the same [`Actionlike`] type as players.
+
+use crate::action_state::ActionData;
+use crate::axislike::DualAxisData;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::marker::PhantomData;
+
+/// Marks an entity whose [`ActionState`](crate::action_state::ActionState) for actions of type `A` is driven by an [`ActionWriter`] rather than by physical input
+///
+/// Actions of other types on the same entity continue to read physical input.
+/// When inserted as a resource, the [`ActionState`](crate::action_state::ActionState) resource is driven by the [`ActionWriter`] resource instead.
+#[derive(Component, Resource, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ExternallyControlled<A: Actionlike>(PhantomData<A>);
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for ExternallyControlled<A> {
+    fn default() -> Self {
+        Self(PhantomData)
+    }
+}
+
+/// The complete state of every action of type `A`, as set by an AI controller
+///
+/// Each call to [`ActionWriter::write`] atomically replaces the previous state.
+/// The written state is applied every update until it is replaced,
+/// so controllers that make decisions less often than once per frame keep holding their actions.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::action_writer::{ActionWriter, ExternallyControlled};
+/// use leafwing_input_manager::axislike::DualAxisData;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Jump,
+/// }
+///
+/// fn bot_controller(mut query: Query<&mut ActionWriter<Action>, With<ExternallyControlled<Action>>>) {
+///     for mut writer in query.iter_mut() {
+///         writer.write(|frame| {
+///             frame
+///                 .press(Action::Jump)
+///                 .set_axis_pair(Action::Move, DualAxisData::new(0.0, 1.0));
+///         });
+///     }
+/// }
+///
+/// let mut world = World::new();
+/// world.spawn((
+///     InputManagerBundle::<Action>::default(),
+///     ActionWriter::<Action>::default(),
+///     ExternallyControlled::<Action>::default(),
+/// ));
+/// ```
+#[derive(Component, Resource, Debug, Clone, PartialEq)]
+pub struct ActionWriter<A: Actionlike> {
+    action_data: Vec<ActionData>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for ActionWriter<A> {
+    fn default() -> Self {
+        Self {
+            action_data: vec![ActionData::default(); A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ActionWriter<A> {
+    /// Replaces the state of every action with the state built by `build`
+    ///
+    /// Actions that are not pressed by `build` are released.
+    pub fn write(&mut self, build: impl FnOnce(&mut ActionFrame<A>)) {
+        let mut frame = ActionFrame {
+            action_data: vec![ActionData::default(); A::n_variants()],
+            _phantom: PhantomData,
+        };
+        build(&mut frame);
+        self.action_data = frame.action_data;
+    }
+
+    /// Releases every action
+    pub fn release_all(&mut self) {
+        self.write(|_| {});
+    }
+
+    /// The state that will be applied to the [`ActionState`](crate::action_state::ActionState) during the next update
+    ///
+    /// The position in this slice corresponds to [`Actionlike::index`].
+    #[must_use]
+    pub fn action_data(&self) -> &[ActionData] {
+        &self.action_data
+    }
+}
+
+/// The state of every action, as built during a call to [`ActionWriter::write`]
+#[derive(Debug)]
+pub struct ActionFrame<A: Actionlike> {
+    action_data: Vec<ActionData>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> ActionFrame<A> {
+    /// Presses the `action`, with a value of `1.0`
+    pub fn press(&mut self, action: A) -> &mut Self {
+        let data = &mut self.action_data[action.index()];
+        data.state = ButtonState::JustPressed;
+        data.value = 1.0;
+        self
+    }
+
+    /// Sets the value of the `action`, pressing it if the `value` is non-zero
+    pub fn set_value(&mut self, action: A, value: f32) -> &mut Self {
+        let data = &mut self.action_data[action.index()];
+        data.state = if value != 0.0 {
+            ButtonState::JustPressed
+        } else {
+            ButtonState::Released
+        };
+        data.value = value;
+        self
+    }
+
+    /// Sets the axis pair of the `action`, pressing it if the `axis_pair` is non-zero
+    ///
+    /// The value of the action is set to the length of the `axis_pair`.
+    pub fn set_axis_pair(&mut self, action: A, axis_pair: DualAxisData) -> &mut Self {
+        self.set_value(action.clone(), axis_pair.length());
+        self.action_data[action.index()].axis_pair = Some(axis_pair);
+        self
+    }
+}
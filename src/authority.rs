@@ -0,0 +1,116 @@
+//! Client-side prediction of some actions, while others wait for confirmation from the server
+//!
+//! In networked games, responsive actions like movement are usually predicted: the client acts on its own inputs immediately.
+//! Other actions, like purchases, should only take effect once the server has confirmed them.
+//! Add an [`ActionAuthority`] alongside the [`ActionState`] of the local player to choose the [`AuthorityLevel`] of each action.
+//!
+//! The following systems are not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually:
+//! - [`process_confirmed_action_diffs`](crate::systems::process_confirmed_action_diffs) applies the [`ActionDiff`](crate::action_state::ActionDiff)s echoed by the server
+//!   to the [`ActionAuthority::confirmed`] state.
+//! - [`reconcile_action_states`](crate::systems::reconcile_action_states) stores the local inputs in [`ActionAuthority::predicted`],
+//!   then replaces the [`AuthorityLevel::Confirmed`] actions of the [`ActionState`] with their confirmed state.
+//!   Run it in [`PreUpdate`](bevy::app::PreUpdate) after [`InputManagerSystem::ManualControl`](crate::plugin::InputManagerSystem::ManualControl),
+//!   so that gameplay systems only see the reconciled state.
+//! - [`restore_predicted_action_states`](crate::systems::restore_predicted_action_states) puts the local inputs back into the [`ActionState`].
+//!   Run it in [`PostUpdate`](bevy::app::PostUpdate) before generating the [`ActionDiff`](crate::action_state::ActionDiff)s sent to the server,
+//!   so that requests for confirmed actions are still sent, and the next frame starts from the local inputs.
+
+use crate::action_state::ActionState;
+use crate::timing::ActionTimings;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Component;
+
+/// Whether an action is acted on immediately, or only once the server confirms it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AuthorityLevel {
+    /// The client predicts the action from its own inputs
+    #[default]
+    Predicted,
+    /// The action only changes once the server has confirmed it
+    Confirmed,
+}
+
+/// Tracks the predicted and server-confirmed state of each action of a locally controlled entity
+///
+/// See the [module-level documentation](crate::authority) for the systems that use it.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::authority::{ActionAuthority, AuthorityLevel};
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Buy,
+/// }
+///
+/// let authority = ActionAuthority::default().with_level(Action::Buy, AuthorityLevel::Confirmed);
+/// assert_eq!(authority.level(Action::Move), AuthorityLevel::Predicted);
+/// assert_eq!(authority.level(Action::Buy), AuthorityLevel::Confirmed);
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ActionAuthority<A: Actionlike> {
+    levels: Vec<AuthorityLevel>,
+    /// The state of each action according to the local inputs alone
+    ///
+    /// Updated by [`reconcile_action_states`](crate::systems::reconcile_action_states).
+    pub predicted: ActionState<A>,
+    /// The timing of each action according to the local inputs alone
+    ///
+    /// Only updated for entities with an [`ActionTimings`].
+    pub predicted_timings: ActionTimings<A>,
+    /// The state of each action according to the server
+    ///
+    /// Updated by [`process_confirmed_action_diffs`](crate::systems::process_confirmed_action_diffs).
+    pub confirmed: ActionState<A>,
+    /// The timing of each action according to the server
+    pub confirmed_timings: ActionTimings<A>,
+}
+
+impl<A: Actionlike> Default for ActionAuthority<A> {
+    fn default() -> Self {
+        Self {
+            levels: vec![AuthorityLevel::default(); A::n_variants()],
+            predicted: ActionState::default(),
+            predicted_timings: ActionTimings::default(),
+            confirmed: ActionState::default(),
+            confirmed_timings: ActionTimings::default(),
+        }
+    }
+}
+
+impl<A: Actionlike> ActionAuthority<A> {
+    /// Sets the [`AuthorityLevel`] of the `action`
+    #[must_use]
+    pub fn with_level(mut self, action: A, level: AuthorityLevel) -> Self {
+        self.set_level(action, level);
+        self
+    }
+
+    /// Sets the [`AuthorityLevel`] of the `action`
+    pub fn set_level(&mut self, action: A, level: AuthorityLevel) {
+        self.levels[action.index()] = level;
+    }
+
+    /// The [`AuthorityLevel`] of the `action`
+    #[must_use]
+    pub fn level(&self, action: A) -> AuthorityLevel {
+        self.levels[action.index()]
+    }
+
+    /// Iterates over the actions with [`AuthorityLevel::Confirmed`]
+    pub fn confirmed_actions(&self) -> impl Iterator<Item = A> + '_ {
+        A::variants().filter(|action| self.level(action.clone()) == AuthorityLevel::Confirmed)
+    }
+
+    /// Does the server disagree with the local prediction of whether the `action` is pressed?
+    ///
+    /// This is expected for a short time after each press or release, until the server has confirmed it.
+    #[must_use]
+    pub fn mispredicted(&self, action: A) -> bool {
+        self.predicted.pressed(action.clone()) != self.confirmed.pressed(action)
+    }
+}
@@ -0,0 +1,341 @@
+//! Repeating held actions at an accelerating rate, for menu navigation
+//!
+//! Holding a direction in a menu typically moves the selection once, waits for a short delay,
+//! then keeps moving it at a steady rate that speeds up the longer the button is held.
+//! Add an [`AutoRepeats`] resource or component alongside an [`ActionState`] and its [`ActionTimings`],
+//! and configure it per action with [`AutoRepeats::set`] and an [`AutoRepeat`].
+//!
+//! Each time the [`count_auto_repeats`](crate::systems::count_auto_repeats) system runs, after the [`ActionState`] is ticked,
+//! the number of repeats that became due while the action was held is recorded.
+//! Check them with [`AutoRepeats::just_repeated`], or with [`AutoRepeats::just_pressed_or_repeated`], which also includes presses.
+//! Operating system key repeats are surfaced separately, by the [`key_repeat`](crate::key_repeat) module.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::auto_repeat::{AutoRepeat, AutoRepeats};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     Up,
+//!     Down,
+//! }
+//!
+//! let repeat = AutoRepeat::new(Duration::from_millis(400), Duration::from_millis(150))
+//!     .with_acceleration(0.8, Duration::from_millis(40));
+//!
+//! let mut auto_repeats = AutoRepeats::<MenuAction>::default();
+//! auto_repeats.set(MenuAction::Up, Some(repeat));
+//! auto_repeats.set(MenuAction::Down, Some(repeat));
+//!
+//! let mut app = App::new();
+//! app.insert_resource(auto_repeats);
+//! ```
+
+use crate::action_state::ActionState;
+use crate::timing::ActionTimings;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// How often a held action repeats
+///
+/// The first repeat happens once the action has been held for the `delay`.
+/// Each following repeat happens after the current interval, which starts at the `interval`
+/// and is multiplied by the `acceleration` after every repeat, until it reaches the `min_interval`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct AutoRepeat {
+    delay: Duration,
+    interval: Duration,
+    acceleration: f32,
+    min_interval: Duration,
+}
+
+impl AutoRepeat {
+    /// The shortest interval allowed between two repeats
+    pub const SHORTEST_INTERVAL: Duration = Duration::from_millis(1);
+
+    /// Repeats every `interval` once the action has been held for the `delay`, without accelerating
+    ///
+    /// The `interval` is raised to [`AutoRepeat::SHORTEST_INTERVAL`] if it is shorter.
+    #[must_use]
+    pub fn new(delay: Duration, interval: Duration) -> Self {
+        let interval = interval.max(Self::SHORTEST_INTERVAL);
+
+        Self {
+            delay,
+            interval,
+            acceleration: 1.0,
+            min_interval: interval,
+        }
+    }
+
+    /// Multiplies the interval by the `acceleration` after every repeat, until it reaches the `min_interval`
+    ///
+    /// The `acceleration` is clamped to `(0.0, 1.0]`, and the `min_interval` can not be longer than the starting interval.
+    #[must_use]
+    pub fn with_acceleration(mut self, acceleration: f32, min_interval: Duration) -> Self {
+        self.acceleration = acceleration.clamp(f32::EPSILON, 1.0);
+        self.min_interval = min_interval.clamp(Self::SHORTEST_INTERVAL, self.interval);
+        self
+    }
+
+    /// How long the action must be held before it first repeats
+    #[must_use]
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// The interval between the first and second repeats
+    #[must_use]
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The factor that the interval is multiplied by after every repeat
+    #[must_use]
+    pub fn acceleration(&self) -> f32 {
+        self.acceleration
+    }
+
+    /// The interval below which repeats stop accelerating
+    #[must_use]
+    pub fn min_interval(&self) -> Duration {
+        self.min_interval
+    }
+
+    /// The total number of repeats that are due once the action has been held for `held`
+    #[must_use]
+    pub fn repeats_within(&self, held: Duration) -> u32 {
+        if held < self.delay {
+            return 0;
+        }
+
+        // Step through the accelerating repeats one by one
+        let mut last_repeat = self.delay;
+        let mut interval = self.interval;
+        let mut repeats = 1;
+        while interval > self.min_interval {
+            if last_repeat + interval > held {
+                return repeats;
+            }
+            last_repeat += interval;
+            repeats += 1;
+            // Rounded to the nearest nanosecond, so that floating point error can't keep the interval above its floor
+            let accelerated = (interval.as_nanos() as f64 * f64::from(self.acceleration)).round();
+            interval = Duration::from_nanos(accelerated as u64).max(self.min_interval);
+        }
+
+        // Once the interval has reached its floor, the remaining repeats are evenly spaced
+        let steady_repeats = (held - last_repeat).as_nanos() / interval.as_nanos();
+        repeats.saturating_add(u32::try_from(steady_repeats).unwrap_or(u32::MAX))
+    }
+}
+
+/// How each action of type `A` repeats while held, and the repeats that became due during the last tick
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] and [`ActionTimings`] that it repeats.
+/// Repeats are measured with the [`ActionTimings`], so they are never counted for [`ActionState`]s without one.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::{Duration, Instant};
+/// use leafwing_input_manager::auto_repeat::{AutoRepeat, AutoRepeats};
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::timing::ActionTimings;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Down,
+/// }
+///
+/// let mut auto_repeats = AutoRepeats::<Action>::default();
+/// auto_repeats.set(Action::Down, Some(AutoRepeat::new(Duration::from_millis(500), Duration::from_millis(100))));
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// let mut timings = ActionTimings::<Action>::default();
+/// let t0 = Instant::now();
+/// let t1 = t0 + Duration::from_millis(500);
+///
+/// action_state.press(Action::Down);
+/// action_state.tick_with_timings(&mut timings, t1, t0);
+/// auto_repeats.tick(&action_state, &timings, t1, t0);
+/// assert!(auto_repeats.just_repeated(Action::Down));
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct AutoRepeats<A: Actionlike> {
+    /// How each action repeats while held, indexed by [`Actionlike::index`]
+    auto_repeat: Vec<Option<AutoRepeat>>,
+    /// The number of repeats of each action that became due during the last tick, indexed by [`Actionlike::index`]
+    repeats: Vec<u32>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for AutoRepeats<A> {
+    fn default() -> Self {
+        AutoRepeats {
+            auto_repeat: vec![None; A::n_variants()],
+            repeats: vec![0; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> AutoRepeats<A> {
+    /// Sets how the `action` repeats while held, or disables repeating if [`None`]
+    ///
+    /// Disabling repeating clears any repeats that were already recorded for the `action`.
+    pub fn set(&mut self, action: A, auto_repeat: Option<AutoRepeat>) -> &mut Self {
+        let index = action.index();
+        self.auto_repeat[index] = auto_repeat;
+        if auto_repeat.is_none() {
+            self.repeats[index] = 0;
+        }
+        self
+    }
+
+    /// How the `action` repeats while held, if at all
+    #[must_use]
+    pub fn get(&self, action: A) -> Option<&AutoRepeat> {
+        self.auto_repeat[action.index()].as_ref()
+    }
+
+    /// The number of repeats of the `action` that became due during the last tick
+    ///
+    /// This is usually at most one, but can be higher if a frame took longer than the current repeat interval.
+    #[must_use]
+    pub fn repeats(&self, action: A) -> u32 {
+        self.repeats[action.index()]
+    }
+
+    /// Did a repeat of the `action` become due during the last tick?
+    #[must_use]
+    pub fn just_repeated(&self, action: A) -> bool {
+        self.repeats(action) > 0
+    }
+
+    /// Was the `action` just pressed in the `action_state`, or did a repeat of it become due during the last tick?
+    ///
+    /// This is the typical check for menu navigation.
+    /// Check [`KeyRepeats::just_repeated`](crate::key_repeat::KeyRepeats::just_repeated) as well to include operating system key repeats.
+    #[must_use]
+    pub fn just_pressed_or_repeated(&self, action_state: &ActionState<A>, action: A) -> bool {
+        action_state.just_pressed(action.clone()) || self.just_repeated(action)
+    }
+
+    /// Records the repeats that became due for each held action during a tick from the `previous_instant` to the `current_instant`
+    ///
+    /// This should be called after the `action_state` and its `timings` were [ticked](ActionState::tick_with_timings) with the same instants.
+    pub fn tick(
+        &mut self,
+        action_state: &ActionState<A>,
+        timings: &ActionTimings<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        let delta = current_instant.saturating_duration_since(previous_instant);
+
+        for (index, action) in A::variants().enumerate() {
+            let action_data = action_state.action_data(action.clone());
+            self.repeats[index] = match self.auto_repeat[index] {
+                Some(auto_repeat) if action_data.state.pressed() && !action_data.consumed => {
+                    // Actions pressed since the last tick are timed from its start, so they were not held before it
+                    let held = timings.current_duration(action_state, action);
+                    auto_repeat.repeats_within(held)
+                        - auto_repeat.repeats_within(held.saturating_sub(delta))
+                }
+                _ => 0,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Up,
+        Down,
+    }
+
+    #[test]
+    fn steady_repeats() {
+        let repeat = AutoRepeat::new(Duration::from_millis(500), Duration::from_millis(100));
+
+        assert_eq!(repeat.repeats_within(Duration::from_millis(499)), 0);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(500)), 1);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(650)), 2);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(1500)), 11);
+    }
+
+    #[test]
+    fn accelerating_repeats() {
+        let repeat = AutoRepeat::new(Duration::ZERO, Duration::from_millis(400))
+            .with_acceleration(0.5, Duration::from_millis(100));
+
+        // Repeats are due at 0, 400, 600 and 700 milliseconds, then every 100 milliseconds
+        assert_eq!(repeat.repeats_within(Duration::from_millis(399)), 1);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(400)), 2);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(699)), 3);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(700)), 4);
+        assert_eq!(repeat.repeats_within(Duration::from_millis(1000)), 7);
+    }
+
+    #[test]
+    fn held_actions_repeat() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let mut auto_repeats = AutoRepeats::<Action>::default();
+        auto_repeats.set(
+            Action::Up,
+            Some(AutoRepeat::new(
+                Duration::from_millis(500),
+                Duration::from_millis(100),
+            )),
+        );
+
+        let t0 = Instant::now();
+        let mut tick = |action_state: &mut ActionState<Action>, from: u64, to: u64| {
+            let (current, previous) = (
+                t0 + Duration::from_millis(to),
+                t0 + Duration::from_millis(from),
+            );
+            action_state.tick_with_timings(&mut timings, current, previous);
+            auto_repeats.tick(action_state, &timings, current, previous);
+            auto_repeats.clone()
+        };
+
+        action_state.press(Action::Up);
+        action_state.press(Action::Down);
+        let repeats = tick(&mut action_state, 0, 100);
+        assert!(!repeats.just_repeated(Action::Up));
+
+        let repeats = tick(&mut action_state, 100, 500);
+        assert_eq!(repeats.repeats(Action::Up), 1);
+        assert!(repeats.just_pressed_or_repeated(&action_state, Action::Up));
+        // Actions without an auto repeat never repeat
+        assert!(!repeats.just_repeated(Action::Down));
+
+        let repeats = tick(&mut action_state, 500, 550);
+        assert!(!repeats.just_repeated(Action::Up));
+
+        // A long frame can make several repeats due at once
+        let repeats = tick(&mut action_state, 550, 750);
+        assert_eq!(repeats.repeats(Action::Up), 2);
+
+        action_state.release(Action::Up);
+        let repeats = tick(&mut action_state, 750, 1000);
+        assert!(!repeats.just_repeated(Action::Up));
+    }
+}
@@ -3,20 +3,26 @@
 use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
 use crate::orientation::{Direction, Rotation};
 use crate::prelude::QwertyScanCode;
-use crate::user_input::InputKind;
+use crate::user_input::{InputKind, UserInput};
 use bevy::input::{
     gamepad::{GamepadAxisType, GamepadButtonType},
     keyboard::KeyCode,
+    mouse::MouseButton,
 };
 use bevy::math::Vec2;
 use bevy::reflect::Reflect;
-use bevy::utils::FloatOrd;
+use bevy::utils::{FloatOrd, HashMap};
 use serde::{Deserialize, Serialize};
 
 /// A single directional axis with a configurable trigger zone.
 ///
 /// These can be stored in a [`InputKind`] to create a virtual button.
 ///
+/// Each axis has a positive and a negative trigger zone.
+/// By default, these zones are unbounded, but they can be capped using `positive_high` and `negative_high`.
+/// This allows several actions to be bound to different ranges of the same axis,
+/// such as a half-pull and a full-pull of an analog trigger.
+///
 /// # Warning
 ///
 /// `positive_low` must be greater than or equal to `negative_low` for this type to be validly constructed.
@@ -28,6 +34,16 @@ pub struct SingleAxis {
     pub positive_low: f32,
     /// Any axis value lower than this will trigger the input.
     pub negative_low: f32,
+    /// Any axis value higher than this will no longer trigger the input.
+    ///
+    /// Defaults to [`f32::MAX`], which leaves the positive trigger zone unbounded.
+    #[serde(default = "SingleAxis::unbounded_positive")]
+    pub positive_high: f32,
+    /// Any axis value lower than this will no longer trigger the input.
+    ///
+    /// Defaults to [`f32::MIN`], which leaves the negative trigger zone unbounded.
+    #[serde(default = "SingleAxis::unbounded_negative")]
+    pub negative_high: f32,
     /// Whether to invert output values from this axis.
     pub inverted: bool,
     /// The target value for this input, used for input mocking.
@@ -44,6 +60,8 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             positive_low: threshold,
             negative_low: -threshold,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -59,6 +77,8 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: Some(value),
         }
@@ -71,6 +91,8 @@ impl SingleAxis {
             axis_type: AxisType::MouseWheel(MouseWheelAxisType::X),
             positive_low: 0.,
             negative_low: 0.,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -83,6 +105,8 @@ impl SingleAxis {
             axis_type: AxisType::MouseWheel(MouseWheelAxisType::Y),
             positive_low: 0.,
             negative_low: 0.,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -95,6 +119,8 @@ impl SingleAxis {
             axis_type: AxisType::MouseMotion(MouseMotionAxisType::X),
             positive_low: 0.,
             negative_low: 0.,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -107,6 +133,8 @@ impl SingleAxis {
             axis_type: AxisType::MouseMotion(MouseMotionAxisType::Y),
             positive_low: 0.,
             negative_low: 0.,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -120,6 +148,8 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             negative_low: threshold,
             positive_low: f32::MAX,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
@@ -133,11 +163,98 @@ impl SingleAxis {
             axis_type: axis_type.into(),
             negative_low: f32::MIN,
             positive_low: threshold,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
             value: None,
         }
     }
 
+    /// Creates a [`SingleAxis`] that is only triggered by positive values in the range `(low, high]`.
+    ///
+    /// Negative values will not trigger the input.
+    ///
+    /// Binding several actions to adjacent ranges of the same axis creates a multi-stage input.
+    /// For example, a trigger can aim when half-pulled and fire when fully pulled:
+    ///
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadAxisType;
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Aim,
+    ///     Fire,
+    /// }
+    ///
+    /// let input_map = InputMap::new([
+    ///     // Aiming stays active while firing
+    ///     (SingleAxis::positive_range(GamepadAxisType::RightZ, 0.2, 1.0), Action::Aim),
+    ///     (SingleAxis::positive_range(GamepadAxisType::RightZ, 0.9, 1.0), Action::Fire),
+    /// ]);
+    /// ```
+    ///
+    /// # Ordering
+    ///
+    /// When the ranges of the stages are nested, as above, every value that triggers a later stage also triggers the earlier ones.
+    /// As a result, a later stage is never pressed before the earlier stages, even if the axis skips past several thresholds in a single frame,
+    /// and it is always released no later than they are.
+    ///
+    /// Bindings to different ranges of the same axis never clash with each other,
+    /// so every stage is pressed regardless of the [`ClashStrategy`](crate::clashing_inputs::ClashStrategy) in use.
+    #[must_use]
+    pub fn positive_range(axis_type: impl Into<AxisType>, low: f32, high: f32) -> SingleAxis {
+        SingleAxis {
+            positive_high: high,
+            ..SingleAxis::positive_only(axis_type, low)
+        }
+    }
+
+    /// Creates a [`SingleAxis`] that is only triggered by negative values in the range `[high, low)`.
+    ///
+    /// Both `low` and `high` should be negative, with `high` being further from zero.
+    /// Positive values will not trigger the input.
+    ///
+    /// Nested ranges offer the same ordering guarantees as [`SingleAxis::positive_range`].
+    #[must_use]
+    pub fn negative_range(axis_type: impl Into<AxisType>, low: f32, high: f32) -> SingleAxis {
+        SingleAxis {
+            negative_high: high,
+            ..SingleAxis::negative_only(axis_type, low)
+        }
+    }
+
+    /// Creates a pair of [`SingleAxis`] that split the axis into its positive and negative halves.
+    ///
+    /// The first element is triggered by values above `positive_threshold`,
+    /// while the second element is triggered by values below `-negative_threshold`.
+    /// The negative half is inverted, so both halves report values in the range `0.0..=1.0`.
+    ///
+    /// This is useful when the two halves of an axis should drive different actions,
+    /// such as accelerating and braking with the same stick.
+    #[must_use]
+    pub fn split(
+        axis_type: impl Into<AxisType>,
+        positive_threshold: f32,
+        negative_threshold: f32,
+    ) -> (SingleAxis, SingleAxis) {
+        let axis_type = axis_type.into();
+
+        (
+            SingleAxis::positive_only(axis_type, positive_threshold),
+            SingleAxis::negative_only(axis_type, -negative_threshold).inverted(),
+        )
+    }
+
+    /// Does the provided `value` fall into one of the trigger zones of this axis?
+    #[must_use]
+    #[inline]
+    pub fn in_trigger_zone(&self, value: f32) -> bool {
+        (value > self.positive_low && value <= self.positive_high)
+            || (value < self.negative_low && value >= self.negative_high)
+    }
+
     /// Returns this [`SingleAxis`] with the deadzone set to the specified value
     #[must_use]
     pub fn with_deadzone(mut self, deadzone: f32) -> SingleAxis {
@@ -146,12 +263,32 @@ impl SingleAxis {
         self
     }
 
+    /// Returns this [`SingleAxis`] with the upper bounds of its trigger zones set to the specified values
+    ///
+    /// Values beyond these bounds will no longer trigger the input.
+    #[must_use]
+    pub fn with_upper_bounds(mut self, positive_high: f32, negative_high: f32) -> SingleAxis {
+        self.positive_high = positive_high;
+        self.negative_high = negative_high;
+        self
+    }
+
     /// Returns this [`SingleAxis`] inverted.
     #[must_use]
     pub fn inverted(mut self) -> Self {
         self.inverted = !self.inverted;
         self
     }
+
+    // Used as the serde default, to allow older bindings to be deserialized
+    fn unbounded_positive() -> f32 {
+        f32::MAX
+    }
+
+    // Used as the serde default, to allow older bindings to be deserialized
+    fn unbounded_negative() -> f32 {
+        f32::MIN
+    }
 }
 
 impl PartialEq for SingleAxis {
@@ -159,6 +296,8 @@ impl PartialEq for SingleAxis {
         self.axis_type == other.axis_type
             && FloatOrd(self.positive_low) == FloatOrd(other.positive_low)
             && FloatOrd(self.negative_low) == FloatOrd(other.negative_low)
+            && FloatOrd(self.positive_high) == FloatOrd(other.positive_high)
+            && FloatOrd(self.negative_high) == FloatOrd(other.negative_high)
     }
 }
 impl Eq for SingleAxis {}
@@ -167,6 +306,8 @@ impl std::hash::Hash for SingleAxis {
         self.axis_type.hash(state);
         FloatOrd(self.positive_low).hash(state);
         FloatOrd(self.negative_low).hash(state);
+        FloatOrd(self.positive_high).hash(state);
+        FloatOrd(self.negative_high).hash(state);
     }
 }
 
@@ -291,6 +432,42 @@ impl DualAxis {
     }
 }
 
+/// The movement of the mouse while a [`MouseButton`] is held down.
+///
+/// The axis pair of this input is the total [`MouseMotion`](bevy::input::mouse::MouseMotion) delta
+/// of the current frame, and is only reported while `button` is pressed.
+/// This is useful for dragging-based controls, such as panning an RTS camera.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::{MouseButton, Reflect};
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+/// enum CameraAction {
+///     Pan,
+///     Rotate,
+/// }
+///
+/// let input_map = InputMap::new([
+///     (MouseDrag::new(MouseButton::Right), CameraAction::Pan),
+///     (MouseDrag::new(MouseButton::Middle), CameraAction::Rotate),
+/// ]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct MouseDrag {
+    /// The mouse button that must be held for the motion to be reported
+    pub button: MouseButton,
+}
+
+impl MouseDrag {
+    /// Creates a [`MouseDrag`] that is active while `button` is held
+    #[must_use]
+    pub const fn new(button: MouseButton) -> MouseDrag {
+        MouseDrag { button }
+    }
+}
+
 #[allow(clippy::doc_markdown)] // False alarm because it thinks DPad is an un-quoted item
 /// A virtual DPad that you can get an [`DualAxis`] from.
 ///
@@ -566,6 +743,324 @@ impl TryFrom<AxisType> for MouseMotionAxisType {
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct AxisConversionError;
 
+/// Named sensitivity multipliers, shared by every binding assigned to the same group
+///
+/// Bindings are assigned to a group by name (e.g. `"look"` or `"steer"`),
+/// and the values and axis pairs they produce are scaled by the sensitivity of that group.
+/// This allows a single options slider to adjust every related binding at once.
+///
+/// Bindings that are not assigned to a group, or whose group has no sensitivity set, are not scaled.
+/// These are typically configured via an [`InputMap`](crate::input_map::InputMap).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SensitivityGroups {
+    #[serde(with = "crate::serde_helpers::pairs")]
+    members: HashMap<UserInput, String>,
+    #[serde(with = "crate::serde_helpers::float_ord_pairs")]
+    sensitivities: HashMap<String, FloatOrd>,
+}
+
+impl SensitivityGroups {
+    /// Assigns the `input` to the sensitivity group called `group`, replacing any previous assignment
+    pub fn assign(&mut self, input: impl Into<UserInput>, group: impl Into<String>) -> &mut Self {
+        self.members.insert(input.into(), group.into());
+        self
+    }
+
+    /// Removes the `input` from its sensitivity group, if any
+    pub fn unassign(&mut self, input: &UserInput) -> &mut Self {
+        self.members.remove(input);
+        self
+    }
+
+    /// Returns the name of the sensitivity group that the `input` belongs to, if any
+    #[must_use]
+    pub fn group_of(&self, input: &UserInput) -> Option<&str> {
+        self.members.get(input).map(String::as_str)
+    }
+
+    /// Sets the sensitivity multiplier of the `group`
+    pub fn set_sensitivity(&mut self, group: impl Into<String>, sensitivity: f32) -> &mut Self {
+        self.sensitivities
+            .insert(group.into(), FloatOrd(sensitivity));
+        self
+    }
+
+    /// Returns the sensitivity multiplier of the `group`, defaulting to `1.0`
+    #[must_use]
+    pub fn sensitivity(&self, group: &str) -> f32 {
+        self.sensitivities
+            .get(group)
+            .map_or(1.0, |sensitivity| sensitivity.0)
+    }
+
+    /// Returns the sensitivity multiplier applied to the `input`, defaulting to `1.0`
+    #[must_use]
+    pub fn sensitivity_of(&self, input: &UserInput) -> f32 {
+        self.group_of(input)
+            .map_or(1.0, |group| self.sensitivity(group))
+    }
+
+    /// Adds all of the assignments and sensitivities of `other` to these groups, replacing any existing entries
+    pub fn extend(&mut self, other: &SensitivityGroups) -> &mut Self {
+        self.members.extend(
+            other
+                .members
+                .iter()
+                .map(|(input, group)| (input.clone(), group.clone())),
+        );
+        self.sensitivities.extend(
+            other
+                .sensitivities
+                .iter()
+                .map(|(group, sensitivity)| (group.clone(), *sensitivity)),
+        );
+        self
+    }
+}
+
+/// A transformation applied to the axis pair produced by a single binding, before it reaches the [`ActionState`](crate::action_state::ActionState)
+///
+/// Processors are configured per binding via [`InputMap::add_axis_pair_processor`](crate::input_map::InputMap::add_axis_pair_processor),
+/// so isometric and third-person games don't need to repeat this math in every gameplay system.
+/// Only the axis pair is processed: the value of the action is unaffected.
+///
+/// # Example
+/// ```rust
+/// use bevy::math::Vec2;
+/// use leafwing_input_manager::axislike::{AxisPairProcessor, DualAxisData};
+/// use leafwing_input_manager::orientation::Rotation;
+///
+/// // Pushing the stick up moves towards the top-right of an isometric grid
+/// let isometric = AxisPairProcessor::Rotate(Rotation::from_degrees(-45.0));
+/// let processed = isometric.process(DualAxisData::new(0.0, 1.0));
+/// assert!((processed.xy() - Vec2::new(1.0, 1.0).normalize()).length() < 0.001);
+///
+/// // Pushing the stick up moves the way the camera is facing
+/// let camera_relative = AxisPairProcessor::camera_relative(Vec2::new(-1.0, 0.0));
+/// let processed = camera_relative.process(DualAxisData::new(0.0, 1.0));
+/// assert!((processed.xy() - Vec2::new(-1.0, 0.0)).length() < 0.001);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AxisPairProcessor {
+    /// Rotates the axis pair counterclockwise by the provided [`Rotation`]
+    Rotate(Rotation),
+    /// Maps the x-axis onto the `right` direction and the y-axis onto the `forward` direction
+    ///
+    /// Unlike [`AxisPairProcessor::Rotate`], the two directions do not need to be perpendicular,
+    /// which allows input to be projected onto skewed spaces such as dimetric grids.
+    Basis {
+        /// The direction that a positive x value points towards
+        right: Rotation,
+        /// The direction that a positive y value points towards
+        forward: Rotation,
+    },
+    /// Removes small, unintentional stick movements using the provided [`DeadZoneShape`]
+    DeadZone(DeadZoneShape),
+}
+
+impl AxisPairProcessor {
+    /// Creates a processor that transforms input into the space of a camera looking along `forward`
+    ///
+    /// Pushing the stick up moves along `forward`, and pushing it right moves perpendicular to it, clockwise.
+    /// Returns [`AxisPairProcessor::Rotate`] by zero if `forward` is too close to zero to have a direction.
+    #[must_use]
+    pub fn camera_relative(forward: Vec2) -> AxisPairProcessor {
+        let forward = Rotation::from_xy(forward).unwrap_or(Rotation::NORTH);
+        AxisPairProcessor::Rotate(forward - Rotation::NORTH)
+    }
+
+    /// Applies this processor to the `axis_pair`
+    #[must_use]
+    pub fn process(&self, axis_pair: DualAxisData) -> DualAxisData {
+        let xy = axis_pair.xy();
+        match self {
+            AxisPairProcessor::Rotate(rotation) => {
+                DualAxisData::from_xy(rotation.into_xy().rotate(xy))
+            }
+            AxisPairProcessor::Basis { right, forward } => {
+                DualAxisData::from_xy(xy.x * right.into_xy() + xy.y * forward.into_xy())
+            }
+            AxisPairProcessor::DeadZone(shape) => DualAxisData::from_xy(shape.apply(xy)),
+        }
+    }
+}
+
+impl From<DeadZoneShape> for AxisPairProcessor {
+    fn from(shape: DeadZoneShape) -> Self {
+        AxisPairProcessor::DeadZone(shape)
+    }
+}
+
+/// The shape of the dead zone applied to an axis pair by [`AxisPairProcessor::DeadZone`]
+///
+/// Different genres call for different stick feel:
+/// axial dead zones make it easy to move in straight lines, radial dead zones preserve the angle of the stick,
+/// and bowtie dead zones make it easy to hit the cardinal directions at full deflection while keeping fine control near the center.
+///
+/// To avoid stacking dead zones, bind the stick using [`DualAxis::with_deadzone`] with a deadzone of `0.0`
+/// so that the per-axis thresholds of the [`DualAxis`] do not apply.
+///
+/// # Example
+/// ```rust
+/// use bevy::math::Vec2;
+/// use leafwing_input_manager::axislike::DeadZoneShape;
+///
+/// // Each axis is filtered separately
+/// assert_eq!(DeadZoneShape::axial(0.2).apply(Vec2::new(0.15, 0.5)), Vec2::new(0.0, 0.5));
+///
+/// // The angle of the stick is preserved
+/// assert_eq!(DeadZoneShape::radial(0.2).apply(Vec2::new(0.15, 0.5)), Vec2::new(0.15, 0.5));
+/// assert_eq!(DeadZoneShape::radial(0.2).apply(Vec2::new(0.1, 0.1)), Vec2::ZERO);
+///
+/// // The remaining range is rescaled, so output starts at zero at the edge of the dead zone
+/// let scaled = DeadZoneShape::scaled_radial(0.2).apply(Vec2::new(0.0, 0.6));
+/// assert!((scaled - Vec2::new(0.0, 0.5)).length() < 0.001);
+///
+/// // Small horizontal movements are ignored when the stick is pushed all the way up
+/// assert_eq!(DeadZoneShape::bowtie(0.2).apply(Vec2::new(0.1, 1.0)).x, 0.0);
+/// assert_eq!(DeadZoneShape::bowtie(0.2).apply(Vec2::new(0.1, 0.0)).x, 0.1);
+/// ```
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub enum DeadZoneShape {
+    /// Each axis is zeroed when its absolute value is below `radius`, without rescaling
+    Axial {
+        /// The size of the dead zone on each axis
+        radius: f32,
+    },
+    /// The axis pair is zeroed when its length is below `radius`, without rescaling
+    Radial {
+        /// The radius of the dead zone
+        radius: f32,
+    },
+    /// The axis pair is zeroed when its length is below `radius`,
+    /// and the remaining range of lengths is rescaled to start from zero
+    ///
+    /// The rescaled length is then raised to the power of `exponent`:
+    /// values above `1.0` give finer control near the center of the stick.
+    ScaledRadial {
+        /// The radius of the dead zone
+        radius: f32,
+        /// The exponent of the response curve applied to the rescaled length
+        exponent: f32,
+    },
+    /// The dead zone of each axis grows with the magnitude of the other axis, up to `radius` at full deflection
+    ///
+    /// The remaining range of each axis is rescaled to start from zero.
+    Bowtie {
+        /// The size of the dead zone of each axis when the other axis is fully deflected
+        radius: f32,
+    },
+}
+
+impl Default for DeadZoneShape {
+    /// A [`DeadZoneShape::ScaledRadial`] with a radius of [`DualAxis::DEFAULT_DEADZONE`] and a linear response
+    fn default() -> Self {
+        DeadZoneShape::scaled_radial(DualAxis::DEFAULT_DEADZONE)
+    }
+}
+
+impl DeadZoneShape {
+    /// Creates a [`DeadZoneShape::Axial`] dead zone of the provided `radius`
+    #[must_use]
+    pub const fn axial(radius: f32) -> Self {
+        DeadZoneShape::Axial { radius }
+    }
+
+    /// Creates a [`DeadZoneShape::Radial`] dead zone of the provided `radius`
+    #[must_use]
+    pub const fn radial(radius: f32) -> Self {
+        DeadZoneShape::Radial { radius }
+    }
+
+    /// Creates a [`DeadZoneShape::ScaledRadial`] dead zone of the provided `radius`, with a linear response
+    #[must_use]
+    pub const fn scaled_radial(radius: f32) -> Self {
+        DeadZoneShape::ScaledRadial {
+            radius,
+            exponent: 1.0,
+        }
+    }
+
+    /// Creates a [`DeadZoneShape::Bowtie`] dead zone of the provided `radius`
+    #[must_use]
+    pub const fn bowtie(radius: f32) -> Self {
+        DeadZoneShape::Bowtie { radius }
+    }
+
+    /// Returns this dead zone with the provided response curve `exponent`
+    ///
+    /// This only affects [`DeadZoneShape::ScaledRadial`] dead zones.
+    #[must_use]
+    pub fn with_exponent(mut self, new_exponent: f32) -> Self {
+        if let DeadZoneShape::ScaledRadial { exponent, .. } = &mut self {
+            *exponent = new_exponent;
+        }
+        self
+    }
+
+    /// Applies this dead zone to the provided `xy` pair
+    #[must_use]
+    pub fn apply(&self, xy: Vec2) -> Vec2 {
+        match *self {
+            DeadZoneShape::Axial { radius } => {
+                let axial = |value: f32| if value.abs() < radius { 0.0 } else { value };
+                Vec2::new(axial(xy.x), axial(xy.y))
+            }
+            DeadZoneShape::Radial { radius } => {
+                if xy.length() < radius {
+                    Vec2::ZERO
+                } else {
+                    xy
+                }
+            }
+            DeadZoneShape::ScaledRadial { radius, exponent } => {
+                let length = xy.length();
+                if length < radius || radius >= 1.0 {
+                    return Vec2::ZERO;
+                }
+
+                let scaled_length = ((length - radius) / (1.0 - radius)).powf(exponent);
+                xy / length * scaled_length
+            }
+            DeadZoneShape::Bowtie { radius } => {
+                let bowtie = |value: f32, other: f32| {
+                    let deadzone = (radius * other.abs()).min(1.0);
+                    if value.abs() < deadzone || deadzone >= 1.0 {
+                        0.0
+                    } else {
+                        value.signum() * (value.abs() - deadzone) / (1.0 - deadzone)
+                    }
+                };
+                Vec2::new(bowtie(xy.x, xy.y), bowtie(xy.y, xy.x))
+            }
+        }
+    }
+
+    /// The parameters of this dead zone, in a form that can be compared and hashed
+    fn key(&self) -> (u8, FloatOrd, FloatOrd) {
+        match *self {
+            DeadZoneShape::Axial { radius } => (0, FloatOrd(radius), FloatOrd(0.0)),
+            DeadZoneShape::Radial { radius } => (1, FloatOrd(radius), FloatOrd(0.0)),
+            DeadZoneShape::ScaledRadial { radius, exponent } => {
+                (2, FloatOrd(radius), FloatOrd(exponent))
+            }
+            DeadZoneShape::Bowtie { radius } => (3, FloatOrd(radius), FloatOrd(0.0)),
+        }
+    }
+}
+
+impl PartialEq for DeadZoneShape {
+    fn eq(&self, other: &Self) -> bool {
+        self.key() == other.key()
+    }
+}
+impl Eq for DeadZoneShape {}
+impl std::hash::Hash for DeadZoneShape {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.key().hash(state);
+    }
+}
+
 /// A wrapped [`Vec2`] that represents the combination of two input axes.
 ///
 /// The neutral origin is always at 0, 0.
@@ -649,10 +1144,7 @@ impl DualAxisData {
     #[must_use]
     #[inline]
     pub fn rotation(&self) -> Option<Rotation> {
-        match Rotation::from_xy(self.xy) {
-            Ok(rotation) => Some(rotation),
-            Err(_) => None,
-        }
+        Rotation::from_xy(self.xy).ok()
     }
 
     /// How far from the origin is this axis's position?
@@ -0,0 +1,78 @@
+//! Constructors for building large [`InputMap`]s programmatically, used by the benchmarks in `benches/`
+//!
+//! This module is only available with the `bench` feature, and is not intended for use in games.
+//! Every key produced here is identified by its [`ScanCode`], so there is no limit on how many distinct keys can be bound.
+
+use crate::input_map::{InputMap, MAX_BINDINGS_PER_ACTION};
+use crate::user_input::{InputKind, UserInput};
+use crate::Actionlike;
+
+use bevy::ecs::world::World;
+use bevy::input::keyboard::ScanCode;
+use bevy::input::Input;
+
+/// The maximum number of buttons in a single [`UserInput::Chord`]
+const MAX_CHORD_LENGTH: usize = 8;
+
+/// A distinct key for each `index`
+#[must_use]
+pub fn nth_key(index: usize) -> InputKind {
+    InputKind::KeyLocation(ScanCode(index as u32))
+}
+
+/// Creates an [`InputMap`] that binds `bindings_per_action` distinct keys to each action
+///
+/// The `j`th key bound to the action at [`Actionlike::index`] `i` is `nth_key(i * bindings_per_action + j)`,
+/// so no two actions share a key, and none of the bindings clash.
+///
+/// # Panics
+///
+/// Panics if `bindings_per_action` is greater than [`MAX_BINDINGS_PER_ACTION`].
+#[must_use]
+pub fn input_map_with_bindings<A: Actionlike>(bindings_per_action: usize) -> InputMap<A> {
+    assert!(bindings_per_action <= MAX_BINDINGS_PER_ACTION);
+
+    let mut input_map = InputMap::default();
+    for action in A::variants() {
+        for binding in 0..bindings_per_action {
+            let key = nth_key(action.index() * bindings_per_action + binding);
+            input_map.insert(key, action.clone());
+        }
+    }
+    input_map
+}
+
+/// Creates an [`InputMap`] in which the bindings of every action clash with those of the actions before it
+///
+/// The action at [`Actionlike::index`] `i` is bound to the chord of the keys `0..=i`,
+/// capped at the longest possible chord, so pressing [`clashing_keys`] presses every action at once.
+#[must_use]
+pub fn clashing_input_map<A: Actionlike>() -> InputMap<A> {
+    let mut input_map = InputMap::default();
+    for action in A::variants() {
+        let chord_length = (action.index() + 1).min(MAX_CHORD_LENGTH);
+        input_map.insert(UserInput::chord((0..chord_length).map(nth_key)), action);
+    }
+    input_map
+}
+
+/// The indexes of the keys that press every action of a [`clashing_input_map`]
+pub fn clashing_keys<A: Actionlike>() -> impl Iterator<Item = usize> {
+    0..A::n_variants().min(MAX_CHORD_LENGTH)
+}
+
+/// Presses the [`nth_key`] of each of the `indexes` in the `world`, until they are released with [`release_keys`]
+pub fn press_keys(world: &mut World, indexes: impl IntoIterator<Item = usize>) {
+    let mut scan_codes = world.resource_mut::<Input<ScanCode>>();
+    for index in indexes {
+        scan_codes.press(ScanCode(index as u32));
+    }
+}
+
+/// Releases the [`nth_key`] of each of the `indexes` in the `world`
+pub fn release_keys(world: &mut World, indexes: impl IntoIterator<Item = usize>) {
+    let mut scan_codes = world.resource_mut::<Input<ScanCode>>();
+    for index in indexes {
+        scan_codes.release(ScanCode(index as u32));
+    }
+}
@@ -0,0 +1,391 @@
+//! Capturing the next input that the player presses, for rebinding menus
+//!
+//! Call [`BindingCapture::start`] with the action to rebind,
+//! and the [`capture_bindings`](crate::systems::capture_bindings) system will wait for the player to press a binding.
+//! Every button that is held together is collected, and the binding is captured as soon as one of them is released,
+//! so chords like `Ctrl + S` can be bound by pressing them.
+//! Exactly one [`BindingCaptured`] event is then sent, with the [`CaptureOutcome`]:
+//! the captured input, or whether the capture was cancelled or timed out.
+//!
+//! Pressing one of the [`BindingCapture::cancel_inputs`] (`Escape` by default) cancels the capture,
+//! and so does calling [`BindingCapture::cancel`].
+//! If nothing is pressed before the [`BindingCapture::timeout`], the capture times out.
+//! Bindings that are [excluded](BindingCapture::exclude), like `Escape` and `Alt + F4`, are ignored, and the capture keeps waiting.
+//!
+//! The left and right variants of modifier keys are captured as a [`Modifier`], so that either of them can be used.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::binding_capture::{BindingCapture, BindingCaptured, CaptureOutcome};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! fn start_rebinding(mut capture: ResMut<BindingCapture<Action>>) {
+//!     capture.start(Action::Jump);
+//! }
+//!
+//! fn apply_rebinding(
+//!     mut events: EventReader<BindingCaptured<Action>>,
+//!     mut query: Query<&mut InputMap<Action>>,
+//! ) {
+//!     for event in events.iter() {
+//!         if let CaptureOutcome::Captured(input) = &event.outcome {
+//!             for mut input_map in query.iter_mut() {
+//!                 input_map.clear_action(event.action);
+//!                 input_map.insert(input.clone(), event.action);
+//!             }
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::user_input::{InputKind, Modifier, UserInput};
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Event, Resource};
+use bevy::input::keyboard::KeyCode;
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+/// How a binding capture started with [`BindingCapture::start`] ended
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[allow(clippy::large_enum_variant)]
+pub enum CaptureOutcome {
+    /// The player pressed this input
+    Captured(UserInput),
+    /// The player pressed one of the [`BindingCapture::cancel_inputs`], or [`BindingCapture::cancel`] was called
+    Cancelled,
+    /// Nothing was pressed before the [`BindingCapture::timeout`]
+    TimedOut,
+}
+
+/// Sent once each binding capture started with [`BindingCapture::start`] has ended
+///
+/// These events are generated by the [`capture_bindings`](crate::systems::capture_bindings) system.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct BindingCaptured<A: Actionlike> {
+    /// The action that the binding was captured for
+    pub action: A,
+    /// How the capture ended
+    pub outcome: CaptureOutcome,
+}
+
+/// The progress of a binding capture
+#[derive(Debug, Default, Clone, PartialEq)]
+pub enum CaptureState {
+    /// No binding is being captured
+    #[default]
+    Idle,
+    /// Waiting for the player to press a button
+    Waiting {
+        /// When the capture started, recorded by the first update after [`BindingCapture::start`]
+        started: Option<Instant>,
+    },
+    /// The player is holding buttons, and the binding will be captured once one of them is released
+    Holding {
+        /// The buttons that were pressed, in order
+        held: Vec<InputKind>,
+    },
+}
+
+/// The state machine that captures the next binding pressed by the player, for actions of type `A`
+///
+/// This resource is inserted by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin),
+/// and updated by the [`capture_bindings`](crate::systems::capture_bindings) system.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct BindingCapture<A: Actionlike> {
+    action: Option<A>,
+    state: CaptureState,
+    cancel_requested: bool,
+    timeout: Option<Duration>,
+    cancel_inputs: Vec<InputKind>,
+    excluded: Vec<UserInput>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for BindingCapture<A> {
+    fn default() -> Self {
+        Self {
+            action: None,
+            state: CaptureState::Idle,
+            cancel_requested: false,
+            timeout: Some(Duration::from_secs(5)),
+            cancel_inputs: vec![InputKind::Keyboard(KeyCode::Escape)],
+            excluded: [
+                UserInput::Single(InputKind::Keyboard(KeyCode::Escape)),
+                UserInput::modified(Modifier::Alt, KeyCode::F4),
+            ]
+            .into_iter()
+            .map(|mut input| {
+                input.canonicalize();
+                input
+            })
+            .collect(),
+        }
+    }
+}
+
+impl<A: Actionlike> BindingCapture<A> {
+    /// Starts capturing a binding for the `action`, abandoning any capture in progress without sending an event
+    pub fn start(&mut self, action: A) {
+        self.action = Some(action);
+        self.state = CaptureState::Waiting { started: None };
+        self.cancel_requested = false;
+    }
+
+    /// Cancels the capture in progress, if any
+    ///
+    /// A [`BindingCaptured`] event with [`CaptureOutcome::Cancelled`] will be sent during the next update.
+    pub fn cancel(&mut self) {
+        if self.action.is_some() {
+            self.cancel_requested = true;
+        }
+    }
+
+    /// Is a binding being captured?
+    #[must_use]
+    pub fn is_capturing(&self) -> bool {
+        self.action.is_some()
+    }
+
+    /// The action that a binding is being captured for, if any
+    #[must_use]
+    pub fn action(&self) -> Option<A> {
+        self.action.clone()
+    }
+
+    /// The progress of the capture
+    #[must_use]
+    pub fn state(&self) -> &CaptureState {
+        &self.state
+    }
+
+    /// How long to wait for the first press before timing out, if at all
+    ///
+    /// Defaults to 5 seconds.
+    #[must_use]
+    pub fn timeout(&self) -> Option<Duration> {
+        self.timeout
+    }
+
+    /// Sets how long to wait for the first press before timing out, or [`None`] to wait forever
+    pub fn set_timeout(&mut self, timeout: Option<Duration>) -> &mut Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// The inputs that cancel the capture when pressed before anything else
+    ///
+    /// Defaults to `Escape`.
+    #[must_use]
+    pub fn cancel_inputs(&self) -> &[InputKind] {
+        &self.cancel_inputs
+    }
+
+    /// Adds an input that cancels the capture when pressed before anything else
+    pub fn add_cancel_input(&mut self, input: impl Into<InputKind>) -> &mut Self {
+        let input = normalize(input.into());
+        if !self.cancel_inputs.contains(&input) {
+            self.cancel_inputs.push(input);
+        }
+        self
+    }
+
+    /// Removes all inputs that cancel the capture, so that it can only be cancelled with [`BindingCapture::cancel`]
+    pub fn clear_cancel_inputs(&mut self) -> &mut Self {
+        self.cancel_inputs.clear();
+        self
+    }
+
+    /// The bindings that can't be captured
+    ///
+    /// Defaults to `Escape` and `Alt + F4`.
+    #[must_use]
+    pub fn excluded(&self) -> &[UserInput] {
+        &self.excluded
+    }
+
+    /// Prevents the `input` from being captured
+    pub fn exclude(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        let mut input = input.into();
+        input.canonicalize();
+        if !self.excluded.contains(&input) {
+            self.excluded.push(input);
+        }
+        self
+    }
+
+    /// Allows the `input` to be captured again, if it was excluded
+    pub fn allow(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        let mut input = input.into();
+        input.canonicalize();
+        self.excluded.retain(|excluded| *excluded != input);
+        self
+    }
+
+    /// Advances the capture by a frame ending at the `current_instant`, during which the `just_pressed` and `just_released` buttons changed
+    ///
+    /// Returns the action and the outcome if the capture ended.
+    pub fn update(
+        &mut self,
+        just_pressed: impl IntoIterator<Item = InputKind>,
+        just_released: impl IntoIterator<Item = InputKind>,
+        current_instant: Instant,
+    ) -> Option<(A, CaptureOutcome)> {
+        self.action.as_ref()?;
+        if self.cancel_requested {
+            return self.finish(CaptureOutcome::Cancelled);
+        }
+
+        for input in just_pressed.into_iter().map(normalize) {
+            match &mut self.state {
+                CaptureState::Waiting { .. } if self.cancel_inputs.contains(&input) => {
+                    return self.finish(CaptureOutcome::Cancelled);
+                }
+                CaptureState::Waiting { .. } | CaptureState::Idle => {
+                    self.state = CaptureState::Holding { held: vec![input] };
+                }
+                CaptureState::Holding { held } => {
+                    if !held.contains(&input) {
+                        held.push(input);
+                    }
+                }
+            }
+        }
+
+        match &mut self.state {
+            CaptureState::Idle => None,
+            CaptureState::Waiting { started } => {
+                let started = *started.get_or_insert(current_instant);
+                let timed_out = self.timeout.is_some_and(|timeout| {
+                    current_instant.saturating_duration_since(started) >= timeout
+                });
+                if timed_out {
+                    self.finish(CaptureOutcome::TimedOut)
+                } else {
+                    None
+                }
+            }
+            CaptureState::Holding { held } => {
+                let released = just_released
+                    .into_iter()
+                    .map(normalize)
+                    .any(|input| held.contains(&input));
+                if !released {
+                    return None;
+                }
+
+                let mut input = match held.as_slice() {
+                    [single] => UserInput::Single(*single),
+                    _ => UserInput::chord(held.iter().copied()),
+                };
+                input.canonicalize();
+
+                if self.excluded.contains(&input) {
+                    // Keep waiting for another binding
+                    self.state = CaptureState::Waiting {
+                        started: Some(current_instant),
+                    };
+                    None
+                } else {
+                    self.finish(CaptureOutcome::Captured(input))
+                }
+            }
+        }
+    }
+
+    /// Ends the capture with the `outcome`
+    fn finish(&mut self, outcome: CaptureOutcome) -> Option<(A, CaptureOutcome)> {
+        self.state = CaptureState::Idle;
+        self.cancel_requested = false;
+        self.action.take().map(|action| (action, outcome))
+    }
+}
+
+/// Replaces the left and right variants of modifier keys with the corresponding [`Modifier`]
+fn normalize(input: InputKind) -> InputKind {
+    let InputKind::Keyboard(key_code) = input else {
+        return input;
+    };
+
+    [
+        Modifier::Alt,
+        Modifier::Control,
+        Modifier::Shift,
+        Modifier::Win,
+    ]
+    .into_iter()
+    .find(|modifier| modifier.key_codes().contains(&key_code))
+    .map_or(input, InputKind::Modifier)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Save,
+    }
+
+    fn key(key_code: KeyCode) -> Option<InputKind> {
+        Some(InputKind::Keyboard(key_code))
+    }
+
+    #[test]
+    fn chords_are_captured_on_release() {
+        let mut capture = BindingCapture::<Action>::default();
+        let now = Instant::now();
+        assert_eq!(capture.update(key(KeyCode::S), None, now), None);
+
+        capture.start(Action::Save);
+        assert_eq!(capture.update(key(KeyCode::ControlLeft), None, now), None);
+        assert_eq!(capture.update(key(KeyCode::S), None, now), None);
+
+        let (action, outcome) = capture.update(None, key(KeyCode::S), now).unwrap();
+        assert_eq!(action, Action::Save);
+        assert_eq!(
+            outcome,
+            CaptureOutcome::Captured(UserInput::modified(Modifier::Control, KeyCode::S))
+        );
+        assert!(!capture.is_capturing());
+    }
+
+    #[test]
+    fn captures_can_be_cancelled_or_time_out() {
+        let mut capture = BindingCapture::<Action>::default();
+        let start = Instant::now();
+
+        // Excluded bindings are ignored
+        capture.start(Action::Save);
+        capture.update(key(KeyCode::AltRight), None, start);
+        capture.update(key(KeyCode::F4), None, start);
+        assert_eq!(capture.update(None, key(KeyCode::F4), start), None);
+        assert!(capture.is_capturing());
+
+        assert_eq!(
+            capture.update(key(KeyCode::Escape), None, start),
+            Some((Action::Save, CaptureOutcome::Cancelled))
+        );
+
+        capture.start(Action::Save);
+        capture.cancel();
+        assert_eq!(
+            capture.update(None, None, start),
+            Some((Action::Save, CaptureOutcome::Cancelled))
+        );
+
+        capture.start(Action::Save);
+        assert_eq!(capture.update(None, None, start), None);
+        assert_eq!(
+            capture.update(None, None, start + Duration::from_secs(5)),
+            Some((Action::Save, CaptureOutcome::TimedOut))
+        );
+    }
+}
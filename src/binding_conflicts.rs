@@ -0,0 +1,269 @@
+//! Finding and classifying the conflicts that a new binding would cause, for rebinding menus
+//!
+//! Not every overlap between bindings is a mistake.
+//! Binding `Ctrl + S` to one action and `S` to another is a [clash](crate::clashing_inputs) that the [`ClashStrategy`](crate::clashing_inputs::ClashStrategy) resolves,
+//! and binding `Escape` to both a menu action and a gameplay action is harmless if they are never active at the same time.
+//! [`InputMap::binding_conflicts`] reports each conflict along with a [`ConflictKind`],
+//! so that rebinding menus can allow intentional overlaps while blocking true duplicates.
+//!
+//! Actions whose [`ActionInfo::category`](crate::action_metadata::ActionInfo::category) differ are assumed to never be active at the same time.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::binding_conflicts::{ConflictKind, ConflictResolution, ConflictSeverity};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     #[actionlike(category = "Gameplay")]
+//!     Save,
+//!     #[actionlike(category = "Gameplay")]
+//!     QuickSave,
+//!     #[actionlike(category = "Gameplay")]
+//!     Jump,
+//!     #[actionlike(category = "Menu")]
+//!     Confirm,
+//! }
+//!
+//! let input_map = InputMap::new([
+//!     (UserInput::from(KeyCode::S), Action::Save),
+//!     (KeyCode::Space.into(), Action::Jump),
+//! ]);
+//!
+//! // True duplicates should be blocked
+//! let conflicts = input_map.binding_conflicts(KeyCode::Space, Action::QuickSave);
+//! assert_eq!(conflicts[0].kind, ConflictKind::ExactDuplicate);
+//! assert_eq!(conflicts[0].kind.severity(), ConflictSeverity::Blocking);
+//!
+//! // Chords that contain another binding are resolved by the `ClashStrategy`
+//! let conflicts = input_map.binding_conflicts(UserInput::modified(Modifier::Control, KeyCode::S), Action::QuickSave);
+//! assert_eq!(conflicts[0].kind, ConflictKind::ChordSuperset);
+//! assert!(conflicts[0].kind.resolutions().contains(&ConflictResolution::KeepBoth));
+//!
+//! // Actions in other categories are never active at the same time
+//! let conflicts = input_map.binding_conflicts(KeyCode::Space, Action::Confirm);
+//! assert_eq!(conflicts[0].kind.severity(), ConflictSeverity::Benign);
+//! ```
+
+use crate::action_metadata::ActionMetadata;
+use crate::input_map::InputMap;
+use crate::user_input::UserInput;
+use crate::Actionlike;
+
+use serde::{Deserialize, Serialize};
+
+/// How a new binding overlaps with the existing binding of another action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ConflictKind {
+    /// The exact same input is bound to the other action, so both would always be pressed together
+    ExactDuplicate,
+    /// One of the inputs contains every button of the other, such as `Ctrl + S` and `S`
+    ///
+    /// These inputs clash, and are resolved by the [`ClashStrategy`](crate::clashing_inputs::ClashStrategy).
+    ChordSuperset,
+    /// The inputs overlap, but the actions belong to different categories that are not expected to be active at the same time
+    CrossCategory,
+}
+
+impl ConflictKind {
+    /// How serious this kind of conflict is
+    #[must_use]
+    pub fn severity(&self) -> ConflictSeverity {
+        match self {
+            ConflictKind::ExactDuplicate => ConflictSeverity::Blocking,
+            ConflictKind::ChordSuperset => ConflictSeverity::Warning,
+            ConflictKind::CrossCategory => ConflictSeverity::Benign,
+        }
+    }
+
+    /// The ways in which a rebinding menu could resolve this kind of conflict, from the most to the least recommended
+    #[must_use]
+    pub fn resolutions(&self) -> &'static [ConflictResolution] {
+        match self {
+            ConflictKind::ExactDuplicate => &[
+                ConflictResolution::ReplaceExisting,
+                ConflictResolution::Cancel,
+            ],
+            ConflictKind::ChordSuperset => &[
+                ConflictResolution::KeepBoth,
+                ConflictResolution::ReplaceExisting,
+                ConflictResolution::Cancel,
+            ],
+            ConflictKind::CrossCategory => &[
+                ConflictResolution::KeepBoth,
+                ConflictResolution::ReplaceExisting,
+            ],
+        }
+    }
+}
+
+/// How serious a [`ConflictKind`] is, ordered from the least to the most serious
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum ConflictSeverity {
+    /// The overlap is harmless, and can be allowed without asking the player
+    Benign,
+    /// The overlap is handled, but may not be what the player intended
+    Warning,
+    /// The new binding would make the actions indistinguishable, and should not be allowed as-is
+    Blocking,
+}
+
+/// A suggested resolution of a [`BindingConflict`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ConflictResolution {
+    /// Bind the new input, keeping the existing binding of the other action
+    KeepBoth,
+    /// Remove the existing binding from the other action, then bind the new input
+    ReplaceExisting,
+    /// Don't bind the new input
+    Cancel,
+}
+
+/// A conflict between a new binding and the existing binding of another action
+#[derive(Debug, Clone, PartialEq)]
+pub struct BindingConflict<A: Actionlike> {
+    /// The other action, that the conflicting input is already bound to
+    pub action: A,
+    /// The existing binding of the other action
+    pub existing: UserInput,
+    /// How the new binding overlaps with the existing one
+    pub kind: ConflictKind,
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// The conflicts that binding the `input` to the `action` would cause with the bindings of other actions
+    ///
+    /// The categories of actions are read from [`Actionlike::info`].
+    /// Use [`InputMap::binding_conflicts_with_metadata`] if they are edited at runtime.
+    #[must_use]
+    pub fn binding_conflicts(
+        &self,
+        input: impl Into<UserInput>,
+        action: A,
+    ) -> Vec<BindingConflict<A>> {
+        self.find_binding_conflicts(input.into(), action, |action| action.info().category)
+    }
+
+    /// The conflicts that binding the `input` to the `action` would cause with the bindings of other actions,
+    /// reading the categories of actions from the `metadata`
+    #[must_use]
+    pub fn binding_conflicts_with_metadata(
+        &self,
+        input: impl Into<UserInput>,
+        action: A,
+        metadata: &ActionMetadata<A>,
+    ) -> Vec<BindingConflict<A>> {
+        self.find_binding_conflicts(input.into(), action, |action| {
+            metadata.get(action).category.clone()
+        })
+    }
+
+    fn find_binding_conflicts(
+        &self,
+        mut input: UserInput,
+        action: A,
+        category: impl Fn(A) -> Option<String>,
+    ) -> Vec<BindingConflict<A>> {
+        input.canonicalize();
+        let action_category = category(action.clone());
+
+        let mut conflicts = Vec::new();
+        for other_action in A::variants() {
+            if other_action.index() == action.index() {
+                continue;
+            }
+
+            let mut other_category = None;
+            for existing in self.get(other_action.clone()).iter() {
+                let kind = if *existing == input {
+                    ConflictKind::ExactDuplicate
+                } else if existing.clashes(&input) {
+                    ConflictKind::ChordSuperset
+                } else {
+                    continue;
+                };
+
+                let other_category =
+                    other_category.get_or_insert_with(|| category(other_action.clone()));
+                let cross_category = matches!(
+                    (&action_category, &*other_category),
+                    (Some(a), Some(b)) if a != b
+                );
+
+                conflicts.push(BindingConflict {
+                    action: other_action.clone(),
+                    existing: existing.clone(),
+                    kind: if cross_category {
+                        ConflictKind::CrossCategory
+                    } else {
+                        kind
+                    },
+                });
+            }
+        }
+
+        // The most serious conflicts come first
+        conflicts.sort_by_key(|conflict| std::cmp::Reverse(conflict.kind.severity()));
+        conflicts
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::action_metadata::ActionInfo;
+    use crate::axislike::VirtualDPad;
+    use crate::user_input::Modifier;
+    use bevy::prelude::{KeyCode, Reflect};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Move,
+        Jump,
+        Save,
+        Back,
+    }
+
+    #[test]
+    fn conflicts_are_classified() {
+        let input_map = InputMap::new([
+            (UserInput::from(VirtualDPad::wasd()), Action::Move),
+            (KeyCode::Space.into(), Action::Jump),
+            (KeyCode::Escape.into(), Action::Back),
+        ]);
+
+        // Binding an action to its own input is not a conflict
+        assert!(input_map
+            .binding_conflicts(KeyCode::Space, Action::Jump)
+            .is_empty());
+        assert!(input_map
+            .binding_conflicts(KeyCode::Return, Action::Jump)
+            .is_empty());
+
+        let conflicts = input_map.binding_conflicts(KeyCode::Space, Action::Save);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].action, Action::Jump);
+        assert_eq!(conflicts[0].existing, KeyCode::Space.into());
+        assert_eq!(conflicts[0].kind, ConflictKind::ExactDuplicate);
+
+        let conflicts = input_map.binding_conflicts(
+            UserInput::modified(Modifier::Control, KeyCode::Space),
+            Action::Save,
+        );
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, ConflictKind::ChordSuperset);
+
+        // Categories can be assigned at runtime
+        let mut metadata = ActionMetadata::<Action>::default();
+        metadata.set(Action::Back, ActionInfo::new("Back").with_category("Menu"));
+        metadata.set(
+            Action::Save,
+            ActionInfo::new("Save").with_category("Gameplay"),
+        );
+        let conflicts =
+            input_map.binding_conflicts_with_metadata(KeyCode::Escape, Action::Save, &metadata);
+        assert_eq!(conflicts[0].kind, ConflictKind::CrossCategory);
+        assert_eq!(conflicts[0].kind.severity(), ConflictSeverity::Benign);
+    }
+}
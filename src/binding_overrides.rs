@@ -0,0 +1,219 @@
+//! Overriding bindings from a string or an environment variable, so that alternate bindings can be tested without editing code
+//!
+//! [`InputMap::apply_overrides_from_str`] parses a list of `Action=Input` entries, separated by semicolons or new lines.
+//! The first entry for an action replaces all of its bindings, and any later entries for the same action add to them.
+//! An entry without an input, like `Jump=`, removes every binding of the action.
+//!
+//! Inputs are made of one or more buttons joined by `+`, which are bound as a chord:
+//! - the [`Modifier`]s `Alt`, `Control` (or `Ctrl`), `Shift` and `Win`
+//! - the names of [`KeyCode`](bevy::input::keyboard::KeyCode) variants, like `Space`, `W` or `Key1`
+//! - [`MouseButton`]s prefixed with `Mouse:`, like `Mouse:Left`
+//! - [`GamepadButtonType`]s prefixed with `Gamepad:`, like `Gamepad:South`
+//!
+//! Actions are matched by the name of their variant.
+//!
+//! During development, [`InputMap::apply_overrides_from_env`] reads the same format from an environment variable,
+//! so QA can try out different bindings by launching the game with something like `BINDINGS="Jump=Gamepad:East;Dash=Shift+Space"`.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use serde::Deserialize;
+//!
+//! #[derive(Actionlike, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Dash,
+//! }
+//!
+//! let mut input_map = InputMap::new([(KeyCode::Space, Action::Jump), (KeyCode::ShiftLeft, Action::Dash)]);
+//! input_map
+//!     .apply_overrides_from_str("Jump=Gamepad:South; Jump=Mouse:Right; Dash=Ctrl+D")
+//!     .unwrap();
+//!
+//! assert_eq!(input_map.get(Action::Jump).len(), 2);
+//! assert!(input_map.get(Action::Dash).contains(&UserInput::modified(Modifier::Control, KeyCode::D)));
+//! ```
+
+use crate::errors::InputManagerError;
+use crate::input_map::InputMap;
+use crate::user_input::{InputKind, Modifier, UserInput};
+use crate::Actionlike;
+
+use bevy::input::gamepad::GamepadButtonType;
+use bevy::input::keyboard::KeyCode;
+use bevy::input::mouse::MouseButton;
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::DeserializeOwned;
+
+impl<A: Actionlike + DeserializeOwned> InputMap<A> {
+    /// Replaces the bindings of the actions listed in the `overrides`, a list of `Action=Input` entries
+    ///
+    /// See the [module documentation](crate::binding_overrides) for the format of the entries.
+    /// If any entry is invalid, an error is returned and the map is left unchanged.
+    pub fn apply_overrides_from_str(
+        &mut self,
+        overrides: &str,
+    ) -> Result<&mut Self, InputManagerError> {
+        let entries = overrides
+            .split([';', '\n'])
+            .map(str::trim)
+            .filter(|entry| !entry.is_empty())
+            .map(parse_entry::<A>)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut overridden: Vec<usize> = Vec::new();
+        for (action, input) in entries {
+            if !overridden.contains(&action.index()) {
+                overridden.push(action.index());
+                self.clear_action(action.clone());
+            }
+            if let Some(input) = input {
+                self.insert(input, action);
+            }
+        }
+
+        Ok(self)
+    }
+
+    /// Replaces the bindings of the actions listed in the environment `variable`, if it is set
+    ///
+    /// See [`InputMap::apply_overrides_from_str`] for details.
+    pub fn apply_overrides_from_env(
+        &mut self,
+        variable: &str,
+    ) -> Result<&mut Self, InputManagerError> {
+        match std::env::var(variable) {
+            Ok(overrides) => self.apply_overrides_from_str(&overrides),
+            Err(_) => Ok(self),
+        }
+    }
+}
+
+/// Parses an `Action=Input` entry, where the input may be empty
+fn parse_entry<A: Actionlike + DeserializeOwned>(
+    entry: &str,
+) -> Result<(A, Option<UserInput>), InputManagerError> {
+    let Some((action, input)) = entry.split_once('=') else {
+        return Err(InputManagerError::MalformedOverride {
+            entry: entry.to_string(),
+        });
+    };
+
+    let action = action.trim();
+    let action = deserialize_name::<A>(action).ok_or_else(|| InputManagerError::UnknownAction {
+        name: action.to_string(),
+    })?;
+
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok((action, None));
+    }
+
+    let buttons = input
+        .split('+')
+        .map(|button| parse_button(button.trim()))
+        .collect::<Result<Vec<_>, _>>()?;
+    let input = match buttons.as_slice() {
+        [button] => UserInput::Single(*button),
+        _ => UserInput::chord(buttons),
+    };
+    Ok((action, Some(input)))
+}
+
+/// Parses the name of a single button
+fn parse_button(name: &str) -> Result<InputKind, InputManagerError> {
+    let button = if let Some(button) = name.strip_prefix("Mouse:") {
+        deserialize_name::<MouseButton>(button).map(InputKind::Mouse)
+    } else if let Some(button) = name.strip_prefix("Gamepad:") {
+        deserialize_name::<GamepadButtonType>(button).map(InputKind::GamepadButton)
+    } else if name == "Ctrl" {
+        Some(InputKind::Modifier(Modifier::Control))
+    } else {
+        deserialize_name::<Modifier>(name)
+            .map(InputKind::Modifier)
+            .or_else(|| deserialize_name::<KeyCode>(name).map(InputKind::Keyboard))
+    };
+
+    button.ok_or_else(|| InputManagerError::UnknownInput {
+        name: name.to_string(),
+    })
+}
+
+/// Deserializes a unit variant from its name
+fn deserialize_name<T: DeserializeOwned>(name: &str) -> Option<T> {
+    T::deserialize(StrDeserializer::<ValueError>::new(name)).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use serde::Deserialize;
+
+    #[derive(Actionlike, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Run,
+        Shoot,
+    }
+
+    #[test]
+    fn overrides_replace_bindings() {
+        let mut input_map = InputMap::new([
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::ShiftLeft, Action::Run),
+            (KeyCode::F, Action::Shoot),
+        ]);
+
+        input_map
+            .apply_overrides_from_str(
+                "Jump = Gamepad:South\n Jump=Ctrl+Shift+J; Shoot=Mouse:Left;\nRun=",
+            )
+            .unwrap();
+
+        let jump: Vec<_> = input_map.get(Action::Jump).iter().cloned().collect();
+        assert_eq!(
+            jump,
+            [
+                UserInput::Single(InputKind::GamepadButton(GamepadButtonType::South)),
+                UserInput::chord([
+                    InputKind::Modifier(Modifier::Control),
+                    InputKind::Modifier(Modifier::Shift),
+                    InputKind::Keyboard(KeyCode::J),
+                ]),
+            ]
+        );
+        assert!(input_map.get(Action::Run).is_empty());
+        assert!(input_map
+            .get(Action::Shoot)
+            .contains(&UserInput::Single(InputKind::Mouse(MouseButton::Left))));
+    }
+
+    #[test]
+    fn invalid_overrides_are_rejected() {
+        let mut input_map = InputMap::new([(KeyCode::Space, Action::Jump)]);
+        let original = input_map.clone();
+
+        assert_eq!(
+            input_map.apply_overrides_from_str("Jump=W; Fly=Space"),
+            Err(InputManagerError::UnknownAction {
+                name: "Fly".to_string()
+            })
+        );
+        assert_eq!(
+            input_map.apply_overrides_from_str("Jump=Mouse:Middle+Nothing"),
+            Err(InputManagerError::UnknownInput {
+                name: "Nothing".to_string()
+            })
+        );
+        assert_eq!(
+            input_map.apply_overrides_from_str("Jump"),
+            Err(InputManagerError::MalformedOverride {
+                entry: "Jump".to_string()
+            })
+        );
+        assert_eq!(input_map, original);
+    }
+}
@@ -0,0 +1,326 @@
+//! Separate saved bindings for each kind of controller, selected automatically when a controller connects
+//!
+//! Players who own several controllers often want different bindings for each of them:
+//! the buttons within easy reach differ between a PS5 controller and an Xbox pad.
+//! Store the bindings for each controller in a [`BindingProfiles`] component or resource, keyed by its [`ControllerIdentity`].
+//! Whenever a gamepad connects, the [`apply_binding_profiles`](crate::systems::apply_binding_profiles) system
+//! replaces the [`InputMap`] that uses this gamepad with the matching profile,
+//! and sends a [`BindingProfileSelected`] event.
+//!
+//! Bevy only reports the name of each controller, so the [`GamepadVendor`] is guessed from that name.
+//! Profiles are matched by the exact name of the controller first, and by its vendor otherwise.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::binding_profiles::{BindingProfiles, ControllerIdentity, GamepadVendor};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! let mut profiles = BindingProfiles::<Action>::default();
+//! profiles.insert(
+//!     ControllerIdentity::new("DualSense Wireless Controller"),
+//!     InputMap::new([(GamepadButtonType::East, Action::Jump)]),
+//! );
+//! profiles.insert(
+//!     ControllerIdentity::new("Xbox Wireless Controller"),
+//!     InputMap::new([(GamepadButtonType::South, Action::Jump)]),
+//! );
+//!
+//! // Other Sony controllers fall back to the DualSense profile
+//! let dualshock = ControllerIdentity::new("Sony DualShock 4");
+//! assert_eq!(dualshock.vendor(), GamepadVendor::Sony);
+//! assert_eq!(
+//!     profiles.get(&dualshock).unwrap().get(Action::Jump).len(),
+//!     1
+//! );
+//!
+//! fn spawn_player(mut commands: Commands) {
+//!     commands.spawn((
+//!         InputManagerBundle::<Action>::default(),
+//!         BindingProfiles::<Action>::default(),
+//!     ));
+//! }
+//! ```
+
+use crate::input_map::InputMap;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity, Event, Resource};
+use bevy::input::gamepad::{Gamepad, Gamepads};
+use serde::{Deserialize, Serialize};
+
+/// The company that made a controller, as guessed from its name
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum GamepadVendor {
+    /// Sony controllers, for the PS3, PS4 and PS5
+    Sony,
+    /// Xbox controllers
+    Microsoft,
+    /// Switch controllers, like the Pro Controller and the Joy-Cons
+    Nintendo,
+    /// Any other controller
+    #[default]
+    Other,
+}
+
+impl GamepadVendor {
+    /// Guesses the vendor of a controller from its `name`
+    #[must_use]
+    pub fn from_name(name: &str) -> Self {
+        let name = name.to_lowercase();
+        let contains_any =
+            |patterns: &[&str]| patterns.iter().any(|pattern| name.contains(pattern));
+
+        if contains_any(&[
+            "dualsense",
+            "dualshock",
+            "playstation",
+            "sony",
+            "ps3",
+            "ps4",
+            "ps5",
+        ]) {
+            GamepadVendor::Sony
+        } else if contains_any(&["xbox", "x-box", "microsoft", "xinput"]) {
+            GamepadVendor::Microsoft
+        } else if contains_any(&["nintendo", "switch", "joy-con", "pro controller"]) {
+            GamepadVendor::Nintendo
+        } else {
+            GamepadVendor::Other
+        }
+    }
+}
+
+/// Identifies a kind of controller, so that it can be given its own [`BindingProfiles`] entry
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct ControllerIdentity {
+    name: String,
+    vendor: GamepadVendor,
+}
+
+impl ControllerIdentity {
+    /// Identifies a controller by its `name`, guessing its vendor from it
+    #[must_use]
+    pub fn new(name: impl Into<String>) -> Self {
+        let name = name.into();
+        let vendor = GamepadVendor::from_name(&name);
+        Self { name, vendor }
+    }
+
+    /// Identifies the connected `gamepad`, returning [`None`] if it is not connected
+    #[must_use]
+    pub fn of(gamepad: Gamepad, gamepads: &Gamepads) -> Option<Self> {
+        gamepads.name(gamepad).map(Self::new)
+    }
+
+    /// The name that the controller reports
+    #[must_use]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The company that made the controller, as guessed from its name
+    #[must_use]
+    pub fn vendor(&self) -> GamepadVendor {
+        self.vendor
+    }
+}
+
+/// The bindings saved for each kind of controller, for actions of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`InputMap`] that it sets.
+#[derive(Resource, Component, Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "InputMap<A>: Serialize",
+    deserialize = "InputMap<A>: Deserialize<'de>"
+))]
+pub struct BindingProfiles<A: Actionlike> {
+    profiles: Vec<(ControllerIdentity, InputMap<A>)>,
+    #[serde(skip)]
+    active: Option<ControllerIdentity>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for BindingProfiles<A> {
+    fn default() -> Self {
+        Self {
+            profiles: Vec::new(),
+            active: None,
+        }
+    }
+}
+
+impl<A: Actionlike> BindingProfiles<A> {
+    /// Saves the `input_map` as the profile of the controller with this `identity`, returning the profile that it replaced
+    pub fn insert(
+        &mut self,
+        identity: ControllerIdentity,
+        input_map: InputMap<A>,
+    ) -> Option<InputMap<A>> {
+        match self
+            .profiles
+            .iter_mut()
+            .find(|(existing, _)| existing.name == identity.name)
+        {
+            Some((_, existing)) => Some(std::mem::replace(existing, input_map)),
+            None => {
+                self.profiles.push((identity, input_map));
+                None
+            }
+        }
+    }
+
+    /// Removes the profile of the controller with this `identity`, returning it if it existed
+    pub fn remove(&mut self, identity: &ControllerIdentity) -> Option<InputMap<A>> {
+        let index = self
+            .profiles
+            .iter()
+            .position(|(existing, _)| existing.name == identity.name)?;
+        Some(self.profiles.remove(index).1)
+    }
+
+    /// The profile to use for the controller with this `identity`
+    ///
+    /// This is the profile saved for a controller with the same name if there is one,
+    /// or else the first profile saved for a controller from the same known [`GamepadVendor`].
+    #[must_use]
+    pub fn get(&self, identity: &ControllerIdentity) -> Option<&InputMap<A>> {
+        self.profiles
+            .iter()
+            .find(|(existing, _)| existing.name == identity.name)
+            .or_else(|| {
+                self.profiles.iter().find(|(existing, _)| {
+                    identity.vendor != GamepadVendor::Other && existing.vendor == identity.vendor
+                })
+            })
+            .map(|(_, input_map)| input_map)
+    }
+
+    /// Iterates over the saved profiles, in the order they were inserted
+    pub fn iter(&self) -> impl Iterator<Item = (&ControllerIdentity, &InputMap<A>)> {
+        self.profiles
+            .iter()
+            .map(|(identity, input_map)| (identity, input_map))
+    }
+
+    /// The controller whose profile was last applied by the [`apply_binding_profiles`](crate::systems::apply_binding_profiles) system, if any
+    #[must_use]
+    pub fn active(&self) -> Option<&ControllerIdentity> {
+        self.active.as_ref()
+    }
+
+    /// Applies the profile of the `gamepad` with this `identity` to the `input_map`, keeping its associated gamepad
+    ///
+    /// Returns `false` if there is no matching profile, in which case the `input_map` is left unchanged.
+    pub fn apply(&mut self, identity: &ControllerIdentity, input_map: &mut InputMap<A>) -> bool {
+        let Some(profile) = self.get(identity) else {
+            return false;
+        };
+
+        let associated_gamepad = input_map.gamepad();
+        *input_map = profile.clone();
+        match associated_gamepad {
+            Some(gamepad) => input_map.set_gamepad(gamepad),
+            None => input_map.clear_gamepad(),
+        };
+        self.active = Some(identity.clone());
+        true
+    }
+}
+
+/// Sent when a [`BindingProfiles`] entry was applied because its controller connected
+///
+/// These events are generated by the [`apply_binding_profiles`](crate::systems::apply_binding_profiles) system.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct BindingProfileSelected<A: Actionlike> {
+    /// The gamepad that connected
+    pub gamepad: Gamepad,
+    /// The identity of the connected controller
+    pub identity: ControllerIdentity,
+    /// The entity whose [`InputMap`] was set, or [`None`] for the resource
+    pub entity: Option<Entity>,
+    _phantom: std::marker::PhantomData<A>,
+}
+
+impl<A: Actionlike> BindingProfileSelected<A> {
+    /// Creates an event for a profile applied to the `entity` when the `gamepad` with this `identity` connected
+    #[must_use]
+    pub fn new(gamepad: Gamepad, identity: ControllerIdentity, entity: Option<Entity>) -> Self {
+        Self {
+            gamepad,
+            identity,
+            entity,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::input::gamepad::GamepadButtonType;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+    }
+
+    #[test]
+    fn vendors_are_guessed() {
+        assert_eq!(
+            GamepadVendor::from_name("DualSense Wireless Controller"),
+            GamepadVendor::Sony
+        );
+        assert_eq!(
+            GamepadVendor::from_name("Xbox Series X Controller"),
+            GamepadVendor::Microsoft
+        );
+        assert_eq!(
+            GamepadVendor::from_name("Nintendo Switch Pro Controller"),
+            GamepadVendor::Nintendo
+        );
+        assert_eq!(
+            GamepadVendor::from_name("8BitDo SN30 Pro"),
+            GamepadVendor::Other
+        );
+    }
+
+    #[test]
+    fn profiles_are_matched_by_name_then_vendor() {
+        let mut profiles = BindingProfiles::<Action>::default();
+        let dualsense = ControllerIdentity::new("DualSense Wireless Controller");
+        let sony_map = InputMap::new([(GamepadButtonType::East, Action::Jump)]);
+        profiles.insert(dualsense.clone(), sony_map.clone());
+        profiles.insert(
+            ControllerIdentity::new("Generic USB Gamepad"),
+            InputMap::new([(GamepadButtonType::North, Action::Jump)]),
+        );
+
+        assert_eq!(profiles.get(&dualsense), Some(&sony_map));
+        assert_eq!(
+            profiles.get(&ControllerIdentity::new("PS4 Controller")),
+            Some(&sony_map)
+        );
+        // Unknown vendors are only matched by name
+        assert_eq!(
+            profiles.get(&ControllerIdentity::new("Other USB Gamepad")),
+            None
+        );
+
+        let mut input_map = InputMap::<Action>::default();
+        input_map.set_gamepad(Gamepad { id: 3 });
+        assert!(profiles.apply(&dualsense, &mut input_map));
+        assert_eq!(input_map.gamepad(), Some(Gamepad { id: 3 }));
+        assert_eq!(input_map.get(Action::Jump).len(), 1);
+        assert_eq!(profiles.active(), Some(&dualsense));
+
+        assert!(profiles.remove(&dualsense).is_some());
+        assert!(profiles.get(&dualsense).is_none());
+    }
+}
@@ -0,0 +1,154 @@
+//! Temporarily preventing inputs from reaching actions, for stuns, cutscenes and other losses of control
+//!
+//! Entities with a [`BlockInput`] component are skipped by the [`update_action_state`](crate::systems::update_action_state) system,
+//! so their [`InputMap`](crate::input_map::InputMap) has no effect until the component is removed.
+//! Pushing a reason onto the [`InputBlockers`] resource blocks every entity and the [`ActionState`](crate::action_state::ActionState) resource at once,
+//! until each reason has been removed again.
+//!
+//! The actions of newly blocked [`ActionState`](crate::action_state::ActionState)s are released by the [`release_blocked_actions`](crate::systems::release_blocked_actions) system,
+//! so that actions aren't held down for the duration of the block.
+//! Actions can still be pressed manually, by an [`ActionStateDriver`](crate::action_state::ActionStateDriver) or over the network.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::block_input::{BlockInput, InputBlockers};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Attack,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Stunned;
+//!
+//! // Stunned entities ignore their inputs
+//! fn stun(mut commands: Commands, query: Query<Entity, Added<Stunned>>) {
+//!     for entity in query.iter() {
+//!         commands.entity(entity).insert(BlockInput::<Action>::default());
+//!     }
+//! }
+//!
+//! // Every entity ignores its inputs during cutscenes
+//! fn start_cutscene(mut blockers: ResMut<InputBlockers<Action>>) {
+//!     blockers.push("cutscene");
+//! }
+//!
+//! fn end_cutscene(mut blockers: ResMut<InputBlockers<Action>>) {
+//!     blockers.remove("cutscene");
+//! }
+//! ```
+
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::borrow::Cow;
+use std::marker::PhantomData;
+
+/// Prevents the [`InputMap`](crate::input_map::InputMap) of this entity from updating its [`ActionState`](crate::action_state::ActionState) for actions of type `A`
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BlockInput<A: Actionlike> {
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for BlockInput<A> {
+    fn default() -> Self {
+        Self {
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// A stack of reasons for which inputs are blocked for every entity with actions of type `A`
+///
+/// Inputs are blocked for as long as any reason remains,
+/// so that overlapping blocks, such as a cutscene that starts during a dialog, don't unblock each other.
+/// This resource is inserted by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct InputBlockers<A: Actionlike> {
+    reasons: Vec<Cow<'static, str>>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for InputBlockers<A> {
+    fn default() -> Self {
+        Self {
+            reasons: Vec::new(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> InputBlockers<A> {
+    /// Blocks inputs until the `reason` is removed
+    pub fn push(&mut self, reason: impl Into<Cow<'static, str>>) {
+        self.reasons.push(reason.into());
+    }
+
+    /// Removes the most recently pushed reason, returning it if there was one
+    pub fn pop(&mut self) -> Option<Cow<'static, str>> {
+        self.reasons.pop()
+    }
+
+    /// Removes the most recently pushed copy of the `reason`, returning whether it was present
+    pub fn remove(&mut self, reason: &str) -> bool {
+        match self.reasons.iter().rposition(|blocker| blocker == reason) {
+            Some(index) => {
+                self.reasons.remove(index);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes every reason, unblocking inputs
+    pub fn clear(&mut self) {
+        self.reasons.clear();
+    }
+
+    /// Are inputs currently blocked?
+    #[must_use]
+    pub fn is_blocked(&self) -> bool {
+        !self.reasons.is_empty()
+    }
+
+    /// The reasons that inputs are blocked for, from the oldest to the most recent
+    pub fn reasons(&self) -> impl Iterator<Item = &str> {
+        self.reasons.iter().map(|reason| reason.as_ref())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Attack,
+    }
+
+    #[test]
+    fn overlapping_blocks() {
+        let mut blockers = InputBlockers::<Action>::default();
+        assert!(!blockers.is_blocked());
+
+        blockers.push("dialog");
+        blockers.push(String::from("cutscene"));
+        assert_eq!(
+            blockers.reasons().collect::<Vec<_>>(),
+            vec!["dialog", "cutscene"]
+        );
+
+        assert!(blockers.remove("dialog"));
+        assert!(!blockers.remove("dialog"));
+        assert!(blockers.is_blocked());
+
+        assert_eq!(blockers.pop().as_deref(), Some("cutscene"));
+        assert!(!blockers.is_blocked());
+        assert_eq!(blockers.pop(), None);
+    }
+}
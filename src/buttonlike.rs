@@ -1,6 +1,11 @@
 //! Tools for working with button-like user inputs (mouse clicks, gamepad button, keyboard inputs and so on)
 //!
+use crate::errors::{report, InputManagerError};
+
+use bevy::input::gamepad::{GamepadAxisType, GamepadButtonType};
 use bevy::reflect::Reflect;
+use bevy::utils::{FloatOrd, HashMap};
+use leafwing_input_manager_core::ButtonState as CoreButtonState;
 use serde::{Deserialize, Serialize};
 
 /// The current state of a particular button,
@@ -84,6 +89,28 @@ impl ButtonState {
     }
 }
 
+impl From<ButtonState> for CoreButtonState {
+    fn from(state: ButtonState) -> Self {
+        match state {
+            ButtonState::JustPressed => CoreButtonState::JustPressed,
+            ButtonState::Pressed => CoreButtonState::Pressed,
+            ButtonState::JustReleased => CoreButtonState::JustReleased,
+            ButtonState::Released => CoreButtonState::Released,
+        }
+    }
+}
+
+impl From<CoreButtonState> for ButtonState {
+    fn from(state: CoreButtonState) -> Self {
+        match state {
+            CoreButtonState::JustPressed => ButtonState::JustPressed,
+            CoreButtonState::Pressed => ButtonState::Pressed,
+            CoreButtonState::JustReleased => ButtonState::JustReleased,
+            CoreButtonState::Released => ButtonState::Released,
+        }
+    }
+}
+
 /// A buttonlike-input triggered by [`MouseWheel`](bevy::input::mouse::MouseWheel) events
 ///
 /// These will be considered pressed if non-zero net movement in the correct direction is detected.
@@ -113,3 +140,87 @@ pub enum MouseMotionDirection {
     /// Corresponds to `-x`
     Left,
 }
+
+/// Overrides for the values at which individual analog gamepad inputs are considered pressed
+///
+/// By default, gamepad buttons are pressed according to the global [`GamepadSettings`](bevy::input::gamepad::GamepadSettings),
+/// and gamepad axes are triggered according to the zones of each [`SingleAxis`](crate::axislike::SingleAxis) binding.
+/// Thresholds stored here take priority over these, allowing triggers and sticks to use different press points.
+///
+/// These are typically configured via an [`InputMap`](crate::input_map::InputMap).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ButtonThresholds {
+    #[serde(with = "crate::serde_helpers::float_ord_pairs")]
+    buttons: HashMap<GamepadButtonType, FloatOrd>,
+    #[serde(with = "crate::serde_helpers::float_ord_pairs")]
+    axes: HashMap<GamepadAxisType, FloatOrd>,
+}
+
+impl ButtonThresholds {
+    /// Returns the threshold at which the `button` is considered pressed, if overridden
+    #[must_use]
+    pub fn button(&self, button: GamepadButtonType) -> Option<f32> {
+        self.buttons.get(&button).map(|threshold| threshold.0)
+    }
+
+    /// Sets the analog value at or above which the `button` is considered pressed
+    ///
+    /// Buttons which do not report an analog value will fall back to their digital state.
+    /// Thresholds outside of `0.0..=1.0` are ignored, and an [`InputManagerError`] is [reported](crate::errors).
+    pub fn set_button(&mut self, button: GamepadButtonType, threshold: f32) -> &mut Self {
+        match InputManagerError::check_threshold(threshold) {
+            Ok(threshold) => {
+                self.buttons.insert(button, FloatOrd(threshold));
+            }
+            Err(error) => report(error),
+        }
+        self
+    }
+
+    /// Removes the threshold override of the `button`
+    pub fn clear_button(&mut self, button: GamepadButtonType) -> &mut Self {
+        self.buttons.remove(&button);
+        self
+    }
+
+    /// Returns the threshold beyond which the `axis` is considered triggered, if overridden
+    #[must_use]
+    pub fn axis(&self, axis: GamepadAxisType) -> Option<f32> {
+        self.axes.get(&axis).map(|threshold| threshold.0)
+    }
+
+    /// Sets the absolute value beyond which the `axis` is considered triggered
+    ///
+    /// This replaces the deadzone of every [`SingleAxis`](crate::axislike::SingleAxis) binding of this axis.
+    /// Ranged trigger zones, such as those created by [`SingleAxis::positive_range`](crate::axislike::SingleAxis::positive_range),
+    /// are left untouched.
+    /// Thresholds outside of `0.0..=1.0` are ignored, and an [`InputManagerError`] is [reported](crate::errors).
+    pub fn set_axis(&mut self, axis: GamepadAxisType, threshold: f32) -> &mut Self {
+        match InputManagerError::check_threshold(threshold) {
+            Ok(threshold) => {
+                self.axes.insert(axis, FloatOrd(threshold));
+            }
+            Err(error) => report(error),
+        }
+        self
+    }
+
+    /// Removes the threshold override of the `axis`
+    pub fn clear_axis(&mut self, axis: GamepadAxisType) -> &mut Self {
+        self.axes.remove(&axis);
+        self
+    }
+
+    /// Adds all of the overrides of `other` to these thresholds, replacing any existing overrides for the same inputs
+    pub fn extend(&mut self, other: &ButtonThresholds) -> &mut Self {
+        self.buttons.extend(other.buttons.iter());
+        self.axes.extend(other.axes.iter());
+        self
+    }
+
+    /// Are there no overrides stored?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.buttons.is_empty() && self.axes.is_empty()
+    }
+}
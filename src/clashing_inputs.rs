@@ -7,6 +7,8 @@ use crate::input_streams::InputStreams;
 use crate::user_input::{InputKind, UserInput};
 use crate::Actionlike;
 
+#[cfg(feature = "trace")]
+use bevy::log::info_span;
 use bevy::prelude::Resource;
 use itertools::Itertools;
 use petitset::PetitSet;
@@ -56,36 +58,104 @@ impl ClashStrategy {
 impl UserInput {
     /// Does `self` clash with `other`?
     #[must_use]
-    fn clashes(&self, other: &UserInput) -> bool {
+    pub(crate) fn clashes(&self, other: &UserInput) -> bool {
         use UserInput::*;
 
+        // An `Unless` binding can't be pressed at the same time as inputs that require its blocker
+        if let Unless { input, blocker } = self {
+            return !other.requires(blocker) && input.clashes(other);
+        }
+        if let Unless { input, blocker } = other {
+            return !self.requires(blocker) && self.clashes(input);
+        }
+
+        // An `Any` expression clashes whenever one of its alternatives does
+        if let Any(inputs) = self {
+            return inputs.iter().any(|input| input.clashes(other));
+        }
+        if let Any(inputs) = other {
+            return inputs.iter().any(|input| self.clashes(input));
+        }
+
+        // An `All` expression made only of buttons behaves like a chord
+        if let All(_) = self {
+            return match self.flattened_chord() {
+                Some(chord) => Chord(chord).clashes(other),
+                None => false,
+            };
+        }
+        if let All(_) = other {
+            return match other.flattened_chord() {
+                Some(chord) => self.clashes(&Chord(chord)),
+                None => false,
+            };
+        }
+
         match self {
             Single(self_button) => match other {
                 Single(_) => false,
                 Chord(other_chord) => button_chord_clash(self_button, other_chord),
                 VirtualDPad(other_dpad) => dpad_button_clash(other_dpad, self_button),
                 VirtualAxis(other_axis) => virtual_axis_button_clash(other_axis, self_button),
+                WindowEvent(_) | Custom(_) | All(_) | Any(_) | Unless { .. } => false,
             },
             Chord(self_chord) => match other {
                 Single(other_button) => button_chord_clash(other_button, self_chord),
                 Chord(other_chord) => chord_chord_clash(self_chord, other_chord),
                 VirtualDPad(other_dpad) => dpad_chord_clash(other_dpad, self_chord),
                 VirtualAxis(other_axis) => virtual_axis_chord_clash(other_axis, self_chord),
+                WindowEvent(_) | Custom(_) | All(_) | Any(_) | Unless { .. } => false,
             },
             VirtualDPad(self_dpad) => match other {
                 Single(other_button) => dpad_button_clash(self_dpad, other_button),
                 Chord(other_chord) => dpad_chord_clash(self_dpad, other_chord),
                 VirtualDPad(other_dpad) => dpad_dpad_clash(self_dpad, other_dpad),
                 VirtualAxis(other_axis) => virtual_axis_dpad_clash(other_axis, self_dpad),
+                WindowEvent(_) | Custom(_) | All(_) | Any(_) | Unless { .. } => false,
             },
             VirtualAxis(self_axis) => match other {
                 Single(other_button) => virtual_axis_button_clash(self_axis, other_button),
                 Chord(other_chord) => virtual_axis_chord_clash(self_axis, other_chord),
                 VirtualDPad(other_dpad) => virtual_axis_dpad_clash(self_axis, other_dpad),
                 VirtualAxis(other_axis) => virtual_axis_virtual_axis_clash(self_axis, other_axis),
+                WindowEvent(_) | Custom(_) | All(_) | Any(_) | Unless { .. } => false,
             },
+            // Window events and custom inputs are not made of buttons, so they can never clash
+            WindowEvent(_) | Custom(_) | All(_) | Any(_) | Unless { .. } => false,
+        }
+    }
+
+    /// Can `self` only be pressed while all of the buttons of the `blocker` are held?
+    fn requires(&self, blocker: &UserInput) -> bool {
+        match (self.flattened_chord(), blocker.flattened_chord()) {
+            (Some(chord), Some(blocker)) => blocker.iter().all(|button| chord.contains(button)),
+            _ => false,
         }
     }
+
+    /// The buttons of an input made only of buttons, chords and [`All`](UserInput::All) expressions, as a single chord
+    fn flattened_chord(&self) -> Option<PetitSet<InputKind, 8>> {
+        let mut chord = PetitSet::default();
+        let mut stack = vec![self];
+        while let Some(input) = stack.pop() {
+            let buttons: Vec<InputKind> = match input {
+                UserInput::Single(button) => vec![*button],
+                UserInput::Chord(buttons) => buttons.iter().copied().collect(),
+                UserInput::All(inputs) => {
+                    stack.extend(inputs);
+                    continue;
+                }
+                _ => return None,
+            };
+            for button in buttons {
+                if !chord.contains(&button) && chord.len() == chord.capacity() {
+                    return None;
+                }
+                chord.insert(button);
+            }
+        }
+        Some(chord)
+    }
 }
 
 impl<A: Actionlike> InputMap<A> {
@@ -98,6 +168,9 @@ impl<A: Actionlike> InputMap<A> {
         input_streams: &InputStreams,
         clash_strategy: ClashStrategy,
     ) {
+        #[cfg(feature = "trace")]
+        let _span = info_span!("handle_clashes", ?clash_strategy).entered();
+
         for clash in self.get_clashes(action_data, input_streams) {
             // Remove the action in the pair that was overruled, if any
             if let Some(culled_action) = resolve_clash(&clash, clash_strategy, input_streams) {
@@ -111,7 +184,7 @@ impl<A: Actionlike> InputMap<A> {
         let mut clashes = Vec::default();
 
         for action_pair in A::variants().combinations(2) {
-            let action_a = action_pair.get(0).unwrap().clone();
+            let action_a = action_pair.first().unwrap().clone();
             let action_b = action_pair.get(1).unwrap().clone();
 
             if let Some(clash) = self.possible_clash(action_a, action_b) {
@@ -193,7 +266,7 @@ impl<A: Actionlike> Clash<A> {
             index_b: action_b.index(),
             inputs_a: Vec::default(),
             inputs_b: Vec::default(),
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 
@@ -205,7 +278,7 @@ impl<A: Actionlike> Clash<A> {
             index_b,
             inputs_a: Vec::default(),
             inputs_b: Vec::default(),
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 }
@@ -505,6 +578,41 @@ mod tests {
             assert!(ctrl_up.clashes(&directions_dpad));
         }
 
+        #[test]
+        fn expression_clash_detection() {
+            let a: UserInput = A.into();
+            let b: UserInput = B.into();
+            let ab = UserInput::chord([A, B]);
+            let all_ab = UserInput::all([a.clone(), b.clone()]);
+            let all_ab_c = UserInput::all([ab.clone(), C.into()]);
+            let any_ab_x = UserInput::any([all_ab.clone(), X.into()]);
+
+            // `All` expressions of buttons behave like chords
+            assert!(a.clashes(&all_ab));
+            assert!(all_ab.clashes(&a));
+            assert!(ab.clashes(&all_ab_c));
+            assert!(!all_ab.clashes(&ab));
+            // `Any` expressions clash when one of their alternatives does
+            assert!(a.clashes(&any_ab_x));
+            assert!(any_ab_x.clashes(&all_ab_c));
+            assert!(!any_ab_x.clashes(&UserInput::from(Y)));
+        }
+
+        #[test]
+        fn unless_clash_detection() {
+            let a_unless_b = UserInput::from(A).unless(B);
+            let ab = UserInput::chord([A, B]);
+            let ac = UserInput::chord([A, C]);
+
+            // Inputs that require the blocker can't be pressed at the same time
+            assert!(!a_unless_b.clashes(&ab));
+            assert!(!ab.clashes(&a_unless_b));
+            assert!(!a_unless_b.clashes(&UserInput::chord([A, B, C])));
+            // Other inputs clash as they would with the input itself
+            assert!(a_unless_b.clashes(&ac));
+            assert!(ac.clashes(&a_unless_b));
+        }
+
         #[test]
         fn button_chord_clash_construction() {
             let input_map = test_input_map();
@@ -515,7 +623,7 @@ mod tests {
                 index_b: OneAndTwo.index(),
                 inputs_a: vec![Key1.into()],
                 inputs_b: vec![UserInput::chord([Key1, Key2])],
-                _phantom: PhantomData::default(),
+                _phantom: PhantomData,
             };
 
             assert_eq!(observed_clash, correct_clash);
@@ -533,7 +641,7 @@ mod tests {
                 index_b: OneAndTwo.index(),
                 inputs_a: vec![UserInput::chord([Key1, Key2, Key3])],
                 inputs_b: vec![UserInput::chord([Key1, Key2])],
-                _phantom: PhantomData::default(),
+                _phantom: PhantomData,
             };
 
             assert_eq!(observed_clash, correct_clash);
@@ -552,6 +660,21 @@ mod tests {
                 .is_some());
         }
 
+        #[test]
+        fn ranged_axis_stages_do_not_clash() {
+            use crate::axislike::SingleAxis;
+            use bevy::input::gamepad::GamepadAxisType;
+
+            let half_pull = SingleAxis::positive_range(GamepadAxisType::RightZ, 0.2, 1.0);
+            let full_pull = SingleAxis::positive_range(GamepadAxisType::RightZ, 0.9, 1.0);
+            let modified_full_pull =
+                UserInput::chord([InputKind::Keyboard(Key1), InputKind::SingleAxis(full_pull)]);
+
+            assert!(!UserInput::from(half_pull).clashes(&full_pull.into()));
+            assert!(!UserInput::from(half_pull).clashes(&modified_full_pull));
+            assert!(UserInput::from(full_pull).clashes(&modified_full_pull));
+        }
+
         #[test]
         fn clash_caching() {
             let mut input_map = test_input_map();
@@ -1,6 +1,6 @@
 //! Run conditions for actions.
 
-use crate::{prelude::ActionState, Actionlike};
+use crate::{ime::ImeActive, prelude::ActionState, Actionlike};
 use bevy::prelude::Res;
 
 /// Stateful run condition that can be toggled via an action press using [`ActionState::just_pressed`].
@@ -31,6 +31,14 @@ where
     move |action_state: Res<ActionState<T>>| action_state.just_pressed(action.clone())
 }
 
+/// Run condition that is active if [`ActionState::any_just_pressed`] is true, such as on a "press any button to continue" screen.
+pub fn any_action_just_pressed<T>() -> impl FnMut(Res<ActionState<T>>) -> bool
+where
+    T: Actionlike,
+{
+    move |action_state: Res<ActionState<T>>| action_state.any_just_pressed()
+}
+
 /// Run condition that is active if [`ActionState::just_released`] is true for the given action.
 pub fn action_just_released<T>(action: T) -> impl FnMut(Res<ActionState<T>>) -> bool
 where
@@ -38,3 +46,10 @@ where
 {
     move |action_state: Res<ActionState<T>>| action_state.just_released(action.clone())
 }
+
+/// Run condition that is active unless an input method editor is composing text, as recorded by [`ImeActive`].
+///
+/// Use this for systems that read the keyboard directly and should not react while the player is typing.
+pub fn ime_inactive(ime_active: Option<Res<ImeActive>>) -> bool {
+    !ime_active.is_some_and(|ime_active| ime_active.composing())
+}
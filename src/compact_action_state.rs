@@ -0,0 +1,285 @@
+//! A compact serialized representation of [`ActionState`], for snapshots of games with large action enums
+//!
+//! By default, an [`ActionState`] serializes the [`ActionData`] of every action, even the ones that have never been touched.
+//! Use this module with serde's `with` attribute to only store the actions whose data differs from the default,
+//! keyed by their [`Actionlike::index`]. The omitted actions are reconstructed as released when deserializing.
+//!
+//! Indices are stored as a `u16`, so action types with more than [`MAX_ACTIONS`](crate::MAX_ACTIONS) variants can not be serialized.
+//!
+//! As with the default representation, the per-frame bookkeeping of the [`ActionState`] is not stored.
+//! Timing is stored separately, in an [`ActionTimings`](crate::timing::ActionTimings), and must be serialized on its own.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Run,
+//! }
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct PlayerSnapshot {
+//!     position: Vec2,
+//!     #[serde(with = "leafwing_input_manager::compact_action_state")]
+//!     action_state: ActionState<Action>,
+//! }
+//! ```
+
+use crate::action_state::{ActionData, ActionState};
+use crate::axislike::DualAxisData;
+use crate::buttonlike::ButtonState;
+use crate::errors::InputManagerError;
+use crate::Actionlike;
+
+use bevy::math::Vec2;
+use serde::de::Error as _;
+use serde::ser::Error as _;
+use serde::ser::SerializeMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::collections::BTreeMap;
+
+/// The serialized form of the [`ActionData`] of a single action
+#[derive(Serialize, Deserialize)]
+#[serde(rename = "ActionData")]
+struct CompactActionData {
+    state: ButtonState,
+    value: f32,
+    axis_pair: Option<DualAxisData>,
+    #[serde(default)]
+    cursor_position: Option<Vec2>,
+    consumed: bool,
+    #[serde(default)]
+    disabled: bool,
+    #[serde(default)]
+    chord_progress: (usize, usize),
+    #[serde(default)]
+    latched: bool,
+}
+
+impl CompactActionData {
+    fn new(action_data: &ActionData) -> Self {
+        Self {
+            state: action_data.state,
+            value: action_data.value,
+            axis_pair: action_data.axis_pair,
+            cursor_position: action_data.cursor_position,
+            consumed: action_data.consumed,
+            disabled: action_data.disabled,
+            chord_progress: action_data.chord_progress,
+            latched: action_data.latched,
+        }
+    }
+
+    fn into_action_data(self) -> ActionData {
+        ActionData {
+            state: self.state,
+            value: self.value,
+            axis_pair: self.axis_pair,
+            cursor_position: self.cursor_position,
+            consumed: self.consumed,
+            disabled: self.disabled,
+            chord_progress: self.chord_progress,
+            latched: self.latched,
+        }
+    }
+}
+
+/// Serializes the `action_state` as a map from action index to [`ActionData`], omitting actions with default data
+///
+/// Fails if `A` has more than [`MAX_ACTIONS`](crate::MAX_ACTIONS) variants.
+pub fn serialize<A: Actionlike, S: Serializer>(
+    action_state: &ActionState<A>,
+    serializer: S,
+) -> Result<S::Ok, S::Error> {
+    InputManagerError::check_action_count::<A>().map_err(S::Error::custom)?;
+
+    let changed: Vec<(u16, CompactActionData)> = A::variants()
+        .map(|action| {
+            (
+                // Can't truncate, since the number of actions was checked
+                action.index() as u16,
+                CompactActionData::new(action_state.action_data(action)),
+            )
+        })
+        .filter(|(_, action_data)| !is_default(action_data))
+        .collect();
+
+    let mut map = serializer.serialize_map(Some(changed.len()))?;
+    for (index, action_data) in changed {
+        map.serialize_entry(&index, &action_data)?;
+    }
+    map.end()
+}
+
+/// Deserializes an [`ActionState`] that was serialized with [`serialize`]
+///
+/// Actions that are missing from the map are released, with default data.
+/// Fails if `A` has more than [`MAX_ACTIONS`](crate::MAX_ACTIONS) variants.
+pub fn deserialize<'de, A: Actionlike, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<ActionState<A>, D::Error> {
+    InputManagerError::check_action_count::<A>().map_err(D::Error::custom)?;
+    let changed = BTreeMap::<u16, CompactActionData>::deserialize(deserializer)?;
+
+    let mut action_state = ActionState::default();
+    for (index, action_data) in changed {
+        let action = A::get_at(usize::from(index)).ok_or_else(|| {
+            D::Error::custom(format!(
+                "action index {index} is out of range for an action type with {} variants",
+                A::n_variants()
+            ))
+        })?;
+        action_state.set_action_data(action, action_data.into_action_data());
+    }
+    Ok(action_state)
+}
+
+/// Is the serialized form of the `action_data` the same as that of [`ActionData::default`]?
+fn is_default(action_data: &CompactActionData) -> bool {
+    let default = ActionData::default();
+    action_data.state == default.state
+        && action_data.value == default.value
+        && action_data.axis_pair == default.axis_pair
+        && action_data.cursor_position == default.cursor_position
+        && action_data.consumed == default.consumed
+        && action_data.disabled == default.disabled
+        && action_data.chord_progress == default.chord_progress
+        && action_data.latched == default.latched
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as leafwing_input_manager;
+    use crate::action_metadata::ActionInfo;
+    use crate::action_state::ActionState;
+    use crate::Actionlike;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+    use serde::{Deserialize, Serialize};
+    use serde_test::{assert_de_tokens, assert_ser_tokens, assert_ser_tokens_error, Token};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Hide,
+    }
+
+    #[derive(Serialize, Deserialize, PartialEq, Debug)]
+    struct Snapshot {
+        #[serde(with = "crate::compact_action_state")]
+        action_state: ActionState<Action>,
+    }
+
+    /// The tokens of a [`Snapshot`] in which only `Action::Jump` has just been pressed
+    fn jump_pressed_tokens() -> Vec<Token> {
+        vec![
+            Token::Struct {
+                name: "Snapshot",
+                len: 1,
+            },
+            Token::Str("action_state"),
+            Token::Map { len: Some(1) },
+            Token::U16(1),
+            Token::Struct {
+                name: "ActionData",
+                len: 8,
+            },
+            Token::Str("state"),
+            Token::UnitVariant {
+                name: "ButtonState",
+                variant: "JustPressed",
+            },
+            Token::Str("value"),
+            Token::F32(0.0),
+            Token::Str("axis_pair"),
+            Token::None,
+            Token::Str("cursor_position"),
+            Token::None,
+            Token::Str("consumed"),
+            Token::Bool(false),
+            Token::Str("disabled"),
+            Token::Bool(false),
+            Token::Str("chord_progress"),
+            Token::Tuple { len: 2 },
+            Token::U64(0),
+            Token::U64(0),
+            Token::TupleEnd,
+            Token::Str("latched"),
+            Token::Bool(false),
+            Token::StructEnd,
+            Token::MapEnd,
+            Token::StructEnd,
+        ]
+    }
+
+    #[test]
+    fn only_changed_actions_are_stored() {
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Hide);
+        let now = Instant::now();
+        action_state.tick(now, now);
+        // Released actions are omitted, even if they have been used before
+        action_state.reset(Action::Hide);
+        action_state.press(Action::Jump);
+
+        assert_ser_tokens(&Snapshot { action_state }, &jump_pressed_tokens());
+    }
+
+    #[test]
+    fn omitted_actions_are_released() {
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Jump);
+
+        assert_de_tokens(&Snapshot { action_state }, &jump_pressed_tokens());
+    }
+
+    /// An action type whose number of actions is only known at runtime
+    #[derive(Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    struct Slot(usize);
+
+    impl Actionlike for Slot {
+        fn n_variants() -> usize {
+            crate::MAX_ACTIONS + 1
+        }
+
+        fn get_at(index: usize) -> Option<Self> {
+            (index < Self::n_variants()).then_some(Slot(index))
+        }
+
+        fn index(&self) -> usize {
+            self.0
+        }
+
+        fn info(&self) -> ActionInfo {
+            ActionInfo::new(format!("{self:?}"))
+        }
+    }
+
+    #[test]
+    fn too_many_actions_are_rejected() {
+        #[derive(Serialize)]
+        struct SlotSnapshot {
+            #[serde(with = "crate::compact_action_state")]
+            action_state: ActionState<Slot>,
+        }
+
+        let snapshot = SlotSnapshot {
+            action_state: ActionState::default(),
+        };
+        assert_ser_tokens_error(
+            &snapshot,
+            &[
+                Token::Struct {
+                    name: "SlotSnapshot",
+                    len: 1,
+                },
+                Token::Str("action_state"),
+            ],
+            "the action type has 65537 variants, but at most 65536 actions can be encoded",
+        );
+    }
+}
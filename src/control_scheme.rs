@@ -0,0 +1,351 @@
+//! Preset control schemes, loaded as assets so that default bindings can be edited without recompiling
+//!
+//! A [`ControlScheme`] is a complete [`InputMap`] with a name and a description, stored in a RON file.
+//! Add a [`ControlSchemePlugin`] to load them with the [`AssetServer`](bevy::asset::AssetServer),
+//! then insert the [`Handle`] of a scheme on each entity whose [`InputMap`] it should set.
+//! The [`apply_control_schemes`](crate::systems::apply_control_schemes) system replaces the [`InputMap`] once the scheme has loaded,
+//! and again every time the file changes if Bevy's asset hot-reloading is enabled.
+//! When a scheme is applied again, the actions that the player rebound in the meantime keep their bindings,
+//! unless the entity has a [`SchemeMergePolicy::Replace`] component.
+//! Actions whose bindings disappeared are released, so that they don't stay pressed.
+//!
+//! Like [`InputMap`] assets, control schemes require the action type to implement [`TypeUuid`],
+//! along with [`Serialize`] and [`Deserialize`].
+//!
+//! This module requires the `asset` feature.
+//!
+//! A control scheme, such as `assets/default.controls.ron`, looks like this:
+//!
+//! ```ron
+//! (
+//!     name: "Default",
+//!     description: "WASD to move, Space to jump",
+//!     input_map: (
+//!         map: {
+//!             Jump: [Single(Keyboard(Space))],
+//!             Run: [Single(Modifier(Shift)), Single(GamepadButton(LeftTrigger))],
+//!         },
+//!     ),
+//! )
+//! ```
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::reflect::TypeUuid;
+//! use leafwing_input_manager::control_scheme::{ControlScheme, ControlSchemePlugin};
+//! use leafwing_input_manager::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(
+//!     Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect, TypeUuid, Serialize, Deserialize,
+//! )]
+//! #[uuid = "9a3e1c46-6a8b-4d51-a3c6-5e8d0f2f9d07"]
+//! enum Action {
+//!     Jump,
+//!     Run,
+//! }
+//!
+//! fn spawn_player(mut commands: Commands, asset_server: Res<AssetServer>) {
+//!     let scheme: Handle<ControlScheme<Action>> = asset_server.load("default.controls.ron");
+//!     commands.spawn((InputManagerBundle::<Action>::default(), scheme));
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .add_plugins(AssetPlugin::default())
+//!     .add_plugins(InputManagerPlugin::<Action>::default())
+//!     .add_plugins(ControlSchemePlugin::<Action>::default())
+//!     .add_systems(Startup, spawn_player);
+//! ```
+
+use crate::input_map::InputMap;
+use crate::plugin::InputManagerSystem;
+use crate::Actionlike;
+
+use bevy::app::{App, Plugin, PreUpdate};
+use bevy::asset::{AddAsset, AssetLoader, LoadContext, LoadedAsset};
+use bevy::ecs::prelude::*;
+use bevy::reflect::{TypePath, TypeUuid};
+use bevy::utils::BoxedFuture;
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use std::hash::Hash;
+use std::marker::PhantomData;
+
+#[cfg(doc)]
+use bevy::asset::Handle;
+
+/// A named, complete set of bindings for actions of type `A`, that can be loaded from a RON file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, TypeUuid, TypePath)]
+#[uuid = "5F0C6D2E-7B0A-4E59-9C34-0C1D1E7B8A42"]
+#[serde(bound(
+    serialize = "InputMap<A>: Serialize",
+    deserialize = "InputMap<A>: Deserialize<'de>"
+))]
+pub struct ControlScheme<A: Actionlike> {
+    /// The name of the scheme, as shown to players
+    pub name: String,
+    /// A short description of the scheme, as shown to players
+    #[serde(default)]
+    pub description: String,
+    /// The bindings of the scheme
+    pub input_map: InputMap<A>,
+}
+
+impl<A: Actionlike + DeserializeOwned + Eq + Hash> ControlScheme<A> {
+    /// Parses a control scheme from the contents of a RON file
+    pub fn from_ron(bytes: &[u8]) -> Result<Self, ron::error::SpannedError> {
+        ron::de::from_bytes(bytes)
+    }
+}
+
+impl<A: Actionlike> ControlScheme<A> {
+    /// Merges the bindings of this scheme with the `live` [`InputMap`] that it is applied to, according to the `policy`
+    ///
+    /// `previous` is the [`InputMap`] of the scheme when it was last applied, if ever.
+    /// Actions whose bindings in the `live` map differ from those in the `previous` map are considered to be overridden by the player.
+    /// The gamepad associated with the `live` map is always kept.
+    #[must_use]
+    pub fn merged_with(
+        &self,
+        live: &InputMap<A>,
+        previous: Option<&InputMap<A>>,
+        policy: SchemeMergePolicy,
+    ) -> InputMap<A> {
+        let mut merged = self.input_map.clone();
+        match live.gamepad() {
+            Some(gamepad) => merged.set_gamepad(gamepad),
+            None => merged.clear_gamepad(),
+        };
+
+        let (SchemeMergePolicy::PreserveOverrides, Some(previous)) = (policy, previous) else {
+            return merged;
+        };
+        for action in A::variants() {
+            let live_bindings = live.get(action.clone());
+            let previous_bindings = previous.get(action.clone());
+            let overridden = live_bindings.len() != previous_bindings.len()
+                || live_bindings
+                    .iter()
+                    .any(|input| !previous_bindings.contains(input));
+            if overridden {
+                merged.clear_action(action.clone());
+                for input in live_bindings.iter() {
+                    merged.insert(input.clone(), action.clone());
+                }
+            }
+        }
+        merged
+    }
+}
+
+/// How a [`ControlScheme`] is applied to an [`InputMap`] that it was already applied to, such as when its file is hot-reloaded
+///
+/// Add this component to an entity with the [`Handle`] of a [`ControlScheme`] to change its policy.
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemeMergePolicy {
+    /// Keep the bindings of the actions that were changed since the scheme was last applied, such as by the player rebinding them
+    #[default]
+    PreserveOverrides,
+    /// Replace every binding with those of the scheme, discarding any changes
+    Replace,
+}
+
+/// The [`InputMap`] of the [`ControlScheme`] that was last applied to an entity
+///
+/// This component is inserted by the [`apply_control_schemes`](crate::systems::apply_control_schemes) system,
+/// and is used to detect which bindings were overridden by the player.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct AppliedControlScheme<A: Actionlike> {
+    input_map: InputMap<A>,
+}
+
+impl<A: Actionlike> AppliedControlScheme<A> {
+    /// Records that the `input_map` of a [`ControlScheme`] was applied
+    #[must_use]
+    pub fn new(input_map: InputMap<A>) -> Self {
+        Self { input_map }
+    }
+
+    /// The [`InputMap`] of the scheme, as it was when it was last applied
+    #[must_use]
+    pub fn input_map(&self) -> &InputMap<A> {
+        &self.input_map
+    }
+}
+
+/// Loads [`ControlScheme`] assets from RON files with the extensions of the [`ControlSchemePlugin`]
+pub struct ControlSchemeLoader<A: Actionlike> {
+    extensions: Vec<&'static str>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A> AssetLoader for ControlSchemeLoader<A>
+where
+    A: Actionlike + TypeUuid + DeserializeOwned + Eq + Hash,
+{
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let scheme = ControlScheme::<A>::from_ron(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(scheme));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &self.extensions
+    }
+}
+
+/// A [`Plugin`] that loads [`ControlScheme`] assets for actions of type `A`, and applies them to the [`InputMap`] of entities with their [`Handle`]
+///
+/// Requires Bevy's `AssetPlugin`, and the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) of the same action type.
+/// See the [module documentation](crate::control_scheme) for more details.
+pub struct ControlSchemePlugin<A: Actionlike> {
+    extension: &'static str,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for ControlSchemePlugin<A> {
+    fn default() -> Self {
+        Self {
+            extension: "controls.ron",
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ControlSchemePlugin<A> {
+    /// Loads control schemes from files with the `extension`, rather than `controls.ron`
+    ///
+    /// Each action type needs its own extension, as Bevy picks the asset loader of a file by its extension alone.
+    #[must_use]
+    pub fn with_extension(mut self, extension: &'static str) -> Self {
+        self.extension = extension;
+        self
+    }
+
+    /// The extension of the files that control schemes are loaded from, without the preceding dot
+    #[must_use]
+    pub fn extension(&self) -> &'static str {
+        self.extension
+    }
+}
+
+impl<A> Plugin for ControlSchemePlugin<A>
+where
+    A: Actionlike + TypeUuid + DeserializeOwned + Eq + Hash,
+{
+    fn build(&self, app: &mut App) {
+        use crate::systems::apply_control_schemes;
+
+        app.add_asset::<ControlScheme<A>>()
+            .add_asset_loader(ControlSchemeLoader::<A> {
+                extensions: vec![self.extension],
+                _phantom: PhantomData,
+            })
+            .add_systems(
+                PreUpdate,
+                apply_control_schemes::<A>.before(InputManagerSystem::Update),
+            );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::user_input::{InputKind, Modifier, UserInput};
+    use bevy::prelude::{KeyCode, Reflect};
+
+    #[derive(
+        Actionlike,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash,
+        Debug,
+        Reflect,
+        TypeUuid,
+        Serialize,
+        Deserialize,
+    )]
+    #[uuid = "0B8F3E25-35A1-4D7B-8B2C-6E1F4A9D3C10"]
+    enum Action {
+        Jump,
+        Run,
+    }
+
+    #[test]
+    fn schemes_are_parsed_from_ron() {
+        let scheme = ControlScheme::<Action>::from_ron(
+            br#"(
+                name: "Default",
+                input_map: (
+                    map: {
+                        Jump: [Single(Keyboard(Space))],
+                        Run: [Single(Modifier(Shift))],
+                    },
+                ),
+            )"#,
+        )
+        .unwrap();
+
+        assert_eq!(scheme.name, "Default");
+        assert_eq!(scheme.description, "");
+        assert_eq!(
+            scheme
+                .input_map
+                .get(Action::Jump)
+                .iter()
+                .collect::<Vec<_>>(),
+            [&UserInput::Single(InputKind::Keyboard(KeyCode::Space))]
+        );
+        assert_eq!(
+            scheme.input_map.get(Action::Run).iter().collect::<Vec<_>>(),
+            [&UserInput::Single(InputKind::Modifier(Modifier::Shift))]
+        );
+
+        let serialized = ron::ser::to_string(&scheme).unwrap();
+        assert_eq!(
+            ControlScheme::<Action>::from_ron(serialized.as_bytes()).unwrap(),
+            scheme
+        );
+    }
+
+    #[test]
+    fn overrides_are_preserved() {
+        let previous = InputMap::new([(KeyCode::Space, Action::Jump), (KeyCode::R, Action::Run)]);
+        let mut live = previous.clone();
+        live.clear_action(Action::Run);
+        live.insert(KeyCode::ShiftLeft, Action::Run);
+
+        let scheme = ControlScheme {
+            name: "Default".to_string(),
+            description: String::new(),
+            input_map: InputMap::new([(KeyCode::W, Action::Jump), (KeyCode::E, Action::Run)]),
+        };
+
+        let merged =
+            scheme.merged_with(&live, Some(&previous), SchemeMergePolicy::PreserveOverrides);
+        assert_eq!(
+            merged.get(Action::Jump).iter().collect::<Vec<_>>(),
+            [&UserInput::from(KeyCode::W)]
+        );
+        assert_eq!(
+            merged.get(Action::Run).iter().collect::<Vec<_>>(),
+            [&UserInput::from(KeyCode::ShiftLeft)]
+        );
+
+        let replaced = scheme.merged_with(&live, Some(&previous), SchemeMergePolicy::Replace);
+        assert_eq!(replaced, scheme.input_map);
+    }
+}
@@ -0,0 +1,105 @@
+//! Binding inputs from custom devices to actions
+//!
+//! Plugins that bridge other kinds of input into Bevy, such as speech recognition or specialized hardware,
+//! can send a [`RawInputEvent`] whenever the state of one of their inputs changes.
+//! Each input is identified by an arbitrary `u32` chosen by the sending plugin,
+//! and can be bound to actions using [`UserInput::Custom`](crate::user_input::UserInput::Custom).
+//!
+//! Each frame, the [`collect_raw_input_events`](crate::systems::collect_raw_input_events) system stores the latest value of each input
+//! in the [`CustomInputs`] resource. Inputs keep their value until another event changes it,
+//! and are pressed while their value is non-zero.
+//! These bindings cannot be pressed using [`MockInput`](crate::input_mocking::MockInput): send a [`RawInputEvent`] instead.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::custom_input::RawInputEvent;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! /// The id used by our speech recognition plugin for the word "jump"
+//! const SAID_JUMP: u32 = 0;
+//!
+//! let input_map = InputMap::new([(UserInput::Custom(SAID_JUMP), Action::Jump)]);
+//!
+//! fn recognize_speech(mut events: EventWriter<RawInputEvent>) {
+//!     // The word was recognized this frame
+//!     events.send(RawInputEvent::pressed(SAID_JUMP));
+//! }
+//! ```
+
+use bevy::ecs::prelude::{Event, Resource};
+use bevy::utils::HashMap;
+
+/// Sets the value of the custom input with the provided `id`
+///
+/// Bindings to [`UserInput::Custom`](crate::user_input::UserInput::Custom) with a matching `id` are pressed while the value is non-zero.
+#[derive(Event, Debug, Clone, Copy, PartialEq)]
+pub struct RawInputEvent {
+    /// The identifier of the input, as chosen by the plugin that sends it
+    pub id: u32,
+    /// The new value of the input
+    pub value: f32,
+}
+
+impl RawInputEvent {
+    /// Sets the input with the provided `id` to the provided `value`
+    #[must_use]
+    pub fn new(id: u32, value: f32) -> Self {
+        Self { id, value }
+    }
+
+    /// Presses the input with the provided `id`, with a value of `1.0`
+    #[must_use]
+    pub fn pressed(id: u32) -> Self {
+        Self::new(id, 1.0)
+    }
+
+    /// Releases the input with the provided `id`
+    #[must_use]
+    pub fn released(id: u32) -> Self {
+        Self::new(id, 0.0)
+    }
+}
+
+/// The current value of each custom input
+///
+/// This resource is updated by the [`collect_raw_input_events`](crate::systems::collect_raw_input_events) system,
+/// and read when updating the [`ActionState`](crate::action_state::ActionState).
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct CustomInputs {
+    values: HashMap<u32, f32>,
+}
+
+impl CustomInputs {
+    /// The current value of the input with the provided `id`
+    ///
+    /// Inputs that have never been set have a value of `0.0`.
+    #[must_use]
+    pub fn value(&self, id: u32) -> f32 {
+        self.values.get(&id).copied().unwrap_or_default()
+    }
+
+    /// Is the input with the provided `id` pressed?
+    #[must_use]
+    pub fn pressed(&self, id: u32) -> bool {
+        self.value(id) != 0.0
+    }
+
+    /// Sets the value of the input with the provided `id`
+    pub fn set(&mut self, id: u32, value: f32) {
+        if value == 0.0 {
+            self.values.remove(&id);
+        } else {
+            self.values.insert(id, value);
+        }
+    }
+
+    /// Releases every input
+    pub fn release_all(&mut self) {
+        self.values.clear();
+    }
+}
@@ -0,0 +1,210 @@
+//! Sequence numbers and tick stamps for [`ActionDiff`]s sent over unreliable transports
+//!
+//! [`ActionDiff`]s only describe changes, so a single lost or reordered diff can leave an action stuck on the receiving peer.
+//! On the sending peer, [`sequence_action_diffs`](crate::systems::sequence_action_diffs) wraps each diff in a [`SequencedActionDiff`],
+//! numbered per stable identifier and stamped with the tick on which it was generated.
+//! On the receiving peer, [`unsequence_action_diffs`](crate::systems::unsequence_action_diffs) restores their order,
+//! drops duplicated and stale diffs, and sends a [`MissedInput`] event for each gap.
+//! Respond to these events by requesting the full [`ActionState`](crate::action_state::ActionState) of that entity from the sender.
+
+use crate::action_state::ActionDiff;
+use crate::stable_id::StableId;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::ops::Range;
+
+/// An [`ActionDiff`] with the metadata needed to detect lost, duplicated and reordered diffs
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, Event)]
+pub struct SequencedActionDiff<A: Actionlike, ID: StableId> {
+    /// The wrapped diff
+    pub diff: ActionDiff<A, ID>,
+    /// The position of this diff among all diffs sent for the same stable identifier, starting at 0
+    pub sequence: u64,
+    /// The tick of the sending peer on which this diff was generated, starting at 0
+    pub tick: u64,
+}
+
+/// Numbers the [`ActionDiff`]s sent by this peer
+///
+/// Used by [`sequence_action_diffs`](crate::systems::sequence_action_diffs).
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DiffSequencer<A: Actionlike, ID: StableId> {
+    tick: u64,
+    next_sequence: HashMap<ID, u64>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike, ID: StableId> Default for DiffSequencer<A, ID> {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            next_sequence: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike, ID: StableId> DiffSequencer<A, ID> {
+    /// The tick that diffs are currently stamped with
+    #[must_use]
+    pub fn tick(&self) -> u64 {
+        self.tick
+    }
+
+    /// Advances to the next tick
+    pub fn advance_tick(&mut self) {
+        self.tick += 1;
+    }
+
+    /// Wraps the `diff` in a [`SequencedActionDiff`] with the next sequence number of its stable identifier
+    pub fn stamp(&mut self, diff: ActionDiff<A, ID>) -> SequencedActionDiff<A, ID> {
+        let next_sequence = self.next_sequence.entry(diff.id().clone()).or_default();
+        let sequence = *next_sequence;
+        *next_sequence += 1;
+
+        SequencedActionDiff {
+            diff,
+            sequence,
+            tick: self.tick,
+        }
+    }
+}
+
+/// How a received [`SequencedActionDiff`] relates to the diffs received before it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SequenceCheck {
+    /// The diff is the next one expected
+    InOrder,
+    /// The diffs with these sequence numbers were skipped, and are considered lost
+    Gap(Range<u64>),
+    /// The diff was already received, or arrived after a later one
+    Stale,
+}
+
+/// Tracks the next sequence number expected from each stable identifier on the receiving peer
+///
+/// Used by [`unsequence_action_diffs`](crate::systems::unsequence_action_diffs).
+/// Identifiers that were never received are expected to start at 0.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct DiffSequenceTracker<A: Actionlike, ID: StableId> {
+    expected: HashMap<ID, u64>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike, ID: StableId> Default for DiffSequenceTracker<A, ID> {
+    fn default() -> Self {
+        Self {
+            expected: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike, ID: StableId> DiffSequenceTracker<A, ID> {
+    /// The next sequence number expected for the `id`
+    #[must_use]
+    pub fn expected(&self, id: &ID) -> u64 {
+        self.expected.get(id).copied().unwrap_or_default()
+    }
+
+    /// Records that the diff with the `sequence` number was received for the `id`
+    ///
+    /// Stale diffs are not recorded.
+    pub fn receive(&mut self, id: &ID, sequence: u64) -> SequenceCheck {
+        let expected = self.expected(id);
+        if sequence < expected {
+            return SequenceCheck::Stale;
+        }
+
+        self.expected.insert(id.clone(), sequence + 1);
+        if sequence == expected {
+            SequenceCheck::InOrder
+        } else {
+            SequenceCheck::Gap(expected..sequence)
+        }
+    }
+
+    /// Sets the next sequence number expected for the `id`
+    ///
+    /// Call this after resynchronizing the full [`ActionState`](crate::action_state::ActionState) of the entity,
+    /// with the sequence number the sender will use next.
+    pub fn reset(&mut self, id: ID, next_sequence: u64) {
+        self.expected.insert(id, next_sequence);
+    }
+}
+
+/// Sent by [`unsequence_action_diffs`](crate::systems::unsequence_action_diffs) when [`SequencedActionDiff`]s were lost
+///
+/// The [`ActionState`](crate::action_state::ActionState) of the entity may no longer match the sender's.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct MissedInput<A: Actionlike, ID: StableId> {
+    /// The stable identifier of the entity whose diffs were lost
+    pub id: ID,
+    /// The sequence numbers of the lost diffs
+    pub missed: Range<u64>,
+    /// The tick stamped on the diff that revealed the gap
+    pub tick: u64,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike, ID: StableId> MissedInput<A, ID> {
+    /// Creates a [`MissedInput`] event for the `missed` diffs of the `id`
+    #[must_use]
+    pub fn new(id: ID, missed: Range<u64>, tick: u64) -> Self {
+        Self {
+            id,
+            missed,
+            tick,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DiffSequenceTracker, DiffSequencer, SequenceCheck};
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionDiff;
+    use bevy::prelude::Reflect;
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+    }
+
+    #[test]
+    fn sequences_are_per_id() {
+        let mut sequencer = DiffSequencer::<Action, u64>::default();
+        let pressed = |id| ActionDiff::Pressed {
+            action: Action::Jump,
+            id,
+        };
+
+        assert_eq!(sequencer.stamp(pressed(1)).sequence, 0);
+        assert_eq!(sequencer.stamp(pressed(2)).sequence, 0);
+        sequencer.advance_tick();
+        let stamped = sequencer.stamp(pressed(1));
+        assert_eq!(stamped.sequence, 1);
+        assert_eq!(stamped.tick, 1);
+    }
+
+    #[test]
+    fn tracker_detects_gaps_and_stale_diffs() {
+        let mut tracker = DiffSequenceTracker::<Action, u64>::default();
+
+        assert_eq!(tracker.receive(&1, 0), SequenceCheck::InOrder);
+        assert_eq!(tracker.receive(&1, 3), SequenceCheck::Gap(1..3));
+        assert_eq!(tracker.receive(&1, 2), SequenceCheck::Stale);
+        assert_eq!(tracker.receive(&1, 3), SequenceCheck::Stale);
+        assert_eq!(tracker.expected(&1), 4);
+        assert_eq!(tracker.expected(&2), 0);
+
+        tracker.reset(1, 10);
+        assert_eq!(tracker.receive(&1, 10), SequenceCheck::InOrder);
+    }
+}
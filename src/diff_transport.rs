@@ -0,0 +1,93 @@
+//! Traits that connect the action replication systems to a network layer
+//!
+//! [`ActionDiff`](crate::action_state::ActionDiff)s and [`SequencedActionDiff`](crate::diff_sequencing::SequencedActionDiff)s are sent and received as Bevy events.
+//! To replicate them, insert a [`DiffSender`] resource on the sending peer and a [`DiffReceiver`] resource on the receiving peer,
+//! then add [`send_diffs`](crate::systems::send_diffs) after the systems that generate the events,
+//! and [`receive_diffs`](crate::systems::receive_diffs) before the systems that process them.
+//!
+//! [`loopback_channel`] connects a sender and a receiver in the same process, which is useful for tests and local multiplayer.
+//! With the `renet` feature, the [`renet`] module sends each message type on its own channel of a `bevy_renet` connection, and records which peer sent each message.
+//! Other transports are supported by implementing these traits on a resource that owns the connection.
+
+use bevy::ecs::prelude::*;
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex};
+
+#[cfg(feature = "renet")]
+pub mod renet;
+
+/// A resource that sends replicated messages of type `T` to another peer
+pub trait DiffSender<T>: Resource {
+    /// Sends a single `message`
+    fn send(&mut self, message: T);
+}
+
+/// A resource that receives replicated messages of type `T` from another peer
+pub trait DiffReceiver<T>: Resource {
+    /// Returns the next received message, if any
+    ///
+    /// Called repeatedly by [`receive_diffs`](crate::systems::receive_diffs) until it returns `None`.
+    fn receive(&mut self) -> Option<T>;
+}
+
+/// Creates a [`LoopbackSender`] and a [`LoopbackReceiver`] that share a queue of messages
+#[must_use]
+pub fn loopback_channel<T: Send + 'static>() -> (LoopbackSender<T>, LoopbackReceiver<T>) {
+    let queue = Arc::new(Mutex::new(VecDeque::new()));
+    (
+        LoopbackSender {
+            queue: queue.clone(),
+        },
+        LoopbackReceiver { queue },
+    )
+}
+
+/// The sending half of a [`loopback_channel`]
+#[derive(Resource, Debug)]
+pub struct LoopbackSender<T: Send + 'static> {
+    queue: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T: Send + 'static> DiffSender<T> for LoopbackSender<T> {
+    fn send(&mut self, message: T) {
+        self.queue
+            .lock()
+            .expect("The loopback queue should not be poisoned")
+            .push_back(message);
+    }
+}
+
+/// The receiving half of a [`loopback_channel`]
+///
+/// Messages are received in the order they were sent.
+#[derive(Resource, Debug)]
+pub struct LoopbackReceiver<T: Send + 'static> {
+    queue: Arc<Mutex<VecDeque<T>>>,
+}
+
+impl<T: Send + 'static> DiffReceiver<T> for LoopbackReceiver<T> {
+    fn receive(&mut self) -> Option<T> {
+        self.queue
+            .lock()
+            .expect("The loopback queue should not be poisoned")
+            .pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{loopback_channel, DiffReceiver, DiffSender};
+
+    #[test]
+    fn loopback_preserves_order() {
+        let (mut sender, mut receiver) = loopback_channel::<u8>();
+
+        sender.send(1);
+        sender.send(2);
+        assert_eq!(receiver.receive(), Some(1));
+        sender.send(3);
+        assert_eq!(receiver.receive(), Some(2));
+        assert_eq!(receiver.receive(), Some(3));
+        assert_eq!(receiver.receive(), None);
+    }
+}
@@ -0,0 +1,315 @@
+//! A typed transport for message-based networking crates such as `bevy_renet`
+//!
+//! Each replicated message type is sent on its own channel, configured by a [`RenetTransport`] resource,
+//! and serialized with [`bincode`]. Add a [`RenetTransport`] for each message type,
+//! then add [`send_renet_messages`](crate::systems::send_renet_messages) on the sending peer
+//! and [`receive_renet_messages`](crate::systems::receive_renet_messages) on the receiving peer.
+//!
+//! Received messages are sent as [`Received`] events, which record the peer that sent them.
+//! Clients can claim any [`StableId`](crate::stable_id::StableId) in the diffs they send,
+//! so servers should check that the sender owns the identifier of each diff before applying it.
+//! Messages that can't be decoded come from the other peer, so they are logged and skipped rather than [reported](crate::errors).
+//!
+//! This module does not depend on `bevy_renet` directly, so that it works with any version of it.
+//! Instead, implement [`RenetConnection`] for a marker type that describes how to use the connection resource:
+//!
+//! ```rust,ignore
+//! use bevy_renet::renet::{DefaultChannel, RenetServer};
+//! use leafwing_input_manager::diff_transport::renet::{RenetConnection, RenetTransport};
+//!
+//! /// The server side of a `bevy_renet` connection
+//! struct ServerConnection;
+//!
+//! impl RenetConnection for ServerConnection {
+//!     type Connection = RenetServer;
+//!     type PeerId = u64;
+//!
+//!     fn send_message(server: &mut RenetServer, channel: u8, message: Vec<u8>) {
+//!         server.broadcast_message(channel, message);
+//!     }
+//!
+//!     fn receive_message(server: &mut RenetServer, channel: u8) -> Option<(u64, Vec<u8>)> {
+//!         server.clients_id().into_iter().find_map(|client_id| {
+//!             let message = server.receive_message(client_id, channel)?;
+//!             Some((client_id, message.into()))
+//!         })
+//!     }
+//! }
+//!
+//! let transport = RenetTransport::<ActionDiff<Action, u64>, ServerConnection>::new(
+//!     DefaultChannel::ReliableOrdered.into(),
+//! );
+//! ```
+
+use bevy::ecs::prelude::{Event, Resource};
+use bevy::log::warn;
+use serde::{de::DeserializeOwned, Serialize};
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+use crate::errors::{report, InputManagerError};
+
+/// Describes how to send and receive raw messages over the connection resource of a networking crate
+///
+/// This is implemented on a marker type, rather than on the connection itself,
+/// so that it can be implemented for the resources of other crates, such as the `RenetClient` and `RenetServer` of `bevy_renet`.
+pub trait RenetConnection: Send + Sync + 'static {
+    /// The resource that owns the connection
+    type Connection: Resource;
+
+    /// Identifies the peer that sent a message
+    ///
+    /// Servers typically use the client id of the sender, while clients can use `()`, as they only receive messages from the server.
+    type PeerId: Clone + Debug + Send + Sync + 'static;
+
+    /// Sends the `message` on the `channel`
+    ///
+    /// Servers typically broadcast the message to every connected client.
+    fn send_message(connection: &mut Self::Connection, channel: u8, message: Vec<u8>);
+
+    /// Returns the next message received on the `channel`, along with the peer that sent it
+    fn receive_message(
+        connection: &mut Self::Connection,
+        channel: u8,
+    ) -> Option<(Self::PeerId, Vec<u8>)>;
+}
+
+/// A message of type `T`, received from the peer identified by `P`
+///
+/// Sent as an event by [`receive_renet_messages`](crate::systems::receive_renet_messages).
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct Received<T: Send + Sync + 'static, P: Send + Sync + 'static> {
+    /// The peer that sent the message
+    pub sender: P,
+    /// The message itself
+    pub message: T,
+}
+
+/// A resource that sends and receives messages of type `T` over the [`RenetConnection`] `C`
+///
+/// Each message type must use its own channel: messages are not tagged with their type,
+/// so a message of another type on the same channel may be decoded as a `T`.
+#[derive(Resource)]
+pub struct RenetTransport<T, C: RenetConnection> {
+    channel: u8,
+    _phantom: PhantomData<fn() -> (T, C)>,
+}
+
+impl<T, C: RenetConnection> RenetTransport<T, C> {
+    /// Creates a transport that sends and receives messages on the `channel`
+    ///
+    /// Diffs must arrive exactly once and in order, unless they are [sequenced](crate::diff_sequencing),
+    /// so this should be a reliable and ordered channel.
+    #[must_use]
+    pub fn new(channel: u8) -> Self {
+        Self {
+            channel,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// The channel on which messages of type `T` are sent and received
+    #[must_use]
+    pub fn channel(&self) -> u8 {
+        self.channel
+    }
+}
+
+impl<T: Serialize, C: RenetConnection> RenetTransport<T, C> {
+    /// Serializes the `message` and sends it over the `connection`
+    ///
+    /// If the `message` can't be serialized, an [`InputManagerError`] is [reported](crate::errors) and nothing is sent.
+    pub fn send(&self, connection: &mut C::Connection, message: &T) {
+        match bincode::serialize(message) {
+            Ok(bytes) => C::send_message(connection, self.channel, bytes),
+            Err(error) => report(InputManagerError::MalformedMessage {
+                reason: error.to_string(),
+            }),
+        }
+    }
+}
+
+impl<T: DeserializeOwned + Send + Sync + 'static, C: RenetConnection> RenetTransport<T, C> {
+    /// Returns the next message received over the `connection`, along with the peer that sent it
+    ///
+    /// Messages that can't be deserialized are logged as warnings and skipped.
+    pub fn receive(&self, connection: &mut C::Connection) -> Option<Received<T, C::PeerId>> {
+        while let Some((sender, bytes)) = C::receive_message(connection, self.channel) {
+            match bincode::deserialize(&bytes) {
+                Ok(message) => return Some(Received { sender, message }),
+                Err(error) => warn!(
+                    "Skipped a malformed message from {sender:?} on channel {}: {error}",
+                    self.channel
+                ),
+            }
+        }
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Received, RenetConnection, RenetTransport};
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionDiff;
+    use bevy::prelude::{Reflect, Resource};
+    use leafwing_input_manager_macros::Actionlike;
+    use serde::{Deserialize, Serialize};
+    use std::collections::{HashMap, VecDeque};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect, Serialize, Deserialize)]
+    enum Action {
+        Jump,
+        Run,
+    }
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect, Serialize, Deserialize)]
+    enum MenuAction {
+        Open,
+        Close,
+    }
+
+    /// A connection to a single client, whose messages are queued by channel
+    #[derive(Resource, Default)]
+    struct MockServer {
+        queues: HashMap<u8, VecDeque<Vec<u8>>>,
+    }
+
+    struct MockConnection;
+
+    const CLIENT_ID: u64 = 7;
+
+    impl RenetConnection for MockConnection {
+        type Connection = MockServer;
+        type PeerId = u64;
+
+        fn send_message(connection: &mut MockServer, channel: u8, message: Vec<u8>) {
+            connection
+                .queues
+                .entry(channel)
+                .or_default()
+                .push_back(message);
+        }
+
+        fn receive_message(connection: &mut MockServer, channel: u8) -> Option<(u64, Vec<u8>)> {
+            let message = connection.queues.get_mut(&channel)?.pop_front()?;
+            Some((CLIENT_ID, message))
+        }
+    }
+
+    #[test]
+    fn messages_round_trip_with_their_sender() {
+        let mut connection = MockServer::default();
+        let transport = RenetTransport::<ActionDiff<Action, u64>, MockConnection>::new(0);
+
+        let pressed = ActionDiff::Pressed {
+            action: Action::Jump,
+            id: 1,
+        };
+        let released = ActionDiff::Released {
+            action: Action::Run,
+            id: 1,
+        };
+        transport.send(&mut connection, &pressed);
+        transport.send(&mut connection, &released);
+
+        assert_eq!(
+            transport.receive(&mut connection),
+            Some(Received {
+                sender: CLIENT_ID,
+                message: pressed
+            })
+        );
+        assert_eq!(
+            transport.receive(&mut connection),
+            Some(Received {
+                sender: CLIENT_ID,
+                message: released
+            })
+        );
+        assert_eq!(transport.receive(&mut connection), None);
+    }
+
+    #[test]
+    fn message_types_use_separate_channels() {
+        let mut connection = MockServer::default();
+        let actions = RenetTransport::<ActionDiff<Action, u64>, MockConnection>::new(0);
+        let menu_actions = RenetTransport::<ActionDiff<MenuAction, u64>, MockConnection>::new(1);
+
+        menu_actions.send(
+            &mut connection,
+            &ActionDiff::Pressed {
+                action: MenuAction::Close,
+                id: 1,
+            },
+        );
+        assert_eq!(actions.receive(&mut connection), None);
+        assert_eq!(
+            menu_actions
+                .receive(&mut connection)
+                .map(|received| received.message),
+            Some(ActionDiff::Pressed {
+                action: MenuAction::Close,
+                id: 1,
+            })
+        );
+    }
+
+    #[test]
+    fn malformed_messages_are_skipped() {
+        let mut connection = MockServer::default();
+        let transport = RenetTransport::<ActionDiff<Action, u64>, MockConnection>::new(0);
+
+        let pressed = ActionDiff::Pressed {
+            action: Action::Run,
+            id: 1,
+        };
+        MockConnection::send_message(&mut connection, 0, vec![255; 3]);
+        transport.send(&mut connection, &pressed);
+
+        assert_eq!(
+            transport
+                .receive(&mut connection)
+                .map(|received| received.message),
+            Some(pressed)
+        );
+    }
+
+    #[test]
+    fn systems_send_and_receive_events() {
+        use crate::systems::{receive_renet_messages, send_renet_messages};
+        use bevy::prelude::{App, Events, Update};
+
+        type Diff = ActionDiff<Action, u64>;
+
+        let mut app = App::new();
+        app.add_event::<Diff>()
+            .add_event::<Received<Diff, u64>>()
+            .init_resource::<MockServer>()
+            .insert_resource(RenetTransport::<Diff, MockConnection>::new(0))
+            .add_systems(
+                Update,
+                (
+                    send_renet_messages::<Diff, MockConnection>,
+                    receive_renet_messages::<Diff, MockConnection>,
+                ),
+            );
+
+        let pressed = ActionDiff::Pressed {
+            action: Action::Jump,
+            id: 1,
+        };
+        app.world.send_event(pressed.clone());
+        app.update();
+        app.update();
+
+        let mut received = app.world.resource_mut::<Events<Received<Diff, u64>>>();
+        assert_eq!(
+            received.drain().collect::<Vec<_>>(),
+            vec![Received {
+                sender: CLIENT_ID,
+                message: pressed
+            }]
+        );
+    }
+}
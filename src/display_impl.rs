@@ -32,8 +32,35 @@ impl Display for UserInput {
             UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
                 write!(f, "VirtualDPad(negative: {negative}, positive: {positive})")
             }
+            UserInput::WindowEvent(event) => write!(f, "{event}"),
+            UserInput::Custom(id) => write!(f, "Custom({id})"),
+            // The representation of each input, separated by the operator, with nested expressions in parentheses
+            UserInput::All(inputs) => write_expression(f, inputs, " AND "),
+            UserInput::Any(inputs) => write_expression(f, inputs, " OR "),
+            UserInput::Unless { input, blocker } => {
+                write_expression(f, [&**input, &**blocker], " UNLESS ")
+            }
+        }
+    }
+}
+
+fn write_expression<'a>(
+    f: &mut std::fmt::Formatter<'_>,
+    inputs: impl IntoIterator<Item = &'a UserInput>,
+    operator: &str,
+) -> std::fmt::Result {
+    for (i, input) in inputs.into_iter().enumerate() {
+        if i > 0 {
+            write!(f, "{operator}")?;
+        }
+        match input {
+            UserInput::All(_) | UserInput::Any(_) | UserInput::Unless { .. } => {
+                write!(f, "({input})")?
+            }
+            _ => write!(f, "{input}")?,
         }
     }
+    Ok(())
 }
 
 impl Display for InputKind {
@@ -45,6 +72,15 @@ impl Display for InputKind {
             InputKind::Mouse(button) => write!(f, "{button:?}"),
             InputKind::MouseWheel(button) => write!(f, "{button:?}"),
             InputKind::MouseMotion(button) => write!(f, "{button:?}"),
+            InputKind::MouseDrag(drag) => write!(f, "{:?} Drag", drag.button),
+            InputKind::Flick(flick) => match flick.direction {
+                Some(direction) => write!(f, "{direction:?} Flick"),
+                None => write!(f, "Flick"),
+            },
+            InputKind::StickRotation(rotation) => match rotation.direction {
+                Some(direction) => write!(f, "{direction:?} Stick Rotation"),
+                None => write!(f, "Stick Rotation"),
+            },
             InputKind::Keyboard(button) => write!(f, "{button:?}"),
             // TODO: We probably want to display the key on the currently active layout
             InputKind::KeyLocation(scan_code) => write!(f, "{scan_code:?}"),
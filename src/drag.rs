@@ -0,0 +1,256 @@
+//! The press, move and release lifecycle of drag-and-drop gestures
+//!
+//! Add a [`DragStates`] resource or component alongside an [`ActionState`], listing the actions that drag (such as grabbing an inventory item).
+//! Each frame, the [`update_drag_states`](crate::systems::update_drag_states) system combines the presses of these actions
+//! with the cursor position of the primary window, and exposes the [`DragState`] of each action.
+//!
+//! A drag starts at the position of the cursor when the action was pressed,
+//! follows the cursor while the action is held, and reports where it ended during the frame in which the action was released.
+//! Presses that happen while the cursor is outside of the window don't start a drag.
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::math::Vec2;
+use std::marker::PhantomData;
+
+/// The stage of the drag-and-drop gesture of a single action
+///
+/// Positions are in window coordinates, like [`Window::cursor_position`](bevy::window::Window::cursor_position).
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum DragState {
+    /// The action is not dragging
+    #[default]
+    Idle,
+    /// The action is held, and the cursor has moved from `start` to `current`
+    Dragging {
+        /// The position of the cursor when the action was pressed
+        start: Vec2,
+        /// The current position of the cursor
+        current: Vec2,
+    },
+    /// The action was released at `end` during this frame, completing a drag that began at `start`
+    Released {
+        /// The position of the cursor when the action was pressed
+        start: Vec2,
+        /// The position of the cursor when the action was released
+        end: Vec2,
+    },
+}
+
+impl DragState {
+    /// Is the action currently dragging?
+    #[must_use]
+    pub fn dragging(&self) -> bool {
+        matches!(self, DragState::Dragging { .. })
+    }
+
+    /// Was the drag completed during this frame?
+    #[must_use]
+    pub fn released(&self) -> bool {
+        matches!(self, DragState::Released { .. })
+    }
+
+    /// The position of the cursor when the drag started, unless the action is idle
+    #[must_use]
+    pub fn start(&self) -> Option<Vec2> {
+        match *self {
+            DragState::Idle => None,
+            DragState::Dragging { start, .. } | DragState::Released { start, .. } => Some(start),
+        }
+    }
+
+    /// The latest position of the dragged cursor, unless the action is idle
+    #[must_use]
+    pub fn current(&self) -> Option<Vec2> {
+        match *self {
+            DragState::Idle => None,
+            DragState::Dragging { current, .. } => Some(current),
+            DragState::Released { end, .. } => Some(end),
+        }
+    }
+
+    /// How far the cursor has been dragged since the drag started, unless the action is idle
+    #[must_use]
+    pub fn offset(&self) -> Option<Vec2> {
+        Some(self.current()? - self.start()?)
+    }
+}
+
+/// The [`DragState`] of each dragging action of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it tracks.
+///
+/// # Example
+/// ```rust
+/// use bevy::math::Vec2;
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Instant;
+/// use leafwing_input_manager::drag::{DragState, DragStates};
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Grab,
+/// }
+///
+/// let mut drag_states = DragStates::new([Action::Grab]);
+/// let mut action_state = ActionState::<Action>::default();
+///
+/// action_state.press(Action::Grab);
+/// drag_states.update(&action_state, Some(Vec2::new(10.0, 10.0)));
+///
+/// action_state.tick(Instant::now(), Instant::now());
+/// drag_states.update(&action_state, Some(Vec2::new(50.0, 20.0)));
+/// assert_eq!(drag_states.state(Action::Grab).offset(), Some(Vec2::new(40.0, 10.0)));
+///
+/// action_state.release(Action::Grab);
+/// drag_states.update(&action_state, Some(Vec2::new(60.0, 20.0)));
+/// assert_eq!(
+///     drag_states.state(Action::Grab),
+///     DragState::Released { start: Vec2::new(10.0, 10.0), end: Vec2::new(60.0, 20.0) }
+/// );
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct DragStates<A: Actionlike> {
+    /// The state of each action that drags, indexed by [`Actionlike::index`]
+    states: Vec<Option<DragState>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for DragStates<A> {
+    fn default() -> Self {
+        DragStates {
+            states: vec![None; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> DragStates<A> {
+    /// Creates a [`DragStates`] that tracks the drags of the provided `actions`
+    #[must_use]
+    pub fn new(actions: impl IntoIterator<Item = A>) -> Self {
+        let mut drag_states = Self::default();
+        for action in actions {
+            drag_states.add(action);
+        }
+        drag_states
+    }
+
+    /// Starts tracking the drags of the `action`
+    pub fn add(&mut self, action: A) -> &mut Self {
+        let state = &mut self.states[action.index()];
+        if state.is_none() {
+            *state = Some(DragState::Idle);
+        }
+        self
+    }
+
+    /// Stops tracking the drags of the `action`
+    pub fn remove(&mut self, action: A) -> &mut Self {
+        self.states[action.index()] = None;
+        self
+    }
+
+    /// Are the drags of the `action` tracked?
+    #[must_use]
+    pub fn tracks(&self, action: A) -> bool {
+        self.states[action.index()].is_some()
+    }
+
+    /// The current [`DragState`] of the `action`
+    ///
+    /// This is always [`DragState::Idle`] for actions that are not tracked.
+    #[must_use]
+    pub fn state(&self, action: A) -> DragState {
+        self.states[action.index()].unwrap_or_default()
+    }
+
+    /// Advances the drag of each tracked action, using the `action_state` and the current `cursor` position
+    pub fn update(&mut self, action_state: &ActionState<A>, cursor: Option<Vec2>) {
+        for action in A::variants() {
+            let Some(state) = self.states[action.index()].as_mut() else {
+                continue;
+            };
+
+            *state = match *state {
+                DragState::Dragging { start, current } => {
+                    if action_state.pressed(action.clone()) {
+                        DragState::Dragging {
+                            start,
+                            current: cursor.unwrap_or(current),
+                        }
+                    } else {
+                        DragState::Released {
+                            start,
+                            end: cursor.unwrap_or(current),
+                        }
+                    }
+                }
+                DragState::Idle | DragState::Released { .. } => {
+                    if !action_state.newly_pressed(action.clone()) {
+                        DragState::Idle
+                    } else {
+                        // Mouse presses record the exact cursor position of the press
+                        match action_state.cursor_position(action).or(cursor) {
+                            Some(start) => DragState::Dragging {
+                                start,
+                                current: cursor.unwrap_or(start),
+                            },
+                            None => DragState::Idle,
+                        }
+                    }
+                }
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Grab,
+        Jump,
+    }
+
+    #[test]
+    fn lifecycle() {
+        let mut drag_states = DragStates::new([Action::Grab]);
+        let mut action_state = ActionState::<Action>::default();
+
+        // Presses outside of the window don't start a drag
+        action_state.press(Action::Grab);
+        action_state.press(Action::Jump);
+        drag_states.update(&action_state, None);
+        assert_eq!(drag_states.state(Action::Grab), DragState::Idle);
+        assert_eq!(drag_states.state(Action::Jump), DragState::Idle);
+
+        action_state.release(Action::Grab);
+        action_state.tick(Instant::now(), Instant::now());
+        drag_states.update(&action_state, Some(Vec2::ZERO));
+        action_state.press(Action::Grab);
+        drag_states.update(&action_state, Some(Vec2::ONE));
+        assert!(drag_states.state(Action::Grab).dragging());
+
+        // The cursor leaving the window keeps the last known position
+        action_state.tick(Instant::now(), Instant::now());
+        drag_states.update(&action_state, None);
+        assert_eq!(drag_states.state(Action::Grab).current(), Some(Vec2::ONE));
+
+        action_state.release(Action::Grab);
+        drag_states.update(&action_state, Some(Vec2::ZERO));
+        assert!(drag_states.state(Action::Grab).released());
+
+        action_state.tick(Instant::now(), Instant::now());
+        drag_states.update(&action_state, Some(Vec2::ZERO));
+        assert_eq!(drag_states.state(Action::Grab), DragState::Idle);
+    }
+}
@@ -47,6 +47,8 @@ use bevy::{
 };
 use once_cell::sync::OnceCell;
 
+use crate::action_metadata::ActionInfo;
+use crate::errors::{report, InputManagerError};
 use crate::Actionlike;
 
 pub use leafwing_input_manager_macros::DynActionMarker;
@@ -80,7 +82,21 @@ impl DynActionRegistry {
     /// Puts the registered types in a global static and enables [`DynAction`] using systems to work.
     ///
     /// Note: Do not create instances of any type in this crate that uses [`DynAction`] as its [`Actionlike`] type before calling this function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than [`MAX_ACTIONS`](crate::MAX_ACTIONS) types were registered.
     pub fn finish(self) {
+        let found = self.0.len();
+        assert!(
+            found <= crate::MAX_ACTIONS,
+            "{}",
+            InputManagerError::TooManyActions {
+                found,
+                max: crate::MAX_ACTIONS
+            }
+        );
+
         let map = self
             .0
             .into_iter()
@@ -131,20 +147,28 @@ impl Actionlike for DynAction {
     fn index(&self) -> usize {
         self.0
     }
+
+    fn info(&self) -> ActionInfo {
+        ActionInfo::new(format!("DynAction({})", self.0))
+    }
 }
 
 /// Helper trait for registering [`DynAction`] types to an app where the [`DynActionRegistry`] exists as a resource
 pub trait RegisterActionToAppExt {
-    /// Calls [`DynActionRegistry::register`] on the [`DynActionRegistry`] resource if it exists, otherwise panics.
+    /// Calls [`DynActionRegistry::register`] on the [`DynActionRegistry`] resource if it exists
+    ///
+    /// Otherwise, the action is not registered, and an [`InputManagerError`] is [reported](crate::errors).
     fn register_action<A: DynActionMarker>(&mut self) -> &mut Self;
 }
 
 impl RegisterActionToAppExt for App {
     fn register_action<A: DynActionMarker>(&mut self) -> &mut Self {
-        self.world
-            .get_resource_mut::<DynActionRegistry>()
-            .expect("The `DynActionRegistry` isn't currently in the world!")
-            .register::<A>();
+        match self.world.get_resource_mut::<DynActionRegistry>() {
+            Some(mut registry) => registry.register::<A>(),
+            None => report(InputManagerError::MissingResource {
+                resource: std::any::type_name::<DynActionRegistry>(),
+            }),
+        }
         self
     }
 }
@@ -1,5 +1,10 @@
-//! Errors that may occur when working with 2D coordinates
+//! Errors that may occur when working with this crate
+//!
+//! Most mistakes, such as an [`ActionStateDriver`](crate::action_state::ActionStateDriver) targeting a missing entity,
+//! are recoverable: the offending operation is skipped, and an [`InputManagerError`] is logged as a warning.
+//! Enable the `strict` feature to panic on these errors instead, which catches setup mistakes early during development.
 
+use bevy::ecs::entity::Entity;
 use derive_more::{Display, Error};
 
 /// The supplied vector-like struct was too close to zero to be converted into a rotation-like type
@@ -11,3 +16,128 @@ use derive_more::{Display, Error};
 /// In almost all cases, the correct way to handle this error is to simply not change the rotation.
 #[derive(Debug, Clone, Copy, Error, Display, PartialEq, Eq)]
 pub struct NearlySingularConversion;
+
+/// A recoverable mistake made when configuring or driving actions
+#[derive(Debug, Clone, Error, Display, PartialEq)]
+pub enum InputManagerError {
+    /// The entity does not exist, or does not have an [`ActionState`](crate::action_state::ActionState) of the expected action type
+    #[display(
+        fmt = "{:?} does not exist, or does not have an `ActionState<{}>` component",
+        entity,
+        action_type
+    )]
+    MissingActionState {
+        /// The entity that was expected to have an [`ActionState`](crate::action_state::ActionState)
+        entity: Entity,
+        /// The name of the action type
+        action_type: &'static str,
+    },
+    /// The number of [`ActionData`](crate::action_state::ActionData) supplied does not match the number of actions
+    #[display(
+        fmt = "expected data for {} actions, but received data for {}",
+        expected,
+        found
+    )]
+    MismatchedActionCount {
+        /// The number of variants of the action type
+        expected: usize,
+        /// The number of [`ActionData`](crate::action_state::ActionData) supplied
+        found: usize,
+    },
+    /// A threshold is not a number between `0.0` and `1.0`
+    #[display(fmt = "the threshold {} is not between 0.0 and 1.0", threshold)]
+    InvalidThreshold {
+        /// The rejected threshold
+        threshold: f32,
+    },
+    /// A deserialized binding refers to an action that does not exist
+    #[display(fmt = "no action is named {:?}", name)]
+    UnknownAction {
+        /// The name that could not be deserialized into an action
+        #[error(not(source))]
+        name: String,
+    },
+    /// A binding override refers to a button that does not exist
+    #[display(fmt = "no button is named {:?}", name)]
+    UnknownInput {
+        /// The name that could not be parsed into a button
+        #[error(not(source))]
+        name: String,
+    },
+    /// A binding override is not of the form `Action=Input`
+    #[display(
+        fmt = "the binding override {:?} is not of the form `Action=Input`",
+        entry
+    )]
+    MalformedOverride {
+        /// The rejected entry
+        #[error(not(source))]
+        entry: String,
+    },
+    /// A resource that the operation relies on has not been inserted into the world
+    #[display(fmt = "the `{}` resource does not exist", resource)]
+    MissingResource {
+        /// The name of the resource type
+        resource: &'static str,
+    },
+    /// A gamepad was required, but none is assigned to the [`InputMap`](crate::input_map::InputMap)
+    #[display(fmt = "no gamepad is assigned to the input map")]
+    GamepadNotAssigned,
+    /// The action type has more variants than the action indices used for networking can encode
+    #[display(
+        fmt = "the action type has {} variants, but at most {} actions can be encoded",
+        found,
+        max
+    )]
+    TooManyActions {
+        /// The number of variants of the action type
+        found: usize,
+        /// The largest number of actions that can be encoded, [`MAX_ACTIONS`](crate::MAX_ACTIONS)
+        max: usize,
+    },
+    /// A replicated message could not be serialized by a [transport](crate::diff_transport)
+    #[display(fmt = "the replicated message is malformed: {}", reason)]
+    MalformedMessage {
+        /// The error of the serialization format
+        #[error(not(source))]
+        reason: String,
+    },
+}
+
+impl InputManagerError {
+    /// Checks that the `threshold` is a number between `0.0` and `1.0`
+    pub fn check_threshold(threshold: f32) -> Result<f32, InputManagerError> {
+        if (0.0..=1.0).contains(&threshold) {
+            Ok(threshold)
+        } else {
+            Err(InputManagerError::InvalidThreshold { threshold })
+        }
+    }
+
+    /// Checks that the indices of every action of type `A` fit in the encoding used for networking
+    ///
+    /// Derived [`Actionlike`](crate::Actionlike) types are checked at compile time,
+    /// so this is only needed for types that implement it manually, such as those with a number of actions only known at runtime.
+    pub fn check_action_count<A: crate::Actionlike>() -> Result<(), InputManagerError> {
+        if A::n_variants() <= crate::MAX_ACTIONS {
+            Ok(())
+        } else {
+            Err(InputManagerError::TooManyActions {
+                found: A::n_variants(),
+                max: crate::MAX_ACTIONS,
+            })
+        }
+    }
+}
+
+/// Reports an `error` that the crate has recovered from
+///
+/// This logs a warning, or panics if the `strict` feature is enabled.
+#[track_caller]
+pub(crate) fn report(error: InputManagerError) {
+    if cfg!(feature = "strict") {
+        panic!("{error}");
+    } else {
+        bevy::log::warn!("{error}");
+    }
+}
@@ -0,0 +1,185 @@
+//! Mutually exclusive groups of actions, with radio-button semantics
+//!
+//! Some actions only make sense one at a time, such as the weapon slots of a shooter.
+//! Add an [`ExclusiveGroups`] resource or component alongside an [`ActionState`], and declare each group of such actions.
+//! When a member of a group is pressed, every other pressed member of that group is consumed,
+//! and the pressed member becomes the active member of the group until another member is pressed.
+//!
+//! This is applied each frame by the [`apply_exclusive_groups`](crate::systems::apply_exclusive_groups) system.
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+
+/// The mutually exclusive groups of actions of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it controls.
+/// An action can belong to at most one group.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::Instant;
+/// use leafwing_input_manager::exclusive_groups::ExclusiveGroups;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Slot1,
+///     Slot2,
+///     Slot3,
+///     Jump,
+/// }
+///
+/// let mut groups = ExclusiveGroups::<Action>::default();
+/// groups.add([Action::Slot1, Action::Slot2, Action::Slot3]);
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// action_state.press(Action::Slot1);
+/// groups.update(&mut action_state);
+/// assert_eq!(groups.active_member(Action::Slot3), Some(Action::Slot1));
+///
+/// // Pressing another slot releases the first one
+/// action_state.tick(Instant::now(), Instant::now());
+/// action_state.press(Action::Slot2);
+/// groups.update(&mut action_state);
+/// assert!(action_state.released(Action::Slot1));
+/// assert_eq!(groups.active_member(Action::Slot1), Some(Action::Slot2));
+///
+/// // Actions outside of any group are unaffected
+/// assert_eq!(groups.active_member(Action::Jump), None);
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct ExclusiveGroups<A: Actionlike> {
+    /// The members of each group
+    groups: Vec<Vec<A>>,
+    /// The most recently pressed member of each group
+    active: Vec<Option<A>>,
+}
+
+impl<A: Actionlike> Default for ExclusiveGroups<A> {
+    fn default() -> Self {
+        ExclusiveGroups {
+            groups: Vec::new(),
+            active: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike> ExclusiveGroups<A> {
+    /// Declares a new group of mutually exclusive `members`
+    ///
+    /// Members that already belong to another group are removed from it first.
+    pub fn add(&mut self, members: impl IntoIterator<Item = A>) -> &mut Self {
+        let members: Vec<A> = members.into_iter().collect();
+        let is_new_member = |action: &A| {
+            members
+                .iter()
+                .any(|member| member.index() == action.index())
+        };
+
+        for (group, active) in self.groups.iter_mut().zip(self.active.iter_mut()) {
+            group.retain(|existing| !is_new_member(existing));
+            if active.as_ref().is_some_and(is_new_member) {
+                *active = None;
+            }
+        }
+
+        self.groups.push(members);
+        self.active.push(None);
+        self
+    }
+
+    /// Returns the members of the group containing the `action`, if any
+    #[must_use]
+    pub fn group(&self, action: A) -> Option<&[A]> {
+        self.group_index(&action)
+            .map(|index| self.groups[index].as_slice())
+    }
+
+    /// Returns the most recently pressed member of the group containing the `action`
+    ///
+    /// The active member stays active after it is released, until another member of the group is pressed.
+    /// Returns [`None`] if the `action` is not in a group, or if no member of its group has been pressed yet.
+    #[must_use]
+    pub fn active_member(&self, action: A) -> Option<A> {
+        self.group_index(&action)
+            .and_then(|index| self.active[index].clone())
+    }
+
+    /// Applies the groups to the `action_state`
+    ///
+    /// For each group with a member that was just pressed, that member becomes active
+    /// and every other pressed member is consumed.
+    /// If several members were just pressed at once, the first one in the group wins.
+    pub fn update(&mut self, action_state: &mut ActionState<A>) {
+        for (group, active) in self.groups.iter().zip(self.active.iter_mut()) {
+            let Some(pressed) = group
+                .iter()
+                .find(|member| action_state.newly_pressed((*member).clone()))
+            else {
+                continue;
+            };
+
+            for member in group {
+                if member.index() != pressed.index() && action_state.pressed(member.clone()) {
+                    action_state.consume(member.clone());
+                }
+            }
+
+            *active = Some(pressed.clone());
+        }
+    }
+
+    fn group_index(&self, action: &A) -> Option<usize> {
+        self.groups
+            .iter()
+            .position(|group| group.iter().any(|member| member.index() == action.index()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Slot1,
+        Slot2,
+        Jump,
+    }
+
+    #[test]
+    fn replaced_members_stay_released_while_held() {
+        let mut groups = ExclusiveGroups::<Action>::default();
+        groups.add([Action::Slot1, Action::Slot2]);
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Slot1);
+        action_state.press(Action::Jump);
+        groups.update(&mut action_state);
+        action_state.tick(Instant::now(), Instant::now());
+
+        action_state.press(Action::Slot2);
+        groups.update(&mut action_state);
+        assert!(action_state.released(Action::Slot1));
+        assert!(action_state.pressed(Action::Jump));
+
+        // The input of the first slot is still held, but it can't be pressed again until released
+        action_state.press(Action::Slot1);
+        groups.update(&mut action_state);
+        assert!(action_state.released(Action::Slot1));
+        assert_eq!(groups.active_member(Action::Slot1), Some(Action::Slot2));
+
+        action_state.release(Action::Slot1);
+        action_state.tick(Instant::now(), Instant::now());
+        action_state.press(Action::Slot1);
+        groups.update(&mut action_state);
+        assert!(action_state.released(Action::Slot2));
+        assert_eq!(groups.active_member(Action::Slot2), Some(Action::Slot1));
+    }
+}
@@ -0,0 +1,338 @@
+//! Diagnostics for keyboard chords that are likely to be impossible to press due to ghosting
+//!
+//! Most keyboards wire their keys into a grid of rows and columns.
+//! When three keys that form three corners of a rectangle in this grid are held,
+//! the keyboard cannot tell whether the key at the fourth corner is also pressed,
+//! and cheaper keyboards respond by blocking one of the keys.
+//! Many keyboards also limit the number of keys that can be reported at once (their "rollover").
+//!
+//! The wiring of a keyboard cannot be detected from software,
+//! so [`KeyboardMatrix`] models it heuristically, using the physical rows and columns of a QWERTY layout.
+//! Pass a matrix to [`InputMap::ghosting_warnings`] to find default bindings that may not work for all players,
+//! or add the [`warn_about_ghosting`](crate::systems::warn_about_ghosting) system to log these warnings whenever an [`InputMap`] changes.
+//!
+//! ```rust
+//! use bevy::prelude::{KeyCode, Reflect};
+//! use leafwing_input_manager::ghosting::{GhostingRisk, KeyboardMatrix};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Dash,
+//! }
+//!
+//! let mut input_map = InputMap::default();
+//! // W and S share a column, while A and S share a row
+//! input_map.insert_chord([KeyCode::W, KeyCode::A, KeyCode::S], Action::Dash);
+//!
+//! let warnings = input_map.ghosting_warnings(&KeyboardMatrix::default());
+//! assert_eq!(warnings.len(), 1);
+//! assert_eq!(warnings[0].risk, GhostingRisk::Ghost { phantom: KeyCode::Q });
+//! ```
+
+use crate::input_map::InputMap;
+use crate::user_input::{InputKind, UserInput};
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Resource;
+use bevy::input::keyboard::KeyCode;
+use bevy::utils::HashMap;
+
+/// A heuristic model of how the keys of a keyboard are wired together
+///
+/// Keys that share a row or a column of the matrix are assumed to share a wire.
+/// Keys that are not in the matrix (such as modifiers, which are usually wired separately) never cause ghosting.
+///
+/// The [`Default`] matrix uses the physical rows and columns of the alphanumeric keys on a QWERTY keyboard,
+/// along with the 6-key rollover of the USB boot protocol.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardMatrix {
+    /// The `(row, column)` of each key in the matrix
+    positions: HashMap<KeyCode, (u8, u8)>,
+    /// The maximum number of keys in the matrix that can be held at once, if any
+    rollover: Option<usize>,
+}
+
+impl Default for KeyboardMatrix {
+    fn default() -> Self {
+        use KeyCode::*;
+
+        let rows: [&[KeyCode]; 4] = [
+            &[
+                Key1, Key2, Key3, Key4, Key5, Key6, Key7, Key8, Key9, Key0, Minus, Equals,
+            ],
+            &[Q, W, E, R, T, Y, U, I, O, P, BracketLeft, BracketRight],
+            &[A, S, D, F, G, H, J, K, L, Semicolon, Apostrophe],
+            &[Z, X, C, V, B, N, M, Comma, Period, Slash],
+        ];
+
+        let mut matrix = KeyboardMatrix::empty().with_rollover(Some(6));
+        for (row, keys) in rows.into_iter().enumerate() {
+            for (column, key) in keys.iter().enumerate() {
+                matrix.set_position(*key, row as u8, column as u8);
+            }
+        }
+        matrix
+    }
+}
+
+impl KeyboardMatrix {
+    /// Creates a matrix without any keys, and with unlimited rollover
+    ///
+    /// Use this to describe a specific keyboard using [`KeyboardMatrix::set_position`].
+    #[must_use]
+    pub fn empty() -> KeyboardMatrix {
+        KeyboardMatrix {
+            positions: HashMap::default(),
+            rollover: None,
+        }
+    }
+
+    /// Returns this matrix with the rollover limit set to the specified value
+    ///
+    /// [`None`] represents a keyboard with N-key rollover.
+    #[must_use]
+    pub fn with_rollover(mut self, rollover: Option<usize>) -> KeyboardMatrix {
+        self.rollover = rollover;
+        self
+    }
+
+    /// The maximum number of keys in the matrix that can be held at once, if any
+    #[must_use]
+    pub fn rollover(&self) -> Option<usize> {
+        self.rollover
+    }
+
+    /// Places the `key` at the provided `row` and `column` of the matrix
+    pub fn set_position(&mut self, key: KeyCode, row: u8, column: u8) -> &mut Self {
+        self.positions.insert(key, (row, column));
+        self
+    }
+
+    /// Removes the `key` from the matrix, so that it never causes ghosting
+    pub fn remove_position(&mut self, key: KeyCode) -> &mut Self {
+        self.positions.remove(&key);
+        self
+    }
+
+    /// Returns the `(row, column)` of the `key`, if it is part of the matrix
+    #[must_use]
+    pub fn position(&self, key: KeyCode) -> Option<(u8, u8)> {
+        self.positions.get(&key).copied()
+    }
+
+    /// Returns the risks of holding all of the `keys` at once
+    ///
+    /// Keys that are not part of the matrix are ignored, and each key is only counted once.
+    #[must_use]
+    pub fn risks(&self, keys: &[KeyCode]) -> Vec<GhostingRisk> {
+        let mut keys: Vec<(KeyCode, (u8, u8))> = keys
+            .iter()
+            .filter_map(|&key| Some((key, self.position(key)?)))
+            .collect();
+        keys.sort_unstable_by_key(|(key, _)| *key);
+        keys.dedup_by_key(|(key, _)| *key);
+
+        let mut risks = Vec::new();
+
+        if let Some(limit) = self.rollover {
+            if keys.len() > limit {
+                risks.push(GhostingRisk::RolloverExceeded {
+                    keys: keys.len(),
+                    limit,
+                });
+            }
+        }
+
+        // Look for a corner key which shares a row with one key and a column with another
+        for &(_, (corner_row, corner_column)) in keys.iter() {
+            for &(_, (row, column)) in keys.iter() {
+                if row != corner_row || column == corner_column {
+                    continue;
+                }
+
+                for &(_, (other_row, other_column)) in keys.iter() {
+                    if other_column != corner_column || other_row == corner_row {
+                        continue;
+                    }
+
+                    // The fourth corner of the rectangle is only a problem if a key is wired there,
+                    // and it is not already part of the chord
+                    if let Some(phantom) = self.key_at(other_row, column).filter(|phantom| {
+                        keys.binary_search_by_key(phantom, |(key, _)| *key).is_err()
+                    }) {
+                        let risk = GhostingRisk::Ghost { phantom };
+                        if !risks.contains(&risk) {
+                            risks.push(risk);
+                        }
+                    }
+                }
+            }
+        }
+
+        risks
+    }
+
+    fn key_at(&self, row: u8, column: u8) -> Option<KeyCode> {
+        self.positions
+            .iter()
+            .find(|(_, &position)| position == (row, column))
+            .map(|(&key, _)| key)
+    }
+}
+
+/// The reason that a chord might not be pressable on some keyboards
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GhostingRisk {
+    /// The keys in the chord form three corners of a rectangle in the [`KeyboardMatrix`]
+    ///
+    /// The keyboard may register the `phantom` key at the fourth corner, or block one of the keys.
+    Ghost {
+        /// The key at the fourth corner of the rectangle
+        phantom: KeyCode,
+    },
+    /// The chord contains more keys than the keyboard can report at once
+    RolloverExceeded {
+        /// The number of keys in the chord
+        keys: usize,
+        /// The rollover limit of the keyboard
+        limit: usize,
+    },
+}
+
+/// A binding in an [`InputMap`] that is likely to be impossible to press on some keyboards
+#[derive(Debug, Clone, PartialEq)]
+pub struct GhostingWarning<A: Actionlike> {
+    /// The action that the input is bound to
+    pub action: A,
+    /// The problematic input
+    pub input: UserInput,
+    /// Why the input might not be pressable
+    pub risk: GhostingRisk,
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Returns a [`GhostingWarning`] for each risk found in the keyboard chords of this map
+    ///
+    /// Only the keys of [`UserInput::Chord`] are checked, as other inputs do not require keys to be held together.
+    #[must_use]
+    pub fn ghosting_warnings(&self, matrix: &KeyboardMatrix) -> Vec<GhostingWarning<A>> {
+        let mut warnings = Vec::new();
+
+        for (action, _, input) in self.iter() {
+            let UserInput::Chord(chord) = input else {
+                continue;
+            };
+
+            let keys: Vec<KeyCode> = chord
+                .iter()
+                .filter_map(|input_kind| match input_kind {
+                    InputKind::Keyboard(key) => Some(*key),
+                    _ => None,
+                })
+                .collect();
+
+            for risk in matrix.risks(&keys) {
+                warnings.push(GhostingWarning {
+                    action: action.clone(),
+                    input: input.clone(),
+                    risk,
+                });
+            }
+        }
+
+        warnings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        One,
+        Two,
+    }
+
+    #[test]
+    fn feasible_chords() {
+        let matrix = KeyboardMatrix::default();
+
+        // Two keys can never form a rectangle
+        assert!(matrix.risks(&[KeyCode::W, KeyCode::S]).is_empty());
+        // Keys on different rows and columns are safe
+        assert!(matrix
+            .risks(&[KeyCode::Q, KeyCode::S, KeyCode::C])
+            .is_empty());
+        // Modifiers are not part of the matrix
+        assert!(matrix
+            .risks(&[KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::A])
+            .is_empty());
+    }
+
+    #[test]
+    fn rollover() {
+        let mut matrix = KeyboardMatrix::empty().with_rollover(Some(2));
+        // Keys outside of the matrix do not count towards the rollover limit
+        assert!(matrix
+            .risks(&[KeyCode::A, KeyCode::B, KeyCode::C])
+            .is_empty());
+
+        matrix
+            .set_position(KeyCode::A, 0, 0)
+            .set_position(KeyCode::B, 1, 1)
+            .set_position(KeyCode::C, 2, 2);
+        assert_eq!(
+            matrix.risks(&[KeyCode::A, KeyCode::B, KeyCode::C]),
+            [GhostingRisk::RolloverExceeded { keys: 3, limit: 2 }]
+        );
+    }
+
+    #[test]
+    fn unsorted_and_overlapping_keys() {
+        let matrix = KeyboardMatrix::default().with_rollover(Some(4));
+
+        // Repeated keys are only counted once, even when they are not next to each other
+        assert!(matrix
+            .risks(&[KeyCode::E, KeyCode::F, KeyCode::E, KeyCode::D, KeyCode::F])
+            .iter()
+            .all(|risk| !matches!(risk, GhostingRisk::RolloverExceeded { .. })));
+
+        // Each phantom key is only reported once
+        assert_eq!(
+            matrix.risks(&[KeyCode::F, KeyCode::E, KeyCode::D, KeyCode::E]),
+            [GhostingRisk::Ghost {
+                phantom: KeyCode::R
+            }]
+        );
+
+        // Keys at the fourth corner that are already held are not phantoms
+        assert!(matrix
+            .risks(&[KeyCode::F, KeyCode::R, KeyCode::E, KeyCode::D, KeyCode::R])
+            .is_empty());
+    }
+
+    #[test]
+    fn input_map_warnings() {
+        let mut input_map = InputMap::<Action>::default();
+        input_map
+            .insert_chord([KeyCode::E, KeyCode::D, KeyCode::F], Action::One)
+            .insert_chord([KeyCode::ControlLeft, KeyCode::E], Action::Two)
+            .insert(KeyCode::R, Action::Two);
+
+        let warnings = input_map.ghosting_warnings(&KeyboardMatrix::default());
+        assert_eq!(
+            warnings,
+            [GhostingWarning {
+                action: Action::One,
+                input: UserInput::chord([KeyCode::E, KeyCode::D, KeyCode::F]),
+                risk: GhostingRisk::Ghost {
+                    phantom: KeyCode::R
+                },
+            }]
+        );
+    }
+}
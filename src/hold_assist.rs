@@ -0,0 +1,207 @@
+//! An accessibility option that turns actions which must be held into toggles
+//!
+//! Some players can't hold a button down for long periods of time.
+//! Insert a [`HoldAssist`] resource listing the actions that would normally be held (such as sprinting or aiming),
+//! and pressing their inputs once will hold the action until they are pressed again.
+//!
+//! The [`HoldAssist`] applies to every [`ActionState`](crate::action_state::ActionState) of its action type,
+//! and is read by [`update_action_state`](crate::systems::update_action_state).
+//! It can be switched on and off at runtime using [`HoldAssist::set_enabled`], for example from a settings menu;
+//! disabling it releases every toggled action whose input is not held.
+
+use crate::action_state::ActionData;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Resource;
+use std::marker::PhantomData;
+
+/// The actions of type `A` that are toggled by their inputs instead of held
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::hold_assist::HoldAssist;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Sprint,
+///     Jump,
+/// }
+///
+/// fn toggle_hold_assist(mut hold_assist: ResMut<HoldAssist<Action>>) {
+///     let enabled = hold_assist.enabled();
+///     hold_assist.set_enabled(!enabled);
+/// }
+///
+/// let mut app = App::new();
+/// app.insert_resource(HoldAssist::new([Action::Sprint]));
+/// ```
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct HoldAssist<A: Actionlike> {
+    enabled: bool,
+    /// Whether each action is toggled, indexed by [`Actionlike::index`]
+    toggled: Vec<bool>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for HoldAssist<A> {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            toggled: vec![false; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> HoldAssist<A> {
+    /// Creates an enabled [`HoldAssist`] that toggles the provided `actions`
+    #[must_use]
+    pub fn new(actions: impl IntoIterator<Item = A>) -> Self {
+        let mut hold_assist = Self::default();
+        for action in actions {
+            hold_assist.add(action);
+        }
+        hold_assist
+    }
+
+    /// Makes the `action` toggled by its inputs
+    pub fn add(&mut self, action: A) -> &mut Self {
+        self.toggled[action.index()] = true;
+        self
+    }
+
+    /// Makes the `action` held by its inputs again
+    pub fn remove(&mut self, action: A) -> &mut Self {
+        self.toggled[action.index()] = false;
+        self
+    }
+
+    /// Is the `action` toggled by its inputs?
+    ///
+    /// This is false for every action while the [`HoldAssist`] is disabled.
+    #[must_use]
+    pub fn toggles(&self, action: A) -> bool {
+        self.enabled && self.toggled[action.index()]
+    }
+
+    /// Enables or disables the [`HoldAssist`], without forgetting which actions it toggles
+    pub fn set_enabled(&mut self, enabled: bool) -> &mut Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// Is the [`HoldAssist`] enabled?
+    #[must_use]
+    pub fn enabled(&self) -> bool {
+        self.enabled
+    }
+
+    /// Converts the held state of each toggled action in the `action_data` into its toggled state
+    ///
+    /// The `latches` store the toggle state of each action for a single [`ActionState`](crate::action_state::ActionState),
+    /// and are updated to reflect the current frame.
+    pub(crate) fn apply(&self, action_data: &mut [ActionData], latches: &mut Vec<HoldLatch>) {
+        if latches.len() < A::n_variants() {
+            latches.resize(A::n_variants(), HoldLatch::default());
+        }
+
+        for action in A::variants() {
+            let data = &mut action_data[action.index()];
+            let latch = &mut latches[action.index()];
+
+            // The inputs are always tracked, so that enabling the assist mid-press doesn't toggle the action
+            let held = data.state.pressed();
+            let just_held = held && !latch.input_held;
+            latch.input_held = held;
+
+            if !self.toggles(action) {
+                latch.latched = false;
+                continue;
+            }
+
+            if just_held {
+                latch.latched = !latch.latched;
+            }
+
+            if latch.latched {
+                if !held {
+                    data.state = ButtonState::Pressed;
+                    data.value = 1.0;
+                }
+            } else {
+                data.state = ButtonState::Released;
+                data.value = 0.0;
+            }
+        }
+    }
+}
+
+/// The toggle state of a single action affected by a [`HoldAssist`]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct HoldLatch {
+    /// Were the inputs of the action held during the previous update?
+    input_held: bool,
+    /// Is the action currently toggled on?
+    latched: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Sprint,
+        Jump,
+    }
+
+    fn inputs(sprint: bool, jump: bool) -> Vec<ActionData> {
+        [sprint, jump]
+            .into_iter()
+            .map(|pressed| ActionData {
+                state: if pressed {
+                    ButtonState::JustPressed
+                } else {
+                    ButtonState::Released
+                },
+                value: if pressed { 1.0 } else { 0.0 },
+                ..Default::default()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn toggling() {
+        let mut hold_assist = HoldAssist::new([Action::Sprint]);
+        let mut latches = Vec::new();
+        let mut update = |hold_assist: &HoldAssist<Action>, sprint: bool, jump: bool| {
+            let mut action_data = inputs(sprint, jump);
+            hold_assist.apply(&mut action_data, &mut latches);
+            (
+                action_data[0].state.pressed(),
+                action_data[1].state.pressed(),
+            )
+        };
+
+        assert_eq!(update(&hold_assist, true, true), (true, true));
+        // Sprinting stays on once its input is released, but jumping doesn't
+        assert_eq!(update(&hold_assist, false, false), (true, false));
+        assert_eq!(update(&hold_assist, false, false), (true, false));
+        // Pressing again toggles sprinting off, even while the input is held
+        assert_eq!(update(&hold_assist, true, false), (false, false));
+        assert_eq!(update(&hold_assist, true, false), (false, false));
+        assert_eq!(update(&hold_assist, false, false), (false, false));
+
+        // Disabling the assist releases toggled actions
+        assert_eq!(update(&hold_assist, true, false), (true, false));
+        assert_eq!(update(&hold_assist, false, false), (true, false));
+        hold_assist.set_enabled(false);
+        assert_eq!(update(&hold_assist, false, false), (false, false));
+        assert_eq!(update(&hold_assist, true, false), (true, false));
+    }
+}
@@ -0,0 +1,248 @@
+//! Actions that must be held for a duration before they take effect, for hold-to-confirm interactions
+//!
+//! Destructive or irreversible actions, such as quitting or skipping a cutscene, are often confirmed by holding a button,
+//! while a circular indicator fills up.
+//! Add a [`HoldRequirements`] resource or component alongside an [`ActionState`] and its [`ActionTimings`],
+//! and configure it per action with [`HoldRequirements::set`] and a [`HoldFor`].
+//!
+//! While the action is held, [`HoldRequirements::progress`] rises from `0.0` to `1.0`, and can be bound directly to the indicator.
+//! As holds are measured with the [`ActionTimings`] of the action, they only progress for [`ActionState`]s with timings.
+//! [`HoldRequirements::just_completed`] is true for the single tick during which the hold reached its duration,
+//! as recorded by the [`record_hold_completions`](crate::systems::record_hold_completions) system,
+//! and [`HoldRequirements::completed`] stays true until the action is released.
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::hold_for::{HoldFor, HoldRequirements};
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::timing::ActionTimings;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     Quit,
+//! }
+//!
+//! let action_state = ActionState::<MenuAction>::default();
+//! let timings = ActionTimings::<MenuAction>::default();
+//! let mut holds = HoldRequirements::<MenuAction>::default();
+//! holds.set(MenuAction::Quit, Some(HoldFor::new(Duration::from_secs(2))));
+//! assert_eq!(holds.progress(&action_state, &timings, MenuAction::Quit), 0.0);
+//! ```
+
+use crate::action_state::ActionState;
+use crate::timing::ActionTimings;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// How long an action must be held before it completes
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct HoldFor {
+    duration: Duration,
+}
+
+impl HoldFor {
+    /// The shortest duration that an action can be required to be held for
+    pub const SHORTEST_DURATION: Duration = Duration::from_millis(1);
+
+    /// Requires the action to be held for the `duration`
+    ///
+    /// The `duration` is raised to [`HoldFor::SHORTEST_DURATION`] if it is shorter.
+    #[must_use]
+    pub fn new(duration: Duration) -> Self {
+        Self {
+            duration: duration.max(Self::SHORTEST_DURATION),
+        }
+    }
+
+    /// How long the action must be held before it completes
+    #[must_use]
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The fraction of the duration that has elapsed once the action has been held for `held`, between `0.0` and `1.0`
+    #[must_use]
+    pub fn progress(&self, held: Duration) -> f32 {
+        (held.as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+    }
+}
+
+/// How long each action of type `A` must be held before it completes, and the holds that completed during the last tick
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] and [`ActionTimings`] that it measures.
+#[derive(Resource, Component, Debug, Clone, PartialEq, Eq)]
+pub struct HoldRequirements<A: Actionlike> {
+    /// How long each action must be held, indexed by [`Actionlike::index`]
+    hold_for: Vec<Option<HoldFor>>,
+    /// Whether each action completed its [`HoldFor`] during the last tick, indexed by [`Actionlike::index`]
+    completions: Vec<bool>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for HoldRequirements<A> {
+    fn default() -> Self {
+        HoldRequirements {
+            hold_for: vec![None; A::n_variants()],
+            completions: vec![false; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> HoldRequirements<A> {
+    /// Sets how long the `action` must be held before it completes, or removes the requirement if [`None`]
+    pub fn set(&mut self, action: A, hold_for: Option<HoldFor>) -> &mut Self {
+        let index = action.index();
+        self.hold_for[index] = hold_for;
+        if hold_for.is_none() {
+            self.completions[index] = false;
+        }
+        self
+    }
+
+    /// How long the `action` must be held before it completes, if at all
+    #[must_use]
+    pub fn get(&self, action: A) -> Option<&HoldFor> {
+        self.hold_for[action.index()].as_ref()
+    }
+
+    /// How far the `action` has progressed towards completing its [`HoldFor`], between `0.0` and `1.0`
+    ///
+    /// This is `0.0` while the action is released or consumed, and for actions without a [`HoldFor`].
+    #[must_use]
+    pub fn progress(
+        &self,
+        action_state: &ActionState<A>,
+        timings: &ActionTimings<A>,
+        action: A,
+    ) -> f32 {
+        let Some(hold_for) = self.get(action.clone()) else {
+            return 0.0;
+        };
+
+        let action_data = action_state.action_data(action.clone());
+        if action_data.state.pressed() && !action_data.consumed {
+            hold_for.progress(timings.current_duration(action_state, action))
+        } else {
+            0.0
+        }
+    }
+
+    /// Has the `action` been held for at least the duration of its [`HoldFor`]?
+    ///
+    /// This stays true until the action is released, and is always false for actions without a [`HoldFor`].
+    #[must_use]
+    pub fn completed(
+        &self,
+        action_state: &ActionState<A>,
+        timings: &ActionTimings<A>,
+        action: A,
+    ) -> bool {
+        self.get(action.clone()).is_some() && self.progress(action_state, timings, action) >= 1.0
+    }
+
+    /// Did the `action` complete its [`HoldFor`] during the last tick?
+    #[must_use]
+    pub fn just_completed(&self, action: A) -> bool {
+        self.completions[action.index()]
+    }
+
+    /// Records which held actions reached the duration of their [`HoldFor`] during a tick from the `previous_instant` to the `current_instant`
+    ///
+    /// This should be called after the `action_state` and its `timings` were [ticked](ActionState::tick_with_timings) with the same instants.
+    pub fn tick(
+        &mut self,
+        action_state: &ActionState<A>,
+        timings: &ActionTimings<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        let delta = current_instant.saturating_duration_since(previous_instant);
+
+        for (index, action) in A::variants().enumerate() {
+            let action_data = action_state.action_data(action.clone());
+            self.completions[index] = match self.hold_for[index] {
+                Some(hold_for) if action_data.state.pressed() && !action_data.consumed => {
+                    // Actions pressed since the last tick are timed from its start, so they were not held before it
+                    let held = timings.current_duration(action_state, action);
+                    held.saturating_sub(delta) < hold_for.duration && held >= hold_for.duration
+                }
+                _ => false,
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Quit,
+        Jump,
+    }
+
+    #[test]
+    fn progress_is_clamped() {
+        let hold_for = HoldFor::new(Duration::from_millis(500));
+
+        assert_eq!(hold_for.progress(Duration::ZERO), 0.0);
+        assert_eq!(hold_for.progress(Duration::from_millis(250)), 0.5);
+        assert_eq!(hold_for.progress(Duration::from_secs(2)), 1.0);
+        assert_eq!(
+            HoldFor::new(Duration::ZERO).duration(),
+            HoldFor::SHORTEST_DURATION
+        );
+    }
+
+    #[test]
+    fn held_actions_complete() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let mut holds = HoldRequirements::<Action>::default();
+        holds.set(Action::Quit, Some(HoldFor::new(Duration::from_millis(500))));
+
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        let mut tick = |action_state: &mut ActionState<Action>,
+                        timings: &mut ActionTimings<Action>,
+                        from: u64,
+                        to: u64| {
+            action_state.tick_with_timings(timings, t0 + ms(to), t0 + ms(from));
+            holds.tick(action_state, timings, t0 + ms(to), t0 + ms(from));
+            holds.clone()
+        };
+
+        action_state.press(Action::Quit);
+        action_state.press(Action::Jump);
+        let holds = tick(&mut action_state, &mut timings, 0, 250);
+        assert_eq!(holds.progress(&action_state, &timings, Action::Quit), 0.5);
+        assert!(!holds.completed(&action_state, &timings, Action::Quit));
+        assert_eq!(holds.progress(&action_state, &timings, Action::Jump), 0.0);
+
+        let holds = tick(&mut action_state, &mut timings, 250, 500);
+        assert_eq!(holds.progress(&action_state, &timings, Action::Quit), 1.0);
+        assert!(holds.just_completed(Action::Quit));
+        assert!(holds.completed(&action_state, &timings, Action::Quit));
+        assert!(!holds.just_completed(Action::Jump));
+
+        let holds = tick(&mut action_state, &mut timings, 500, 750);
+        assert!(!holds.just_completed(Action::Quit));
+        assert!(holds.completed(&action_state, &timings, Action::Quit));
+
+        // Releasing the action cancels the hold
+        action_state.release(Action::Quit);
+        assert_eq!(holds.progress(&action_state, &timings, Action::Quit), 0.0);
+        assert!(!holds.completed(&action_state, &timings, Action::Quit));
+    }
+}
@@ -0,0 +1,345 @@
+//! Detecting when the player has stopped giving inputs, for attract modes and AFK detection
+//!
+//! The [`InputActivity`] resource records when each [`ActionState`] of the same action type was last active,
+//! either pressed or with a non-zero value, and for how long it has been idle since.
+//! It also combines every [`ActionState`], so that the game is only considered idle once nobody is giving inputs.
+//! These are updated each frame by the [`generate_input_idle_events`](crate::systems::generate_input_idle_events) system,
+//! and read with [`InputActivity::last_activity`] and [`InputActivity::idle_for`], or their per-entity counterparts.
+//!
+//! Register thresholds on it with [`InputActivity::add_threshold`],
+//! and an [`InputIdle`] event will be sent each time an entity, or the game as a whole, has been idle for that long.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::idle::{InputActivity, InputIdle};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! fn setup(mut activity: ResMut<InputActivity<Action>>) {
+//!     activity.add_threshold(Duration::from_secs(60));
+//! }
+//!
+//! fn start_attract_mode(mut events: EventReader<InputIdle<Action>>) {
+//!     for event in events.iter() {
+//!         // Only react once every player has stopped playing
+//!         if event.entity.is_none() {
+//!             println!("Nobody has played for {:?}", event.threshold);
+//!         }
+//!     }
+//! }
+//! ```
+//!
+//! Leaving the attract mode usually shouldn't depend on the bindings: any key, even an unbound one, should do.
+//! The [`detect_any_input`](crate::systems::detect_any_input) system sends a single [`AnyInputDetected`] event
+//! in each frame during which a keyboard key, mouse button or gamepad button was pressed, or a gamepad stick was pushed,
+//! along with the kinds of devices that were used.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::idle::AnyInputDetected;
+//! use leafwing_input_manager::input_map::InputSource;
+//!
+//! fn leave_attract_mode(mut events: EventReader<AnyInputDetected>) {
+//!     for event in events.iter() {
+//!         if event.contains(InputSource::Gamepad) {
+//!             println!("Showing gamepad prompts on the title screen");
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::action_state::ActionState;
+use crate::input_map::InputSource;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Entity, Event, Resource};
+use bevy::utils::{Duration, HashMap, Instant};
+use std::marker::PhantomData;
+
+/// Sent when an [`ActionState`], or every [`ActionState`] of type `A`, has been idle for one of the thresholds registered in the [`InputActivity`]
+///
+/// Each event is only sent once per period of inactivity.
+/// These events are generated by the [`generate_input_idle_events`](crate::systems::generate_input_idle_events) system.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct InputIdle<A: Actionlike> {
+    /// The threshold that the idle duration reached
+    pub threshold: Duration,
+    /// The entity whose [`ActionState`] is idle,
+    /// or [`None`] if every [`ActionState`], including the resource, is idle
+    pub entity: Option<Entity>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> InputIdle<A> {
+    /// Creates an event for an `entity` that has been idle for the `threshold`
+    #[must_use]
+    pub fn new(threshold: Duration, entity: Option<Entity>) -> Self {
+        Self {
+            threshold,
+            entity,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// Sent once per frame during which any input was pressed, whether or not it is bound to an action
+///
+/// These events are generated by the [`detect_any_input`](crate::systems::detect_any_input) system,
+/// and are shared between every action type.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct AnyInputDetected {
+    /// The kinds of devices that inputs were pressed on during this frame, without duplicates
+    pub sources: Vec<InputSource>,
+}
+
+impl AnyInputDetected {
+    /// Was an input pressed on a device of this kind of `source`?
+    #[must_use]
+    pub fn contains(&self, source: InputSource) -> bool {
+        self.sources.contains(&source)
+    }
+}
+
+/// When an [`ActionState`] was last active, and how long it has been idle for since
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+struct IdleTime {
+    /// The instant of the last update during which an action was active
+    last_activity: Option<Instant>,
+    /// How long no action has been active for
+    idle_for: Duration,
+    /// The value of `idle_for` before the last update
+    previous_idle_for: Duration,
+}
+
+impl IdleTime {
+    /// Records that a frame ending at the `current_instant` has passed, during which an action may have been `active`
+    fn tick(&mut self, active: bool, current_instant: Instant, previous_instant: Instant) {
+        self.previous_idle_for = self.idle_for;
+        if active {
+            self.last_activity = Some(current_instant);
+            self.idle_for = Duration::ZERO;
+        } else {
+            self.idle_for += current_instant.saturating_duration_since(previous_instant);
+        }
+    }
+
+    /// Did the idle duration reach the `threshold` during the last update?
+    fn reached(&self, threshold: Duration) -> bool {
+        self.previous_idle_for < threshold && self.idle_for >= threshold
+    }
+}
+
+/// Is any action of the `action_state` currently pressed or has a non-zero value?
+fn any_active<A: Actionlike>(action_state: &ActionState<A>) -> bool {
+    A::variants().any(|action| {
+        let action_data = action_state.action_data(action);
+        action_data.state.pressed() || action_data.value != 0.0
+    })
+}
+
+/// When each [`ActionState`] of type `A`, and all of them taken together, was last active, and the idle thresholds at which [`InputIdle`] events are sent
+///
+/// This resource is inserted and updated by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+/// No events are sent until a threshold is added.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct InputActivity<A: Actionlike> {
+    thresholds: Vec<Duration>,
+    idle_time: IdleTime,
+    entities: HashMap<Entity, IdleTime>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for InputActivity<A> {
+    fn default() -> Self {
+        Self {
+            thresholds: Vec::new(),
+            idle_time: IdleTime::default(),
+            entities: HashMap::default(),
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> InputActivity<A> {
+    /// Registers a `threshold` at which an [`InputIdle`] event is sent
+    ///
+    /// Registering the same threshold twice has no effect.
+    pub fn add_threshold(&mut self, threshold: Duration) -> &mut Self {
+        if !self.thresholds.contains(&threshold) {
+            self.thresholds.push(threshold);
+        }
+        self
+    }
+
+    /// Removes a previously registered `threshold`
+    pub fn remove_threshold(&mut self, threshold: Duration) -> &mut Self {
+        self.thresholds.retain(|&existing| existing != threshold);
+        self
+    }
+
+    /// Returns the registered thresholds
+    #[must_use]
+    pub fn thresholds(&self) -> &[Duration] {
+        &self.thresholds
+    }
+
+    /// The most recent instant at which any [`ActionState`] of type `A` was active
+    #[must_use]
+    pub fn last_activity(&self) -> Option<Instant> {
+        self.idle_time.last_activity
+    }
+
+    /// How long every [`ActionState`] of type `A` has been idle for
+    #[must_use]
+    pub fn idle_for(&self) -> Duration {
+        self.idle_time.idle_for
+    }
+
+    /// The most recent instant at which the [`ActionState`] of the `entity` was active
+    ///
+    /// Returns [`None`] if it has not been active since it was first updated, or if the `entity` has no [`ActionState`].
+    #[must_use]
+    pub fn entity_last_activity(&self, entity: Entity) -> Option<Instant> {
+        self.entities.get(&entity)?.last_activity
+    }
+
+    /// How long the [`ActionState`] of the `entity` has been idle for
+    ///
+    /// Returns [`None`] if the `entity` had no [`ActionState`] during the last update.
+    #[must_use]
+    pub fn entity_idle_for(&self, entity: Entity) -> Option<Duration> {
+        self.entities
+            .get(&entity)
+            .map(|idle_time| idle_time.idle_for)
+    }
+
+    /// Records the activity of the `action_states` during a frame from the `previous_instant` to the `current_instant`,
+    /// returning an [`InputIdle`] event for each threshold that was reached
+    ///
+    /// Each [`ActionState`] is paired with its entity, or [`None`] for the [`ActionState`] resource,
+    /// which only counts towards the activity of all of them taken together.
+    /// Entities that are missing from the `action_states` are forgotten.
+    /// Nothing is updated if there are no `action_states`.
+    pub fn update<'a>(
+        &mut self,
+        action_states: impl IntoIterator<Item = (Option<Entity>, &'a ActionState<A>)>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) -> Vec<InputIdle<A>> {
+        let mut events = Vec::new();
+        let mut entities = HashMap::with_capacity(self.entities.len());
+        let mut any_state = false;
+        let mut active_anywhere = false;
+
+        for (entity, action_state) in action_states {
+            let active = any_active(action_state);
+            any_state = true;
+            active_anywhere |= active;
+
+            let Some(entity) = entity else {
+                continue;
+            };
+            let mut idle_time = self.entities.remove(&entity).unwrap_or_default();
+            idle_time.tick(active, current_instant, previous_instant);
+            events.extend(
+                self.reached_by(&idle_time)
+                    .map(|threshold| InputIdle::new(threshold, Some(entity))),
+            );
+            entities.insert(entity, idle_time);
+        }
+
+        self.entities = entities;
+        if any_state {
+            self.idle_time
+                .tick(active_anywhere, current_instant, previous_instant);
+            events.extend(
+                self.reached_by(&self.idle_time)
+                    .map(|threshold| InputIdle::new(threshold, None)),
+            );
+        }
+        events
+    }
+
+    /// The thresholds that the `idle_time` reached during the last update
+    fn reached_by<'a>(&'a self, idle_time: &'a IdleTime) -> impl Iterator<Item = Duration> + 'a {
+        self.thresholds
+            .iter()
+            .copied()
+            .filter(|&threshold| idle_time.reached(threshold))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Run,
+    }
+
+    #[test]
+    fn idle_thresholds() {
+        let mut activity = InputActivity::<Action>::default();
+        activity.add_threshold(Duration::from_secs(2));
+
+        let player_one = Entity::from_raw(1);
+        let player_two = Entity::from_raw(2);
+        let mut one = ActionState::<Action>::default();
+        let two = ActionState::<Action>::default();
+        let start = Instant::now();
+        let second = |n| start + Duration::from_secs(n);
+
+        one.press(Action::Jump);
+        for n in 1..=3 {
+            let events = activity.update(
+                [(Some(player_one), &one), (Some(player_two), &two)],
+                second(n),
+                second(n - 1),
+            );
+            one.release(Action::Jump);
+
+            match n {
+                1 => assert!(events.is_empty()),
+                // Player two is idle first, but player one still played a second ago
+                2 => assert_eq!(
+                    events,
+                    [InputIdle::new(Duration::from_secs(2), Some(player_two))]
+                ),
+                _ => assert_eq!(
+                    events,
+                    [
+                        InputIdle::new(Duration::from_secs(2), Some(player_one)),
+                        InputIdle::new(Duration::from_secs(2), None)
+                    ]
+                ),
+            }
+        }
+
+        assert_eq!(activity.entity_last_activity(player_one), Some(second(1)));
+        assert_eq!(
+            activity.entity_idle_for(player_one),
+            Some(Duration::from_secs(2))
+        );
+        assert_eq!(activity.entity_last_activity(player_two), None);
+        assert_eq!(activity.last_activity(), Some(second(1)));
+        assert_eq!(activity.idle_for(), Duration::from_secs(2));
+
+        // Activity restarts the idle duration, and missing entities are forgotten
+        let mut two = two;
+        two.action_data_mut(Action::Run).value = 0.5;
+        activity.update([(Some(player_two), &two)], second(4), second(3));
+        assert_eq!(activity.entity_idle_for(player_two), Some(Duration::ZERO));
+        assert_eq!(activity.entity_idle_for(player_one), None);
+        assert_eq!(activity.idle_for(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,84 @@
+//! Separating text entry from actions while an input method editor is composing text
+//!
+//! Players typing Chinese, Japanese or Korean text use an input method editor (IME),
+//! which turns several key presses into a single character.
+//! The keys pressed while composing are text, not commands, and should not fire gameplay actions.
+//!
+//! Each frame, the [`collect_ime_events`](crate::systems::collect_ime_events) system reads the [`Ime`](bevy::window::Ime) events
+//! sent by the window and records whether a composition is active in the [`ImeActive`] resource.
+//! While it is, keyboard inputs are ignored when updating the [`ActionState`](crate::action_state::ActionState),
+//! so actions bound to keys (including in chords) are released.
+//! Once the composition ends, keyboard inputs remain ignored until every key has been released,
+//! so that the key used to confirm the text does not also trigger an action.
+//!
+//! Remember to enable IME on the window using [`Window::ime_enabled`](bevy::window::Window::ime_enabled).
+//! Systems that should only run while the player is not typing can use the [`ime_inactive`](crate::common_conditions::ime_inactive) run condition.
+
+use bevy::ecs::prelude::Resource;
+
+/// Whether an input method editor is currently composing text
+///
+/// This resource is updated by the [`collect_ime_events`](crate::systems::collect_ime_events) system,
+/// and read when updating the [`ActionState`](crate::action_state::ActionState).
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct ImeActive {
+    composing: bool,
+    suppressing_keyboard: bool,
+}
+
+impl ImeActive {
+    /// Is the input method editor composing text?
+    #[must_use]
+    pub fn composing(&self) -> bool {
+        self.composing
+    }
+
+    /// Are keyboard inputs currently ignored when updating the [`ActionState`](crate::action_state::ActionState)?
+    ///
+    /// This is true while composing, and after the composition ends until every key has been released.
+    #[must_use]
+    pub fn suppresses_keyboard(&self) -> bool {
+        self.suppressing_keyboard
+    }
+
+    /// Starts or ends the composition of text
+    pub fn set_composing(&mut self, composing: bool) {
+        self.composing = composing;
+        self.suppressing_keyboard |= composing;
+    }
+
+    /// Stops suppressing the keyboard once composition has ended and no keys are held
+    pub fn update_suppression(&mut self, any_key_pressed: bool) {
+        self.suppressing_keyboard =
+            self.composing || (self.suppressing_keyboard && any_key_pressed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::ImeActive;
+
+    #[test]
+    fn suppression_outlasts_composition() {
+        let mut ime_active = ImeActive::default();
+        assert!(!ime_active.suppresses_keyboard());
+
+        ime_active.set_composing(true);
+        ime_active.update_suppression(false);
+        assert!(ime_active.composing());
+        assert!(ime_active.suppresses_keyboard());
+
+        // The key confirming the text is still held
+        ime_active.set_composing(false);
+        ime_active.update_suppression(true);
+        assert!(!ime_active.composing());
+        assert!(ime_active.suppresses_keyboard());
+
+        ime_active.update_suppression(false);
+        assert!(!ime_active.suppresses_keyboard());
+
+        // Keys pressed after the composition ended are not suppressed
+        ime_active.update_suppression(true);
+        assert!(!ime_active.suppresses_keyboard());
+    }
+}
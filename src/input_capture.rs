@@ -0,0 +1,281 @@
+//! Optional capture of the raw input events that contributed to each action
+//!
+//! Actions abstract away the inputs that pressed them, but some games still need the original event data:
+//! for example, text navigation that responds to the operating system's key repeat events.
+//!
+//! Enable capturing by adding a [`CapturedInputs`] resource or component alongside an [`ActionState`] and its [`InputMap`].
+//! Each frame, the [`capture_raw_inputs`](crate::systems::capture_raw_inputs) system will then record the keyboard,
+//! mouse button and gamepad events that match the bindings of each pressed (or just released) action.
+//! These are available through [`CapturedInputs::get`] until the system runs again, during the next frame.
+//!
+//! [`bevy::input`] events do not carry the time at which the operating system received them,
+//! so by default every event is timestamped with the start of the frame during which it was processed.
+//! Platform integrations that know the precise timestamp of each event, such as a custom runner,
+//! can supply them through the [`RawEventTimestamps`] resource.
+//! The timestamp of the event that last pressed or released each action is also stored in its [`Timing::event_timestamp`](crate::timing::Timing::event_timestamp),
+//! for [`ActionState`]s with an [`ActionTimings`](crate::timing::ActionTimings).
+//!
+//! When several actions are pressed or released during the same frame, the order of the events that caused them is recorded as well.
+//! Read it with [`CapturedInputs::transition_order`] or [`CapturedInputs::ordered_transitions`],
+//! for example to consistently resolve simultaneous button presses in a fighting game.
+//! Events are ordered by their timestamps, and otherwise by the order in which they were sent.
+//! As [`bevy::input`] sends the events of each kind of device separately, events without precise timestamps are ordered
+//! keyboard first, then mouse buttons, then gamepad buttons, then gamepad axes.
+
+use crate::action_state::ActionState;
+use crate::axislike::AxisType;
+use crate::input_map::InputMap;
+use crate::user_input::RawInputs;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::input::gamepad::{Gamepad, GamepadAxisChangedEvent, GamepadButtonChangedEvent};
+use bevy::input::keyboard::KeyboardInput;
+use bevy::input::mouse::MouseButtonInput;
+use bevy::prelude::ScanCode;
+use bevy::utils::Instant;
+use std::marker::PhantomData;
+
+/// A raw input event, as sent by [`bevy::input`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum CapturedEvent {
+    /// A key was pressed or released
+    Keyboard(KeyboardInput),
+    /// A mouse button was pressed or released
+    MouseButton(MouseButtonInput),
+    /// The value of a gamepad button changed
+    GamepadButton(GamepadButtonChangedEvent),
+    /// The value of a gamepad axis changed
+    GamepadAxis(GamepadAxisChangedEvent),
+}
+
+impl CapturedEvent {
+    /// Does this event affect any of the `raw_inputs`?
+    ///
+    /// If a `gamepad` is provided, events from other gamepads are ignored.
+    #[must_use]
+    pub fn matches(&self, raw_inputs: &RawInputs, gamepad: Option<Gamepad>) -> bool {
+        let from_gamepad = |event_gamepad: Gamepad| gamepad.map_or(true, |g| g == event_gamepad);
+
+        match self {
+            CapturedEvent::Keyboard(event) => {
+                event
+                    .key_code
+                    .is_some_and(|key_code| raw_inputs.keycodes.contains(&key_code))
+                    || raw_inputs.scan_codes.contains(&ScanCode(event.scan_code))
+            }
+            CapturedEvent::MouseButton(event) => raw_inputs.mouse_buttons.contains(&event.button),
+            CapturedEvent::GamepadButton(event) => {
+                from_gamepad(event.gamepad)
+                    && raw_inputs.gamepad_buttons.contains(&event.button_type)
+            }
+            CapturedEvent::GamepadAxis(event) => {
+                from_gamepad(event.gamepad)
+                    && raw_inputs
+                        .axis_data
+                        .iter()
+                        .any(|(axis_type, _)| *axis_type == AxisType::Gamepad(event.axis_type))
+            }
+        }
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Returns the positions of the `events` that affect any of the inputs bound to the `action`
+    ///
+    /// Events from gamepads other than the [associated gamepad](InputMap::gamepad) of this map are ignored.
+    pub(crate) fn matching_events<'a>(
+        &self,
+        action: A,
+        events: impl IntoIterator<Item = &'a CapturedEvent>,
+    ) -> Vec<usize> {
+        let raw_inputs: Vec<RawInputs> = self
+            .get(action)
+            .iter()
+            .map(|input| input.raw_inputs())
+            .collect();
+
+        events
+            .into_iter()
+            .enumerate()
+            .filter(|(_, event)| {
+                raw_inputs
+                    .iter()
+                    .any(|raw_inputs| event.matches(raw_inputs, self.gamepad()))
+            })
+            .map(|(position, _)| position)
+            .collect()
+    }
+}
+
+/// A raw input event that contributed to an action, along with when it was received
+#[derive(Debug, Clone, PartialEq)]
+pub struct CapturedInput {
+    /// The original event
+    pub event: CapturedEvent,
+    /// When the event was received
+    ///
+    /// [`bevy::input`] events do not carry their own timestamps,
+    /// so unless a more precise timestamp was supplied in the [`RawEventTimestamps`],
+    /// this is the start of the frame during which the event was processed.
+    pub timestamp: Instant,
+}
+
+/// The precise timestamps of the raw input events sent during the current frame
+///
+/// This resource is inserted by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), but is only filled in by platform integrations
+/// that know when each event was received, before the events are processed in [`PreUpdate`](bevy::app::PreUpdate).
+/// It is cleared at the end of every frame by the [`clear_raw_event_timestamps`](crate::systems::clear_raw_event_timestamps) system.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct RawEventTimestamps {
+    timestamps: Vec<(CapturedEvent, Instant)>,
+}
+
+impl RawEventTimestamps {
+    /// Records that the `event` was received at the `timestamp`
+    pub fn record(&mut self, event: CapturedEvent, timestamp: Instant) {
+        self.timestamps.push((event, timestamp));
+    }
+
+    /// The timestamp at which the `event` was received, if it was recorded
+    #[must_use]
+    pub fn get(&self, event: &CapturedEvent) -> Option<Instant> {
+        self.timestamps
+            .iter()
+            .find(|(recorded, _)| recorded == event)
+            .map(|(_, timestamp)| *timestamp)
+    }
+
+    /// Removes every recorded timestamp
+    pub fn clear(&mut self) {
+        self.timestamps.clear();
+    }
+}
+
+/// The raw input events that contributed to each action of type `A` during the current frame
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] and [`InputMap`] whose inputs it captures.
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct CapturedInputs<A: Actionlike> {
+    /// The captured events of each action, indexed by [`Actionlike::index`]
+    inputs: Vec<Vec<CapturedInput>>,
+    /// The position of the raw input event that pressed or released each action, indexed by [`Actionlike::index`]
+    transition_orders: Vec<Option<usize>>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for CapturedInputs<A> {
+    fn default() -> Self {
+        CapturedInputs {
+            inputs: vec![Vec::new(); A::n_variants()],
+            transition_orders: vec![None; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> CapturedInputs<A> {
+    /// The raw input events that contributed to the `action` during the current frame
+    #[must_use]
+    pub fn get(&self, action: A) -> &[CapturedInput] {
+        &self.inputs[action.index()]
+    }
+
+    /// Records the raw input events that contributed to the `action` during the current frame
+    ///
+    /// These are cleared the next time the [`capture_raw_inputs`](crate::systems::capture_raw_inputs) system runs.
+    pub fn set(&mut self, action: A, inputs: Vec<CapturedInput>) {
+        self.inputs[action.index()] = inputs;
+    }
+
+    /// The position, among the raw input events of the current frame, of the event that pressed or released the `action`
+    ///
+    /// Returns [`None`] if the `action` did not change during the current frame,
+    /// or if it was changed without a matching event, such as by [`ActionState::press`].
+    #[must_use]
+    pub fn transition_order(&self, action: A) -> Option<usize> {
+        self.transition_orders[action.index()]
+    }
+
+    /// The actions that were pressed or released in the `action_state` during the current frame, in the order in which they changed
+    ///
+    /// Actions without a [`transition_order`](CapturedInputs::transition_order) come last,
+    /// and ties are broken by [`Actionlike::index`], so that the order is always consistent.
+    #[must_use]
+    pub fn ordered_transitions(&self, action_state: &ActionState<A>) -> Vec<A> {
+        let mut transitions: Vec<A> = A::variants()
+            .filter(|action| {
+                action_state.newly_pressed(action.clone())
+                    || action_state.just_released(action.clone())
+            })
+            .collect();
+        transitions.sort_by_key(|action| {
+            (
+                self.transition_order(action.clone()).unwrap_or(usize::MAX),
+                action.index(),
+            )
+        });
+        transitions
+    }
+
+    /// Records the position of the raw input event that pressed or released the `action` during the current frame
+    pub(crate) fn set_transition_order(&mut self, action: A, order: usize) {
+        self.transition_orders[action.index()] = Some(order);
+    }
+
+    /// Clears the captured events and transition orders of every action
+    pub fn clear(&mut self) {
+        self.inputs.iter_mut().for_each(Vec::clear);
+        self.transition_orders.fill(None);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::user_input::UserInput;
+    use bevy::input::ButtonState;
+    use bevy::prelude::{Entity, GamepadAxisType, GamepadButtonType, KeyCode, MouseButton};
+
+    #[test]
+    fn matching_events() {
+        let raw_inputs = UserInput::chord([KeyCode::ControlLeft, KeyCode::S]).raw_inputs();
+
+        let key = |key_code| {
+            CapturedEvent::Keyboard(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            })
+        };
+        assert!(key(KeyCode::S).matches(&raw_inputs, None));
+        assert!(!key(KeyCode::W).matches(&raw_inputs, None));
+
+        let mouse = CapturedEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        });
+        assert!(!mouse.matches(&raw_inputs, None));
+
+        let raw_inputs = UserInput::from(GamepadButtonType::South).raw_inputs();
+        let button = CapturedEvent::GamepadButton(GamepadButtonChangedEvent {
+            gamepad: Gamepad { id: 1 },
+            button_type: GamepadButtonType::South,
+            value: 1.0,
+        });
+        assert!(button.matches(&raw_inputs, None));
+        assert!(button.matches(&raw_inputs, Some(Gamepad { id: 1 })));
+        // Events from other gamepads are ignored
+        assert!(!button.matches(&raw_inputs, Some(Gamepad { id: 2 })));
+
+        let axis = CapturedEvent::GamepadAxis(GamepadAxisChangedEvent {
+            gamepad: Gamepad { id: 1 },
+            axis_type: GamepadAxisType::LeftStickX,
+            value: 1.0,
+        });
+        assert!(!axis.matches(&raw_inputs, None));
+    }
+}
@@ -1,24 +1,267 @@
 //! This module contains [`InputMap`] and its supporting methods and impls.
 
-use crate::action_state::ActionData;
-use crate::buttonlike::ButtonState;
+use crate::action_state::{ActionData, ActionState};
+use crate::axislike::{AxisPairProcessor, AxisType, DualAxisData, SensitivityGroups, SingleAxis};
+use crate::buttonlike::{ButtonState, ButtonThresholds};
 use crate::clashing_inputs::ClashStrategy;
+use crate::errors::InputManagerError;
 use crate::input_streams::InputStreams;
+use crate::stick_gestures::{Flick, StickRotation};
+use crate::timing::ActionTimings;
 use crate::user_input::{InputKind, Modifier, UserInput};
 use crate::Actionlike;
 
 use bevy::ecs::component::Component;
 use bevy::ecs::system::Resource;
-use bevy::input::gamepad::Gamepad;
+use bevy::input::gamepad::{Gamepad, GamepadAxisType, GamepadButtonType};
+use bevy::math::Vec2;
 use bevy::reflect::TypeUuid;
+use bevy::utils::Duration;
 
 use core::fmt::Debug;
 use petitset::PetitSet;
 use serde::{Deserialize, Deserializer, Serialize};
-use std::collections::HashMap;
+use smallvec::SmallVec;
+use std::collections::{BTreeMap, HashMap};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
+/// The maximum number of inputs that can be bound to a single action
+///
+/// The bindings of each action are stored inline in a fixed-capacity [`PetitSet`], so that reading them doesn't chase pointers.
+pub const MAX_BINDINGS_PER_ACTION: usize = 16;
+
+/// The number of elements stored inline in the small per-action and per-input lists, before they spill to the heap
+///
+/// Most actions have three or fewer bindings, and most inputs are bound to one or two actions,
+/// so these lists don't allocate in the common case.
+pub const INLINE_BINDINGS: usize = 3;
+
+/// A list that stores up to [`INLINE_BINDINGS`] elements without allocating
+pub(crate) type BindingVec<T> = SmallVec<[T; INLINE_BINDINGS]>;
+
+/// How the values and axis pairs of multiple bindings are combined when they contribute to the same action
+///
+/// For example, an action bound to both a gamepad stick and a [`VirtualDPad`](crate::axislike::VirtualDPad)
+/// will receive an axis pair from each of them.
+/// Configure this per action using [`InputMap::set_aggregation_policy`].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum AggregationPolicy {
+    /// The values and axis pairs of all bindings are added together, without any bounds
+    #[default]
+    Sum,
+    /// The values and axis pairs of all bindings are added together,
+    /// then the value is clamped to `-1.0..=1.0` and the length of the axis pair is clamped to `1.0`
+    SumClamped,
+    /// The value and the axis pair with the largest magnitude are used
+    MaxMagnitude,
+    /// The value and axis pair of the binding that was pressed most recently are used
+    ///
+    /// This requires knowledge of previous frames, and is resolved by the
+    /// [`update_action_state`](crate::systems::update_action_state) system.
+    /// When calling [`InputMap::which_pressed`] directly, this behaves like [`AggregationPolicy::MaxMagnitude`].
+    LatestWins,
+}
+
+impl AggregationPolicy {
+    /// Combines the `current` value of an action with the `value` of another binding
+    #[must_use]
+    pub fn merge_value(&self, current: f32, value: f32) -> f32 {
+        match self {
+            AggregationPolicy::Sum | AggregationPolicy::SumClamped => current + value,
+            AggregationPolicy::MaxMagnitude | AggregationPolicy::LatestWins => {
+                if value.abs() > current.abs() {
+                    value
+                } else {
+                    current
+                }
+            }
+        }
+    }
+
+    /// Combines the `current` axis pair of an action with the `axis_pair` of another binding
+    #[must_use]
+    pub fn merge_axis_pair(&self, current: DualAxisData, axis_pair: DualAxisData) -> DualAxisData {
+        match self {
+            AggregationPolicy::Sum | AggregationPolicy::SumClamped => {
+                current.merged_with(axis_pair)
+            }
+            AggregationPolicy::MaxMagnitude | AggregationPolicy::LatestWins => {
+                if axis_pair.length() > current.length() {
+                    axis_pair
+                } else {
+                    current
+                }
+            }
+        }
+    }
+
+    /// Applies any bounds required by this policy, once every binding has been merged
+    pub fn finish(&self, action_data: &mut ActionData) {
+        if *self == AggregationPolicy::SumClamped {
+            action_data.value = action_data.value.clamp(-1.0, 1.0);
+            action_data.axis_pair = action_data
+                .axis_pair
+                .map(|axis_pair| DualAxisData::from_xy(axis_pair.xy().clamp_length_max(1.0)));
+        }
+    }
+}
+
+/// The kind of physical device that a binding reads from
+///
+/// Sources are ordered from keyboard to gamepad, matching the precedence used by [`InputSource::of`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum InputSource {
+    /// Keys on a keyboard
+    Keyboard,
+    /// Mouse buttons, the mouse wheel and mouse motion
+    Mouse,
+    /// Gamepad buttons and axes
+    Gamepad,
+}
+
+impl InputSource {
+    /// Returns the source of the `input`
+    ///
+    /// Bindings that combine several sources, such as a chord of a keyboard key and a mouse button,
+    /// are classified as [`InputSource::Gamepad`] if they contain any gamepad input,
+    /// then [`InputSource::Mouse`] if they contain any mouse input.
+    #[must_use]
+    pub fn of(input: &UserInput) -> InputSource {
+        // This is called for every binding of actions with a `SourcePriority`, so it walks the input rather than collecting its raw inputs
+        let of_kinds = |kinds: &mut dyn Iterator<Item = &InputKind>| {
+            kinds
+                .map(InputSource::of_kind)
+                .max()
+                .unwrap_or(InputSource::Keyboard)
+        };
+
+        match input {
+            UserInput::Single(kind) => InputSource::of_kind(kind),
+            UserInput::Chord(kinds) => of_kinds(&mut kinds.iter()),
+            UserInput::VirtualDPad(dpad) => {
+                of_kinds(&mut [&dpad.up, &dpad.down, &dpad.left, &dpad.right].into_iter())
+            }
+            UserInput::VirtualAxis(axis) => {
+                of_kinds(&mut [&axis.negative, &axis.positive].into_iter())
+            }
+            UserInput::WindowEvent(_) | UserInput::Custom(_) => InputSource::Keyboard,
+            UserInput::All(inputs) | UserInput::Any(inputs) => inputs
+                .iter()
+                .map(InputSource::of)
+                .max()
+                .unwrap_or(InputSource::Keyboard),
+            UserInput::Unless { input, .. } => InputSource::of(input),
+        }
+    }
+
+    /// Returns the source of a single `kind` of input
+    fn of_kind(kind: &InputKind) -> InputSource {
+        let of_axis = |axis_type: AxisType| match axis_type {
+            AxisType::Gamepad(_) => InputSource::Gamepad,
+            _ => InputSource::Mouse,
+        };
+
+        match kind {
+            InputKind::GamepadButton(_) => InputSource::Gamepad,
+            InputKind::SingleAxis(axis) => of_axis(axis.axis_type),
+            InputKind::DualAxis(stick)
+            | InputKind::Flick(Flick { stick, .. })
+            | InputKind::StickRotation(StickRotation { stick, .. }) => {
+                of_axis(stick.x.axis_type).max(of_axis(stick.y.axis_type))
+            }
+            InputKind::Mouse(_)
+            | InputKind::MouseWheel(_)
+            | InputKind::MouseMotion(_)
+            | InputKind::MouseDrag(_) => InputSource::Mouse,
+            InputKind::Keyboard(_) | InputKind::KeyLocation(_) | InputKind::Modifier(_) => {
+                InputSource::Keyboard
+            }
+        }
+    }
+}
+
+/// The order in which physical sources are preferred when several of them drive the same action at once
+///
+/// When an action has a [`SourcePriority`], only the bindings of the highest-priority source that is currently active
+/// (pressed, or producing a non-zero axis pair) contribute to its value and axis pair.
+/// Sources that are not listed rank below every listed source.
+/// Bindings from every source can still press the action.
+///
+/// Configure this per action using [`InputMap::set_source_priority`].
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct SourcePriority(Vec<InputSource>);
+
+impl SourcePriority {
+    /// Creates a [`SourcePriority`] from a list of `sources`, from most to least preferred
+    #[must_use]
+    pub fn new(sources: impl IntoIterator<Item = InputSource>) -> Self {
+        SourcePriority(sources.into_iter().collect())
+    }
+
+    /// The sources, from most to least preferred
+    #[must_use]
+    pub fn sources(&self) -> &[InputSource] {
+        &self.0
+    }
+
+    /// The rank of the `source`, where lower ranks are preferred
+    #[must_use]
+    pub fn rank(&self, source: InputSource) -> usize {
+        self.0
+            .iter()
+            .position(|&listed| listed == source)
+            .unwrap_or(self.0.len())
+    }
+}
+
+/// The contribution of a single binding to its action
+#[derive(Debug, Clone)]
+struct BindingContribution {
+    pressed: bool,
+    value: f32,
+    axis_pair: Option<DualAxisData>,
+}
+
+impl BindingContribution {
+    /// Is this binding pressed, or producing a non-zero axis pair?
+    fn is_active(&self) -> bool {
+        self.pressed
+            || self
+                .axis_pair
+                .is_some_and(|axis_pair| axis_pair.xy() != Vec2::ZERO)
+    }
+}
+
+/// Reusable storage for the pressed actions read by [`InputMap::which_pressed_into`]
+///
+/// Keeping one of these around between frames means that reading the pressed actions doesn't allocate.
+#[derive(Debug, Clone, Default)]
+pub struct WhichPressedBuffer {
+    /// The [`ActionData`] of each action, indexed by [`Actionlike::index`]
+    action_data: Vec<ActionData>,
+    /// The contributing bindings of each action, as their position in the [`InputMap`] and what they contribute
+    contributions: Vec<BindingVec<(usize, BindingContribution)>>,
+}
+
+impl WhichPressedBuffer {
+    /// The [`ActionData`] of each action from the last call to [`InputMap::which_pressed_into`]
+    ///
+    /// The position in this slice corresponds to [`Actionlike::index`].
+    #[must_use]
+    pub fn action_data(&self) -> &[ActionData] {
+        &self.action_data
+    }
+
+    /// A mutable reference to the [`ActionData`] of each action from the last call to [`InputMap::which_pressed_into`]
+    ///
+    /// The position in this slice corresponds to [`Actionlike::index`].
+    #[must_use]
+    pub fn action_data_mut(&mut self) -> &mut [ActionData] {
+        &mut self.action_data
+    }
+}
+
 /// Maps from raw inputs to an input-method agnostic representation
 ///
 /// Multiple inputs can be mapped to the same action,
@@ -26,7 +269,7 @@ use std::marker::PhantomData;
 ///
 /// The provided input types must be able to be converted into a [`UserInput`].
 ///
-/// The maximum number of bindings (total) that can be stored for each action is 16.
+/// The maximum number of bindings (total) that can be stored for each action is [`MAX_BINDINGS_PER_ACTION`].
 /// Insertions will silently fail if you have reached this cap.
 ///
 /// By default, if two actions would be triggered by a combination of buttons,
@@ -76,10 +319,26 @@ use std::marker::PhantomData;
 #[derive(Resource, Component, Debug, Clone, PartialEq, Eq, TypeUuid)]
 #[uuid = "D7DECC78-8573-42FF-851A-F0344C7D05C9"]
 pub struct InputMap<A: Actionlike> {
-    /// The raw vector of [PetitSet]s used to store the input mapping,
+    /// The raw vector of [`PetitSet`]s used to store the input mapping,
     /// indexed by the `Actionlike::id` of `A`
-    map: Vec<PetitSet<UserInput, 16>>,
+    map: Vec<PetitSet<UserInput, MAX_BINDINGS_PER_ACTION>>,
+    /// The indexes of the actions that each input is bound to, in ascending order
+    ///
+    /// This is kept in sync with `map`, so that an input bound to several actions is only read once in [`InputMap::which_pressed`].
+    /// Every bound input is still read on each call, whether or not it is pressed.
+    actions_by_input: HashMap<UserInput, BindingVec<usize>>,
     associated_gamepad: Option<Gamepad>,
+    button_thresholds: ButtonThresholds,
+    sensitivity_groups: SensitivityGroups,
+    axis_pair_processors: HashMap<UserInput, BindingVec<AxisPairProcessor>>,
+    /// The [`AggregationPolicy`] of each action, keyed by [`Actionlike::index`]
+    aggregation_policies: HashMap<usize, AggregationPolicy>,
+    /// The [`SourcePriority`] of each action, keyed by [`Actionlike::index`]
+    source_priorities: HashMap<usize, SourcePriority>,
+    /// How long a press is kept while waiting for another source to take over, if at all
+    device_hot_swap: Option<Duration>,
+    /// Do actions pressed by a mouse button record the position of the cursor?
+    record_cursor_position: bool,
     marker: PhantomData<A>,
 }
 
@@ -87,7 +346,15 @@ impl<A: Actionlike> Default for InputMap<A> {
     fn default() -> Self {
         InputMap {
             map: A::variants().map(|_| PetitSet::default()).collect(),
+            actions_by_input: HashMap::default(),
             associated_gamepad: None,
+            button_thresholds: ButtonThresholds::default(),
+            sensitivity_groups: SensitivityGroups::default(),
+            axis_pair_processors: HashMap::default(),
+            aggregation_policies: HashMap::default(),
+            source_priorities: HashMap::default(),
+            device_hot_swap: None,
+            record_cursor_position: false,
             marker: PhantomData,
         }
     }
@@ -140,7 +407,7 @@ impl<A: Actionlike> InputMap<A> {
     /// # Example
     /// ```rust
     /// use leafwing_input_manager::prelude::*;
-
+    ///
     /// use bevy::input::keyboard::KeyCode;
     /// use bevy::prelude::Reflect;
     ///
@@ -168,9 +435,11 @@ impl<A: Actionlike> InputMap<A> {
     ///
     /// Panics if the map is full and `input` is not a duplicate.
     pub fn insert(&mut self, input: impl Into<UserInput>, action: A) -> &mut Self {
-        let input = input.into();
+        let mut input = input.into();
+        input.canonicalize();
 
-        self.map[action.index()].insert(input);
+        self.map[action.index()].insert(input.clone());
+        self.index_binding(input, action.index());
 
         self
     }
@@ -217,9 +486,11 @@ impl<A: Actionlike> InputMap<A> {
     ///
     /// Panics if the map is full and `input` is not a duplicate.
     pub fn insert_at(&mut self, input: impl Into<UserInput>, action: A, index: usize) -> &mut Self {
-        let input = input.into();
+        let mut input = input.into();
+        input.canonicalize();
 
         self.map[action.index()].insert_at(input, index);
+        self.reindex_action(action.index());
 
         self
     }
@@ -276,12 +547,54 @@ impl<A: Actionlike> InputMap<A> {
         self
     }
 
+    /// Splits the provided axis into its positive and negative halves, binding each half to a different action
+    ///
+    /// Both halves use the same `threshold`, and report values in the range `0.0..=1.0`.
+    /// To configure each half independently, use [`SingleAxis::split`] instead.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadAxisType;
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Accelerate,
+    ///     Brake,
+    /// }
+    ///
+    /// let mut input_map = InputMap::default();
+    /// input_map.insert_split_axis(
+    ///     GamepadAxisType::LeftStickY,
+    ///     0.1,
+    ///     Action::Accelerate,
+    ///     Action::Brake,
+    /// );
+    ///
+    /// assert_eq!(input_map.len(), 2);
+    /// ```
+    pub fn insert_split_axis(
+        &mut self,
+        axis_type: impl Into<AxisType>,
+        threshold: f32,
+        positive_action: A,
+        negative_action: A,
+    ) -> &mut Self {
+        let (positive, negative) = SingleAxis::split(axis_type, threshold, threshold);
+        self.insert(positive, positive_action);
+        self.insert(negative, negative_action);
+        self
+    }
+
     /// Merges the provided [`InputMap`] into the [`InputMap`] this method was called on
     ///
     /// This adds both of their bindings to the resulting [`InputMap`].
     /// Like usual, any duplicate bindings are ignored.
     ///
     /// If the associated gamepads do not match, the resulting associated gamepad will be set to `None`.
+    /// The [`ButtonThresholds`], [`SensitivityGroups`], axis pair processors, [`AggregationPolicy`]s, [`SourcePriority`]s
+    /// and device hot-swap window of `other` take priority over those of this map.
     pub fn merge(&mut self, other: &InputMap<A>) -> &mut Self {
         let associated_gamepad = if self.associated_gamepad == other.associated_gamepad {
             self.associated_gamepad
@@ -289,8 +602,40 @@ impl<A: Actionlike> InputMap<A> {
             None
         };
 
+        let mut button_thresholds = self.button_thresholds.clone();
+        button_thresholds.extend(&other.button_thresholds);
+
+        let mut sensitivity_groups = self.sensitivity_groups.clone();
+        sensitivity_groups.extend(&other.sensitivity_groups);
+
+        let mut axis_pair_processors = self.axis_pair_processors.clone();
+        axis_pair_processors.extend(
+            other
+                .axis_pair_processors
+                .iter()
+                .map(|(input, processors)| (input.clone(), processors.clone())),
+        );
+
+        let mut aggregation_policies = self.aggregation_policies.clone();
+        aggregation_policies.extend(other.aggregation_policies.iter());
+
+        let mut source_priorities = self.source_priorities.clone();
+        source_priorities.extend(
+            other
+                .source_priorities
+                .iter()
+                .map(|(index, priority)| (*index, priority.clone())),
+        );
+
         let mut new_map = InputMap {
             associated_gamepad,
+            button_thresholds,
+            sensitivity_groups,
+            axis_pair_processors,
+            aggregation_policies,
+            source_priorities,
+            device_hot_swap: other.device_hot_swap.or(self.device_hot_swap),
+            record_cursor_position: self.record_cursor_position || other.record_cursor_position,
             ..Default::default()
         };
 
@@ -319,6 +664,16 @@ impl<A: Actionlike> InputMap<A> {
         self.associated_gamepad
     }
 
+    /// Fetches the [Gamepad] associated with the entity controlled by this entity map, for code that requires one
+    ///
+    /// # Errors
+    ///
+    /// Returns [`InputManagerError::GamepadNotAssigned`] if no gamepad was assigned using [`InputMap::set_gamepad`].
+    pub fn try_gamepad(&self) -> Result<Gamepad, InputManagerError> {
+        self.associated_gamepad
+            .ok_or(InputManagerError::GamepadNotAssigned)
+    }
+
     /// Assigns a particular [`Gamepad`] to the entity controlled by this input map
     ///
     /// If this is not called, input from any connected gamepad will be used.
@@ -337,177 +692,923 @@ impl<A: Actionlike> InputMap<A> {
         self.associated_gamepad = None;
         self
     }
-}
 
-// Check whether buttons are pressed
-impl<A: Actionlike> InputMap<A> {
-    /// Is at least one of the corresponding inputs for `action` found in the provided `input` streams?
-    ///
-    /// Accounts for clashing inputs according to the [`ClashStrategy`].
-    /// If you need to inspect many inputs at once, prefer [`InputMap::which_pressed`] instead.
+    /// Returns the per-input [`ButtonThresholds`] of this input map
     #[must_use]
-    pub fn pressed(
-        &self,
-        action: A,
-        input_streams: &InputStreams,
-        clash_strategy: ClashStrategy,
-    ) -> bool {
-        let action_data = self.which_pressed(input_streams, clash_strategy);
-        action_data[action.index()].state.pressed()
+    pub fn button_thresholds(&self) -> &ButtonThresholds {
+        &self.button_thresholds
     }
 
-    /// Returns the actions that are currently pressed, and the responsible [`UserInput`] for each action
-    ///
-    /// Accounts for clashing inputs according to the [`ClashStrategy`].
-    /// The position in each vector corresponds to `Actionlike::index()`.
+    /// Returns a mutable reference to the per-input [`ButtonThresholds`] of this input map
     #[must_use]
-    pub fn which_pressed(
-        &self,
-        input_streams: &InputStreams,
-        clash_strategy: ClashStrategy,
-    ) -> Vec<ActionData> {
-        let mut action_data = vec![ActionData::default(); A::n_variants()];
-
-        // Generate the raw action presses
-        for action in A::variants() {
-            let mut inputs = Vec::new();
-
-            for input in self.get(action.clone()).iter() {
-                let action = &mut action_data[action.index()];
-
-                // Merge axis pair into action data
-                let axis_pair = input_streams.input_axis_pair(input);
-                if let Some(axis_pair) = axis_pair {
-                    if let Some(current_axis_pair) = &mut action.axis_pair {
-                        *current_axis_pair = current_axis_pair.merged_with(axis_pair);
-                    } else {
-                        action.axis_pair = Some(axis_pair);
-                    }
-                }
-
-                if input_streams.input_pressed(input) {
-                    inputs.push(input.clone());
-
-                    action.value += input_streams.input_value(input);
-                }
-            }
-
-            if !inputs.is_empty() {
-                action_data[action.index()].state = ButtonState::JustPressed;
-            }
-        }
-
-        // Handle clashing inputs, possibly removing some pressed actions from the list
-        self.handle_clashes(&mut action_data, input_streams, clash_strategy);
-
-        action_data
-    }
-}
-
-// Utilities
-impl<A: Actionlike> InputMap<A> {
-    /// Returns an iterator over actions with their inputs
-    pub fn iter(&self) -> impl Iterator<Item = (&PetitSet<UserInput, 16>, A)> {
-        self.map
-            .iter()
-            .enumerate()
-            .map(|(action_index, inputs)| (inputs, A::get_at(action_index).unwrap()))
-    }
-
-    /// Returns an iterator over all mapped inputs
-    pub fn iter_inputs(&self) -> impl Iterator<Item = &PetitSet<UserInput, 16>> {
-        self.map.iter()
+    pub fn button_thresholds_mut(&mut self) -> &mut ButtonThresholds {
+        &mut self.button_thresholds
     }
 
-    /// Returns the `action` mappings
-    #[must_use]
-    pub fn get(&self, action: A) -> &PetitSet<UserInput, 16> {
-        &self.map[action.index()]
+    /// Sets the analog value at or above which the gamepad `button` is considered pressed by this input map
+    ///
+    /// This overrides the global [`GamepadSettings`](bevy::input::gamepad::GamepadSettings) for this button,
+    /// which is useful as triggers and face buttons often need different press points.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::gamepad::GamepadButtonType;
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Accelerate,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([(GamepadButtonType::RightTrigger2, Action::Accelerate)]);
+    /// input_map.set_button_threshold(GamepadButtonType::RightTrigger2, 0.1);
+    ///
+    /// assert_eq!(input_map.button_thresholds().button(GamepadButtonType::RightTrigger2), Some(0.1));
+    /// ```
+    pub fn set_button_threshold(&mut self, button: GamepadButtonType, threshold: f32) -> &mut Self {
+        self.button_thresholds.set_button(button, threshold);
+        self
     }
 
-    /// How many input bindings are registered total?
+    /// Returns the [`SensitivityGroups`] of this input map
     #[must_use]
-    pub fn len(&self) -> usize {
-        let mut i = 0;
-        for action in A::variants() {
-            i += self.get(action).len();
-        }
-        i
+    pub fn sensitivity_groups(&self) -> &SensitivityGroups {
+        &self.sensitivity_groups
     }
 
-    /// Are any input bindings registered at all?
-    #[inline]
+    /// Returns a mutable reference to the [`SensitivityGroups`] of this input map
     #[must_use]
-    pub fn is_empty(&self) -> bool {
-        self.len() == 0
-    }
-}
-
-// Removing
-impl<A: Actionlike> InputMap<A> {
-    /// Clears all inputs registered for the `action`
-    pub fn clear_action(&mut self, action: A) {
-        self.map[action.index()].clear();
+    pub fn sensitivity_groups_mut(&mut self) -> &mut SensitivityGroups {
+        &mut self.sensitivity_groups
     }
 
-    /// Removes the input for the `action` at the provided index
+    /// Assigns the `input` binding to the sensitivity group called `group`
     ///
-    /// Returns `true` if an element was found.
-    pub fn remove_at(&mut self, action: A, index: usize) -> bool {
-        self.map[action.index()].remove_at(index)
+    /// The values and axis pairs produced by this binding will be scaled by the sensitivity of the group,
+    /// as set by [`InputMap::set_sensitivity`].
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Look,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([
+    ///     (DualAxis::right_stick(), Action::Look),
+    ///     (DualAxis::mouse_motion(), Action::Look),
+    /// ]);
+    /// input_map
+    ///     .assign_sensitivity_group(DualAxis::right_stick(), "look")
+    ///     .assign_sensitivity_group(DualAxis::mouse_motion(), "look");
+    ///
+    /// // Later, from an options menu
+    /// input_map.set_sensitivity("look", 2.5);
+    ///
+    /// let right_stick = UserInput::from(DualAxis::right_stick());
+    /// assert_eq!(input_map.sensitivity_groups().sensitivity_of(&right_stick), 2.5);
+    /// ```
+    pub fn assign_sensitivity_group(
+        &mut self,
+        input: impl Into<UserInput>,
+        group: impl Into<String>,
+    ) -> &mut Self {
+        self.sensitivity_groups.assign(input, group);
+        self
     }
 
-    /// Removes the input for the `action`, if it exists
-    ///
-    /// Returns [`Some`] with index if the input was found, or [`None`] if no matching input was found.
-    pub fn remove(&mut self, action: A, input: impl Into<UserInput>) -> Option<usize> {
-        self.map[action.index()].remove(&input.into())
+    /// Sets the sensitivity multiplier of every binding in the `group`
+    pub fn set_sensitivity(&mut self, group: impl Into<String>, sensitivity: f32) -> &mut Self {
+        self.sensitivity_groups.set_sensitivity(group, sensitivity);
+        self
     }
-}
 
-impl<A: Actionlike> From<HashMap<A, Vec<UserInput>>> for InputMap<A> {
-    /// Create `InputMap<A>` from `HashMap<A, Vec<UserInput>>`
+    /// Adds a `processor` to the end of the chain applied to the axis pair produced by the `input` binding
     ///
-    /// # Panics
+    /// Processors are applied in the order that they were added, after sensitivity scaling.
     ///
-    /// Panics if the any value in map contains more than 16 distinct inputs.
     /// # Example
     /// ```rust
-    /// use leafwing_input_manager::input_map::InputMap;
-    /// use leafwing_input_manager::user_input::UserInput;
-    /// use leafwing_input_manager::Actionlike;
-    /// use bevy::input::keyboard::KeyCode;
-    ///
-    /// use std::collections::HashMap;
     /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::axislike::AxisPairProcessor;
+    /// use leafwing_input_manager::orientation::Rotation;
+    /// use leafwing_input_manager::prelude::*;
     ///
     /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
     /// enum Action {
-    ///     Run,
-    ///     Jump,
+    ///     Move,
     /// }
-    /// let mut map: HashMap<Action, Vec<UserInput>> = HashMap::default();
-    /// map.insert(
-    ///     Action::Run,
-    ///     vec![KeyCode::ShiftLeft.into(), KeyCode::ShiftRight.into()],
+    ///
+    /// let mut input_map = InputMap::new([(DualAxis::left_stick(), Action::Move)]);
+    /// input_map.add_axis_pair_processor(
+    ///     DualAxis::left_stick(),
+    ///     AxisPairProcessor::Rotate(Rotation::from_degrees(-45.0)),
     /// );
-    /// let input_map = InputMap::from(map);
     /// ```
-    fn from(map: HashMap<A, Vec<UserInput>>) -> Self {
-        map.iter()
-            .flat_map(|(action, inputs)| inputs.iter().map(|input| (action.clone(), input.clone())))
-            .collect()
-    }
-}
+    pub fn add_axis_pair_processor(
+        &mut self,
+        input: impl Into<UserInput>,
+        processor: AxisPairProcessor,
+    ) -> &mut Self {
+        self.axis_pair_processors
+            .entry(input.into())
+            .or_default()
+            .push(processor);
+        self
+    }
+
+    /// Replaces the chain of processors applied to the axis pair produced by the `input` binding
+    ///
+    /// This is useful for processors that change every frame, such as [`AxisPairProcessor::camera_relative`].
+    pub fn set_axis_pair_processors(
+        &mut self,
+        input: impl Into<UserInput>,
+        processors: impl IntoIterator<Item = AxisPairProcessor>,
+    ) -> &mut Self {
+        self.axis_pair_processors
+            .insert(input.into(), processors.into_iter().collect());
+        self
+    }
+
+    /// Removes every processor applied to the axis pair produced by the `input` binding
+    pub fn clear_axis_pair_processors(&mut self, input: &UserInput) -> &mut Self {
+        self.axis_pair_processors.remove(input);
+        self
+    }
+
+    /// Returns the processors applied to the axis pair produced by the `input` binding, in order
+    #[must_use]
+    pub fn axis_pair_processors(&self, input: &UserInput) -> &[AxisPairProcessor] {
+        self.axis_pair_processors
+            .get(input)
+            .map(|processors| processors.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Sets how the values and axis pairs of the bindings of the `action` are combined
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::input_map::AggregationPolicy;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Move,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([
+    ///     (UserInput::from(DualAxis::left_stick()), Action::Move),
+    ///     (VirtualDPad::wasd().into(), Action::Move),
+    /// ]);
+    /// // Holding W while pushing the stick up doesn't move faster
+    /// input_map.set_aggregation_policy(Action::Move, AggregationPolicy::SumClamped);
+    /// ```
+    pub fn set_aggregation_policy(&mut self, action: A, policy: AggregationPolicy) -> &mut Self {
+        self.aggregation_policies.insert(action.index(), policy);
+        self
+    }
+
+    /// Returns how the values and axis pairs of the bindings of the `action` are combined
+    ///
+    /// Defaults to [`AggregationPolicy::Sum`].
+    #[must_use]
+    pub fn aggregation_policy(&self, action: A) -> AggregationPolicy {
+        self.aggregation_policies
+            .get(&action.index())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Sets which physical sources are preferred when several of them drive the `action` at once
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use leafwing_input_manager::input_map::{InputSource, SourcePriority};
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Look,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([
+    ///     (DualAxis::right_stick(), Action::Look),
+    ///     (DualAxis::mouse_motion(), Action::Look),
+    /// ]);
+    /// // Ignore mouse jitter while the right stick is being used
+    /// input_map.set_source_priority(
+    ///     Action::Look,
+    ///     SourcePriority::new([InputSource::Gamepad, InputSource::Mouse]),
+    /// );
+    /// ```
+    pub fn set_source_priority(&mut self, action: A, priority: SourcePriority) -> &mut Self {
+        self.source_priorities.insert(action.index(), priority);
+        self
+    }
+
+    /// Removes the [`SourcePriority`] of the `action`, so that every source contributes to it
+    pub fn clear_source_priority(&mut self, action: A) -> &mut Self {
+        self.source_priorities.remove(&action.index());
+        self
+    }
+
+    /// Returns the [`SourcePriority`] of the `action`, if any
+    #[must_use]
+    pub fn source_priority(&self, action: A) -> Option<&SourcePriority> {
+        self.source_priorities.get(&action.index())
+    }
+
+    /// Keeps actions pressed for up to the `window` after their bindings are released,
+    /// so that players can switch to an equivalent binding on another [`InputSource`] without interrupting the press
+    ///
+    /// If a binding from another source is pressed within the `window`, the press continues with its timing intact,
+    /// rather than being released and pressed again.
+    /// Otherwise, the action is released once the `window` has passed, or as soon as a binding from the same source is pressed again.
+    /// As the `window` is measured with the [`ActionTimings`] of the action, presses are never handed over without one.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use bevy::utils::Duration;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Charge,
+    /// }
+    ///
+    /// let mut input_map = InputMap::new([
+    ///     (UserInput::from(KeyCode::Space), Action::Charge),
+    ///     (GamepadButtonType::South.into(), Action::Charge),
+    /// ]);
+    /// // Picking up the controller mid-charge doesn't restart the charge
+    /// input_map.set_device_hot_swap(Duration::from_millis(500));
+    /// ```
+    pub fn set_device_hot_swap(&mut self, window: Duration) -> &mut Self {
+        self.device_hot_swap = Some(window);
+        self
+    }
+
+    /// Releases actions as soon as their bindings are released, even when switching devices
+    ///
+    /// This is the default behavior.
+    pub fn clear_device_hot_swap(&mut self) -> &mut Self {
+        self.device_hot_swap = None;
+        self
+    }
+
+    /// Returns the window set by [`InputMap::set_device_hot_swap`], if any
+    #[must_use]
+    pub fn device_hot_swap(&self) -> Option<Duration> {
+        self.device_hot_swap
+    }
+
+    /// Enables or disables recording the position of the cursor when an action is pressed by a mouse button
+    ///
+    /// Recording is disabled by default. The recorded position is read using [`ActionState::cursor_position`].
+    pub fn set_record_cursor_position(&mut self, enabled: bool) -> &mut Self {
+        self.record_cursor_position = enabled;
+        self
+    }
+
+    /// Do actions pressed by a mouse button record the position of the cursor?
+    #[must_use]
+    pub fn records_cursor_position(&self) -> bool {
+        self.record_cursor_position
+    }
+
+    /// Sets the absolute value beyond which the gamepad `axis` is considered triggered by this input map
+    ///
+    /// This replaces the deadzone of every [`SingleAxis`] bound to this axis.
+    /// See [`ButtonThresholds::set_axis`] for more details.
+    pub fn set_axis_threshold(&mut self, axis: GamepadAxisType, threshold: f32) -> &mut Self {
+        self.button_thresholds.set_axis(axis, threshold);
+        self
+    }
+}
+
+// Check whether buttons are pressed
+impl<A: Actionlike> InputMap<A> {
+    /// Is at least one of the corresponding inputs for `action` found in the provided `input` streams?
+    ///
+    /// Accounts for clashing inputs according to the [`ClashStrategy`].
+    /// If you need to inspect many inputs at once, prefer [`InputMap::which_pressed`] instead.
+    #[must_use]
+    pub fn pressed(
+        &self,
+        action: A,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+    ) -> bool {
+        let action_data = self.which_pressed(input_streams, clash_strategy);
+        action_data[action.index()].state.pressed()
+    }
+
+    /// Returns the actions that are currently pressed, and the responsible [`UserInput`] for each action
+    ///
+    /// Accounts for clashing inputs according to the [`ClashStrategy`].
+    /// The position in each vector corresponds to `Actionlike::index()`.
+    #[must_use]
+    pub fn which_pressed(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+    ) -> Vec<ActionData> {
+        let mut buffer = WhichPressedBuffer::default();
+        self.which_pressed_into(input_streams, clash_strategy, &mut buffer);
+        buffer.action_data
+    }
+
+    /// Writes the actions that are currently pressed into the reusable `buffer`,
+    /// overwriting the results of any previous call
+    ///
+    /// This behaves like [`InputMap::which_pressed`], but doesn't allocate once the `buffer` has been used,
+    /// which matters when reading the pressed actions of many entities every frame.
+    /// The results can be read with [`WhichPressedBuffer::action_data`].
+    pub fn which_pressed_into(
+        &self,
+        input_streams: &InputStreams,
+        clash_strategy: ClashStrategy,
+        buffer: &mut WhichPressedBuffer,
+    ) {
+        let WhichPressedBuffer {
+            action_data,
+            contributions,
+        } = buffer;
+        action_data.clear();
+        action_data.resize(A::n_variants(), ActionData::default());
+        contributions.resize(A::n_variants(), BindingVec::new());
+        contributions
+            .iter_mut()
+            .for_each(|contributions| contributions.clear());
+
+        // Apply the thresholds configured for this map
+        let input_streams = &InputStreams {
+            button_thresholds: Some(&self.button_thresholds),
+            ..input_streams.clone()
+        };
+
+        // Read each bound input once, keeping those that can affect their actions
+        for (input, action_indexes) in self.actions_by_input.iter() {
+            if let UserInput::Chord(chord) = input {
+                let held = chord
+                    .iter()
+                    .filter(|&&button| input_streams.button_pressed(button))
+                    .count();
+                let progress = (held, chord.len());
+
+                for &index in action_indexes {
+                    let (best_held, best_total) = action_data[index].chord_progress;
+                    if best_total == 0
+                        || held > best_held
+                        || (held == best_held && progress.1 < best_total)
+                    {
+                        action_data[index].chord_progress = progress;
+                    }
+                }
+            }
+
+            let contribution = self.binding_contribution(input, input_streams);
+            if !contribution.pressed && contribution.axis_pair.is_none() {
+                continue;
+            }
+
+            for &index in action_indexes {
+                if let Some(position) = self.map[index].find(input) {
+                    contributions[index].push((position, contribution.clone()));
+                }
+            }
+        }
+
+        // Generate the raw action presses
+        for (index, contributions) in contributions.iter_mut().enumerate() {
+            if contributions.is_empty() {
+                continue;
+            }
+            let action = A::get_at(index).unwrap();
+            let policy = self.aggregation_policy(action.clone());
+            let mut pressed = false;
+
+            // Merge the bindings in the order that they were inserted, regardless of the order of the lookup
+            let bindings = &self.map[index];
+            contributions.sort_by_key(|(position, _)| *position);
+            let binding_at = |position: usize| bindings.get_at(position).unwrap();
+
+            // Only the most preferred active source contributes values, if a priority is set
+            let preferred_source = self.source_priority(action.clone()).and_then(|priority| {
+                contributions
+                    .iter()
+                    .filter(|(_, contribution)| contribution.is_active())
+                    .map(|(position, _)| InputSource::of(binding_at(*position)))
+                    .min_by_key(|&source| priority.rank(source))
+            });
+
+            for (position, contribution) in contributions.iter() {
+                let input = binding_at(*position);
+                let action = &mut action_data[index];
+
+                pressed |= contribution.pressed;
+
+                if preferred_source.is_some_and(|source| InputSource::of(input) != source) {
+                    continue;
+                }
+
+                // Merge axis pair into action data
+                if let Some(axis_pair) = contribution.axis_pair {
+                    action.axis_pair = Some(match action.axis_pair {
+                        Some(current_axis_pair) => {
+                            policy.merge_axis_pair(current_axis_pair, axis_pair)
+                        }
+                        None => axis_pair,
+                    });
+                }
+
+                if contribution.pressed {
+                    action.value = policy.merge_value(action.value, contribution.value);
+
+                    if self.record_cursor_position
+                        && action.cursor_position.is_none()
+                        && !input.raw_inputs().mouse_buttons.is_empty()
+                    {
+                        action.cursor_position = input_streams.cursor_position;
+                    }
+                }
+            }
+
+            policy.finish(&mut action_data[index]);
+
+            if pressed {
+                action_data[index].state = ButtonState::JustPressed;
+            }
+        }
+
+        // Handle clashing inputs, possibly removing some pressed actions from the list
+        self.handle_clashes(action_data, input_streams, clash_strategy);
+    }
+
+    /// Reads the pressed state, value and axis pair of a single `input` binding,
+    /// after applying its sensitivity and axis pair processors
+    fn binding_contribution(
+        &self,
+        input: &UserInput,
+        input_streams: &InputStreams,
+    ) -> BindingContribution {
+        let sensitivity = self.sensitivity_groups.sensitivity_of(input);
+        let pressed = input_streams.input_pressed(input);
+
+        BindingContribution {
+            pressed,
+            value: if pressed {
+                input_streams.input_value(input) * sensitivity
+            } else {
+                0.0
+            },
+            axis_pair: input_streams.input_axis_pair(input).map(|axis_pair| {
+                self.axis_pair_processors(input).iter().fold(
+                    DualAxisData::from_xy(axis_pair.xy() * sensitivity),
+                    |axis_pair, processor| processor.process(axis_pair),
+                )
+            }),
+        }
+    }
+
+    /// Replaces the value and axis pair of each pressed [`AggregationPolicy::LatestWins`] action
+    /// with those of its most recently pressed binding
+    ///
+    /// The `buffer` must hold the results of [`InputMap::which_pressed_into`] for this map,
+    /// whose binding contributions are reused rather than read again.
+    /// The `binding_recency` stores the pressed bindings of each action from the previous frame, ordered from least to most recently pressed,
+    /// and is updated to reflect the current frame.
+    pub(crate) fn apply_latest_wins(
+        &self,
+        buffer: &mut WhichPressedBuffer,
+        binding_recency: &mut Vec<BindingVec<UserInput>>,
+    ) {
+        if binding_recency.len() < A::n_variants() {
+            binding_recency.resize(A::n_variants(), BindingVec::new());
+        }
+
+        let WhichPressedBuffer {
+            action_data,
+            contributions,
+        } = buffer;
+
+        for action in A::variants() {
+            let index = action.index();
+            let recency = &mut binding_recency[index];
+            if self.aggregation_policy(action) != AggregationPolicy::LatestWins {
+                recency.clear();
+                continue;
+            }
+
+            let bindings = &self.map[index];
+            let pressed = || {
+                contributions[index]
+                    .iter()
+                    .filter(|(_, contribution)| contribution.pressed)
+                    .map(|(position, contribution)| {
+                        (bindings.get_at(*position).unwrap(), contribution)
+                    })
+            };
+
+            recency.retain(|input| pressed().any(|(pressed, _)| pressed == input));
+            for (input, _) in pressed() {
+                if !recency.contains(input) {
+                    recency.push(input.clone());
+                }
+            }
+
+            let data = &mut action_data[index];
+            if !data.state.pressed() {
+                continue;
+            }
+
+            let latest = recency.last().and_then(|latest| {
+                pressed()
+                    .find(|(input, _)| *input == latest)
+                    .map(|(_, contribution)| contribution)
+            });
+            if let Some(latest) = latest {
+                data.value = latest.value;
+                if latest.axis_pair.is_some() {
+                    data.axis_pair = latest.axis_pair;
+                }
+            }
+        }
+    }
+
+    /// Keeps each action in the `action_data` pressed while its press is being handed over to another [`InputSource`],
+    /// as configured by [`InputMap::set_device_hot_swap`]
+    ///
+    /// The `hot_swaps` store the progress of each hand-over for the `action_state` that is being updated,
+    /// and are updated to reflect the current frame.
+    pub(crate) fn apply_device_hot_swap(
+        &self,
+        action_data: &mut [ActionData],
+        input_streams: &InputStreams,
+        action_state: &ActionState<A>,
+        action_timings: Option<&ActionTimings<A>>,
+        hot_swaps: &mut Vec<HotSwap>,
+    ) {
+        let (Some(window), Some(action_timings)) = (self.device_hot_swap, action_timings) else {
+            hot_swaps.clear();
+            return;
+        };
+        if hot_swaps.len() < A::n_variants() {
+            hot_swaps.resize(A::n_variants(), HotSwap::default());
+        }
+
+        // Apply the thresholds configured for this map
+        let input_streams = &InputStreams {
+            button_thresholds: Some(&self.button_thresholds),
+            ..input_streams.clone()
+        };
+
+        for action in A::variants() {
+            let hot_swap = &mut hot_swaps[action.index()];
+            let previous = action_state.action_data(action.clone());
+            let data = &mut action_data[action.index()];
+
+            // Presses that were released or consumed in the meantime have nothing to hand over
+            if !previous.state.pressed() {
+                *hot_swap = HotSwap::default();
+            }
+
+            if data.state.pressed() {
+                let mut sources: Vec<InputSource> = Vec::new();
+                for input in self.get(action.clone()).iter() {
+                    let source = InputSource::of(input);
+                    if !sources.contains(&source)
+                        && self.binding_contribution(input, input_streams).pressed
+                    {
+                        sources.push(source);
+                    }
+                }
+
+                if hot_swap.released_at.is_some()
+                    && sources
+                        .iter()
+                        .any(|source| hot_swap.sources.contains(source))
+                {
+                    // The same device was pressed again, so this is a new press: the pending release goes through first
+                    *hot_swap = HotSwap::default();
+                    data.state = ButtonState::Released;
+                    data.value = 0.0;
+                } else {
+                    hot_swap.sources = sources;
+                    hot_swap.released_at = None;
+                }
+            } else if !hot_swap.sources.is_empty() {
+                let held = action_timings.current_duration(action_state, action.clone());
+                let released_at = *hot_swap.released_at.get_or_insert(held);
+
+                if held.saturating_sub(released_at) < window {
+                    data.state = ButtonState::Pressed;
+                    data.value = previous.value;
+                } else {
+                    *hot_swap = HotSwap::default();
+                }
+            }
+        }
+    }
+}
+
+/// The progress of handing the press of a single action over to another [`InputSource`]
+///
+/// See [`InputMap::set_device_hot_swap`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub(crate) struct HotSwap {
+    /// The sources whose bindings were pressing the action when it was last updated
+    sources: Vec<InputSource>,
+    /// How long the action had been held when its bindings were released, if it is waiting for another source
+    released_at: Option<Duration>,
+}
+
+// Utilities
+impl<A: Actionlike> InputMap<A> {
+    /// Returns an iterator over every binding, as the action, the slot of the binding and its input
+    ///
+    /// Bindings are ordered by action, then by slot.
+    /// Slots are the indices used by [`InputMap::insert_at`] and [`InputMap::remove_at`],
+    /// and may have gaps if bindings were removed.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    ///     Run,
+    /// }
+    ///
+    /// let input_map = InputMap::new([
+    ///     (KeyCode::Space, Action::Jump),
+    ///     (KeyCode::W, Action::Jump),
+    ///     (KeyCode::ShiftLeft, Action::Run),
+    /// ]);
+    ///
+    /// let bindings: Vec<_> = input_map.iter().collect();
+    /// assert_eq!(bindings[1], (Action::Jump, 1, &UserInput::from(KeyCode::W)));
+    /// assert_eq!(bindings[2], (Action::Run, 0, &UserInput::from(KeyCode::ShiftLeft)));
+    /// ```
+    pub fn iter(&self) -> impl Iterator<Item = (A, usize, &UserInput)> {
+        A::variants().flat_map(move |action| {
+            self.bindings(action.clone())
+                .map(move |(slot, input)| (action.clone(), slot, input))
+        })
+    }
+
+    /// Returns an iterator over the bindings of the `action`, as the slot of each binding and its input
+    ///
+    /// Bindings are ordered by slot, as described in [`InputMap::iter`].
+    pub fn bindings(&self, action: A) -> impl Iterator<Item = (usize, &UserInput)> {
+        let inputs = &self.map[action.index()];
+        (0..MAX_BINDINGS_PER_ACTION)
+            .filter_map(move |slot| inputs.get_at(slot).map(|input| (slot, input)))
+    }
+
+    /// Returns an iterator over all mapped inputs
+    pub fn iter_inputs(
+        &self,
+    ) -> impl Iterator<Item = &PetitSet<UserInput, MAX_BINDINGS_PER_ACTION>> {
+        self.map.iter()
+    }
+
+    /// Returns the `action` mappings
+    #[must_use]
+    pub fn get(&self, action: A) -> &PetitSet<UserInput, MAX_BINDINGS_PER_ACTION> {
+        &self.map[action.index()]
+    }
+
+    /// Returns the actions that the `input` is bound to
+    ///
+    /// This is a cached lookup, and does not iterate over the bindings of every action.
+    pub fn actions_for(&self, input: &UserInput) -> impl Iterator<Item = A> + '_ {
+        self.actions_by_input
+            .get(input)
+            .into_iter()
+            .flatten()
+            .map(|&index| A::get_at(index).unwrap())
+    }
+
+    /// Records that the `input` is bound to the action at `action_index` in the reverse lookup
+    fn index_binding(&mut self, input: UserInput, action_index: usize) {
+        let actions = self.actions_by_input.entry(input).or_default();
+        if let Err(position) = actions.binary_search(&action_index) {
+            actions.insert(position, action_index);
+        }
+    }
+
+    /// Rebuilds the reverse lookup entries of the action at `action_index` from its current bindings
+    fn reindex_action(&mut self, action_index: usize) {
+        self.actions_by_input.retain(|_, actions| {
+            actions.retain(|index| *index != action_index);
+            !actions.is_empty()
+        });
+
+        let inputs: Vec<UserInput> = self.map[action_index].iter().cloned().collect();
+        for input in inputs {
+            self.index_binding(input, action_index);
+        }
+    }
+
+    /// How many input bindings are registered total?
+    #[must_use]
+    pub fn len(&self) -> usize {
+        let mut i = 0;
+        for action in A::variants() {
+            i += self.get(action).len();
+        }
+        i
+    }
+
+    /// Are any input bindings registered at all?
+    #[inline]
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+// Removing
+impl<A: Actionlike> InputMap<A> {
+    /// Clears all inputs registered for the `action`
+    pub fn clear_action(&mut self, action: A) {
+        self.map[action.index()].clear();
+        self.reindex_action(action.index());
+    }
+
+    /// Removes the input for the `action` at the provided index
+    ///
+    /// Returns `true` if an element was found.
+    pub fn remove_at(&mut self, action: A, index: usize) -> bool {
+        let removed = self.map[action.index()].remove_at(index);
+        self.reindex_action(action.index());
+        removed
+    }
+
+    /// Removes the input for the `action`, if it exists
+    ///
+    /// Returns [`Some`] with index if the input was found, or [`None`] if no matching input was found.
+    pub fn remove(&mut self, action: A, input: impl Into<UserInput>) -> Option<usize> {
+        let removed = self.map[action.index()].remove(&input.into());
+        self.reindex_action(action.index());
+        removed
+    }
+}
+
+impl<A: Actionlike> From<HashMap<A, Vec<UserInput>>> for InputMap<A> {
+    /// Create `InputMap<A>` from `HashMap<A, Vec<UserInput>>`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the any value in map contains more than 16 distinct inputs.
+    /// # Example
+    /// ```rust
+    /// use leafwing_input_manager::input_map::InputMap;
+    /// use leafwing_input_manager::user_input::UserInput;
+    /// use leafwing_input_manager::Actionlike;
+    /// use bevy::input::keyboard::KeyCode;
+    ///
+    /// use std::collections::HashMap;
+    /// use bevy::prelude::Reflect;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Reflect)]
+    /// enum Action {
+    ///     Run,
+    ///     Jump,
+    /// }
+    /// let mut map: HashMap<Action, Vec<UserInput>> = HashMap::default();
+    /// map.insert(
+    ///     Action::Run,
+    ///     vec![KeyCode::ShiftLeft.into(), KeyCode::ShiftRight.into()],
+    /// );
+    /// let input_map = InputMap::from(map);
+    /// ```
+    fn from(map: HashMap<A, Vec<UserInput>>) -> Self {
+        map.iter()
+            .flat_map(|(action, inputs)| inputs.iter().map(|input| (action.clone(), input.clone())))
+            .collect()
+    }
+}
+
+impl<A: Actionlike> FromIterator<(A, UserInput)> for InputMap<A> {
+    /// Create `InputMap<A>` from iterator with item type `(A, UserInput)`
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are more than 16 distinct inputs for the same action.
+    fn from_iter<T: IntoIterator<Item = (A, UserInput)>>(iter: T) -> Self {
+        InputMap::new(iter.into_iter().map(|(action, input)| (input, action)))
+    }
+}
+
+/// The serialized form of an [`InputMap`]
+///
+/// The inputs of each action are stored by action rather than by input, so that saved bindings are easy to read and edit.
+/// This is also used to serialize [`VersionedInputMapRef`](crate::migration::VersionedInputMapRef)s.
+#[derive(Serialize)]
+#[serde(rename = "InputMap")]
+pub(crate) struct InputMapRef<'a, A: Ord> {
+    pub(crate) map: BTreeMap<A, Vec<&'a UserInput>>,
+    pub(crate) associated_gamepad: Option<Gamepad>,
+    pub(crate) button_thresholds: &'a ButtonThresholds,
+    pub(crate) sensitivity_groups: &'a SensitivityGroups,
+    pub(crate) axis_pair_processors: Vec<(&'a UserInput, &'a [AxisPairProcessor])>,
+    pub(crate) aggregation_policies: BTreeMap<A, AggregationPolicy>,
+    pub(crate) source_priorities: BTreeMap<A, &'a SourcePriority>,
+    pub(crate) device_hot_swap: Option<Duration>,
+    pub(crate) record_cursor_position: bool,
+}
+
+impl<'a, A: Actionlike + Ord> From<&'a InputMap<A>> for InputMapRef<'a, A> {
+    fn from(input_map: &'a InputMap<A>) -> Self {
+        InputMapRef {
+            map: A::variants()
+                .map(|action| (action.clone(), input_map.get(action).iter().collect()))
+                .collect(),
+            associated_gamepad: input_map.associated_gamepad,
+            button_thresholds: &input_map.button_thresholds,
+            sensitivity_groups: &input_map.sensitivity_groups,
+            axis_pair_processors: input_map
+                .axis_pair_processors
+                .iter()
+                .map(|(input, processors)| (input, processors.as_slice()))
+                .collect(),
+            aggregation_policies: A::variants()
+                .filter_map(|action| {
+                    let policy = input_map.aggregation_policies.get(&action.index())?;
+                    Some((action, *policy))
+                })
+                .collect(),
+            source_priorities: A::variants()
+                .filter_map(|action| {
+                    let priority = input_map.source_priorities.get(&action.index())?;
+                    Some((action, priority))
+                })
+                .collect(),
+            device_hot_swap: input_map.device_hot_swap,
+            record_cursor_position: input_map.record_cursor_position,
+        }
+    }
+}
 
-impl<A: Actionlike> FromIterator<(A, UserInput)> for InputMap<A> {
-    /// Create `InputMap<A>` from iterator with item type `(A, UserInput)`
-    ///
-    /// # Panics
-    ///
-    /// Panics if there are more than 16 distinct inputs for the same action.
-    fn from_iter<T: IntoIterator<Item = (A, UserInput)>>(iter: T) -> Self {
-        InputMap::new(iter.into_iter().map(|(action, input)| (input, action)))
+/// The deserialized form of an [`InputMap`]
+///
+/// Everything but the `map` is optional, so that files saved before the other settings were serialized can still be read.
+/// This is also built by [`InputMapMigrations::migrate`](crate::migration::InputMapMigrations::migrate).
+#[derive(Deserialize)]
+#[serde(
+    rename = "InputMap",
+    bound(deserialize = "A: Deserialize<'de> + Eq + Hash")
+)]
+pub(crate) struct InputMapData<A> {
+    pub(crate) map: HashMap<A, Vec<UserInput>>,
+    #[serde(default)]
+    pub(crate) associated_gamepad: Option<Gamepad>,
+    #[serde(default)]
+    pub(crate) button_thresholds: ButtonThresholds,
+    #[serde(default)]
+    pub(crate) sensitivity_groups: SensitivityGroups,
+    #[serde(default)]
+    pub(crate) axis_pair_processors: Vec<(UserInput, Vec<AxisPairProcessor>)>,
+    #[serde(default)]
+    pub(crate) aggregation_policies: HashMap<A, AggregationPolicy>,
+    #[serde(default)]
+    pub(crate) source_priorities: HashMap<A, SourcePriority>,
+    #[serde(default)]
+    pub(crate) device_hot_swap: Option<Duration>,
+    #[serde(default)]
+    pub(crate) record_cursor_position: bool,
+}
+
+impl<A: Actionlike> From<InputMapData<A>> for InputMap<A> {
+    fn from(data: InputMapData<A>) -> Self {
+        let mut input_map = InputMap::from(data.map);
+        input_map.associated_gamepad = data.associated_gamepad;
+        input_map.button_thresholds = data.button_thresholds;
+        input_map.sensitivity_groups = data.sensitivity_groups;
+        for (input, processors) in data.axis_pair_processors {
+            input_map.set_axis_pair_processors(input, processors);
+        }
+        for (action, policy) in data.aggregation_policies {
+            input_map.set_aggregation_policy(action, policy);
+        }
+        for (action, priority) in data.source_priorities {
+            input_map.set_source_priority(action, priority);
+        }
+        input_map.device_hot_swap = data.device_hot_swap;
+        input_map.record_cursor_position = data.record_cursor_position;
+        input_map
     }
 }
 
@@ -519,18 +1620,7 @@ where
     where
         S: serde::Serializer,
     {
-        use serde::ser::SerializeStruct;
-        use std::collections::BTreeMap;
-
-        let mut input_map = serializer.serialize_struct("InputMap", 1)?;
-        input_map.serialize_field(
-            "map",
-            &self
-                .iter()
-                .map(|(set, action)| (action, set.iter().collect()))
-                .collect::<BTreeMap<A, Vec<&UserInput>>>(),
-        )?;
-        input_map.end()
+        InputMapRef::from(self).serialize(serializer)
     }
 }
 
@@ -542,59 +1632,11 @@ where
     where
         D: Deserializer<'de>,
     {
-        use serde::de::Visitor;
-
-        #[derive(Deserialize, PartialEq)]
-        #[serde(field_identifier, rename_all = "lowercase")]
-        enum Field {
-            Map,
-        }
-
-        struct InputMapVisitor<'de, A: Actionlike + Deserialize<'de>> {
-            marker: PhantomData<&'de A>,
-        }
-
-        impl<'de, A> Visitor<'de> for InputMapVisitor<'de, A>
-        where
-            A: Actionlike + Eq + Hash + Deserialize<'de>,
-        {
-            type Value = InputMap<A>;
-
-            fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
-                write!(formatter, "a struct with field 'map' of type map where key is `Actionlike` and value is sequents of `UserInput`")
-            }
-
-            fn visit_seq<S>(self, mut seq: S) -> Result<Self::Value, S::Error>
-            where
-                S: serde::de::SeqAccess<'de>,
-            {
-                let map = seq.next_element::<HashMap<A, Vec<UserInput>>>()?;
-                map.ok_or_else(|| {
-                    serde::de::Error::invalid_length(0, &"one argument with type `map`")
-                })
-                .map(InputMap::from)
-            }
-
-            fn visit_map<M>(self, mut map: M) -> Result<Self::Value, M::Error>
-            where
-                M: serde::de::MapAccess<'de>,
-            {
-                map.next_key::<Field>()?
-                    .filter(|key| *key == Field::Map)
-                    .ok_or_else(|| serde::de::Error::missing_field("map"))?;
-                let value = map.next_value::<HashMap<A, Vec<UserInput>>>()?;
-                Ok(value.into())
-            }
-        }
-
-        let visitor = InputMapVisitor {
-            marker: PhantomData,
-        };
-        const FIELDS: &[&str] = &["map"];
-        deserializer.deserialize_struct("InputMap", FIELDS, visitor)
+        InputMapData::<A>::deserialize(deserializer).map(InputMap::from)
     }
 }
 
+#[cfg(test)]
 mod tests {
     use bevy::prelude::Reflect;
     use serde::{Deserialize, Serialize};
@@ -707,6 +1749,50 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reverse_lookup() {
+        use bevy::input::keyboard::KeyCode;
+
+        let space = UserInput::from(KeyCode::Space);
+        let mut input_map = InputMap::<Action>::default();
+        input_map.insert_one_to_many(KeyCode::Space, [Action::Hide, Action::Run]);
+        input_map.insert(KeyCode::ShiftLeft, Action::Run);
+
+        let actions: Vec<Action> = input_map.actions_for(&space).collect();
+        assert_eq!(actions, [Action::Run, Action::Hide]);
+
+        input_map.remove(Action::Run, KeyCode::Space);
+        let actions: Vec<Action> = input_map.actions_for(&space).collect();
+        assert_eq!(actions, [Action::Hide]);
+
+        input_map.clear_action(Action::Hide);
+        assert_eq!(input_map.actions_for(&space).count(), 0);
+        assert_eq!(
+            input_map,
+            InputMap::new([(KeyCode::ShiftLeft, Action::Run)])
+        );
+    }
+
+    #[test]
+    fn bindings_keep_their_slots() {
+        use bevy::input::keyboard::KeyCode;
+
+        let mut input_map = InputMap::new([
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::W, Action::Jump),
+            (KeyCode::ShiftLeft, Action::Run),
+        ]);
+        input_map.remove_at(Action::Jump, 0);
+
+        let w = UserInput::from(KeyCode::W);
+        let jump: Vec<_> = input_map.bindings(Action::Jump).collect();
+        assert_eq!(jump, [(1, &w)]);
+
+        let shift = UserInput::from(KeyCode::ShiftLeft);
+        let bindings: Vec<_> = input_map.iter().collect();
+        assert_eq!(bindings, [(Action::Run, 0, &shift), (Action::Jump, 1, &w)]);
+    }
+
     #[test]
     fn merging() {
         use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode};
@@ -730,18 +1816,123 @@ mod tests {
 
     #[test]
     fn gamepad_swapping() {
+        use crate::errors::InputManagerError;
         use bevy::input::gamepad::Gamepad;
 
         let mut input_map = InputMap::<Action>::default();
         assert_eq!(input_map.gamepad(), None);
+        assert_eq!(
+            input_map.try_gamepad(),
+            Err(InputManagerError::GamepadNotAssigned)
+        );
 
         input_map.set_gamepad(Gamepad { id: 0 });
         assert_eq!(input_map.gamepad(), Some(Gamepad { id: 0 }));
+        assert_eq!(input_map.try_gamepad(), Ok(Gamepad { id: 0 }));
 
         input_map.clear_gamepad();
         assert_eq!(input_map.gamepad(), None);
     }
 
+    #[test]
+    #[cfg(not(feature = "strict"))]
+    fn invalid_thresholds_are_ignored() {
+        use bevy::input::gamepad::{GamepadAxisType, GamepadButtonType};
+
+        let mut input_map = InputMap::<Action>::default();
+        input_map
+            .set_button_threshold(GamepadButtonType::RightTrigger2, 0.2)
+            .set_button_threshold(GamepadButtonType::RightTrigger2, 1.5)
+            .set_axis_threshold(GamepadAxisType::LeftStickX, f32::NAN);
+
+        assert_eq!(
+            input_map
+                .button_thresholds()
+                .button(GamepadButtonType::RightTrigger2),
+            Some(0.2)
+        );
+        assert_eq!(
+            input_map
+                .button_thresholds()
+                .axis(GamepadAxisType::LeftStickX),
+            None
+        );
+    }
+
+    #[test]
+    fn which_pressed_into_reuses_buffer() {
+        use crate::input_map::WhichPressedBuffer;
+        use crate::input_streams::InputStreams;
+        use bevy::input::InputPlugin;
+        use bevy::prelude::{App, KeyCode};
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        let input_map = InputMap::new([(KeyCode::Space, Action::Jump), (KeyCode::R, Action::Run)]);
+        let mut buffer = WhichPressedBuffer::default();
+
+        app.send_input(KeyCode::Space);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        input_map.which_pressed_into(&input_streams, ClashStrategy::PressAll, &mut buffer);
+        assert_eq!(
+            buffer.action_data(),
+            input_map.which_pressed(&input_streams, ClashStrategy::PressAll)
+        );
+        assert!(buffer.action_data()[Action::Jump.index()].state.pressed());
+
+        // Later reads overwrite the earlier results
+        app.release_input(KeyCode::Space);
+        app.send_input(KeyCode::R);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        input_map.which_pressed_into(&input_streams, ClashStrategy::PressAll, &mut buffer);
+        assert_eq!(buffer.action_data().len(), Action::n_variants());
+        assert!(buffer.action_data()[Action::Jump.index()].state.released());
+        assert!(buffer.action_data()[Action::Run.index()].state.pressed());
+    }
+
+    #[test]
+    fn chord_progress() {
+        use crate::input_streams::InputStreams;
+        use bevy::input::InputPlugin;
+        use bevy::prelude::{App, KeyCode};
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+        let mut input_map = InputMap::default();
+        input_map
+            .insert_chord(
+                [KeyCode::ControlLeft, KeyCode::ShiftLeft, KeyCode::S],
+                Action::Hide,
+            )
+            .insert_chord([KeyCode::R, KeyCode::E], Action::Hide)
+            .insert(KeyCode::Space, Action::Jump);
+
+        let progress = |app: &App| {
+            let input_streams = InputStreams::from_world(&app.world, None);
+            let action_data = input_map.which_pressed(&input_streams, ClashStrategy::PressAll);
+            (
+                action_data[Action::Hide.index()].chord_progress,
+                action_data[Action::Jump.index()].chord_progress,
+            )
+        };
+
+        // With nothing held, the smallest chord is reported
+        app.update();
+        assert_eq!(progress(&app), ((0, 2), (0, 0)));
+
+        app.send_input(KeyCode::ControlLeft);
+        app.send_input(KeyCode::ShiftLeft);
+        app.update();
+        assert_eq!(progress(&app), ((2, 3), (0, 0)));
+
+        app.send_input(KeyCode::R);
+        app.send_input(KeyCode::E);
+        app.update();
+        assert_eq!(progress(&app), ((2, 2), (0, 0)));
+    }
+
     #[test]
     fn from() {
         use bevy::prelude::KeyCode;
@@ -784,7 +1975,7 @@ mod tests {
             &[
                 Token::Struct {
                     name: "InputMap",
-                    len: 1,
+                    len: 9,
                 },
                 Token::Str("map"),
                 Token::Map { len: Some(3) },
@@ -853,7 +2044,7 @@ mod tests {
                 },
                 Token::UnitVariant {
                     name: "KeyCode",
-                    variant: "R",
+                    variant: "E",
                 },
                 Token::Some,
                 Token::NewtypeVariant {
@@ -862,7 +2053,7 @@ mod tests {
                 },
                 Token::UnitVariant {
                     name: "KeyCode",
-                    variant: "E",
+                    variant: "R",
                 },
                 Token::None,
                 Token::None,
@@ -873,6 +2064,296 @@ mod tests {
                 Token::SeqEnd,
                 Token::SeqEnd,
                 Token::MapEnd,
+                Token::Str("associated_gamepad"),
+                Token::None,
+                Token::Str("button_thresholds"),
+                Token::Struct {
+                    name: "ButtonThresholds",
+                    len: 2,
+                },
+                Token::Str("buttons"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("axes"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::Str("sensitivity_groups"),
+                Token::Struct {
+                    name: "SensitivityGroups",
+                    len: 2,
+                },
+                Token::Str("members"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("sensitivities"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::Str("axis_pair_processors"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("aggregation_policies"),
+                Token::Map { len: Some(0) },
+                Token::MapEnd,
+                Token::Str("source_priorities"),
+                Token::Map { len: Some(0) },
+                Token::MapEnd,
+                Token::Str("device_hot_swap"),
+                Token::None,
+                Token::Str("record_cursor_position"),
+                Token::Bool(false),
+                Token::StructEnd,
+            ],
+        )
+    }
+
+    #[test]
+    fn input_sources() {
+        use crate::input_map::InputSource;
+        use bevy::input::gamepad::GamepadButtonType;
+        use bevy::prelude::{KeyCode, MouseButton};
+
+        let key = UserInput::from(KeyCode::Space);
+        assert_eq!(InputSource::of(&key), InputSource::Keyboard);
+        assert_eq!(
+            InputSource::of(&UserInput::chord([
+                InputKind::Keyboard(KeyCode::ShiftLeft),
+                InputKind::Mouse(MouseButton::Left)
+            ])),
+            InputSource::Mouse
+        );
+        assert_eq!(
+            InputSource::of(&DualAxis::left_stick().into()),
+            InputSource::Gamepad
+        );
+        assert_eq!(
+            InputSource::of(&DualAxis::mouse_motion().into()),
+            InputSource::Mouse
+        );
+        assert_eq!(
+            InputSource::of(&VirtualDPad::arrow_keys().into()),
+            InputSource::Keyboard
+        );
+        assert_eq!(
+            InputSource::of(&UserInput::any([
+                key.clone(),
+                GamepadButtonType::South.into()
+            ])),
+            InputSource::Gamepad
+        );
+        assert_eq!(
+            InputSource::of(&key.unless(MouseButton::Right)),
+            InputSource::Keyboard
+        );
+    }
+
+    #[test]
+    fn serde_settings() {
+        use crate::axislike::AxisPairProcessor;
+        use crate::input_map::{AggregationPolicy, InputSource, SourcePriority};
+        use crate::orientation::Rotation;
+        use bevy::input::gamepad::{Gamepad, GamepadButtonType};
+        use bevy::prelude::KeyCode;
+        use bevy::utils::Duration;
+        use serde_test::assert_tokens;
+        use serde_test::Token;
+
+        let mut input_map = InputMap::<Action>::default();
+        input_map.set_gamepad(Gamepad { id: 1 });
+        input_map.set_button_threshold(GamepadButtonType::RightTrigger2, 0.25);
+        input_map.set_sensitivity("look", 2.0);
+        input_map.add_axis_pair_processor(
+            KeyCode::Space,
+            AxisPairProcessor::Rotate(Rotation::new(90_000_000)),
+        );
+        input_map.set_aggregation_policy(Action::Run, AggregationPolicy::MaxMagnitude);
+        input_map.set_source_priority(
+            Action::Run,
+            SourcePriority::new([InputSource::Gamepad, InputSource::Keyboard]),
+        );
+        input_map.set_device_hot_swap(Duration::from_millis(100));
+        input_map.set_record_cursor_position(true);
+
+        assert_tokens(
+            &input_map,
+            &[
+                Token::Struct {
+                    name: "InputMap",
+                    len: 9,
+                },
+                Token::Str("map"),
+                Token::Map { len: Some(3) },
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Run",
+                },
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Jump",
+                },
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Hide",
+                },
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::MapEnd,
+                Token::Str("associated_gamepad"),
+                Token::Some,
+                Token::Struct {
+                    name: "Gamepad",
+                    len: 1,
+                },
+                Token::Str("id"),
+                Token::U64(1),
+                Token::StructEnd,
+                Token::Str("button_thresholds"),
+                Token::Struct {
+                    name: "ButtonThresholds",
+                    len: 2,
+                },
+                Token::Str("buttons"),
+                Token::Seq { len: Some(1) },
+                Token::Tuple { len: 2 },
+                Token::UnitVariant {
+                    name: "GamepadButtonType",
+                    variant: "RightTrigger2",
+                },
+                Token::F32(0.25),
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::Str("axes"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::Str("sensitivity_groups"),
+                Token::Struct {
+                    name: "SensitivityGroups",
+                    len: 2,
+                },
+                Token::Str("members"),
+                Token::Seq { len: Some(0) },
+                Token::SeqEnd,
+                Token::Str("sensitivities"),
+                Token::Seq { len: Some(1) },
+                Token::Tuple { len: 2 },
+                Token::Str("look"),
+                Token::F32(2.0),
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::StructEnd,
+                Token::Str("axis_pair_processors"),
+                Token::Seq { len: Some(1) },
+                Token::Tuple { len: 2 },
+                Token::NewtypeVariant {
+                    name: "UserInput",
+                    variant: "Single",
+                },
+                Token::NewtypeVariant {
+                    name: "InputKind",
+                    variant: "Keyboard",
+                },
+                Token::UnitVariant {
+                    name: "KeyCode",
+                    variant: "Space",
+                },
+                Token::Seq { len: Some(1) },
+                Token::NewtypeVariant {
+                    name: "AxisPairProcessor",
+                    variant: "Rotate",
+                },
+                Token::U32(90_000_000),
+                Token::SeqEnd,
+                Token::TupleEnd,
+                Token::SeqEnd,
+                Token::Str("aggregation_policies"),
+                Token::Map { len: Some(1) },
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Run",
+                },
+                Token::UnitVariant {
+                    name: "AggregationPolicy",
+                    variant: "MaxMagnitude",
+                },
+                Token::MapEnd,
+                Token::Str("source_priorities"),
+                Token::Map { len: Some(1) },
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Run",
+                },
+                Token::NewtypeStruct {
+                    name: "SourcePriority",
+                },
+                Token::Seq { len: Some(2) },
+                Token::UnitVariant {
+                    name: "InputSource",
+                    variant: "Gamepad",
+                },
+                Token::UnitVariant {
+                    name: "InputSource",
+                    variant: "Keyboard",
+                },
+                Token::SeqEnd,
+                Token::MapEnd,
+                Token::Str("device_hot_swap"),
+                Token::Some,
+                Token::Struct {
+                    name: "Duration",
+                    len: 2,
+                },
+                Token::Str("secs"),
+                Token::U64(0),
+                Token::Str("nanos"),
+                Token::U32(100_000_000),
+                Token::StructEnd,
+                Token::Str("record_cursor_position"),
+                Token::Bool(true),
+                Token::StructEnd,
+            ],
+        )
+    }
+
+    #[test]
+    fn deserialize_without_settings() {
+        use bevy::prelude::KeyCode;
+        use serde_test::assert_de_tokens;
+        use serde_test::Token;
+
+        assert_de_tokens(
+            &InputMap::new([(KeyCode::Space, Action::Jump)]),
+            &[
+                Token::Struct {
+                    name: "InputMap",
+                    len: 1,
+                },
+                Token::Str("map"),
+                Token::Map { len: Some(1) },
+                Token::UnitVariant {
+                    name: "Action",
+                    variant: "Jump",
+                },
+                Token::Seq { len: Some(1) },
+                Token::NewtypeVariant {
+                    name: "UserInput",
+                    variant: "Single",
+                },
+                Token::NewtypeVariant {
+                    name: "InputKind",
+                    variant: "Keyboard",
+                },
+                Token::UnitVariant {
+                    name: "KeyCode",
+                    variant: "Space",
+                },
+                Token::SeqEnd,
+                Token::MapEnd,
                 Token::StructEnd,
             ],
         )
@@ -20,12 +20,13 @@ use bevy::ecs::world::World;
 use bevy::ecs::{component::Component, query::With, system::Query};
 use bevy::input::gamepad::{GamepadAxisChangedEvent, GamepadButtonChangedEvent};
 use bevy::input::mouse::MouseScrollUnit;
+#[cfg(feature = "touch")]
+use bevy::input::touch::{TouchInput, Touches};
 use bevy::input::ButtonState;
 use bevy::input::{
     gamepad::{Gamepad, GamepadButton, GamepadEvent},
     keyboard::{KeyCode, KeyboardInput},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
-    touch::{TouchInput, Touches},
     Input,
 };
 use bevy::math::Vec2;
@@ -431,7 +432,9 @@ impl MockInput for World {
         self.insert_resource(Events::<MouseWheel>::default());
         self.insert_resource(Events::<CursorMoved>::default());
 
+        #[cfg(feature = "touch")]
         self.insert_resource(Touches::default());
+        #[cfg(feature = "touch")]
         self.insert_resource(Events::<TouchInput>::default());
     }
 
@@ -0,0 +1,221 @@
+//! An on-screen display of the live state of each action, built with `bevy_ui`
+//!
+//! Add an [`InputOverlay`] to an entity with a [`TextBundle`](bevy::ui::node_bundles::TextBundle),
+//! and the [`update_input_overlays`](crate::systems::update_input_overlays) system will rewrite its [`Text`]
+//! each frame to list the actions of the `source` [`ActionState`], highlighting those that are pressed.
+//! Actions with an axis pair, such as stick movement, also display their current position.
+//!
+//! This is useful for streamers, trailers and debugging.
+//! To display the inputs of a player from a spectator's point of view, or during a replay,
+//! point the overlay at an entity with a [`MirrorActionState`](crate::mirror::MirrorActionState).
+
+use crate::action_metadata::ActionMetadata;
+use crate::action_state::ActionState;
+use crate::input_map::InputMap;
+use crate::localization::DefaultNames;
+use crate::Actionlike;
+
+use bevy::asset::Handle;
+use bevy::ecs::prelude::{Component, Entity};
+use bevy::render::color::Color;
+use bevy::text::{Font, Text, TextSection, TextStyle};
+
+/// Displays the [`ActionState`] of the `source` entity in the [`Text`] of this entity
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::input_overlay::InputOverlay;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Jump,
+/// }
+///
+/// let mut world = World::new();
+/// let player = world.spawn(InputManagerBundle::<Action>::default()).id();
+///
+/// world.spawn((
+///     TextBundle::default(),
+///     InputOverlay::<Action>::new(player).with_colors(Color::YELLOW, Color::GRAY),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct InputOverlay<A: Actionlike> {
+    /// The entity whose [`ActionState`] is displayed, or [`None`] to display the [`ActionState`] resource
+    pub source: Option<Entity>,
+    /// The actions that are displayed, or [`None`] to display every action
+    pub actions: Option<Vec<A>>,
+    /// Should the inputs bound to each action in the source's [`InputMap`] be displayed?
+    pub show_bindings: bool,
+    /// The font used to display each action
+    pub font: Handle<Font>,
+    /// The size of the font used to display each action
+    pub font_size: f32,
+    /// The color of actions that are pressed
+    pub pressed_color: Color,
+    /// The color of actions that are released
+    pub released_color: Color,
+}
+
+impl<A: Actionlike> InputOverlay<A> {
+    /// Displays the [`ActionState`] of the `source` entity
+    #[must_use]
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source: Some(source),
+            ..Self::resource()
+        }
+    }
+
+    /// Displays the [`ActionState`] resource
+    #[must_use]
+    pub fn resource() -> Self {
+        Self {
+            source: None,
+            actions: None,
+            show_bindings: true,
+            font: Handle::default(),
+            font_size: 20.0,
+            pressed_color: Color::WHITE,
+            released_color: Color::DARK_GRAY,
+        }
+    }
+
+    /// Only displays the provided `actions`, in the provided order
+    #[must_use]
+    pub fn with_actions(mut self, actions: impl IntoIterator<Item = A>) -> Self {
+        self.actions = Some(actions.into_iter().collect());
+        self
+    }
+
+    /// Sets whether the inputs bound to each action are displayed
+    #[must_use]
+    pub fn with_bindings(mut self, show_bindings: bool) -> Self {
+        self.show_bindings = show_bindings;
+        self
+    }
+
+    /// Sets the `font` and `font_size` used to display each action
+    #[must_use]
+    pub fn with_font(mut self, font: Handle<Font>, font_size: f32) -> Self {
+        self.font = font;
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the colors of pressed and released actions
+    #[must_use]
+    pub fn with_colors(mut self, pressed_color: Color, released_color: Color) -> Self {
+        self.pressed_color = pressed_color;
+        self.released_color = released_color;
+        self
+    }
+
+    /// Iterates over the actions that are displayed, in order
+    pub fn displayed_actions(&self) -> Box<dyn Iterator<Item = A> + '_> {
+        match &self.actions {
+            Some(actions) => Box::new(actions.iter().cloned()),
+            None => Box::new(A::variants()),
+        }
+    }
+
+    /// Builds the line of text that displays each action
+    ///
+    /// Action names are taken from the `metadata` if provided, and from [`Actionlike::info`] otherwise.
+    #[must_use]
+    pub fn sections(
+        &self,
+        action_state: &ActionState<A>,
+        input_map: Option<&InputMap<A>>,
+        metadata: Option<&ActionMetadata<A>>,
+    ) -> Vec<TextSection> {
+        self.displayed_actions()
+            .map(|action| {
+                let mut line = match metadata {
+                    Some(metadata) => metadata.localized_name(action.clone(), &DefaultNames),
+                    None => action.info().name,
+                };
+
+                if self.show_bindings {
+                    if let Some(input_map) = input_map {
+                        let bindings = input_map.localized_names(action.clone(), &DefaultNames);
+                        if !bindings.is_empty() {
+                            line.push_str(&format!(" [{}]", bindings.join(", ")));
+                        }
+                    }
+                }
+
+                if let Some(axis_pair) = action_state.axis_pair(action.clone()) {
+                    line.push_str(&format!(" ({:.2}, {:.2})", axis_pair.x(), axis_pair.y()));
+                }
+                line.push('\n');
+
+                let color = if action_state.pressed(action) {
+                    self.pressed_color
+                } else {
+                    self.released_color
+                };
+
+                TextSection::new(
+                    line,
+                    TextStyle {
+                        font: self.font.clone(),
+                        font_size: self.font_size,
+                        color,
+                    },
+                )
+            })
+            .collect()
+    }
+
+    /// Replaces the sections of the `text` with the current [`sections`](Self::sections)
+    pub fn update_text(
+        &self,
+        text: &mut Text,
+        action_state: &ActionState<A>,
+        input_map: Option<&InputMap<A>>,
+        metadata: Option<&ActionMetadata<A>>,
+    ) {
+        text.sections = self.sections(action_state, input_map, metadata);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::axislike::DualAxisData;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Move,
+        Jump,
+    }
+
+    #[test]
+    fn overlay_sections() {
+        let overlay = InputOverlay::<Action>::resource();
+        let input_map = InputMap::new([(KeyCode::Space, Action::Jump)]);
+
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Jump);
+        action_state.action_data_mut(Action::Move).axis_pair = Some(DualAxisData::new(0.5, -1.0));
+
+        let sections = overlay.sections(&action_state, Some(&input_map), None);
+        assert_eq!(sections.len(), 2);
+        assert_eq!(sections[0].value, "Move (0.50, -1.00)\n");
+        assert_eq!(sections[0].style.color, overlay.released_color);
+        assert_eq!(sections[1].value, "Jump [Space]\n");
+        assert_eq!(sections[1].style.color, overlay.pressed_color);
+
+        let overlay = overlay.with_actions([Action::Jump]).with_bindings(false);
+        let sections = overlay.sections(&action_state, Some(&input_map), None);
+        assert_eq!(sections.len(), 1);
+        assert_eq!(sections[0].value, "Jump\n");
+    }
+}
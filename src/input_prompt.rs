@@ -0,0 +1,300 @@
+//! Prompts that wait for the player to press an expected action, or sequence of actions
+//!
+//! An [`InputPrompt`] watches the [`ActionState`] that it is stored alongside (as a resource or a component),
+//! and an [`InputPromptEvent`] is sent whenever the prompt makes progress, completes, times out or sees an unexpected action.
+//! This is useful for tutorials ("Press Jump to continue"), quick-time events and similar mechanics.
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::input_prompt::{InputPrompt, PromptOutcome, PromptStatus};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Dash,
+//! }
+//!
+//! let mut prompt = InputPrompt::sequence([Action::Jump, Action::Dash])
+//!     .with_timeout(Duration::from_secs(5));
+//! let mut action_state = ActionState::<Action>::default();
+//!
+//! action_state.press(Action::Jump);
+//! prompt.update(&action_state, Duration::from_millis(100));
+//! assert_eq!(prompt.expected(), Some(Action::Dash));
+//!
+//! action_state.release(Action::Jump);
+//! action_state.press(Action::Dash);
+//! let outcomes = prompt.update(&action_state, Duration::from_millis(100));
+//! assert_eq!(outcomes.last(), Some(&PromptOutcome::Completed));
+//! assert_eq!(prompt.status(), PromptStatus::Completed);
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity, Event, Resource};
+use bevy::utils::Duration;
+
+/// Whether an [`InputPrompt`] is still waiting for input
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PromptStatus {
+    /// The prompt is waiting for the next expected action
+    #[default]
+    Active,
+    /// Every action in the sequence was pressed
+    Completed,
+    /// The timeout elapsed before the sequence was completed
+    TimedOut,
+}
+
+/// Something that happened to an [`InputPrompt`] during an update
+#[derive(Debug, Clone, PartialEq)]
+pub enum PromptOutcome<A: Actionlike> {
+    /// The expected `action` was pressed, completing the step at position `step` in the sequence
+    Progressed {
+        /// The action that was pressed
+        action: A,
+        /// The index of the completed step
+        step: usize,
+    },
+    /// Every action in the sequence was pressed
+    Completed,
+    /// The timeout elapsed before the sequence was completed
+    TimedOut,
+    /// An action other than the expected one was just pressed
+    WrongInput {
+        /// The action that the prompt was waiting for
+        expected: A,
+        /// The action that was pressed instead
+        received: A,
+    },
+}
+
+/// Sent by the [`update_input_prompts`](crate::systems::update_input_prompts) system for each [`PromptOutcome`]
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct InputPromptEvent<A: Actionlike> {
+    /// What happened to the prompt
+    pub outcome: PromptOutcome<A>,
+    /// The entity that the prompt belongs to, or [`None`] if the prompt is a resource
+    pub entity: Option<Entity>,
+}
+
+/// Waits for the player to press a sequence of actions, optionally within a time limit
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it watches.
+/// Once the prompt has completed or timed out, it stops responding to input until [`InputPrompt::restart`] is called.
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct InputPrompt<A: Actionlike> {
+    sequence: Vec<A>,
+    progress: usize,
+    timeout: Option<Duration>,
+    elapsed: Duration,
+    reset_on_wrong_input: bool,
+    status: PromptStatus,
+}
+
+impl<A: Actionlike> InputPrompt<A> {
+    /// Creates a prompt that waits for a single `action`
+    #[must_use]
+    pub fn new(action: A) -> Self {
+        Self::sequence([action])
+    }
+
+    /// Creates a prompt that waits for each of the `actions`, in order
+    ///
+    /// A prompt with an empty sequence completes during its first update.
+    #[must_use]
+    pub fn sequence(actions: impl IntoIterator<Item = A>) -> Self {
+        InputPrompt {
+            sequence: actions.into_iter().collect(),
+            progress: 0,
+            timeout: None,
+            elapsed: Duration::ZERO,
+            reset_on_wrong_input: false,
+            status: PromptStatus::Active,
+        }
+    }
+
+    /// Returns this prompt with a time limit for completing the whole sequence
+    ///
+    /// When the prompt is advanced by [`update_input_prompts`](crate::systems::update_input_prompts),
+    /// the time limit is scaled by the [`TimingForgiveness`](crate::timing_forgiveness::TimingForgiveness) resource, if any.
+    #[must_use]
+    pub fn with_timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = Some(timeout);
+        self
+    }
+
+    /// Returns this prompt configured to restart the sequence from the beginning whenever a wrong action is pressed
+    ///
+    /// By default, wrong inputs are reported but do not affect the progress of the prompt.
+    #[must_use]
+    pub fn with_reset_on_wrong_input(mut self, reset_on_wrong_input: bool) -> Self {
+        self.reset_on_wrong_input = reset_on_wrong_input;
+        self
+    }
+
+    /// The current status of the prompt
+    #[must_use]
+    pub fn status(&self) -> PromptStatus {
+        self.status
+    }
+
+    /// The action that the prompt is waiting for, if it is still active
+    #[must_use]
+    pub fn expected(&self) -> Option<A> {
+        match self.status {
+            PromptStatus::Active => self.sequence.get(self.progress).cloned(),
+            _ => None,
+        }
+    }
+
+    /// The number of steps of the sequence that have been completed
+    #[must_use]
+    pub fn progress(&self) -> usize {
+        self.progress
+    }
+
+    /// The full sequence of actions that the prompt is waiting for
+    #[must_use]
+    pub fn actions(&self) -> &[A] {
+        &self.sequence
+    }
+
+    /// The time remaining before the prompt times out, if it has a timeout
+    #[must_use]
+    pub fn remaining(&self) -> Option<Duration> {
+        self.timeout
+            .map(|timeout| timeout.saturating_sub(self.elapsed))
+    }
+
+    /// Resets the progress and elapsed time of the prompt, allowing it to be completed again
+    pub fn restart(&mut self) {
+        self.progress = 0;
+        self.elapsed = Duration::ZERO;
+        self.status = PromptStatus::Active;
+    }
+
+    /// Advances the prompt by `delta`, and checks the `action_state` for newly pressed actions
+    ///
+    /// Returns everything that happened to the prompt during this update, in order.
+    pub fn update(
+        &mut self,
+        action_state: &ActionState<A>,
+        delta: Duration,
+    ) -> Vec<PromptOutcome<A>> {
+        let mut outcomes = Vec::new();
+        if self.status != PromptStatus::Active {
+            return outcomes;
+        }
+
+        self.elapsed += delta;
+        if self.timeout.is_some_and(|timeout| self.elapsed >= timeout) {
+            self.status = PromptStatus::TimedOut;
+            outcomes.push(PromptOutcome::TimedOut);
+            return outcomes;
+        }
+
+        if let Some(expected) = self.sequence.get(self.progress).cloned() {
+            if action_state.newly_pressed(expected.clone()) {
+                outcomes.push(PromptOutcome::Progressed {
+                    action: expected,
+                    step: self.progress,
+                });
+                self.progress += 1;
+            } else {
+                for received in action_state.get_just_pressed() {
+                    outcomes.push(PromptOutcome::WrongInput {
+                        expected: expected.clone(),
+                        received,
+                    });
+                }
+
+                if self.reset_on_wrong_input && !outcomes.is_empty() {
+                    self.progress = 0;
+                }
+            }
+        }
+
+        if self.progress >= self.sequence.len() {
+            self.status = PromptStatus::Completed;
+            outcomes.push(PromptOutcome::Completed);
+        }
+
+        outcomes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Dash,
+        Crouch,
+    }
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    #[test]
+    fn wrong_inputs() {
+        let mut prompt = InputPrompt::sequence([Action::Jump, Action::Dash]);
+        let mut action_state = ActionState::<Action>::default();
+
+        action_state.press(Action::Jump);
+        prompt.update(&action_state, FRAME);
+        action_state.release(Action::Jump);
+
+        action_state.press(Action::Crouch);
+        assert_eq!(
+            prompt.update(&action_state, FRAME),
+            [PromptOutcome::WrongInput {
+                expected: Action::Dash,
+                received: Action::Crouch
+            }]
+        );
+        // Wrong inputs do not reset progress by default
+        assert_eq!(prompt.progress(), 1);
+
+        let mut prompt = prompt.with_reset_on_wrong_input(true);
+        prompt.update(&action_state, FRAME);
+        assert_eq!(prompt.progress(), 0);
+        assert_eq!(prompt.expected(), Some(Action::Jump));
+    }
+
+    #[test]
+    fn timeout() {
+        let mut prompt = InputPrompt::new(Action::Jump).with_timeout(FRAME * 2);
+        let mut action_state = ActionState::<Action>::default();
+
+        assert!(prompt.update(&action_state, FRAME).is_empty());
+        assert_eq!(prompt.remaining(), Some(FRAME));
+        assert_eq!(
+            prompt.update(&action_state, FRAME),
+            [PromptOutcome::TimedOut]
+        );
+        assert_eq!(prompt.status(), PromptStatus::TimedOut);
+
+        // Finished prompts ignore input until restarted
+        action_state.press(Action::Jump);
+        assert!(prompt.update(&action_state, FRAME).is_empty());
+
+        prompt.restart();
+        assert_eq!(
+            prompt.update(&action_state, FRAME),
+            [
+                PromptOutcome::Progressed {
+                    action: Action::Jump,
+                    step: 0
+                },
+                PromptOutcome::Completed
+            ]
+        );
+    }
+}
@@ -1,7 +1,7 @@
 //! Unified input streams for working with [`bevy::input`] data.
 
 use bevy::input::{
-    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadEvent, Gamepads},
+    gamepad::{Gamepad, GamepadAxis, GamepadButton, GamepadButtonType, GamepadEvent, Gamepads},
     keyboard::{KeyCode, KeyboardInput, ScanCode},
     mouse::{MouseButton, MouseButtonInput, MouseMotion, MouseWheel},
     Axis, Input,
@@ -10,14 +10,20 @@ use petitset::PetitSet;
 
 use bevy::ecs::prelude::{Events, ResMut, World};
 use bevy::ecs::system::SystemState;
+use bevy::math::Vec2;
+use bevy::window::{PrimaryWindow, Window};
 
 use crate::axislike::{
-    AxisType, DualAxisData, MouseMotionAxisType, MouseWheelAxisType, SingleAxis, VirtualAxis,
-    VirtualDPad,
+    AxisType, DualAxisData, MouseDrag, MouseMotionAxisType, MouseWheelAxisType, SingleAxis,
+    VirtualAxis, VirtualDPad,
 };
-use crate::buttonlike::{MouseMotionDirection, MouseWheelDirection};
+use crate::buttonlike::{ButtonThresholds, MouseMotionDirection, MouseWheelDirection};
+use crate::custom_input::CustomInputs;
+use crate::input_map::MAX_BINDINGS_PER_ACTION;
 use crate::prelude::DualAxis;
+use crate::stick_gestures::{Flick, StickHistory, StickRotation};
 use crate::user_input::{InputKind, UserInput};
+use crate::window_events::WindowEventInputs;
 
 /// A collection of [`Input`] structs, which can be used to update an [`InputMap`](crate::input_map::InputMap).
 ///
@@ -42,8 +48,33 @@ pub struct InputStreams<'a> {
     pub mouse_wheel: Option<&'a Events<MouseWheel>>,
     /// A [`MouseMotion`] event stream
     pub mouse_motion: &'a Events<MouseMotion>,
+    /// The position of the cursor in the primary window, if any
+    pub cursor_position: Option<Vec2>,
+    /// Overrides for the thresholds of individual gamepad buttons and axes
+    pub button_thresholds: Option<&'a ButtonThresholds>,
     /// The [`Gamepad`] that this struct will detect inputs from
     pub associated_gamepad: Option<Gamepad>,
+    /// The window events received during the current frame
+    pub window_events: Option<&'a WindowEventInputs>,
+    /// The current value of each custom input
+    pub custom_inputs: Option<&'a CustomInputs>,
+    /// The recent positions of each gamepad stick, used to detect stick gestures
+    pub stick_history: Option<&'a StickHistory>,
+}
+
+/// Finds the [`Window`] marked with [`PrimaryWindow`], if any.
+///
+/// Creating a `Query<&Window, With<PrimaryWindow>>` requires mutable access to the [`World`],
+/// so this matches archetypes in the same way, rather than visiting every entity.
+fn primary_window(world: &World) -> Option<&Window> {
+    let primary_window = world.component_id::<PrimaryWindow>()?;
+
+    world
+        .archetypes()
+        .iter()
+        .filter(|archetype| archetype.contains(primary_window))
+        .flat_map(|archetype| archetype.entities())
+        .find_map(|archetype_entity| world.get::<Window>(archetype_entity.entity()))
 }
 
 // Constructors
@@ -59,6 +90,7 @@ impl<'a> InputStreams<'a> {
         let mouse_buttons = world.get_resource::<Input<MouseButton>>();
         let mouse_wheel = world.get_resource::<Events<MouseWheel>>();
         let mouse_motion = world.resource::<Events<MouseMotion>>();
+        let cursor_position = primary_window(world).and_then(Window::cursor_position);
 
         InputStreams {
             gamepad_buttons,
@@ -70,7 +102,12 @@ impl<'a> InputStreams<'a> {
             mouse_buttons,
             mouse_wheel,
             mouse_motion,
+            cursor_position,
+            button_thresholds: None,
             associated_gamepad: gamepad,
+            window_events: world.get_resource::<WindowEventInputs>(),
+            custom_inputs: world.get_resource::<CustomInputs>(),
+            stick_history: world.get_resource::<StickHistory>(),
         }
     }
 }
@@ -109,12 +146,23 @@ impl<'a> InputStreams<'a> {
             UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
                 self.button_pressed(*negative) || self.button_pressed(*positive)
             }
+            UserInput::WindowEvent(event) => self
+                .window_events
+                .is_some_and(|window_events| window_events.received(*event)),
+            UserInput::Custom(id) => self
+                .custom_inputs
+                .is_some_and(|custom_inputs| custom_inputs.pressed(*id)),
+            UserInput::All(inputs) => inputs.iter().all(|input| self.input_pressed(input)),
+            UserInput::Any(inputs) => inputs.iter().any(|input| self.input_pressed(input)),
+            UserInput::Unless { input, blocker } => {
+                self.input_pressed(input) && !self.input_pressed(blocker)
+            }
         }
     }
 
     /// Is at least one of the `inputs` pressed?
     #[must_use]
-    pub fn any_pressed(&self, inputs: &PetitSet<UserInput, 16>) -> bool {
+    pub fn any_pressed(&self, inputs: &PetitSet<UserInput, MAX_BINDINGS_PER_ACTION>) -> bool {
         for input in inputs.iter() {
             if self.input_pressed(input) {
                 return true;
@@ -132,17 +180,24 @@ impl<'a> InputStreams<'a> {
                 self.button_pressed(InputKind::SingleAxis(axis.x))
                     || self.button_pressed(InputKind::SingleAxis(axis.y))
             }
-            InputKind::SingleAxis(axis) => {
-                let value = self.input_value(&UserInput::Single(button));
-
-                value < axis.negative_low || value > axis.positive_low
+            InputKind::SingleAxis(_) => {
+                // Values outside of the trigger zones of the axis are reported as zero
+                self.input_value(&UserInput::Single(button)) != 0.0
             }
             InputKind::GamepadButton(gamepad_button) => {
                 if let Some(gamepad) = self.guess_gamepad() {
-                    self.gamepad_buttons.pressed(GamepadButton {
+                    let button = GamepadButton {
                         gamepad,
                         button_type: gamepad_button,
-                    })
+                    };
+
+                    let threshold = self
+                        .button_thresholds
+                        .and_then(|thresholds| thresholds.button(gamepad_button));
+                    match (threshold, self.gamepad_button_axes.get(button)) {
+                        (Some(threshold), Some(value)) => value >= threshold,
+                        _ => self.gamepad_buttons.pressed(button),
+                    }
                 } else {
                     false
                 }
@@ -161,6 +216,9 @@ impl<'a> InputStreams<'a> {
             InputKind::Mouse(mouse_button) => {
                 matches!(self.mouse_buttons, Some(mouse_buttons) if mouse_buttons.pressed(mouse_button))
             }
+            InputKind::MouseDrag(drag) => self.button_pressed(InputKind::Mouse(drag.button)),
+            InputKind::Flick(flick) => self.flick_position(&flick).is_some(),
+            InputKind::StickRotation(rotation) => self.stick_rotation_speed(&rotation).is_some(),
             InputKind::MouseWheel(mouse_wheel_direction) => {
                 let Some(mouse_wheel) = self.mouse_wheel else {
                     return false;
@@ -259,7 +317,7 @@ impl<'a> InputStreams<'a> {
         // Helper that takes the value returned by an axis and returns 0.0 if it is not within the
         // triggering range.
         let value_in_axis_range = |axis: &SingleAxis, value: f32| -> f32 {
-            if value >= axis.negative_low && value <= axis.positive_low {
+            if !axis.in_trigger_zone(value) {
                 0.0
             } else if axis.inverted {
                 -value
@@ -278,7 +336,7 @@ impl<'a> InputStreams<'a> {
                                 .get(GamepadAxis { gamepad, axis_type })
                                 .unwrap_or_default();
 
-                            value_in_axis_range(single_axis, value)
+                            value_in_axis_range(&self.thresholded_axis(single_axis), value)
                         } else {
                             0.0
                         }
@@ -320,7 +378,7 @@ impl<'a> InputStreams<'a> {
                 self.input_value(&UserInput::Single(*positive)).abs()
                     - self.input_value(&UserInput::Single(*negative)).abs()
             }
-            UserInput::Single(InputKind::DualAxis(_)) => {
+            UserInput::Single(InputKind::DualAxis(_) | InputKind::MouseDrag(_)) => {
                 self.input_axis_pair(input).unwrap_or_default().length()
             }
             UserInput::VirtualDPad { .. } => {
@@ -328,26 +386,89 @@ impl<'a> InputStreams<'a> {
             }
             // This is required because upstream bevy::input still waffles about whether triggers are buttons or axes
             UserInput::Single(InputKind::GamepadButton(button_type)) => {
-                if let Some(gamepad) = self.guess_gamepad() {
-                    // Get the value from the registered gamepad
-                    self.gamepad_button_axes
-                        .get(GamepadButton {
-                            gamepad,
-                            button_type: *button_type,
-                        })
-                        .unwrap_or_else(use_button_value)
-                } else {
+                self.gamepad_button_value(*button_type)
+            }
+            UserInput::Single(InputKind::StickRotation(rotation)) => {
+                self.stick_rotation_speed(rotation).unwrap_or_default()
+            }
+            // Pressure-sensitive buttons scale the value of the chords that they are part of
+            UserInput::Chord(inputs) => {
+                if !self.input_pressed(input) {
+                    return 0.0;
+                }
+
+                inputs
+                    .iter()
+                    .filter_map(|input_kind| match input_kind {
+                        InputKind::GamepadButton(button_type) => {
+                            Some(self.gamepad_button_value(*button_type))
+                        }
+                        _ => None,
+                    })
+                    .fold(1.0, f32::min)
+            }
+            UserInput::Custom(id) => self
+                .custom_inputs
+                .map(|custom_inputs| custom_inputs.value(*id))
+                .unwrap_or_default(),
+            // Like chords, expressions are limited by their weakest input
+            UserInput::All(inputs) => {
+                if !self.input_pressed(input) {
+                    return 0.0;
+                }
+
+                inputs
+                    .iter()
+                    .map(|input| self.input_value(input))
+                    .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+                    .unwrap_or_default()
+            }
+            // The strongest alternative wins
+            UserInput::Any(inputs) => inputs
+                .iter()
+                .map(|input| self.input_value(input))
+                .max_by(|a, b| a.abs().total_cmp(&b.abs()))
+                .unwrap_or_default(),
+            UserInput::Unless { input, blocker } => {
+                if self.input_pressed(blocker) {
                     0.0
+                } else {
+                    self.input_value(input)
                 }
             }
             _ => use_button_value(),
         }
     }
 
+    /// Returns the pressure applied to the gamepad button of type `button_type`
+    ///
+    /// Controllers that report button pressure will return a value between `0.0` and `1.0`.
+    /// For buttons without pressure data, this falls back to `1.0` when pressed and `0.0` when released.
+    #[must_use]
+    pub fn gamepad_button_value(&self, button_type: GamepadButtonType) -> f32 {
+        let Some(gamepad) = self.guess_gamepad() else {
+            return 0.0;
+        };
+
+        let button = GamepadButton {
+            gamepad,
+            button_type,
+        };
+        // Get the value from the registered gamepad
+        self.gamepad_button_axes.get(button).unwrap_or_else(|| {
+            if self.gamepad_buttons.pressed(button) {
+                1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
     /// Get the axis pair associated to the user input.
     ///
     /// If `input` is a chord, returns result of the first dual axis in the chord.
-
+    /// If `input` is an [`All`](UserInput::All) or [`Any`](UserInput::Any) expression, returns the result of its first input with an axis pair,
+    /// preferring the inputs that are pressed.
     /// If `input` is not a [`DualAxis`](crate::axislike::DualAxis) or [`VirtualDPad`], returns [`None`].
     ///
     /// # Warning
@@ -358,17 +479,21 @@ impl<'a> InputStreams<'a> {
         match input {
             UserInput::Chord(inputs) => inputs
                 .iter()
-                .flat_map(|input_kind| {
-                    if let InputKind::DualAxis(dual_axis) = input_kind {
-                        Some(self.extract_dual_axis_data(dual_axis))
-                    } else {
-                        None
-                    }
+                .flat_map(|input_kind| match input_kind {
+                    InputKind::DualAxis(dual_axis) => Some(self.extract_dual_axis_data(dual_axis)),
+                    InputKind::MouseDrag(drag) => Some(self.extract_mouse_drag_data(drag)),
+                    _ => None,
                 })
                 .next(),
             UserInput::Single(InputKind::DualAxis(dual_axis)) => {
                 Some(self.extract_dual_axis_data(dual_axis))
             }
+            UserInput::Single(InputKind::MouseDrag(drag)) => {
+                Some(self.extract_mouse_drag_data(drag))
+            }
+            UserInput::Single(InputKind::Flick(flick)) => Some(DualAxisData::from_xy(
+                self.flick_position(flick).unwrap_or_default(),
+            )),
             UserInput::VirtualDPad(VirtualDPad {
                 up,
                 down,
@@ -381,18 +506,88 @@ impl<'a> InputStreams<'a> {
                     - self.input_value(&UserInput::Single(*down)).abs();
                 Some(DualAxisData::new(x, y))
             }
+            UserInput::All(inputs) => {
+                if !self.input_pressed(input) {
+                    return Some(DualAxisData::new(0.0, 0.0));
+                }
+
+                inputs.iter().find_map(|input| self.input_axis_pair(input))
+            }
+            // Prefer the axis pair of an alternative that is pressed
+            UserInput::Any(inputs) => inputs
+                .iter()
+                .filter(|input| self.input_pressed(input))
+                .find_map(|input| self.input_axis_pair(input))
+                .or_else(|| inputs.iter().find_map(|input| self.input_axis_pair(input))),
+            UserInput::Unless { input, blocker } => {
+                let axis_pair = self.input_axis_pair(input)?;
+                if self.input_pressed(blocker) {
+                    Some(DualAxisData::new(0.0, 0.0))
+                } else {
+                    Some(axis_pair)
+                }
+            }
             _ => None,
         }
     }
 
+    /// Returns the position of the stick of the `flick`, if it was flicked during this frame
+    fn flick_position(&self, flick: &Flick) -> Option<Vec2> {
+        flick.detect(self.stick_history?, self.guess_gamepad()?)
+    }
+
+    /// Returns the speed of the `rotation` in turns per second, if its stick is being rotated during this frame
+    fn stick_rotation_speed(&self, rotation: &StickRotation) -> Option<f32> {
+        rotation.detect(self.stick_history?, self.guess_gamepad()?)
+    }
+
+    fn extract_mouse_drag_data(&self, drag: &MouseDrag) -> DualAxisData {
+        if !self.button_pressed(InputKind::Mouse(drag.button)) {
+            return DualAxisData::new(0.0, 0.0);
+        }
+
+        let mut event_reader = self.mouse_motion.get_reader();
+        let delta: Vec2 = event_reader
+            .iter(self.mouse_motion)
+            .map(|mouse_motion_event| mouse_motion_event.delta)
+            .sum();
+
+        DualAxisData::from_xy(delta)
+    }
+
+    /// Applies any threshold override for the axis to the unbounded trigger zones of `axis`
+    fn thresholded_axis(&self, axis: &SingleAxis) -> SingleAxis {
+        let mut axis = *axis;
+        let AxisType::Gamepad(axis_type) = axis.axis_type else {
+            return axis;
+        };
+        let Some(threshold) = self
+            .button_thresholds
+            .and_then(|thresholds| thresholds.axis(axis_type))
+        else {
+            return axis;
+        };
+
+        // Disabled zones and ranged zones keep their configured bounds
+        if axis.positive_low < f32::MAX && axis.positive_high == f32::MAX {
+            axis.positive_low = threshold;
+        }
+        if axis.negative_low > f32::MIN && axis.negative_high == f32::MIN {
+            axis.negative_low = -threshold;
+        }
+        axis
+    }
+
     fn extract_dual_axis_data(&self, dual_axis: &DualAxis) -> DualAxisData {
         let x = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.x)));
         let y = self.input_value(&UserInput::Single(InputKind::SingleAxis(dual_axis.y)));
+        let x_axis = self.thresholded_axis(&dual_axis.x);
+        let y_axis = self.thresholded_axis(&dual_axis.y);
 
-        if x > dual_axis.x.positive_low
-            || x < dual_axis.x.negative_low
-            || y > dual_axis.y.positive_low
-            || y < dual_axis.y.negative_low
+        if x > x_axis.positive_low
+            || x < x_axis.negative_low
+            || y > y_axis.positive_low
+            || y < y_axis.negative_low
         {
             DualAxisData::new(x, y)
         } else {
@@ -512,7 +707,12 @@ impl<'a> From<MutableInputStreams<'a>> for InputStreams<'a> {
             mouse_buttons: Some(mutable_streams.mouse_buttons),
             mouse_wheel: Some(mutable_streams.mouse_wheel),
             mouse_motion: mutable_streams.mouse_motion,
+            cursor_position: None,
+            button_thresholds: None,
             associated_gamepad: mutable_streams.associated_gamepad,
+            window_events: None,
+            custom_inputs: None,
+            stick_history: None,
         }
     }
 }
@@ -529,7 +729,12 @@ impl<'a> From<&'a MutableInputStreams<'a>> for InputStreams<'a> {
             mouse_buttons: Some(mutable_streams.mouse_buttons),
             mouse_wheel: Some(mutable_streams.mouse_wheel),
             mouse_motion: mutable_streams.mouse_motion,
+            cursor_position: None,
+            button_thresholds: None,
             associated_gamepad: mutable_streams.associated_gamepad,
+            window_events: None,
+            custom_inputs: None,
+            stick_history: None,
         }
     }
 }
@@ -568,4 +773,85 @@ mod tests {
         let input_streams = MutableInputStreams::from_world(&mut app.world, None);
         assert!(input_streams.pressed(Modifier::Control));
     }
+
+    #[test]
+    fn cursor_position_of_primary_window() {
+        use super::InputStreams;
+        use bevy::window::PrimaryWindow;
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let mut secondary_window = Window::default();
+        secondary_window.set_cursor_position(Some(Vec2::new(1.0, 2.0)));
+        app.world.spawn(secondary_window);
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert_eq!(input_streams.cursor_position, None);
+
+        let mut primary_window = Window::default();
+        primary_window.set_cursor_position(Some(Vec2::new(3.0, 4.0)));
+        app.world.spawn((primary_window, PrimaryWindow));
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert_eq!(input_streams.cursor_position, Some(Vec2::new(3.0, 4.0)));
+    }
+
+    #[test]
+    fn expressions() {
+        use super::InputStreams;
+        use crate::user_input::UserInput;
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        // (Shift AND Click) OR Enter
+        let input = UserInput::any([
+            UserInput::all([KeyCode::ShiftLeft.into(), MouseButton::Left.into()]),
+            KeyCode::Return.into(),
+        ]);
+
+        app.send_input(KeyCode::ShiftLeft);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(!input_streams.input_pressed(&input));
+        assert_eq!(input_streams.input_value(&input), 0.0);
+
+        app.send_input(MouseButton::Left);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(input_streams.input_pressed(&input));
+        assert_eq!(input_streams.input_value(&input), 1.0);
+
+        app.reset_inputs();
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(!input_streams.input_pressed(&input));
+
+        app.send_input(KeyCode::Return);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(input_streams.input_pressed(&input));
+    }
+
+    #[test]
+    fn unless() {
+        use super::InputStreams;
+        use crate::user_input::UserInput;
+
+        let mut app = App::new();
+        app.add_plugins(InputPlugin);
+
+        let input = UserInput::from(MouseButton::Left).unless(KeyCode::ShiftLeft);
+
+        app.send_input(MouseButton::Left);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(input_streams.input_pressed(&input));
+        assert_eq!(input_streams.input_value(&input), 1.0);
+
+        app.send_input(KeyCode::ShiftLeft);
+        app.update();
+        let input_streams = InputStreams::from_world(&app.world, None);
+        assert!(!input_streams.input_pressed(&input));
+        assert_eq!(input_streams.input_value(&input), 0.0);
+    }
 }
@@ -0,0 +1,204 @@
+//! Keeping the `just_*` flags of an [`ActionState`] set for longer than a single tick
+//!
+//! By default, [`ActionState::just_pressed`] and [`ActionState::just_released`] are only true
+//! until the next time the [`ActionState`] is ticked, which is usually once per frame.
+//! Systems that run less often, such as AI that only thinks every few frames, can miss these transient presses entirely.
+//!
+//! Add a [`PersistentJustFlags`] resource or component alongside an [`ActionState`], configured with a [`JustPersistence`], to keep the flags set for longer.
+//! This affects every reader of the [`ActionState`], so consider using a dedicated [`ActionState`] for low-frequency systems.
+//! The [`tick_action_state`](crate::systems::tick_action_state) system ticks them together, using [`PersistentJustFlags::tick`].
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use bevy::utils::Instant;
+//! use leafwing_input_manager::just_persistence::{JustPersistence, PersistentJustFlags};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Attack,
+//! }
+//!
+//! let mut action_state = ActionState::<Action>::default();
+//! let mut just_flags = PersistentJustFlags::<Action>::new(JustPersistence::Ticks(5));
+//!
+//! action_state.press(Action::Attack);
+//! for _ in 0..4 {
+//!     just_flags.tick(&mut action_state, Instant::now(), Instant::now());
+//! }
+//! assert!(action_state.just_pressed(Action::Attack));
+//!
+//! just_flags.tick(&mut action_state, Instant::now(), Instant::now());
+//! assert!(!action_state.just_pressed(Action::Attack));
+//! assert!(action_state.pressed(Action::Attack));
+//! ```
+
+use crate::action_state::ActionState;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// How long the `just_*` flags of an [`ActionState`] stay set after an action is pressed or released
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum JustPersistence {
+    /// The flags are cleared by the n-th tick after the transition
+    ///
+    /// `Ticks(1)` matches the default behavior, and `Ticks(0)` is treated as `Ticks(1)`.
+    Ticks(u32),
+    /// The flags are cleared by the first tick at which at least this much time has passed since the transition
+    ///
+    /// The flags are always cleared by the second tick after the transition, even if this is [`Duration::ZERO`].
+    Duration(Duration),
+}
+
+impl JustPersistence {
+    /// Should the `just_*` flag be cleared, once the transition is `age` old?
+    fn expired(&self, age: &JustAge) -> bool {
+        match *self {
+            JustPersistence::Ticks(ticks) => age.ticks >= ticks.max(1),
+            JustPersistence::Duration(duration) => age.ticks >= 1 && age.elapsed >= duration,
+        }
+    }
+}
+
+/// How long ago an action was last pressed or released
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct JustAge {
+    /// The `just_*` state that is kept set
+    state: ButtonState,
+    /// The number of ticks since the transition
+    ticks: u32,
+    /// The time elapsed across those ticks
+    elapsed: Duration,
+}
+
+/// Keeps the `just_*` flags of an [`ActionState`] of type `A` set for as long as its [`JustPersistence`]
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] whose flags it keeps.
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct PersistentJustFlags<A: Actionlike> {
+    /// How long the `just_*` flags stay set
+    persistence: JustPersistence,
+    /// The age of the `just_*` flag that is kept set for each action, indexed by [`Actionlike::index`]
+    ages: Vec<Option<JustAge>>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for PersistentJustFlags<A> {
+    /// Keeps the flags for a single tick, matching the default behavior
+    fn default() -> Self {
+        Self::new(JustPersistence::Ticks(1))
+    }
+}
+
+impl<A: Actionlike> PersistentJustFlags<A> {
+    /// Keeps the `just_*` flags set for as long as the `persistence`
+    #[must_use]
+    pub fn new(persistence: JustPersistence) -> Self {
+        Self {
+            persistence,
+            ages: vec![None; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+
+    /// How long the `just_*` flags stay set
+    #[must_use]
+    pub fn persistence(&self) -> JustPersistence {
+        self.persistence
+    }
+
+    /// Sets how long the `just_*` flags stay set
+    ///
+    /// Flags that are already set are kept according to the new `persistence`.
+    pub fn set_persistence(&mut self, persistence: JustPersistence) {
+        self.persistence = persistence;
+    }
+
+    /// [Ticks](ActionState::tick) the `action_state`, only clearing the `just_*` flags whose persistence has expired
+    ///
+    /// The [`ActionTimings`](crate::timing::ActionTimings) of the `action_state`, if any, should be ticked beforehand with the same instants.
+    pub fn tick(
+        &mut self,
+        action_state: &mut ActionState<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        let delta = current_instant.saturating_duration_since(previous_instant);
+
+        for (index, action) in A::variants().enumerate() {
+            let state = action_state.action_data(action).state;
+            if !state.just_pressed() && !state.just_released() {
+                self.ages[index] = None;
+                continue;
+            }
+
+            // Flags that differ from the one that was kept are new transitions
+            let mut age = match self.ages[index] {
+                Some(age) if age.state == state => age,
+                _ => JustAge {
+                    state,
+                    ticks: 0,
+                    elapsed: Duration::ZERO,
+                },
+            };
+            age.ticks = age.ticks.saturating_add(1);
+            age.elapsed += delta;
+            self.ages[index] = (!self.persistence.expired(&age)).then_some(age);
+        }
+
+        action_state.tick(current_instant, previous_instant);
+
+        for (index, action) in A::variants().enumerate() {
+            if let Some(age) = self.ages[index] {
+                action_state.action_data_mut(action).state = age.state;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Attack,
+    }
+
+    #[test]
+    fn duration_persistence() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut just_flags =
+            PersistentJustFlags::new(JustPersistence::Duration(Duration::from_millis(50)));
+
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+        action_state.press(Action::Attack);
+        just_flags.tick(&mut action_state, t0 + ms(30), t0);
+        assert!(action_state.just_pressed(Action::Attack));
+        just_flags.tick(&mut action_state, t0 + ms(60), t0 + ms(30));
+        assert!(!action_state.just_pressed(Action::Attack));
+
+        // Releasing restarts the persistence
+        action_state.release(Action::Attack);
+        just_flags.tick(&mut action_state, t0 + ms(90), t0 + ms(60));
+        assert!(action_state.just_released(Action::Attack));
+        just_flags.tick(&mut action_state, t0 + ms(120), t0 + ms(90));
+        assert!(!action_state.just_released(Action::Attack));
+
+        // Consumed actions stop being just released too, even though their timing is frozen
+        action_state.press(Action::Attack);
+        action_state.consume(Action::Attack);
+        just_flags.tick(&mut action_state, t0 + ms(200), t0 + ms(120));
+        assert!(!action_state.just_released(Action::Attack));
+    }
+}
@@ -0,0 +1,72 @@
+//! Guarantees about operating system key repeats, and an opt-in mode that surfaces them
+//!
+//! While a key is held, most operating systems send repeated key press events after a short delay.
+//! These repeats never re-trigger [`ActionState::just_pressed`]:
+//! an action is only just pressed on the frame that it transitions from released to pressed.
+//!
+//! Menu navigation and text editing often want to respond to these repeats, matching the feel of the platform.
+//! Add a [`KeyRepeats`] resource or component alongside an [`ActionState`] and its [`InputMap`](crate::input_map::InputMap),
+//! and the [`surface_key_repeats`](crate::systems::surface_key_repeats) system will count the repeat events
+//! of the keys bound to each pressed action. Check them with [`KeyRepeats::just_repeated`]
+//! or [`KeyRepeats::just_pressed_or_repeated`].
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::marker::PhantomData;
+
+/// The number of operating system key repeats received for each action of type `A` during the current frame
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] whose keys it counts.
+#[derive(Resource, Component, Debug, Clone, PartialEq, Eq)]
+pub struct KeyRepeats<A: Actionlike> {
+    /// The repeats of each action, indexed by [`Actionlike::index`]
+    repeats: Vec<u32>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for KeyRepeats<A> {
+    fn default() -> Self {
+        KeyRepeats {
+            repeats: vec![0; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> KeyRepeats<A> {
+    /// The number of operating system key repeats received for the `action` during the current frame
+    #[must_use]
+    pub fn repeats(&self, action: A) -> u32 {
+        self.repeats[action.index()]
+    }
+
+    /// Was an operating system key repeat received for the `action` during the current frame?
+    #[must_use]
+    pub fn just_repeated(&self, action: A) -> bool {
+        self.repeats(action) > 0
+    }
+
+    /// Was the `action` just pressed in the `action_state`, or was a key repeat received for it during the current frame?
+    ///
+    /// This is the typical check for menu navigation.
+    /// Check [`AutoRepeats::just_repeated`](crate::auto_repeat::AutoRepeats::just_repeated) as well to include [auto repeats](crate::auto_repeat).
+    #[must_use]
+    pub fn just_pressed_or_repeated(&self, action_state: &ActionState<A>, action: A) -> bool {
+        action_state.just_pressed(action.clone()) || self.just_repeated(action)
+    }
+
+    /// Records the number of operating system key repeats received for the `action` during the current frame
+    ///
+    /// These are cleared the next time the [`surface_key_repeats`](crate::systems::surface_key_repeats) system runs.
+    pub fn set(&mut self, action: A, repeats: u32) {
+        self.repeats[action.index()] = repeats;
+    }
+
+    /// Clears the key repeats of every action
+    pub fn clear(&mut self) {
+        self.repeats.fill(0);
+    }
+}
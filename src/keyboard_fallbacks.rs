@@ -0,0 +1,340 @@
+//! Generating keyboard bindings from the gamepad bindings of an [`InputMap`]
+//!
+//! Prototypes are often built around a gamepad, leaving keyboard players unable to test them.
+//! A [`KeyboardFallbacks`] table declares which key stands in for each gamepad button and stick,
+//! and [`InputMap::insert_keyboard_fallbacks`] uses it to bind a keyboard equivalent of every gamepad binding.
+//!
+//! The [default](KeyboardFallbacks::default) table maps the face buttons to `Space`, `Escape`, `R` and `F`,
+//! the left stick to `WASD` and the right stick to the arrow keys.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::keyboard_fallbacks::KeyboardFallbacks;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Move,
+//!     Jump,
+//!     Interact,
+//! }
+//!
+//! let mut input_map = InputMap::new([
+//!     (UserInput::from(DualAxis::left_stick()), Action::Move),
+//!     (GamepadButtonType::South.into(), Action::Jump),
+//!     (GamepadButtonType::West.into(), Action::Interact),
+//! ]);
+//!
+//! let fallbacks = KeyboardFallbacks::default().with_button(GamepadButtonType::West, KeyCode::E);
+//! input_map.insert_keyboard_fallbacks(&fallbacks);
+//!
+//! assert!(input_map.get(Action::Move).contains(&VirtualDPad::wasd().into()));
+//! assert!(input_map.get(Action::Jump).contains(&KeyCode::Space.into()));
+//! assert!(input_map.get(Action::Interact).contains(&KeyCode::E.into()));
+//! ```
+
+use crate::axislike::{AxisType, DualAxis, SingleAxis, VirtualAxis, VirtualDPad};
+use crate::input_map::{InputMap, MAX_BINDINGS_PER_ACTION};
+use crate::user_input::{InputKind, UserInput};
+use crate::Actionlike;
+
+use bevy::input::gamepad::{GamepadAxisType, GamepadButtonType};
+use bevy::input::keyboard::KeyCode;
+use bevy::utils::HashMap;
+
+/// A declarative table of the keyboard inputs that stand in for each gamepad button and stick axis
+///
+/// Each stick axis is replaced by a [`VirtualAxis`], whose `negative` and `positive` inputs stand in for each direction of the axis.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyboardFallbacks {
+    buttons: HashMap<GamepadButtonType, InputKind>,
+    axes: HashMap<GamepadAxisType, VirtualAxis>,
+}
+
+impl Default for KeyboardFallbacks {
+    /// The conventional keyboard equivalents of a gamepad
+    ///
+    /// | Gamepad                      | Keyboard              |
+    /// |------------------------------|-----------------------|
+    /// | South, East, West, North     | Space, Escape, R, F   |
+    /// | Left and right bumpers       | Q, E                  |
+    /// | Left and right triggers      | Left Shift, Left Ctrl |
+    /// | Left and right stick clicks  | C, V                  |
+    /// | Start, Select                | Enter, Tab            |
+    /// | `DPad` up, right, down, left | 1, 2, 3, 4            |
+    /// | Left stick                   | WASD                  |
+    /// | Right stick                  | Arrow keys            |
+    fn default() -> Self {
+        let wasd = VirtualDPad::wasd();
+
+        KeyboardFallbacks::empty()
+            .with_button(GamepadButtonType::South, KeyCode::Space)
+            .with_button(GamepadButtonType::East, KeyCode::Escape)
+            .with_button(GamepadButtonType::West, KeyCode::R)
+            .with_button(GamepadButtonType::North, KeyCode::F)
+            .with_button(GamepadButtonType::LeftTrigger, KeyCode::Q)
+            .with_button(GamepadButtonType::RightTrigger, KeyCode::E)
+            .with_button(GamepadButtonType::LeftTrigger2, KeyCode::ShiftLeft)
+            .with_button(GamepadButtonType::RightTrigger2, KeyCode::ControlLeft)
+            .with_button(GamepadButtonType::LeftThumb, KeyCode::C)
+            .with_button(GamepadButtonType::RightThumb, KeyCode::V)
+            .with_button(GamepadButtonType::Start, KeyCode::Return)
+            .with_button(GamepadButtonType::Select, KeyCode::Tab)
+            .with_button(GamepadButtonType::DPadUp, KeyCode::Key1)
+            .with_button(GamepadButtonType::DPadRight, KeyCode::Key2)
+            .with_button(GamepadButtonType::DPadDown, KeyCode::Key3)
+            .with_button(GamepadButtonType::DPadLeft, KeyCode::Key4)
+            .with_axis(
+                GamepadAxisType::LeftStickX,
+                VirtualAxis {
+                    negative: wasd.left,
+                    positive: wasd.right,
+                },
+            )
+            .with_axis(
+                GamepadAxisType::LeftStickY,
+                VirtualAxis {
+                    negative: wasd.down,
+                    positive: wasd.up,
+                },
+            )
+            .with_axis(
+                GamepadAxisType::RightStickX,
+                VirtualAxis::horizontal_arrow_keys(),
+            )
+            .with_axis(
+                GamepadAxisType::RightStickY,
+                VirtualAxis::vertical_arrow_keys(),
+            )
+    }
+}
+
+impl KeyboardFallbacks {
+    /// Creates a table without any fallbacks
+    #[must_use]
+    pub fn empty() -> Self {
+        KeyboardFallbacks {
+            buttons: HashMap::default(),
+            axes: HashMap::default(),
+        }
+    }
+
+    /// Returns this table, with the `button` replaced by the `key`
+    #[must_use]
+    pub fn with_button(mut self, button: GamepadButtonType, key: impl Into<InputKind>) -> Self {
+        self.buttons.insert(button, key.into());
+        self
+    }
+
+    /// Returns this table, with the two directions of the stick `axis` replaced by the `keys`
+    #[must_use]
+    pub fn with_axis(mut self, axis: GamepadAxisType, keys: VirtualAxis) -> Self {
+        self.axes.insert(axis, keys);
+        self
+    }
+
+    /// Returns this table, without a fallback for the `button`
+    #[must_use]
+    pub fn without_button(mut self, button: GamepadButtonType) -> Self {
+        self.buttons.remove(&button);
+        self
+    }
+
+    /// Returns this table, without a fallback for the stick `axis`
+    #[must_use]
+    pub fn without_axis(mut self, axis: GamepadAxisType) -> Self {
+        self.axes.remove(&axis);
+        self
+    }
+
+    /// The keyboard equivalent of the gamepad `input`
+    ///
+    /// Returns [`None`] if any part of the `input` is not a gamepad input with a fallback in this table,
+    /// so inputs that are already bound to other devices are never converted.
+    #[must_use]
+    pub fn fallback(&self, input: &UserInput) -> Option<UserInput> {
+        let mut fallback = match input {
+            UserInput::Single(InputKind::SingleAxis(axis)) => self.single_axis(axis)?,
+            UserInput::Single(InputKind::DualAxis(dual_axis)) => {
+                UserInput::VirtualDPad(self.dual_axis(dual_axis)?)
+            }
+            UserInput::Single(kind) => UserInput::Single(self.kind(kind)?),
+            UserInput::Chord(chord) => UserInput::chord(
+                chord
+                    .iter()
+                    .map(|kind| self.kind(kind))
+                    .collect::<Option<Vec<_>>>()?,
+            ),
+            UserInput::VirtualDPad(dpad) => UserInput::VirtualDPad(VirtualDPad {
+                up: self.kind(&dpad.up)?,
+                down: self.kind(&dpad.down)?,
+                left: self.kind(&dpad.left)?,
+                right: self.kind(&dpad.right)?,
+            }),
+            UserInput::VirtualAxis(axis) => UserInput::VirtualAxis(VirtualAxis {
+                negative: self.kind(&axis.negative)?,
+                positive: self.kind(&axis.positive)?,
+            }),
+            UserInput::All(inputs) => UserInput::All(self.fallbacks(inputs)?),
+            UserInput::Any(inputs) => UserInput::Any(self.fallbacks(inputs)?),
+            UserInput::Unless { input, blocker } => {
+                self.fallback(input)?.unless(self.fallback(blocker)?)
+            }
+            UserInput::WindowEvent(_) | UserInput::Custom(_) => return None,
+        };
+        fallback.canonicalize();
+        Some(fallback)
+    }
+
+    /// The keyboard equivalents of each of the `inputs`, if they all have one
+    fn fallbacks(&self, inputs: &[UserInput]) -> Option<Vec<UserInput>> {
+        inputs.iter().map(|input| self.fallback(input)).collect()
+    }
+
+    /// The keyboard equivalent of a gamepad button, or of one direction of a stick axis
+    fn kind(&self, kind: &InputKind) -> Option<InputKind> {
+        match kind {
+            InputKind::GamepadButton(button) => self.buttons.get(button).copied(),
+            InputKind::SingleAxis(axis) => match self.single_axis(axis)? {
+                UserInput::Single(kind) => Some(kind),
+                _ => None,
+            },
+            _ => None,
+        }
+    }
+
+    /// The keys that stand in for the stick axis of the `axis`, or for the one direction that triggers it
+    fn single_axis(&self, axis: &SingleAxis) -> Option<UserInput> {
+        let AxisType::Gamepad(axis_type) = axis.axis_type else {
+            return None;
+        };
+        let keys = self.axes.get(&axis_type)?;
+        // Half-axes, such as those created by `SingleAxis::positive_only`, only stand in for one direction
+        if axis.positive_low == f32::MAX {
+            Some(UserInput::Single(keys.negative))
+        } else if axis.negative_low == f32::MIN {
+            Some(UserInput::Single(keys.positive))
+        } else if axis.inverted {
+            Some(UserInput::VirtualAxis(keys.clone().inverted()))
+        } else {
+            Some(UserInput::VirtualAxis(keys.clone()))
+        }
+    }
+
+    /// The keys that stand in for the two stick axes of the `dual_axis`
+    fn dual_axis(&self, dual_axis: &DualAxis) -> Option<VirtualDPad> {
+        let axis_keys = |axis: &SingleAxis| {
+            let AxisType::Gamepad(axis_type) = axis.axis_type else {
+                return None;
+            };
+            let keys = self.axes.get(&axis_type)?.clone();
+            Some(if axis.inverted { keys.inverted() } else { keys })
+        };
+        let x = axis_keys(&dual_axis.x)?;
+        let y = axis_keys(&dual_axis.y)?;
+
+        Some(VirtualDPad {
+            up: y.positive,
+            down: y.negative,
+            left: x.negative,
+            right: x.positive,
+        })
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Binds the keyboard equivalent of each gamepad binding in this map, as described by the `fallbacks`
+    ///
+    /// Fallbacks that are already bound to any action are skipped, so that existing keyboard bindings are never doubled up,
+    /// as are fallbacks for actions that already have [`MAX_BINDINGS_PER_ACTION`] bindings.
+    pub fn insert_keyboard_fallbacks(&mut self, fallbacks: &KeyboardFallbacks) -> &mut Self {
+        let generated: Vec<(UserInput, A)> = self
+            .iter()
+            .filter_map(|(action, _, input)| Some((fallbacks.fallback(input)?, action)))
+            .collect();
+
+        for (fallback, action) in generated {
+            if self.actions_for(&fallback).next().is_some()
+                || self.get(action.clone()).len() >= MAX_BINDINGS_PER_ACTION
+            {
+                continue;
+            }
+            self.insert(fallback, action);
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::{MouseButton, Reflect};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Aim,
+        Throttle,
+        Sprint,
+        Menu,
+        Shoot,
+    }
+
+    #[test]
+    fn fallbacks_follow_the_table() {
+        let mut input_map = InputMap::new([
+            (UserInput::from(GamepadButtonType::South), Action::Jump),
+            (DualAxis::right_stick().into(), Action::Aim),
+            (
+                SingleAxis::symmetric(GamepadAxisType::LeftStickY, 0.1).into(),
+                Action::Throttle,
+            ),
+            (
+                UserInput::chord([GamepadButtonType::LeftThumb, GamepadButtonType::South]),
+                Action::Sprint,
+            ),
+            (GamepadButtonType::Start.into(), Action::Menu),
+            (KeyCode::Return.into(), Action::Menu),
+            (MouseButton::Left.into(), Action::Shoot),
+            (GamepadButtonType::Mode.into(), Action::Shoot),
+        ]);
+        input_map.insert_keyboard_fallbacks(&KeyboardFallbacks::default());
+
+        assert!(input_map.get(Action::Jump).contains(&KeyCode::Space.into()));
+        assert!(input_map
+            .get(Action::Aim)
+            .contains(&VirtualDPad::arrow_keys().into()));
+        let wasd = VirtualDPad::wasd();
+        assert!(input_map.get(Action::Throttle).contains(
+            &VirtualAxis {
+                negative: wasd.down,
+                positive: wasd.up,
+            }
+            .into()
+        ));
+        assert!(input_map
+            .get(Action::Sprint)
+            .contains(&UserInput::chord([KeyCode::C, KeyCode::Space])));
+
+        // Existing keyboard bindings are not doubled up, and inputs without a fallback are left alone
+        assert_eq!(input_map.get(Action::Menu).len(), 2);
+        assert_eq!(input_map.get(Action::Shoot).len(), 2);
+    }
+
+    #[test]
+    fn half_axes_fall_back_to_a_single_key() {
+        let fallbacks = KeyboardFallbacks::default();
+        let (positive, negative) = SingleAxis::split(GamepadAxisType::RightStickX, 0.1, 0.1);
+
+        assert_eq!(
+            fallbacks.fallback(&positive.into()),
+            Some(KeyCode::Right.into())
+        );
+        assert_eq!(
+            fallbacks.fallback(&negative.into()),
+            Some(KeyCode::Left.into())
+        );
+        assert_eq!(KeyboardFallbacks::empty().fallback(&positive.into()), None);
+    }
+}
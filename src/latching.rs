@@ -0,0 +1,175 @@
+//! Latched actions, whose presses stay `just_pressed` until they are consumed
+//!
+//! One-shot commands, such as opening a menu or using an item, are often handled by systems that don't run every frame,
+//! such as those in [`FixedUpdate`](bevy::app::FixedUpdate) or behind a run condition.
+//! These systems can miss presses that are cleared by the next [`ActionState::tick`], even with a [`JustPersistence`](crate::just_persistence::JustPersistence).
+//!
+//! Add a [`LatchedActions`] resource or component alongside an [`ActionState`] to mark some of its actions as latched.
+//! Once pressed, a latched action reports [`ActionState::just_pressed`] until [`ActionState::consume`] or [`ActionState::reset`] is called,
+//! even if it has been released in the meantime.
+//! Each press is only latched once, no matter how many times it is pressed before being consumed.
+//!
+//! Presses are latched by the [`latch_presses`](crate::systems::latch_presses) system, right before the [`ActionState`] is ticked.
+//! The latched press of each action is stored in its [`ActionData::latched`](crate::action_state::ActionData::latched).
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use bevy::utils::Instant;
+//! use leafwing_input_manager::latching::LatchedActions;
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     OpenMap,
+//! }
+//!
+//! let mut action_state = ActionState::<Action>::default();
+//! let latched_actions = LatchedActions::new([Action::OpenMap]);
+//!
+//! // A quick tap, that is over before the system that opens the map runs
+//! action_state.press(Action::OpenMap);
+//! latched_actions.update(&mut action_state);
+//! action_state.tick(Instant::now(), Instant::now());
+//! action_state.release(Action::OpenMap);
+//! latched_actions.update(&mut action_state);
+//! action_state.tick(Instant::now(), Instant::now());
+//!
+//! assert!(action_state.just_pressed(Action::OpenMap));
+//! action_state.consume(Action::OpenMap);
+//! assert!(!action_state.just_pressed(Action::OpenMap));
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::marker::PhantomData;
+
+impl<A: Actionlike> ActionState<A> {
+    /// Was the `action` pressed since the last tick, ignoring latched presses?
+    ///
+    /// Systems that react to each press, rather than to the press being pending, use this instead of [`ActionState::just_pressed`].
+    pub(crate) fn newly_pressed(&self, action: A) -> bool {
+        self.action_data(action).state.just_pressed()
+    }
+}
+
+/// The actions of type `A` whose presses stay `just_pressed` until they are consumed
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it latches.
+#[derive(Resource, Component, Debug, Clone, PartialEq, Eq)]
+pub struct LatchedActions<A: Actionlike> {
+    /// Whether each action is latched, indexed by [`Actionlike::index`]
+    latched: Vec<bool>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for LatchedActions<A> {
+    fn default() -> Self {
+        LatchedActions {
+            latched: vec![false; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> LatchedActions<A> {
+    /// Latches the provided `actions`
+    #[must_use]
+    pub fn new(actions: impl IntoIterator<Item = A>) -> Self {
+        let mut latched_actions = Self::default();
+        for action in actions {
+            latched_actions.add(action);
+        }
+        latched_actions
+    }
+
+    /// Latches the presses of the `action`
+    pub fn add(&mut self, action: A) -> &mut Self {
+        self.latched[action.index()] = true;
+        self
+    }
+
+    /// Stops latching the presses of the `action`
+    ///
+    /// Any press that has not yet been consumed is cleared by the next [`LatchedActions::update`].
+    pub fn remove(&mut self, action: A) -> &mut Self {
+        self.latched[action.index()] = false;
+        self
+    }
+
+    /// Do presses of the `action` stay `just_pressed` until it is consumed?
+    #[must_use]
+    pub fn latches(&self, action: A) -> bool {
+        self.latched[action.index()]
+    }
+
+    /// Latches the actions of the `action_state` that were pressed since the last tick,
+    /// and clears the latched presses of actions that are no longer latched
+    ///
+    /// This should be called before each [`ActionState::tick`], so that no press is missed.
+    pub fn update(&self, action_state: &mut ActionState<A>) {
+        for action in A::variants() {
+            let latched = if self.latches(action.clone()) {
+                action_state.action_data(action.clone()).latched
+                    || action_state.newly_pressed(action.clone())
+            } else {
+                false
+            };
+            action_state.action_data_mut(action).latched = latched;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionState;
+    use crate::latching::LatchedActions;
+    use crate::Actionlike;
+    use bevy::prelude::Reflect;
+    use bevy::utils::Instant;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Interact,
+        Jump,
+    }
+
+    #[test]
+    fn latched_presses_survive_ticks() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut latched_actions = LatchedActions::new([Action::Interact]);
+        assert!(latched_actions.latches(Action::Interact));
+        assert!(!latched_actions.latches(Action::Jump));
+
+        let tick = |action_state: &mut ActionState<Action>,
+                    latched_actions: &LatchedActions<Action>| {
+            latched_actions.update(action_state);
+            action_state.tick(Instant::now(), Instant::now());
+        };
+
+        action_state.press(Action::Interact);
+        action_state.press(Action::Jump);
+        for _ in 0..3 {
+            tick(&mut action_state, &latched_actions);
+        }
+        assert!(action_state.just_pressed(Action::Interact));
+        assert!(!action_state.just_pressed(Action::Jump));
+
+        // Consuming the latched press clears it
+        action_state.consume(Action::Interact);
+        tick(&mut action_state, &latched_actions);
+        assert!(!action_state.just_pressed(Action::Interact));
+
+        // Unlatching the action clears pending presses
+        action_state.release(Action::Interact);
+        action_state.press(Action::Interact);
+        tick(&mut action_state, &latched_actions);
+        assert!(action_state.just_pressed(Action::Interact));
+        latched_actions.remove(Action::Interact);
+        latched_actions.update(&mut action_state);
+        assert!(!action_state.just_pressed(Action::Interact));
+    }
+}
@@ -3,49 +3,123 @@
 #![warn(clippy::doc_markdown)]
 #![doc = include_str!("../README.md")]
 
+use crate::action_metadata::ActionInfo;
 use crate::action_state::ActionState;
 use crate::input_map::InputMap;
 use bevy::ecs::prelude::*;
 use bevy::reflect::TypePath;
 use std::marker::PhantomData;
 
+pub mod action_hooks;
+pub mod action_metadata;
 pub mod action_state;
+#[cfg(feature = "stats")]
+pub mod action_stats;
+pub mod action_writer;
+pub mod authority;
+pub mod auto_repeat;
 pub mod axislike;
+#[cfg(feature = "bench")]
+pub mod bench_utils;
+pub mod binding_capture;
+pub mod binding_conflicts;
+pub mod binding_overrides;
+pub mod binding_profiles;
+pub mod block_input;
 pub mod buttonlike;
 pub mod clashing_inputs;
 pub mod common_conditions;
+pub mod compact_action_state;
+#[cfg(feature = "asset")]
+pub mod control_scheme;
+pub mod custom_input;
+pub mod diff_sequencing;
+pub mod diff_transport;
 mod display_impl;
+pub mod drag;
 pub mod dynamic_action;
 pub mod errors;
+pub mod exclusive_groups;
+pub mod ghosting;
+pub mod hold_assist;
+pub mod hold_for;
+pub mod idle;
+pub mod ime;
+pub mod input_capture;
 pub mod input_map;
 pub mod input_mocking;
+#[cfg(feature = "ui")]
+pub mod input_overlay;
+pub mod input_prompt;
 pub mod input_streams;
+pub mod just_persistence;
+pub mod key_repeat;
+pub mod keyboard_fallbacks;
+pub mod latching;
+pub mod localization;
+pub mod migration;
+pub mod mirror;
+pub mod missing_components;
+pub mod navigation_deadband;
 pub mod orientation;
 pub mod plugin;
 pub mod press_scheduler;
+pub mod radial_menu;
+pub mod rate_limits;
+#[cfg(feature = "ui")]
+pub mod rebinding_menu;
+pub mod replay;
 pub mod scan_codes;
+pub mod secret_sequences;
+mod serde_helpers;
+pub mod stable_id;
+pub mod state_transitions;
+pub mod stick_gestures;
 pub mod systems;
+pub mod testing;
+pub mod timing;
+pub mod timing_forgiveness;
+#[cfg(feature = "ui")]
+pub mod ui_navigation;
 pub mod user_input;
+pub mod value_thresholds;
+pub mod virtual_cursor;
+pub mod virtual_input;
+pub mod window_events;
 
 // Importing the derive macro
 pub use leafwing_input_manager_macros::Actionlike;
 
 /// Everything you need to get started
+///
+/// The prelude is deliberately small: the types used by most games to define, bind and read actions.
+/// More specialized types, such as input processors or networking utilities, are available from their modules,
+/// such as [`axislike`], [`buttonlike`], [`user_input`], [`clashing_inputs`] and [`timing`].
 pub mod prelude {
-    pub use crate::action_state::{ActionState, ActionStateDriver};
-    pub use crate::axislike::{DualAxis, MouseWheelAxisType, SingleAxis, VirtualDPad};
-    pub use crate::buttonlike::MouseWheelDirection;
+    pub use crate::action_state::{ActionState, ActionStateDriver, DriverTrigger};
+    pub use crate::axislike::{
+        DeadZoneShape, DualAxis, DualAxisData, MouseDrag, MouseWheelAxisType, SingleAxis,
+        VirtualDPad,
+    };
+    pub use crate::buttonlike::{ButtonState, MouseWheelDirection};
     pub use crate::clashing_inputs::ClashStrategy;
     pub use crate::input_map::InputMap;
     pub use crate::input_mocking::MockInput;
     pub use crate::scan_codes::QwertyScanCode;
-    pub use crate::user_input::{Modifier, UserInput};
+    pub use crate::timing::ActionTimings;
+    pub use crate::user_input::{InputKind, Modifier, UserInput};
 
-    pub use crate::plugin::InputManagerPlugin;
-    pub use crate::plugin::ToggleActions;
+    pub use crate::plugin::{InputManagerPlugin, InputManagerSystem, ToggleActions};
     pub use crate::{Actionlike, InputManagerBundle};
 }
 
+/// The largest number of variants that an [`Actionlike`] type can have
+///
+/// Networking features such as [`compact_action_state`] encode the index of each action as a `u16`.
+/// Deriving [`Actionlike`] on an enum with more variants fails to compile,
+/// and manual implementations can be checked with [`InputManagerError::check_action_count`](crate::errors::InputManagerError::check_action_count).
+pub const MAX_ACTIONS: usize = u16::MAX as usize + 1;
+
 /// Allows a type to be used as a gameplay action in an input-agnostic fashion
 ///
 /// Actions are modelled as "virtual buttons", cleanly abstracting over messy, customizable inputs
@@ -94,6 +168,13 @@ pub trait Actionlike: Send + Sync + Clone + TypePath + 'static {
 
     /// Returns the position in the defining enum of the given action
     fn index(&self) -> usize;
+
+    /// Returns the human-readable [`ActionInfo`] of the given action
+    ///
+    /// When deriving [`Actionlike`], this is populated from the `#[actionlike(...)]` attribute on each variant,
+    /// with the name defaulting to the name of the variant.
+    /// See the [`action_metadata`] module for more details.
+    fn info(&self) -> ActionInfo;
 }
 
 /// An iterator of [`Actionlike`] actions
@@ -129,7 +210,7 @@ impl<A: Actionlike> Default for ActionIter<A> {
     fn default() -> Self {
         ActionIter {
             index: 0,
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
         }
     }
 }
@@ -0,0 +1,234 @@
+//! Hooks for localizing the names of actions and inputs
+//!
+//! By default, inputs are displayed using their English [`Debug`] representation,
+//! and actions are displayed using the name stored in their [`ActionInfo`].
+//! Implement [`LocalizedNames`] to replace these strings,
+//! typically by looking them up in a Fluent bundle or another i18n backend.
+
+use crate::action_metadata::{ActionInfo, ActionMetadata};
+use crate::axislike::{VirtualAxis, VirtualDPad};
+use crate::input_map::InputMap;
+use crate::user_input::{InputKind, UserInput};
+use crate::Actionlike;
+
+/// Provides localized display strings for actions and inputs
+///
+/// Each method returns [`None`] by default, which falls back to the default English representation.
+/// This allows implementors to only localize the strings that they care about.
+///
+/// # Example
+/// ```rust
+/// use bevy::input::keyboard::KeyCode;
+/// use leafwing_input_manager::localization::LocalizedNames;
+/// use leafwing_input_manager::user_input::{InputKind, UserInput};
+///
+/// struct French;
+///
+/// impl LocalizedNames for French {
+///     fn input_name(&self, input: &InputKind) -> Option<String> {
+///         match input {
+///             InputKind::Keyboard(KeyCode::Space) => Some("Espace".to_string()),
+///             InputKind::Keyboard(KeyCode::ShiftLeft) => Some("Maj gauche".to_string()),
+///             _ => None,
+///         }
+///     }
+/// }
+///
+/// let input = UserInput::chord([KeyCode::ShiftLeft, KeyCode::Space]);
+/// assert_eq!(input.localized_name(&French), "Maj gauche+Espace");
+/// ```
+pub trait LocalizedNames {
+    /// Returns the localized name of the action described by `info`
+    ///
+    /// [`ActionInfo::name`] is a convenient key for looking up translations.
+    fn action_name(&self, _info: &ActionInfo) -> Option<String> {
+        None
+    }
+
+    /// Returns the localized name of a single `input`
+    fn input_name(&self, _input: &InputKind) -> Option<String> {
+        None
+    }
+
+    /// Returns the localized name of a complete `input`
+    ///
+    /// Override this to control how chords and virtual inputs are combined.
+    /// When [`None`] is returned, the name is built from the [`input_name`](LocalizedNames::input_name) of each part.
+    fn user_input_name(&self, _input: &UserInput) -> Option<String> {
+        None
+    }
+
+    /// The string placed between the parts of a [`UserInput::Chord`]
+    fn chord_separator(&self) -> &str {
+        "+"
+    }
+}
+
+/// The default, unlocalized names of actions and inputs
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DefaultNames;
+
+impl LocalizedNames for DefaultNames {}
+
+impl InputKind {
+    /// Returns the name of this input, as localized by `names`
+    #[must_use]
+    pub fn localized_name(&self, names: &(impl LocalizedNames + ?Sized)) -> String {
+        names.input_name(self).unwrap_or_else(|| self.to_string())
+    }
+}
+
+impl UserInput {
+    /// Returns the name of this input, as localized by `names`
+    ///
+    /// The parts of virtual inputs are separated by `/`, listed in the order up, left, down, right for a [`VirtualDPad`]
+    /// and negative, positive for a [`VirtualAxis`].
+    #[must_use]
+    pub fn localized_name(&self, names: &(impl LocalizedNames + ?Sized)) -> String {
+        if let Some(name) = names.user_input_name(self) {
+            return name;
+        }
+
+        let join = |inputs: &[&InputKind], separator: &str| -> String {
+            inputs
+                .iter()
+                .map(|input| input.localized_name(names))
+                .collect::<Vec<_>>()
+                .join(separator)
+        };
+
+        match self {
+            UserInput::Single(input) => input.localized_name(names),
+            UserInput::Chord(inputs) => {
+                join(&inputs.iter().collect::<Vec<_>>(), names.chord_separator())
+            }
+            UserInput::VirtualDPad(VirtualDPad {
+                up,
+                down,
+                left,
+                right,
+            }) => join(&[up, left, down, right], "/"),
+            UserInput::VirtualAxis(VirtualAxis { negative, positive }) => {
+                join(&[negative, positive], "/")
+            }
+            UserInput::WindowEvent(event) => event.to_string(),
+            UserInput::Custom(_) => self.to_string(),
+            UserInput::All(inputs) => join_expression(inputs, " AND ", names),
+            UserInput::Any(inputs) => join_expression(inputs, " OR ", names),
+            UserInput::Unless { input, blocker } => {
+                join_expression([&**input, &**blocker], " UNLESS ", names)
+            }
+        }
+    }
+}
+
+/// Joins the localized names of the `inputs` of an expression, with nested expressions in parentheses
+fn join_expression<'a>(
+    inputs: impl IntoIterator<Item = &'a UserInput>,
+    operator: &str,
+    names: &(impl LocalizedNames + ?Sized),
+) -> String {
+    inputs
+        .into_iter()
+        .map(|input| match input {
+            UserInput::All(_) | UserInput::Any(_) | UserInput::Unless { .. } => {
+                format!("({})", input.localized_name(names))
+            }
+            _ => input.localized_name(names),
+        })
+        .collect::<Vec<_>>()
+        .join(operator)
+}
+
+impl<A: Actionlike> ActionMetadata<A> {
+    /// Returns the display name of the `action`, as localized by `names`
+    #[must_use]
+    pub fn localized_name(&self, action: A, names: &(impl LocalizedNames + ?Sized)) -> String {
+        let info = self.get(action);
+        names.action_name(info).unwrap_or_else(|| info.name.clone())
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Returns the names of the inputs bound to the `action`, as localized by `names`
+    #[must_use]
+    pub fn localized_names(
+        &self,
+        action: A,
+        names: &(impl LocalizedNames + ?Sized),
+    ) -> Vec<String> {
+        self.get(action)
+            .iter()
+            .map(|input| input.localized_name(names))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Action {
+        Jump,
+        #[actionlike(name = "Move")]
+        Run,
+    }
+
+    struct German;
+
+    impl LocalizedNames for German {
+        fn action_name(&self, info: &ActionInfo) -> Option<String> {
+            match info.name.as_str() {
+                "Jump" => Some("Springen".to_string()),
+                _ => None,
+            }
+        }
+
+        fn input_name(&self, input: &InputKind) -> Option<String> {
+            match input {
+                InputKind::Keyboard(KeyCode::Space) => Some("Leertaste".to_string()),
+                InputKind::Keyboard(KeyCode::Up) => Some("Hoch".to_string()),
+                _ => None,
+            }
+        }
+
+        fn chord_separator(&self) -> &str {
+            " + "
+        }
+    }
+
+    #[test]
+    fn action_names() {
+        let metadata = ActionMetadata::<Action>::default();
+
+        assert_eq!(metadata.localized_name(Action::Jump, &German), "Springen");
+        // Untranslated names fall back to the name in the metadata
+        assert_eq!(metadata.localized_name(Action::Run, &German), "Move");
+        assert_eq!(metadata.localized_name(Action::Jump, &DefaultNames), "Jump");
+    }
+
+    #[test]
+    fn input_names() {
+        let mut input_map = InputMap::<Action>::default();
+        input_map.insert(KeyCode::Space, Action::Jump);
+        input_map.insert_chord([KeyCode::ControlLeft, KeyCode::Space], Action::Jump);
+        input_map.insert(VirtualDPad::arrow_keys(), Action::Run);
+
+        assert_eq!(
+            input_map.localized_names(Action::Jump, &German),
+            ["Leertaste", "ControlLeft + Leertaste"]
+        );
+        assert_eq!(
+            input_map.localized_names(Action::Run, &German),
+            ["Hoch/Left/Down/Right"]
+        );
+        assert_eq!(
+            input_map.localized_names(Action::Jump, &DefaultNames),
+            ["Space", "ControlLeft+Space"]
+        );
+    }
+}
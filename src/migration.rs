@@ -0,0 +1,603 @@
+//! Tools for loading serialized [`InputMap`]s that were saved by older versions of your game
+//!
+//! Over time, actions get renamed or removed, which would cause old binding files to fail to deserialize.
+//! To avoid this, save your bindings using [`InputMap::versioned`], which tags them with a version number.
+//! When loading, deserialize a [`VersionedInputMap`], and convert it using [`InputMapMigrations::migrate`].
+//! The migration closures registered for each version are applied in order,
+//! and a [`MigrationReport`] describes which entries were remapped or dropped.
+//! Per-action settings, such as aggregation policies, are migrated along with the bindings, and the other settings of the map are kept as they are.
+//!
+//! # Example
+//! ```rust
+//! use bevy::prelude::{KeyCode, Reflect};
+//! use leafwing_input_manager::migration::{ActionMigration, InputMapMigrations, VersionedInputMap};
+//! use leafwing_input_manager::prelude::*;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Actionlike, Serialize, Deserialize, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+//! enum Action {
+//!     Sprint,
+//!     Jump,
+//! }
+//!
+//! // Version 1 of the game called `Sprint` "Run", and had a `Crouch` action that has since been removed
+//! let mut migrations = InputMapMigrations::<Action>::new(2);
+//! migrations.add_migration(1, |name| match name {
+//!     "Run" => ActionMigration::Rename("Sprint".to_string()),
+//!     "Crouch" => ActionMigration::Remove,
+//!     _ => ActionMigration::Keep,
+//! });
+//!
+//! let mut old_bindings = VersionedInputMap {
+//!     version: 1,
+//!     ..Default::default()
+//! };
+//! old_bindings.map.insert("Run".to_string().into(), vec![KeyCode::ShiftLeft.into()]);
+//! old_bindings.map.insert("Crouch".to_string().into(), vec![KeyCode::C.into()]);
+//! old_bindings.map.insert("Jump".to_string().into(), vec![KeyCode::Space.into()]);
+//!
+//! let (input_map, report) = migrations.migrate(old_bindings);
+//!
+//! assert_eq!(input_map, InputMap::new([(KeyCode::ShiftLeft, Action::Sprint), (KeyCode::Space, Action::Jump)]));
+//! assert_eq!(report.remapped, [("Run".to_string(), "Sprint".to_string())]);
+//! assert_eq!(report.dropped, ["Crouch".to_string()]);
+//! ```
+
+use crate::axislike::{AxisPairProcessor, SensitivityGroups};
+use crate::buttonlike::ButtonThresholds;
+use crate::errors::{self, InputManagerError};
+use crate::input_map::{AggregationPolicy, InputMap, InputMapData, InputMapRef, SourcePriority};
+use crate::user_input::UserInput;
+use crate::Actionlike;
+
+use bevy::input::gamepad::Gamepad;
+use bevy::utils::Duration;
+
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::{DeserializeOwned, Deserializer, Visitor};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt::Formatter;
+use std::hash::Hash;
+
+/// An [`InputMap`] tagged with the version of the game that saved it, for use when serializing
+///
+/// Created using [`InputMap::versioned`]. Deserialize the result as a [`VersionedInputMap`].
+#[derive(Debug)]
+pub struct VersionedInputMapRef<'a, A: Actionlike> {
+    /// The version of the game that saved this map
+    pub version: u32,
+    /// The input map to serialize
+    pub input_map: &'a InputMap<A>,
+}
+
+impl<'a, A> Serialize for VersionedInputMapRef<'a, A>
+where
+    A: Actionlike + Serialize + Eq + Hash + Ord,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeStruct;
+
+        let input_map = InputMapRef::from(self.input_map);
+        let mut versioned = serializer.serialize_struct("VersionedInputMap", 10)?;
+        versioned.serialize_field("version", &self.version)?;
+        versioned.serialize_field(
+            "map",
+            &self.input_map.iter().fold(
+                BTreeMap::<A, Vec<&UserInput>>::new(),
+                |mut map, (action, _, input)| {
+                    map.entry(action).or_default().push(input);
+                    map
+                },
+            ),
+        )?;
+        versioned.serialize_field("associated_gamepad", &input_map.associated_gamepad)?;
+        versioned.serialize_field("button_thresholds", input_map.button_thresholds)?;
+        versioned.serialize_field("sensitivity_groups", input_map.sensitivity_groups)?;
+        versioned.serialize_field("axis_pair_processors", &input_map.axis_pair_processors)?;
+        versioned.serialize_field("aggregation_policies", &input_map.aggregation_policies)?;
+        versioned.serialize_field("source_priorities", &input_map.source_priorities)?;
+        versioned.serialize_field("device_hot_swap", &input_map.device_hot_swap)?;
+        versioned.serialize_field("record_cursor_position", &input_map.record_cursor_position)?;
+        versioned.end()
+    }
+}
+
+impl<A: Actionlike> InputMap<A> {
+    /// Tags this [`InputMap`] with a `version` number, so that it can be migrated when loaded by later versions
+    ///
+    /// See the [`migration`](crate::migration) module for more details.
+    #[must_use]
+    pub fn versioned(&self, version: u32) -> VersionedInputMapRef<'_, A> {
+        VersionedInputMapRef {
+            version,
+            input_map: self,
+        }
+    }
+}
+
+/// A serialized [`InputMap`] whose actions are stored by name, so that it can be loaded even when the actions have changed
+///
+/// Convert this into an [`InputMap`] using [`InputMapMigrations::migrate`].
+/// The per-action settings are migrated along with the bindings, and every other setting is carried over unchanged.
+/// Every field but the `version` and the `map` is optional, so that maps saved before these settings were serialized can still be read.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize)]
+pub struct VersionedInputMap {
+    /// The version of the game that saved this map
+    pub version: u32,
+    /// The inputs bound to each action, keyed by the serialized name of the action
+    pub map: BTreeMap<ActionName, Vec<UserInput>>,
+    /// The gamepad that the map reads from, see [`InputMap::set_gamepad`]
+    #[serde(default)]
+    pub associated_gamepad: Option<Gamepad>,
+    /// The press points of gamepad buttons and axes, see [`InputMap::set_button_threshold`]
+    #[serde(default)]
+    pub button_thresholds: ButtonThresholds,
+    /// The sensitivity of each group of bindings, see [`InputMap::set_sensitivity`]
+    #[serde(default)]
+    pub sensitivity_groups: SensitivityGroups,
+    /// The processors applied to the axis pair of each binding, see [`InputMap::set_axis_pair_processors`]
+    #[serde(default)]
+    pub axis_pair_processors: Vec<(UserInput, Vec<AxisPairProcessor>)>,
+    /// How the bindings of each action are combined, keyed by the serialized name of the action
+    #[serde(default)]
+    pub aggregation_policies: BTreeMap<ActionName, AggregationPolicy>,
+    /// The preferred sources of each action, keyed by the serialized name of the action
+    #[serde(default)]
+    pub source_priorities: BTreeMap<ActionName, SourcePriority>,
+    /// How long presses carry over between devices, see [`InputMap::set_device_hot_swap`]
+    #[serde(default)]
+    pub device_hot_swap: Option<Duration>,
+    /// Do mouse presses record the cursor position, see [`InputMap::set_record_cursor_position`]
+    #[serde(default)]
+    pub record_cursor_position: bool,
+}
+
+/// The serialized name of an action, as stored in a [`VersionedInputMap`]
+///
+/// This accepts both strings and identifiers, so that unit enum variants can be read from any self-describing format.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ActionName(pub String);
+
+impl From<String> for ActionName {
+    fn from(name: String) -> Self {
+        ActionName(name)
+    }
+}
+
+impl<'de> Deserialize<'de> for ActionName {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct ActionNameVisitor;
+
+        impl<'de> Visitor<'de> for ActionNameVisitor {
+            type Value = ActionName;
+
+            fn expecting(&self, formatter: &mut Formatter) -> std::fmt::Result {
+                write!(formatter, "the name of an action")
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(ActionName(value.to_string()))
+            }
+        }
+
+        deserializer.deserialize_identifier(ActionNameVisitor)
+    }
+}
+
+/// What should happen to an action found in an older [`VersionedInputMap`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionMigration {
+    /// The action is unchanged
+    Keep,
+    /// The action was renamed to the provided name
+    Rename(String),
+    /// The action was removed, and its bindings should be dropped
+    Remove,
+}
+
+/// Describes the changes made by [`InputMapMigrations::migrate`]
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MigrationReport {
+    /// The version of the map before it was migrated
+    pub from_version: u32,
+    /// The actions that were renamed, stored as `(old_name, new_name)` pairs
+    pub remapped: Vec<(String, String)>,
+    /// The actions whose bindings were dropped
+    ///
+    /// This includes actions that were explicitly removed, as well as names that do not match any current action.
+    pub dropped: Vec<String>,
+}
+
+impl MigrationReport {
+    /// Were any entries remapped or dropped?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.remapped.is_empty() && self.dropped.is_empty()
+    }
+}
+
+type MigrationFn = Box<dyn Fn(&str) -> ActionMigration + Send + Sync>;
+
+/// The migrations needed to load a [`VersionedInputMap`] saved by any older version of your game
+///
+/// Each migration upgrades maps from a single version to the next one.
+#[derive(bevy::ecs::system::Resource)]
+pub struct InputMapMigrations<A: Actionlike> {
+    current_version: u32,
+    migrations: BTreeMap<u32, MigrationFn>,
+    _phantom: std::marker::PhantomData<A>,
+}
+
+impl<A: Actionlike> std::fmt::Debug for InputMapMigrations<A> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("InputMapMigrations")
+            .field("current_version", &self.current_version)
+            .field("migrations", &self.migrations.keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
+
+impl<A: Actionlike + DeserializeOwned + Eq + Hash> InputMapMigrations<A> {
+    /// Creates a new set of migrations, targeting the `current_version` of your bindings format
+    #[must_use]
+    pub fn new(current_version: u32) -> Self {
+        InputMapMigrations {
+            current_version,
+            migrations: BTreeMap::new(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// The version that maps are migrated to
+    #[must_use]
+    pub fn current_version(&self) -> u32 {
+        self.current_version
+    }
+
+    /// Registers a `migration` that upgrades maps saved with `from_version` to the following version
+    ///
+    /// The `migration` is called with the name of each action stored in the map.
+    /// Registering a second migration for the same version replaces the first one.
+    pub fn add_migration(
+        &mut self,
+        from_version: u32,
+        migration: impl Fn(&str) -> ActionMigration + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.migrations.insert(from_version, Box::new(migration));
+        self
+    }
+
+    /// Converts the `versioned` map into an [`InputMap`], applying every migration from its version onwards
+    ///
+    /// Maps saved by a version newer than [`current_version`](Self::current_version) are loaded without migration.
+    pub fn migrate(&self, versioned: VersionedInputMap) -> (InputMap<A>, MigrationReport) {
+        let mut report = MigrationReport {
+            from_version: versioned.version,
+            ..Default::default()
+        };
+
+        // Each name is migrated once, even if it is used by several per-action settings
+        let mut actions = BTreeMap::<String, Option<A>>::new();
+        let names = versioned
+            .map
+            .keys()
+            .chain(versioned.aggregation_policies.keys())
+            .chain(versioned.source_priorities.keys());
+        for ActionName(name) in names {
+            if !actions.contains_key(name) {
+                let action = self.migrate_action(versioned.version, name, &mut report);
+                actions.insert(name.clone(), action);
+            }
+        }
+
+        fn by_action<A: Actionlike + Eq + Hash, T>(
+            entries: BTreeMap<ActionName, T>,
+            actions: &BTreeMap<String, Option<A>>,
+        ) -> HashMap<A, T> {
+            entries
+                .into_iter()
+                .filter_map(|(ActionName(name), value)| Some((actions[&name].clone()?, value)))
+                .collect()
+        }
+
+        let input_map = InputMap::from(InputMapData {
+            map: by_action(versioned.map, &actions),
+            associated_gamepad: versioned.associated_gamepad,
+            button_thresholds: versioned.button_thresholds,
+            sensitivity_groups: versioned.sensitivity_groups,
+            axis_pair_processors: versioned.axis_pair_processors,
+            aggregation_policies: by_action(versioned.aggregation_policies, &actions),
+            source_priorities: by_action(versioned.source_priorities, &actions),
+            device_hot_swap: versioned.device_hot_swap,
+            record_cursor_position: versioned.record_cursor_position,
+        });
+
+        (input_map, report)
+    }
+
+    /// Applies every migration from `version` onwards to the action called `original_name`
+    ///
+    /// Returns the current action, if it still exists, and records any change in the `report`.
+    fn migrate_action(
+        &self,
+        version: u32,
+        original_name: &str,
+        report: &mut MigrationReport,
+    ) -> Option<A> {
+        let mut name = Some(original_name.to_string());
+
+        for (_, migration) in self.migrations.range(version..self.current_version) {
+            let Some(current_name) = &name else {
+                break;
+            };
+
+            match migration(current_name) {
+                ActionMigration::Keep => (),
+                ActionMigration::Rename(new_name) => name = Some(new_name),
+                ActionMigration::Remove => name = None,
+            }
+        }
+
+        let action = name.as_deref().and_then(|name| {
+            let action = A::deserialize(StrDeserializer::<ValueError>::new(name)).ok();
+            if action.is_none() {
+                errors::report(InputManagerError::UnknownAction {
+                    name: name.to_string(),
+                });
+            }
+            action
+        });
+
+        match (action, name) {
+            (Some(action), Some(name)) => {
+                if name != original_name {
+                    report.remapped.push((original_name.to_string(), name));
+                }
+                Some(action)
+            }
+            _ => {
+                report.dropped.push(original_name.to_string());
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::Reflect;
+    use serde_test::{assert_de_tokens, assert_ser_tokens, Token};
+
+    #[derive(
+        Actionlike,
+        Serialize,
+        Deserialize,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash,
+        Debug,
+        Reflect,
+    )]
+    enum Action {
+        Attack,
+        Block,
+    }
+
+    fn migrations() -> InputMapMigrations<Action> {
+        let mut migrations = InputMapMigrations::new(3);
+        migrations
+            .add_migration(1, |name| match name {
+                "Hit" => ActionMigration::Rename("Strike".to_string()),
+                "Dodge" => ActionMigration::Remove,
+                _ => ActionMigration::Keep,
+            })
+            .add_migration(2, |name| match name {
+                "Strike" => ActionMigration::Rename("Attack".to_string()),
+                _ => ActionMigration::Keep,
+            });
+        migrations
+    }
+
+    fn versioned(version: u32, entries: &[(&str, KeyCode)]) -> VersionedInputMap {
+        VersionedInputMap {
+            version,
+            map: entries
+                .iter()
+                .map(|(name, key)| (ActionName(name.to_string()), vec![(*key).into()]))
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn migrations_are_chained() {
+        let (input_map, report) = migrations().migrate(versioned(
+            1,
+            &[
+                ("Hit", KeyCode::J),
+                ("Dodge", KeyCode::K),
+                ("Block", KeyCode::L),
+                ("Unknown", KeyCode::U),
+            ],
+        ));
+
+        assert_eq!(
+            input_map,
+            InputMap::new([(KeyCode::J, Action::Attack), (KeyCode::L, Action::Block)])
+        );
+        assert_eq!(
+            report,
+            MigrationReport {
+                from_version: 1,
+                remapped: vec![("Hit".to_string(), "Attack".to_string())],
+                dropped: vec!["Dodge".to_string(), "Unknown".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn only_later_migrations_apply() {
+        // A map saved by version 2 already uses the name `Strike`, and never had `Hit`
+        let (input_map, report) =
+            migrations().migrate(versioned(2, &[("Strike", KeyCode::J), ("Hit", KeyCode::H)]));
+
+        assert_eq!(input_map, InputMap::new([(KeyCode::J, Action::Attack)]));
+        assert_eq!(
+            report.remapped,
+            [("Strike".to_string(), "Attack".to_string())]
+        );
+        assert_eq!(report.dropped, ["Hit".to_string()]);
+
+        let (_, report) = migrations().migrate(versioned(3, &[("Attack", KeyCode::J)]));
+        assert!(report.is_empty());
+    }
+
+    #[test]
+    fn settings_are_migrated() {
+        use crate::input_map::{AggregationPolicy, InputSource, SourcePriority};
+        use bevy::input::gamepad::GamepadButtonType;
+        use bevy::utils::Duration;
+
+        let mut old_bindings = versioned(1, &[("Hit", KeyCode::J)]);
+        old_bindings.aggregation_policies.insert(
+            ActionName("Hit".to_string()),
+            AggregationPolicy::MaxMagnitude,
+        );
+        old_bindings.aggregation_policies.insert(
+            ActionName("Dodge".to_string()),
+            AggregationPolicy::SumClamped,
+        );
+        old_bindings.source_priorities.insert(
+            ActionName("Block".to_string()),
+            SourcePriority::new([InputSource::Gamepad]),
+        );
+        old_bindings
+            .button_thresholds
+            .set_button(GamepadButtonType::RightTrigger2, 0.25);
+        old_bindings.device_hot_swap = Some(Duration::from_millis(100));
+
+        let (input_map, report) = migrations().migrate(old_bindings);
+
+        assert_eq!(
+            input_map.aggregation_policy(Action::Attack),
+            AggregationPolicy::MaxMagnitude
+        );
+        assert_eq!(
+            input_map.source_priority(Action::Block),
+            Some(&SourcePriority::new([InputSource::Gamepad]))
+        );
+        assert_eq!(
+            input_map
+                .button_thresholds()
+                .button(GamepadButtonType::RightTrigger2),
+            Some(0.25)
+        );
+        assert_eq!(
+            input_map.device_hot_swap(),
+            Some(Duration::from_millis(100))
+        );
+        // Each name is only reported once, even when it is used by several settings
+        assert_eq!(
+            report,
+            MigrationReport {
+                from_version: 1,
+                remapped: vec![("Hit".to_string(), "Attack".to_string())],
+                dropped: vec!["Dodge".to_string()],
+            }
+        );
+    }
+
+    #[test]
+    fn serde() {
+        let input_map = InputMap::new([(KeyCode::J, Action::Attack)]);
+        let tokens = [
+            Token::Struct {
+                name: "VersionedInputMap",
+                len: 10,
+            },
+            Token::Str("version"),
+            Token::U32(3),
+            Token::Str("map"),
+            Token::Map { len: Some(1) },
+            Token::UnitVariant {
+                name: "Action",
+                variant: "Attack",
+            },
+            Token::Seq { len: Some(1) },
+            Token::NewtypeVariant {
+                name: "UserInput",
+                variant: "Single",
+            },
+            Token::NewtypeVariant {
+                name: "InputKind",
+                variant: "Keyboard",
+            },
+            Token::UnitVariant {
+                name: "KeyCode",
+                variant: "J",
+            },
+            Token::SeqEnd,
+            Token::MapEnd,
+            Token::Str("associated_gamepad"),
+            Token::None,
+            Token::Str("button_thresholds"),
+            Token::Struct {
+                name: "ButtonThresholds",
+                len: 2,
+            },
+            Token::Str("buttons"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("axes"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::Str("sensitivity_groups"),
+            Token::Struct {
+                name: "SensitivityGroups",
+                len: 2,
+            },
+            Token::Str("members"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("sensitivities"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::StructEnd,
+            Token::Str("axis_pair_processors"),
+            Token::Seq { len: Some(0) },
+            Token::SeqEnd,
+            Token::Str("aggregation_policies"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("source_priorities"),
+            Token::Map { len: Some(0) },
+            Token::MapEnd,
+            Token::Str("device_hot_swap"),
+            Token::None,
+            Token::Str("record_cursor_position"),
+            Token::Bool(false),
+            Token::StructEnd,
+        ];
+        assert_ser_tokens(&input_map.versioned(3), &tokens);
+
+        // Keys are read back by name
+        let mut de_tokens = tokens;
+        de_tokens[5] = Token::Str("Attack");
+        assert_de_tokens(&versioned(3, &[("Attack", KeyCode::J)]), &de_tokens);
+    }
+}
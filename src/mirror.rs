@@ -0,0 +1,81 @@
+//! Mirroring the live [`ActionState`](crate::action_state::ActionState) of one entity onto another
+//!
+//! Spectator cameras, kill-cams and input overlays often need to display what another player is doing,
+//! without reading any physical inputs themselves.
+//! Add a [`MirrorActionState`] alongside an [`ActionState`](crate::action_state::ActionState) on the spectating entity,
+//! and the [`mirror_action_states`](crate::systems::mirror_action_states) system will copy the state of the `source` entity into it each frame.
+//!
+//! The spectating entity should not have an [`InputMap`](crate::input_map::InputMap), or its own inputs will be mixed in.
+
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity};
+
+/// Copies the [`ActionState`](crate::action_state::ActionState) of the `source` entity into the [`ActionState`](crate::action_state::ActionState) of this entity
+///
+/// Mirroring preserves the full [`ActionData`](crate::action_state::ActionData) of each action, including values and axis pairs.
+/// Timing information is copied too, but only if both entities have an [`ActionTimings`](crate::timing::ActionTimings).
+/// If the `source` entity no longer exists or has no [`ActionState`](crate::action_state::ActionState), the mirrored state is left unchanged,
+/// and an [`InputManagerError`](crate::errors::InputManagerError) is [reported](crate::errors).
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::mirror::MirrorActionState;
+/// use leafwing_input_manager::prelude::*;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Jump,
+///     Chat,
+/// }
+///
+/// let mut world = World::new();
+/// let player = world.spawn(InputManagerBundle::<Action>::default()).id();
+///
+/// // Spectators see the player's movement, but not their chat inputs
+/// world.spawn((
+///     ActionState::<Action>::default(),
+///     MirrorActionState::new(player).with_actions([Action::Move, Action::Jump]),
+/// ));
+/// ```
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct MirrorActionState<A: Actionlike> {
+    /// The entity whose [`ActionState`](crate::action_state::ActionState) is mirrored
+    pub source: Entity,
+    /// The actions that are mirrored, or [`None`] to mirror every action
+    pub actions: Option<Vec<A>>,
+}
+
+impl<A: Actionlike> MirrorActionState<A> {
+    /// Mirrors every action of the `source` entity
+    #[must_use]
+    pub fn new(source: Entity) -> Self {
+        Self {
+            source,
+            actions: None,
+        }
+    }
+
+    /// Only mirrors the provided `actions`
+    #[must_use]
+    pub fn with_actions(mut self, actions: impl IntoIterator<Item = A>) -> Self {
+        self.actions = Some(actions.into_iter().collect());
+        self
+    }
+
+    /// Is the `action` mirrored?
+    #[must_use]
+    pub fn mirrors(&self, action: &A) -> bool {
+        match &self.actions {
+            Some(actions) => actions.iter().any(|a| a.index() == action.index()),
+            None => true,
+        }
+    }
+
+    /// Iterates over the actions that are mirrored
+    pub fn mirrored_actions(&self) -> impl Iterator<Item = A> + '_ {
+        A::variants().filter(|action| self.mirrors(action))
+    }
+}
@@ -0,0 +1,114 @@
+//! Detecting entities that have only one of the [`InputMap`](crate::input_map::InputMap) and [`ActionState`](crate::action_state::ActionState) components
+//!
+//! An [`InputMap`](crate::input_map::InputMap) does nothing without an [`ActionState`](crate::action_state::ActionState) to write to, which is a common source of inputs that silently don't work.
+//! Whenever one of these components is added to an entity that lacks the other,
+//! the [`check_missing_components`](crate::systems::check_missing_components) system applies a [`MissingComponentPolicy`],
+//! configured on the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) for each direction.
+//!
+//! By default, entities with an [`InputMap`](crate::input_map::InputMap) are given a default [`ActionState`](crate::action_state::ActionState),
+//! before the [`ActionState`](crate::action_state::ActionState)s are updated during the same frame,
+//! so spawning an [`InputMap`](crate::input_map::InputMap) on its own is enough.
+//! Entities with an [`ActionState`](crate::action_state::ActionState) but no [`InputMap`](crate::input_map::InputMap) are ignored by default,
+//! as they are commonly controlled by an [`ActionStateDriver`](crate::action_state::ActionStateDriver) or over the network.
+//!
+//! The [`MissingComponentPolicies::diff_target`] policy is applied by [`process_action_diffs`](crate::systems::process_action_diffs)
+//! and [`process_registered_action_diffs`](crate::systems::process_registered_action_diffs)
+//! when an [`ActionDiff`](crate::action_state::ActionDiff) arrives for an entity without an [`ActionState`](crate::action_state::ActionState).
+//! Diffs for these entities are dropped by default, but [`MissingComponentPolicy::Insert`] gives them a default [`ActionState`](crate::action_state::ActionState)
+//! with the diff applied, so entities that join late don't need to be set up before their inputs are replicated.
+
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Entity, Event, Resource};
+use std::marker::PhantomData;
+
+/// What to do with an entity that is missing one of the [`InputMap`](crate::input_map::InputMap) and [`ActionState`](crate::action_state::ActionState) components
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MissingComponentPolicy {
+    /// Do nothing
+    Ignore,
+    /// Log a warning and send a [`MissingComponent`] event
+    #[default]
+    Warn,
+    /// Insert the default value of the missing component
+    Insert,
+}
+
+/// Which component an entity is missing
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MissingComponentKind {
+    /// The entity has an [`InputMap`](crate::input_map::InputMap), but no [`ActionState`](crate::action_state::ActionState)
+    ActionState,
+    /// The entity has an [`ActionState`](crate::action_state::ActionState), but no [`InputMap`](crate::input_map::InputMap)
+    InputMap,
+}
+
+/// Sent when an [`InputMap`](crate::input_map::InputMap) or [`ActionState`](crate::action_state::ActionState) is added to an entity that lacks the other, if the policy is [`MissingComponentPolicy::Warn`]
+///
+/// These events are generated by the [`check_missing_components`](crate::systems::check_missing_components) system.
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct MissingComponent<A: Actionlike> {
+    /// The entity that is missing a component
+    pub entity: Entity,
+    /// The component that is missing
+    pub missing: MissingComponentKind,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> MissingComponent<A> {
+    /// Creates an event for the `entity`, which is `missing` a component
+    #[must_use]
+    pub fn new(entity: Entity, missing: MissingComponentKind) -> Self {
+        Self {
+            entity,
+            missing,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+/// The [`MissingComponentPolicy`]s applied to entities with components for the action type `A`
+///
+/// This resource is inserted by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin),
+/// and can be changed at runtime.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct MissingComponentPolicies<A: Actionlike> {
+    /// Applied to entities with an [`InputMap`](crate::input_map::InputMap) but no [`ActionState`](crate::action_state::ActionState)
+    pub action_state: MissingComponentPolicy,
+    /// Applied to entities with an [`ActionState`](crate::action_state::ActionState) but no [`InputMap`](crate::input_map::InputMap)
+    pub input_map: MissingComponentPolicy,
+    /// Applied to entities without an [`ActionState`](crate::action_state::ActionState) that an [`ActionDiff`](crate::action_state::ActionDiff) is received for
+    pub diff_target: MissingComponentPolicy,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> MissingComponentPolicies<A> {
+    /// Creates the policies applied when the `action_state` or `input_map` is missing
+    ///
+    /// [`ActionDiff`](crate::action_state::ActionDiff)s for entities without an [`ActionState`](crate::action_state::ActionState) are ignored.
+    #[must_use]
+    pub fn new(action_state: MissingComponentPolicy, input_map: MissingComponentPolicy) -> Self {
+        Self {
+            action_state,
+            input_map,
+            diff_target: MissingComponentPolicy::Ignore,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Sets the policy applied to entities without an [`ActionState`](crate::action_state::ActionState) that an [`ActionDiff`](crate::action_state::ActionDiff) is received for
+    #[must_use]
+    pub fn with_diff_target(mut self, diff_target: MissingComponentPolicy) -> Self {
+        self.diff_target = diff_target;
+        self
+    }
+}
+
+impl<A: Actionlike> Default for MissingComponentPolicies<A> {
+    fn default() -> Self {
+        Self::new(
+            MissingComponentPolicy::Insert,
+            MissingComponentPolicy::Ignore,
+        )
+    }
+}
@@ -0,0 +1,232 @@
+//! Deadbands for actions bound to analog sticks, so that menus navigated with a stick don't scroll uncontrollably
+//!
+//! An action bound to a stick axis is pressed whenever the axis crosses its threshold.
+//! A stick resting near that threshold, or drifting back through it as it is released, flickers between pressed and released,
+//! and each flicker is a new [`ActionState::just_pressed`] that moves the selection.
+//!
+//! A [`NavigationDeadband`] adds hysteresis: the action is only pressed once the input reaches the press threshold,
+//! and stays pressed until it falls below the lower release threshold.
+//! Configure it per action in a [`NavigationDeadbands`] resource or component alongside the [`ActionState`] and its [`InputMap`](crate::input_map::InputMap),
+//! which is applied to the inputs of each action before they update the [`ActionState`]. Combine it with an [`AutoRepeat`] in the [`AutoRepeats`](crate::auto_repeat::AutoRepeats) of the action,
+//! so that holding the stick repeats after an initial delay at a steady rate, just like a held d-pad button.
+//!
+//! The magnitude of the input is the length of the action's axis pair if it has one, and the absolute value of the action otherwise.
+//! Buttons have a value of `1.0` while pressed, so actions bound to both a stick and buttons keep working as usual.
+//!
+//! ```rust
+//! use bevy::prelude::Reflect;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::auto_repeat::{AutoRepeat, AutoRepeats};
+//! use leafwing_input_manager::navigation_deadband::{NavigationDeadband, NavigationDeadbands};
+//! use leafwing_input_manager::prelude::*;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     Up,
+//!     Down,
+//! }
+//!
+//! let deadband = NavigationDeadband::new(0.6, 0.3);
+//! let repeat = AutoRepeat::new(Duration::from_millis(400), Duration::from_millis(120));
+//!
+//! let mut navigation_deadbands = NavigationDeadbands::<MenuAction>::default();
+//! let mut auto_repeats = AutoRepeats::<MenuAction>::default();
+//! for action in [MenuAction::Up, MenuAction::Down] {
+//!     navigation_deadbands.set(action, Some(deadband));
+//!     auto_repeats.set(action, Some(repeat));
+//! }
+//! ```
+
+use crate::action_state::{ActionData, ActionState};
+#[cfg(doc)]
+use crate::auto_repeat::AutoRepeat;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// The magnitudes at which an analog action is pressed and released
+///
+/// The action is pressed once its magnitude reaches the `press_threshold`,
+/// and released once its magnitude falls below the `release_threshold`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct NavigationDeadband {
+    press_threshold: f32,
+    release_threshold: f32,
+}
+
+impl NavigationDeadband {
+    /// Presses the action at the `press_threshold`, and releases it below the `release_threshold`
+    ///
+    /// Both thresholds are raised to `0.0` if they are negative, and the `release_threshold` can not be higher than the `press_threshold`.
+    #[must_use]
+    pub fn new(press_threshold: f32, release_threshold: f32) -> Self {
+        let press_threshold = press_threshold.max(0.0);
+
+        Self {
+            press_threshold,
+            release_threshold: release_threshold.clamp(0.0, press_threshold),
+        }
+    }
+
+    /// The magnitude that a released action must reach to be pressed
+    #[must_use]
+    pub fn press_threshold(&self) -> f32 {
+        self.press_threshold
+    }
+
+    /// The magnitude that a pressed action must fall below to be released
+    #[must_use]
+    pub fn release_threshold(&self) -> f32 {
+        self.release_threshold
+    }
+
+    /// Should an action with this `magnitude` be pressed, given whether it `was_pressed`?
+    #[must_use]
+    pub fn pressed(&self, magnitude: f32, was_pressed: bool) -> bool {
+        if was_pressed {
+            magnitude >= self.release_threshold && magnitude > 0.0
+        } else {
+            magnitude >= self.press_threshold && magnitude > 0.0
+        }
+    }
+}
+
+impl Default for NavigationDeadband {
+    /// Presses at half of the stick's range, and releases at a quarter of it
+    fn default() -> Self {
+        Self::new(0.5, 0.25)
+    }
+}
+
+/// The [`NavigationDeadband`] applied to the inputs of each action of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] whose inputs it filters.
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct NavigationDeadbands<A: Actionlike> {
+    /// The deadband of each action, indexed by [`Actionlike::index`]
+    deadbands: Vec<Option<NavigationDeadband>>,
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for NavigationDeadbands<A> {
+    fn default() -> Self {
+        NavigationDeadbands {
+            deadbands: vec![None; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> NavigationDeadbands<A> {
+    /// Sets the deadband applied to the inputs of the `action`, or removes it if [`None`]
+    pub fn set(&mut self, action: A, deadband: Option<NavigationDeadband>) -> &mut Self {
+        self.deadbands[action.index()] = deadband;
+        self
+    }
+
+    /// The deadband applied to the inputs of the `action`, if any
+    #[must_use]
+    pub fn get(&self, action: A) -> Option<&NavigationDeadband> {
+        self.deadbands[action.index()].as_ref()
+    }
+
+    /// Presses or releases each action in the new `action_data` according to its deadband,
+    /// given whether it is currently pressed in the `action_state`
+    pub(crate) fn apply(&self, action_state: &ActionState<A>, action_data: &mut [ActionData]) {
+        for action in A::variants() {
+            let Some(deadband) = self.get(action.clone()) else {
+                continue;
+            };
+
+            let data = &mut action_data[action.index()];
+            let magnitude = data
+                .axis_pair
+                .map_or(data.value.abs(), |axis_pair| axis_pair.length());
+            data.state = if deadband.pressed(magnitude, action_state.pressed(action)) {
+                ButtonState::Pressed
+            } else {
+                ButtonState::Released
+            };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Up,
+        Down,
+    }
+
+    fn stick(value: f32) -> Vec<ActionData> {
+        let pressed = ActionData {
+            state: if value > 0.0 {
+                ButtonState::JustPressed
+            } else {
+                ButtonState::Released
+            },
+            value,
+            ..Default::default()
+        };
+        vec![pressed.clone(), pressed]
+    }
+
+    /// Updates the `action_state` with both actions at the stick `value`, applying the `deadbands`
+    fn update(
+        action_state: &mut ActionState<Action>,
+        deadbands: &NavigationDeadbands<Action>,
+        value: f32,
+    ) {
+        let mut action_data = stick(value);
+        deadbands.apply(action_state, &mut action_data);
+        action_state.update(action_data);
+    }
+
+    #[test]
+    fn thresholds_are_ordered() {
+        let deadband = NavigationDeadband::new(0.4, 0.8);
+        assert_eq!(deadband.press_threshold(), 0.4);
+        assert_eq!(deadband.release_threshold(), 0.4);
+
+        let deadband = NavigationDeadband::new(-1.0, 0.5);
+        assert_eq!(deadband.press_threshold(), 0.0);
+        assert_eq!(deadband.release_threshold(), 0.0);
+    }
+
+    #[test]
+    fn deadband_prevents_flickering() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut deadbands = NavigationDeadbands::<Action>::default();
+        deadbands.set(Action::Up, Some(NavigationDeadband::new(0.6, 0.3)));
+
+        // Below the press threshold, only the action without a deadband is pressed
+        update(&mut action_state, &deadbands, 0.5);
+        assert!(action_state.released(Action::Up));
+        assert!(action_state.pressed(Action::Down));
+
+        update(&mut action_state, &deadbands, 0.7);
+        assert!(action_state.pressed(Action::Up));
+
+        // Drifting back under the press threshold doesn't release the action
+        update(&mut action_state, &deadbands, 0.4);
+        assert!(action_state.pressed(Action::Up));
+
+        update(&mut action_state, &deadbands, 0.2);
+        assert!(action_state.released(Action::Up));
+        assert!(action_state.pressed(Action::Down));
+
+        deadbands.set(Action::Up, None);
+        assert!(deadbands.get(Action::Up).is_none());
+        update(&mut action_state, &deadbands, 0.2);
+        assert!(action_state.pressed(Action::Up));
+    }
+}
@@ -116,11 +116,7 @@ mod orientation_trait {
     impl Orientation for Rotation {
         #[inline]
         fn distance(&self, other: Rotation) -> Rotation {
-            let initial_distance = if self.micro_degrees >= other.micro_degrees {
-                self.micro_degrees - other.micro_degrees
-            } else {
-                other.micro_degrees - self.micro_degrees
-            };
+            let initial_distance = self.micro_degrees.abs_diff(other.micro_degrees);
 
             if initial_distance <= Rotation::FULL_CIRCLE / 2 {
                 Rotation {
@@ -168,6 +164,8 @@ mod orientation_trait {
 }
 
 mod rotation_direction {
+    use serde::{Deserialize, Serialize};
+
     /// A direction that a [`Rotation`](crate::orientation::Rotation) can be applied in.
     ///
     /// # Example
@@ -179,7 +177,7 @@ mod rotation_direction {
     /// assert_eq!(Rotation::NORTH.rotation_direction(Rotation::WEST), RotationDirection::CounterClockwise);
     /// assert_eq!(Rotation::NORTH.rotation_direction(Rotation::SOUTH), RotationDirection::Clockwise);
     /// ```
-    #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
     pub enum RotationDirection {
         /// Corresponds to a positive rotation
         #[default]
@@ -221,6 +219,7 @@ mod rotation {
     use bevy::math::Vec2;
     use core::ops::{Add, AddAssign, Div, Mul, Neg, Sub, SubAssign};
     use derive_more::Display;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
     use std::f32::consts::TAU;
 
     /// A discretized 2-dimensional rotation
@@ -392,6 +391,19 @@ mod rotation {
         }
     }
 
+    // Serialized as the number of micro-degrees, so that deserialized rotations are wrapped into a full circle
+    impl Serialize for Rotation {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            self.micro_degrees.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Rotation {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            u32::deserialize(deserializer).map(Rotation::new)
+        }
+    }
+
     impl Add for Rotation {
         type Output = Rotation;
         fn add(self, rhs: Self) -> Rotation {
@@ -1,7 +1,27 @@
 //! Contains main plugin exported by this crate.
 
+use crate::action_state::DriverTargetDespawned;
+use crate::binding_capture::{BindingCapture, BindingCaptured};
+use crate::binding_profiles::BindingProfileSelected;
+use crate::block_input::InputBlockers;
 use crate::clashing_inputs::ClashStrategy;
+use crate::custom_input::{CustomInputs, RawInputEvent};
+use crate::idle::{AnyInputDetected, InputActivity, InputIdle};
+#[cfg(feature = "keyboard")]
+use crate::ime::ImeActive;
+use crate::input_capture::RawEventTimestamps;
+use crate::input_prompt::InputPromptEvent;
+use crate::missing_components::{
+    MissingComponent, MissingComponentPolicies, MissingComponentPolicy,
+};
 use crate::prelude::ActionState;
+use crate::radial_menu::RadialSelectionChanged;
+use crate::rate_limits::RateLimited;
+use crate::secret_sequences::{SecretSequenceMatched, SecretSequences};
+use crate::stick_gestures::StickHistory;
+use crate::timing::InputClock;
+use crate::value_thresholds::ValueThresholdCrossed;
+use crate::window_events::WindowEventInputs;
 use crate::Actionlike;
 use core::hash::Hash;
 use core::marker::PhantomData;
@@ -36,24 +56,62 @@ use bevy::ui::UiSystem;
 /// you must define an ordering between your systems or behavior will be very erratic.
 /// The stable system sets for these systems are available under [`InputManagerSystem`] enum.
 ///
+/// Systems that only apply to a single input backend are only added when the corresponding cargo feature is enabled.
+///
 /// Complete list:
 ///
+/// - [`check_missing_components`](crate::systems::check_missing_components), which applies the [`MissingComponentPolicies`] to entities with only one of an [`InputMap`](crate::input_map::InputMap) and an [`ActionState`](crate::action_state::ActionState)
+/// - [`insert_action_timings`](crate::systems::insert_action_timings), which adds an [`ActionTimings`](crate::timing::ActionTimings) alongside each [`ActionState`](crate::action_state::ActionState), unless the plugin was created with [`InputManagerPlugin::without_timing`]
+/// - [`latch_presses`](crate::systems::latch_presses), which latches the presses of the actions configured in [`LatchedActions`](crate::latching::LatchedActions), so that they stay just pressed until consumed
 /// - [`tick_action_state`](crate::systems::tick_action_state), which resets the `pressed` and `just_pressed` fields of the [`ActionState`](crate::action_state::ActionState) each frame
+/// - [`count_auto_repeats`](crate::systems::count_auto_repeats), which counts the repeats of held actions configured in [`AutoRepeats`](crate::auto_repeat::AutoRepeats)
+/// - [`record_hold_completions`](crate::systems::record_hold_completions), which records the holds configured in [`HoldRequirements`](crate::hold_for::HoldRequirements) that were completed
+/// - [`collect_window_events`](crate::systems::collect_window_events), which records the window events that can be bound using [`UserInput::WindowEvent`](crate::user_input::UserInput::WindowEvent)
+/// - [`record_stick_history`](crate::systems::record_stick_history), which records the recent positions of each gamepad stick in the [`StickHistory`], to detect the gestures in [`stick_gestures`](crate::stick_gestures)
+/// - [`collect_raw_input_events`](crate::systems::collect_raw_input_events), which stores the values of the custom inputs that can be bound using [`UserInput::Custom`](crate::user_input::UserInput::Custom)
+/// - [`collect_ime_events`](crate::systems::collect_ime_events), which records whether an input method editor is composing text in the [`ImeActive`] resource (requires the `keyboard` feature)
+/// - [`detect_any_input`](crate::systems::detect_any_input), which sends an [`AnyInputDetected`] event in each frame during which any input was pressed, bound or not
+/// - [`detect_secret_sequences`](crate::systems::detect_secret_sequences), which sends a [`SecretSequenceMatched`] event when a sequence registered in [`SecretSequences`] is entered
+/// - [`capture_bindings`](crate::systems::capture_bindings), which captures the next binding pressed by the player for the action started in the [`BindingCapture`], sending a [`BindingCaptured`] event
+/// - [`apply_binding_profiles`](crate::systems::apply_binding_profiles), which applies the [`BindingProfiles`](crate::binding_profiles::BindingProfiles) entry of each gamepad that connects, sending a [`BindingProfileSelected`] event
 /// - [`update_action_state`](crate::systems::update_action_state), which collects [`Input`](bevy::input::Input) resources to update the [`ActionState`](crate::action_state::ActionState)
-/// - [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction), for triggering actions from buttons
+/// - [`apply_action_writers`](crate::systems::apply_action_writers), which sets the [`ActionState`](crate::action_state::ActionState) of [`ExternallyControlled`](crate::action_writer::ExternallyControlled) entities from their [`ActionWriter`](crate::action_writer::ActionWriter)
+/// - [`remove_despawned_driver_targets`](crate::systems::remove_despawned_driver_targets), which removes the targets of each [`ActionStateDriver`](crate::action_state::ActionStateDriver) that no longer exist, sending [`DriverTargetDespawned`] events
+/// - [`update_action_state_from_interaction`](crate::systems::update_action_state_from_interaction), for triggering actions from buttons (requires the `ui` feature)
 ///    - powers the [`ActionStateDriver`](crate::action_state::ActionStateDriver) component based on an [`Interaction`](bevy::ui::Interaction) component
+/// - [`capture_raw_inputs`](crate::systems::capture_raw_inputs), which records the raw input events that contributed to each action in its [`CapturedInputs`](crate::input_capture::CapturedInputs)
+/// - [`clear_raw_event_timestamps`](crate::systems::clear_raw_event_timestamps), which clears the [`RawEventTimestamps`] used by [`capture_raw_inputs`](crate::systems::capture_raw_inputs) at the end of each frame
+/// - [`surface_key_repeats`](crate::systems::surface_key_repeats), which counts the operating system key repeats of each pressed action in its [`KeyRepeats`](crate::key_repeat::KeyRepeats) (requires the `keyboard` feature)
+/// - [`apply_exclusive_groups`](crate::systems::apply_exclusive_groups), which consumes the other members of [`ExclusiveGroups`](crate::exclusive_groups::ExclusiveGroups) whose member was just pressed
+/// - [`enforce_rate_limits`](crate::systems::enforce_rate_limits), which suppresses presses beyond the limits of [`RateLimits`](crate::rate_limits::RateLimits) and sends [`RateLimited`] events instead
+///    - on servers, add the systems that apply received [`ActionDiff`](crate::action_state::ActionDiff)s to [`InputManagerSystem::ManualControl`] so that they are limited too
 /// - [`release_on_disable`](crate::systems::release_on_disable), which resets action states when [`ToggleActions`] is flipped, to avoid persistent presses.
+/// - [`release_blocked_actions`](crate::systems::release_blocked_actions), which releases the actions of entities given a [`BlockInput`](crate::block_input::BlockInput) component, or of every entity when the [`InputBlockers`] become blocked
+/// - [`generate_value_threshold_events`](crate::systems::generate_value_threshold_events), which sends [`ValueThresholdCrossed`] events for actions with [`ValueThresholds`](crate::value_thresholds::ValueThresholds)
+/// - [`generate_input_idle_events`](crate::systems::generate_input_idle_events), which tracks how long every [`ActionState`](crate::action_state::ActionState) has been idle in the [`InputActivity`], sending [`InputIdle`] events
+/// - [`record_action_stats`](crate::systems::record_action_stats), which counts the presses, held durations and peak values of actions with [`ActionStats`](crate::action_stats::ActionStats) (requires the `stats` feature)
+/// - [`update_drag_states`](crate::systems::update_drag_states), which advances the drag-and-drop gestures tracked by [`DragStates`](crate::drag::DragStates)
+/// - [`update_radial_menus`](crate::systems::update_radial_menus), which sends [`RadialSelectionChanged`] events when the selected sector of a [`RadialMenu`](crate::radial_menu::RadialMenu) changes
+/// - [`run_action_hooks`](crate::systems::run_action_hooks), which calls the [`ActionHooks`](crate::action_hooks::ActionHooks) of actions that were just pressed or released
+/// - [`mirror_action_states`](crate::systems::mirror_action_states), which copies the [`ActionState`](crate::action_state::ActionState) of each [`MirrorActionState`](crate::mirror::MirrorActionState) source onto the mirroring entity
+/// - [`update_input_overlays`](crate::systems::update_input_overlays), which displays the state of each action in the text of [`InputOverlay`](crate::input_overlay::InputOverlay) entities (requires the `ui` feature)
+/// - [`build_rebinding_menus`](crate::systems::build_rebinding_menus), [`start_rebinding_from_menus`](crate::systems::start_rebinding_from_menus) and [`update_rebinding_menus`](crate::systems::update_rebinding_menus), which power each [`RebindingMenu`](crate::rebinding_menu::RebindingMenu) (requires the `ui` feature)
+/// - [`update_input_prompts`](crate::systems::update_input_prompts), which advances each [`InputPrompt`](crate::input_prompt::InputPrompt) and sends [`InputPromptEvent`]s
 pub struct InputManagerPlugin<A: Actionlike> {
     _phantom: PhantomData<A>,
     machine: Machine,
+    missing_components: MissingComponentPolicies<A>,
+    timing: bool,
 }
 
 // Deriving default induces an undesired bound on the generic
 impl<A: Actionlike> Default for InputManagerPlugin<A> {
     fn default() -> Self {
         Self {
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
             machine: Machine::Client,
+            missing_components: MissingComponentPolicies::default(),
+            timing: true,
         }
     }
 }
@@ -67,10 +125,103 @@ impl<A: Actionlike> InputManagerPlugin<A> {
     #[must_use]
     pub fn server() -> Self {
         Self {
-            _phantom: PhantomData::default(),
+            _phantom: PhantomData,
             machine: Machine::Server,
+            missing_components: MissingComponentPolicies::default(),
+            timing: true,
         }
     }
+
+    /// Sets what happens to entities that are given an [`InputMap`](crate::input_map::InputMap) but no [`ActionState`],
+    /// and to entities that are given an [`ActionState`] but no [`InputMap`](crate::input_map::InputMap)
+    ///
+    /// By default, a missing [`ActionState`] is [inserted](MissingComponentPolicy::Insert),
+    /// and a missing [`InputMap`](crate::input_map::InputMap) is [ignored](MissingComponentPolicy::Ignore).
+    /// These policies are stored in the [`MissingComponentPolicies`] resource, and only apply on clients.
+    #[must_use]
+    pub fn with_missing_components(
+        mut self,
+        action_state: MissingComponentPolicy,
+        input_map: MissingComponentPolicy,
+    ) -> Self {
+        self.missing_components.action_state = action_state;
+        self.missing_components.input_map = input_map;
+        self
+    }
+
+    /// Sets what happens when an [`ActionDiff`](crate::action_state::ActionDiff) is received for an entity without an [`ActionState`]
+    ///
+    /// By default, these diffs are [ignored](MissingComponentPolicy::Ignore).
+    /// Use [`MissingComponentPolicy::Insert`] to give entities that join late a default [`ActionState`] with the diff applied.
+    /// This policy is stored in the [`MissingComponentPolicies`] resource,
+    /// and is applied by the [`process_action_diffs`](crate::systems::process_action_diffs)
+    /// and [`process_registered_action_diffs`](crate::systems::process_registered_action_diffs) systems.
+    #[must_use]
+    pub fn with_missing_diff_targets(mut self, diff_target: MissingComponentPolicy) -> Self {
+        self.missing_components.diff_target = diff_target;
+        self
+    }
+
+    /// Stops tracking how long actions have been pressed or released, for games that never query durations
+    ///
+    /// No [`ActionTimings`](crate::timing::ActionTimings) is added alongside the [`ActionState`]s of this action type,
+    /// so that they don't store a [`Timing`](crate::timing::Timing) for each action, and ticking them skips timing entirely.
+    /// This saves memory and time for large crowds of input-driven entities.
+    ///
+    /// Insert an [`ActionTimings`](crate::timing::ActionTimings) manually for the few entities that still need timing.
+    /// Features measured in time, such as [auto repeats](crate::auto_repeat) and [holds](crate::hold_for), don't work for the others.
+    #[must_use]
+    pub fn without_timing(mut self) -> Self {
+        self.timing = false;
+        self
+    }
+
+    /// Inserts the resources and event queues read by the systems of this plugin into the `world`, if they don't exist yet
+    ///
+    /// The plugin calls this on the world of the [`App`] or sub-app that it is added to,
+    /// so this is only needed to run the [`systems`](crate::systems) in a [`World`] that is not managed by an [`App`],
+    /// such as a simulation world that is stepped manually with its own [`Schedule`](bevy::ecs::schedule::Schedule).
+    ///
+    /// Such a world must also provide Bevy's [`Time`](bevy::time::Time),
+    /// and update each [`Events`](bevy::ecs::event::Events) queue once per frame, as [`App::add_event`] would.
+    /// Bevy's input resources are optional: without them, no keyboard, mouse or gamepad input is ever pressed,
+    /// and actions are only driven by [`ActionState::press`], [`ActionWriter`](crate::action_writer::ActionWriter)s and so on.
+    pub fn init_resources(&self, world: &mut World) {
+        world.init_resource::<ToggleActions<A>>();
+        world.init_resource::<ClashStrategy>();
+        world.init_resource::<InputClock>();
+        world.init_resource::<Events<RateLimited<A>>>();
+        // Also read when processing action diffs, which servers commonly do
+        world.insert_resource(self.missing_components.clone());
+        world.init_resource::<Events<MissingComponent<A>>>();
+
+        if let Machine::Server = self.machine {
+            return;
+        }
+
+        world.init_resource::<InputBlockers<A>>();
+        world.init_resource::<InputActivity<A>>();
+        world.init_resource::<BindingCapture<A>>();
+        world.init_resource::<Events<DriverTargetDespawned<A>>>();
+        world.init_resource::<Events<ValueThresholdCrossed<A>>>();
+        world.init_resource::<Events<RadialSelectionChanged<A>>>();
+        world.init_resource::<Events<InputPromptEvent<A>>>();
+        world.init_resource::<Events<InputIdle<A>>>();
+        world.init_resource::<Events<BindingCaptured<A>>>();
+        world.init_resource::<Events<BindingProfileSelected<A>>>();
+
+        // Shared between every action type
+        world.init_resource::<WindowEventInputs>();
+        world.init_resource::<RawEventTimestamps>();
+        world.init_resource::<StickHistory>();
+        world.init_resource::<CustomInputs>();
+        world.init_resource::<Events<RawInputEvent>>();
+        #[cfg(feature = "keyboard")]
+        world.init_resource::<ImeActive>();
+        world.init_resource::<SecretSequences>();
+        world.init_resource::<Events<SecretSequenceMatched>>();
+        world.init_resource::<Events<AnyInputDetected>>();
+    }
 }
 
 /// Which machine is this plugin running on?
@@ -87,18 +238,217 @@ impl<A: Actionlike> Plugin for InputManagerPlugin<A> {
             Machine::Client => {
                 app.add_systems(
                     PreUpdate,
-                    tick_action_state::<A>
+                    (
+                        latch_presses::<A>,
+                        tick_action_state::<A>,
+                        (count_auto_repeats::<A>, record_hold_completions::<A>),
+                    )
+                        .chain()
                         .run_if(run_if_enabled::<A>)
                         .in_set(InputManagerSystem::Tick)
                         .before(InputManagerSystem::Update),
+                );
+
+                if self.timing {
+                    app.add_systems(
+                        PreUpdate,
+                        // Inserted components are applied immediately, so that they are updated and ticked during the same frame
+                        (
+                            check_missing_components::<A>,
+                            apply_deferred,
+                            insert_action_timings::<A>,
+                            apply_deferred,
+                        )
+                            .chain()
+                            .before(InputManagerSystem::Tick),
+                    );
+                } else {
+                    app.add_systems(
+                        PreUpdate,
+                        // Inserted components are applied immediately, so that they are updated during the same frame
+                        (check_missing_components::<A>, apply_deferred)
+                            .chain()
+                            .before(InputManagerSystem::Tick),
+                    );
+                }
+
+                app.add_systems(
+                    PreUpdate,
+                    apply_action_writers::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .in_set(InputManagerSystem::Update),
                 )
                 .add_systems(
                     PreUpdate,
-                    release_on_disable::<A>
+                    (release_on_disable::<A>, release_blocked_actions::<A>)
                         .in_set(InputManagerSystem::ReleaseOnDisable)
                         .after(InputManagerSystem::Update),
                 )
-                .add_systems(PostUpdate, release_on_input_map_removed::<A>);
+                .add_systems(
+                    PreUpdate,
+                    capture_raw_inputs::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .after(InputManagerSystem::Update)
+                        .before(InputManagerSystem::ReleaseOnDisable),
+                )
+                .add_systems(
+                    PreUpdate,
+                    apply_exclusive_groups::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .after(InputManagerSystem::Update)
+                        .before(InputManagerSystem::ReleaseOnDisable),
+                )
+                .add_systems(
+                    PreUpdate,
+                    enforce_rate_limits::<A>
+                        .after(InputManagerSystem::Update)
+                        .after(InputManagerSystem::ManualControl)
+                        .before(InputManagerSystem::ReleaseOnDisable),
+                )
+                .add_systems(
+                    PreUpdate,
+                    generate_value_threshold_events::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    apply_binding_profiles::<A>
+                        .after(InputSystem)
+                        .before(InputManagerSystem::Update),
+                )
+                .add_systems(
+                    PreUpdate,
+                    capture_bindings::<A>
+                        .after(InputSystem)
+                        .before(InputManagerSystem::Update),
+                )
+                .add_systems(
+                    PreUpdate,
+                    generate_input_idle_events::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    update_drag_states::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    update_radial_menus::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    run_action_hooks::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    mirror_action_states::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    update_input_prompts::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_systems(
+                    PreUpdate,
+                    remove_despawned_driver_targets::<A>
+                        .after(InputManagerSystem::Tick)
+                        .before(InputManagerSystem::ManualControl),
+                )
+                .add_systems(PostUpdate, release_on_input_map_removed::<A>)
+                .add_event::<MissingComponent<A>>()
+                .add_event::<DriverTargetDespawned<A>>()
+                .add_event::<ValueThresholdCrossed<A>>()
+                .add_event::<RateLimited<A>>()
+                .add_event::<RadialSelectionChanged<A>>()
+                .add_event::<InputPromptEvent<A>>()
+                .add_event::<InputIdle<A>>()
+                .add_event::<BindingCaptured<A>>()
+                .add_event::<BindingProfileSelected<A>>();
+
+                #[cfg(feature = "keyboard")]
+                app.add_systems(
+                    PreUpdate,
+                    surface_key_repeats::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .after(InputManagerSystem::Update)
+                        .before(InputManagerSystem::ReleaseOnDisable),
+                );
+
+                // Window events are shared between every action type, so they are only collected once
+                if !app.world.contains_resource::<WindowEventInputs>() {
+                    app.init_resource::<WindowEventInputs>().add_systems(
+                        PreUpdate,
+                        collect_window_events
+                            .after(InputSystem)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
+                if !app.world.contains_resource::<RawEventTimestamps>() {
+                    app.init_resource::<RawEventTimestamps>()
+                        .add_systems(PostUpdate, clear_raw_event_timestamps);
+                }
+
+                if !app.world.contains_resource::<StickHistory>() {
+                    app.init_resource::<StickHistory>().add_systems(
+                        PreUpdate,
+                        record_stick_history
+                            .after(InputSystem)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
+                if !app.world.contains_resource::<CustomInputs>() {
+                    app.init_resource::<CustomInputs>()
+                        .add_event::<RawInputEvent>()
+                        .add_systems(
+                            PreUpdate,
+                            collect_raw_input_events
+                                .after(InputSystem)
+                                .before(InputManagerSystem::Update),
+                        );
+                }
+
+                #[cfg(feature = "keyboard")]
+                if !app.world.contains_resource::<ImeActive>() {
+                    app.init_resource::<ImeActive>().add_systems(
+                        PreUpdate,
+                        collect_ime_events
+                            .after(InputSystem)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
+                if !app.world.contains_resource::<Events<AnyInputDetected>>() {
+                    app.add_event::<AnyInputDetected>().add_systems(
+                        PreUpdate,
+                        detect_any_input
+                            .after(InputSystem)
+                            .before(InputManagerSystem::Update),
+                    );
+                }
+
+                if !app.world.contains_resource::<SecretSequences>() {
+                    app.init_resource::<SecretSequences>()
+                        .add_event::<SecretSequenceMatched>()
+                        .add_systems(
+                            PreUpdate,
+                            detect_secret_sequences
+                                .after(InputSystem)
+                                .before(InputManagerSystem::Update),
+                        );
+                }
 
                 #[cfg(feature = "egui")]
                 app.add_systems(
@@ -132,21 +482,80 @@ impl<A: Actionlike> Plugin for InputManagerPlugin<A> {
                         .after(UiSystem::Focus)
                         .after(InputSystem),
                 );
+
+                #[cfg(feature = "stats")]
+                app.add_systems(
+                    PreUpdate,
+                    record_action_stats::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                );
+
+                #[cfg(feature = "ui")]
+                app.add_systems(
+                    PreUpdate,
+                    update_input_overlays::<A>
+                        .after(InputManagerSystem::ReleaseOnDisable)
+                        .after(InputManagerSystem::ManualControl),
+                );
+
+                #[cfg(feature = "ui")]
+                app.add_systems(
+                    PreUpdate,
+                    (
+                        build_rebinding_menus::<A>,
+                        // Must run after the action state is updated, so that the click that starts a capture is not captured
+                        start_rebinding_from_menus::<A>
+                            .after(UiSystem::Focus)
+                            .after(InputManagerSystem::Update),
+                        update_rebinding_menus::<A>.after(capture_bindings::<A>),
+                    )
+                        .chain(),
+                );
             }
             Machine::Server => {
                 app.add_systems(
                     PreUpdate,
-                    tick_action_state::<A>
+                    (
+                        latch_presses::<A>,
+                        tick_action_state::<A>,
+                        (count_auto_repeats::<A>, record_hold_completions::<A>),
+                    )
+                        .chain()
                         .run_if(run_if_enabled::<A>)
                         .in_set(InputManagerSystem::Tick),
                 );
+
+                if self.timing {
+                    app.add_systems(
+                        PreUpdate,
+                        // Inserted timings are applied immediately, so that they are ticked during the same frame
+                        (insert_action_timings::<A>, apply_deferred)
+                            .chain()
+                            .before(InputManagerSystem::Tick),
+                    );
+                }
+
+                app.add_systems(
+                    PreUpdate,
+                    apply_action_writers::<A>
+                        .run_if(run_if_enabled::<A>)
+                        .in_set(InputManagerSystem::Update)
+                        .after(InputManagerSystem::Tick),
+                )
+                .add_systems(
+                    PreUpdate,
+                    enforce_rate_limits::<A>
+                        .after(InputManagerSystem::Update)
+                        .after(InputManagerSystem::ManualControl),
+                )
+                .add_event::<RateLimited<A>>();
             }
         };
 
         app.register_type::<ActionState<A>>()
-            // Resources
-            .init_resource::<ToggleActions<A>>()
-            .init_resource::<ClashStrategy>();
+            .register_type::<crate::timing::ActionTimings<A>>();
+        self.init_resources(&mut app.world);
     }
 }
 
@@ -193,7 +602,7 @@ impl<A: Actionlike> Default for ToggleActions<A> {
 pub enum InputManagerSystem {
     /// Advances action timers.
     ///
-    /// Cleans up the state of the input manager, clearing `just_pressed` and just_released`
+    /// Cleans up the state of the input manager, clearing `just_pressed` and `just_released`
     Tick,
     /// Collects input data to update the [`ActionState`](crate::action_state::ActionState)
     Update,
@@ -0,0 +1,222 @@
+//! Selecting sectors of a radial menu, such as a weapon wheel or emote menu, with a dual-axis action
+//!
+//! Add a [`RadialMenu`] resource or component alongside an [`ActionState`], pointing at an action with an axis pair (usually a stick).
+//! Each frame, the [`update_radial_menus`](crate::systems::update_radial_menus) system converts the direction of the axis pair
+//! into the index of the selected sector, and sends a [`RadialSelectionChanged`] event whenever it changes.
+//!
+//! Sector `0` is centered on straight up, and indexes increase clockwise.
+//! Small stick movements below the minimum magnitude are ignored and keep the current selection,
+//! so that letting go of the stick to confirm a choice doesn't change it.
+//! Hysteresis keeps a sector selected until the stick has moved clearly into a neighboring one,
+//! so the selection doesn't flicker when the stick rests on a boundary.
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity, Event, Resource};
+use std::f32::consts::TAU;
+
+/// Sent when the selected sector of a [`RadialMenu`] changes
+///
+/// These events are generated by the [`update_radial_menus`](crate::systems::update_radial_menus) system.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct RadialSelectionChanged<A: Actionlike> {
+    /// The action that drives the menu
+    pub action: A,
+    /// The sector that was selected before, if any
+    pub previous: Option<usize>,
+    /// The sector that is now selected
+    pub selected: usize,
+    /// The entity whose [`ActionState`] changed, or [`None`] if the [`ActionState`] is a resource
+    pub entity: Option<Entity>,
+}
+
+/// A radial menu whose sectors are selected by the axis pair of an action of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that drives it.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use leafwing_input_manager::axislike::DualAxisData;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::radial_menu::RadialMenu;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     WeaponWheel,
+/// }
+///
+/// let mut menu = RadialMenu::new(Action::WeaponWheel, 4);
+/// let mut action_state = ActionState::<Action>::default();
+///
+/// // Pushing the stick right selects the second sector
+/// action_state.action_data_mut(Action::WeaponWheel).axis_pair = Some(DualAxisData::new(1.0, 0.0));
+/// assert_eq!(menu.update(&action_state), Some(1));
+/// assert_eq!(menu.selected(), Some(1));
+///
+/// // Letting go of the stick keeps the selection
+/// action_state.action_data_mut(Action::WeaponWheel).axis_pair = Some(DualAxisData::new(0.0, 0.0));
+/// assert_eq!(menu.update(&action_state), None);
+/// assert_eq!(menu.selected(), Some(1));
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct RadialMenu<A: Actionlike> {
+    action: A,
+    sectors: usize,
+    min_magnitude: f32,
+    hysteresis: f32,
+    selected: Option<usize>,
+}
+
+impl<A: Actionlike> RadialMenu<A> {
+    /// The default minimum magnitude of the axis pair
+    pub const DEFAULT_MIN_MAGNITUDE: f32 = 0.5;
+
+    /// The default hysteresis, as a fraction of the width of a sector
+    pub const DEFAULT_HYSTERESIS: f32 = 0.1;
+
+    /// Creates a menu with the provided number of `sectors`, driven by the axis pair of the `action`
+    ///
+    /// There is always at least one sector.
+    #[must_use]
+    pub fn new(action: A, sectors: usize) -> Self {
+        Self {
+            action,
+            sectors: sectors.max(1),
+            min_magnitude: Self::DEFAULT_MIN_MAGNITUDE,
+            hysteresis: Self::DEFAULT_HYSTERESIS,
+            selected: None,
+        }
+    }
+
+    /// Only changes the selection when the length of the axis pair is at least `min_magnitude`
+    #[must_use]
+    pub fn with_min_magnitude(mut self, min_magnitude: f32) -> Self {
+        self.min_magnitude = min_magnitude.max(0.0);
+        self
+    }
+
+    /// Keeps the selected sector until the direction is more than `hysteresis` past its edges,
+    /// as a fraction of the width of a sector
+    ///
+    /// The `hysteresis` is clamped to `[0.0, 0.5]`.
+    #[must_use]
+    pub fn with_hysteresis(mut self, hysteresis: f32) -> Self {
+        self.hysteresis = hysteresis.clamp(0.0, 0.5);
+        self
+    }
+
+    /// The action whose axis pair drives the menu
+    #[must_use]
+    pub fn action(&self) -> A {
+        self.action.clone()
+    }
+
+    /// The number of sectors in the menu
+    #[must_use]
+    pub fn sectors(&self) -> usize {
+        self.sectors
+    }
+
+    /// The minimum length of the axis pair that changes the selection
+    #[must_use]
+    pub fn min_magnitude(&self) -> f32 {
+        self.min_magnitude
+    }
+
+    /// How far past the edges of the selected sector the direction must move to leave it, as a fraction of the width of a sector
+    #[must_use]
+    pub fn hysteresis(&self) -> f32 {
+        self.hysteresis
+    }
+
+    /// The selected sector, if any
+    #[must_use]
+    pub fn selected(&self) -> Option<usize> {
+        self.selected
+    }
+
+    /// Clears the selection, such as when the menu is closed
+    ///
+    /// No event is sent for this change.
+    pub fn clear_selection(&mut self) {
+        self.selected = None;
+    }
+
+    /// Updates the selection from the axis pair of the action in the `action_state`,
+    /// returning the newly selected sector if the selection changed
+    pub fn update(&mut self, action_state: &ActionState<A>) -> Option<usize> {
+        let axis_pair = action_state.axis_pair(self.action.clone())?;
+        if axis_pair.length() < self.min_magnitude || axis_pair.length() == 0.0 {
+            return None;
+        }
+
+        // Clockwise from straight up, in [0, 1) turns
+        let turns = (axis_pair.x().atan2(axis_pair.y()) / TAU).rem_euclid(1.0);
+        let position = turns * self.sectors as f32;
+
+        if let Some(selected) = self.selected {
+            // The distance from the center of the selected sector, in sectors, wrapping around the circle
+            let offset = (position - selected as f32).rem_euclid(self.sectors as f32);
+            let distance = offset.min(self.sectors as f32 - offset);
+            if distance <= 0.5 + self.hysteresis {
+                return None;
+            }
+        }
+
+        let sector = (position + 0.5).floor() as usize % self.sectors;
+        if self.selected == Some(sector) {
+            return None;
+        }
+        self.selected = Some(sector);
+        Some(sector)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use crate::axislike::DualAxisData;
+    use bevy::math::Vec2;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Wheel,
+    }
+
+    fn point(action_state: &mut ActionState<Action>, degrees: f32, length: f32) {
+        let direction = Vec2::from_angle(degrees.to_radians()) * length;
+        action_state.action_data_mut(Action::Wheel).axis_pair =
+            Some(DualAxisData::from_xy(direction));
+    }
+
+    #[test]
+    fn hysteresis() {
+        // Four sectors, centered on up (90°), right (0°), down (-90°) and left (180°)
+        let mut menu = RadialMenu::new(Action::Wheel, 4).with_hysteresis(0.1);
+        let mut action_state = ActionState::<Action>::default();
+
+        point(&mut action_state, 90.0, 1.0);
+        assert_eq!(menu.update(&action_state), Some(0));
+
+        // Just past the boundary between up and right, but within the hysteresis of 9°
+        point(&mut action_state, 40.0, 1.0);
+        assert_eq!(menu.update(&action_state), None);
+        point(&mut action_state, 30.0, 1.0);
+        assert_eq!(menu.update(&action_state), Some(1));
+
+        // Wrapping around from up to left
+        point(&mut action_state, 90.0, 1.0);
+        assert_eq!(menu.update(&action_state), Some(0));
+        point(&mut action_state, 150.0, 1.0);
+        assert_eq!(menu.update(&action_state), Some(3));
+
+        // Small movements are ignored
+        point(&mut action_state, -90.0, 0.2);
+        assert_eq!(menu.update(&action_state), None);
+        assert_eq!(menu.selected(), Some(3));
+    }
+}
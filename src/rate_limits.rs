@@ -0,0 +1,139 @@
+//! Limiting how often each action can be pressed
+//!
+//! Add a [`RateLimits`] resource or component alongside an [`ActionState`] to cap the number of presses per second of some actions.
+//! Presses beyond the limit are suppressed as soon as the [`ActionState`] is updated, and a [`RateLimited`] event is sent instead.
+//! This can be used to balance gameplay, such as capping the fire rate of a weapon bound to the scroll wheel,
+//! or to sanitize the [`ActionDiff`](crate::action_state::ActionDiff)s received from clients on a server.
+
+use crate::action_state::ActionState;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity, Event, Resource};
+use bevy::utils::{Duration, Instant};
+use std::collections::VecDeque;
+use std::marker::PhantomData;
+
+/// The window over which presses are counted
+const WINDOW: Duration = Duration::from_secs(1);
+
+/// Sent when a press of an action is suppressed because it exceeded the limit set in its [`RateLimits`]
+///
+/// These events are generated by the [`enforce_rate_limits`](crate::systems::enforce_rate_limits) system.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct RateLimited<A: Actionlike> {
+    /// The action whose press was suppressed
+    pub action: A,
+    /// The entity whose [`ActionState`] was limited, or [`None`] if the [`ActionState`] is a resource
+    pub entity: Option<Entity>,
+}
+
+/// The maximum number of presses per second of each action of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it limits.
+/// Only presses that were allowed count towards the limit.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::{Duration, Instant};
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::rate_limits::RateLimits;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Shoot,
+/// }
+///
+/// let mut rate_limits = RateLimits::<Action>::default();
+/// rate_limits.set(Action::Shoot, 1);
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// let start = Instant::now();
+///
+/// action_state.press(Action::Shoot);
+/// assert!(rate_limits.enforce(&mut action_state, start).is_empty());
+///
+/// action_state.release(Action::Shoot);
+/// action_state.press(Action::Shoot);
+/// let limited = rate_limits.enforce(&mut action_state, start + Duration::from_millis(500));
+/// assert_eq!(limited, [Action::Shoot]);
+/// assert!(action_state.released(Action::Shoot));
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct RateLimits<A: Actionlike> {
+    /// The maximum number of presses per second of each action, indexed by [`Actionlike::index`]
+    max_presses_per_second: Vec<Option<u32>>,
+    /// When each allowed press during the last second happened, oldest first
+    recent_presses: Vec<VecDeque<Instant>>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for RateLimits<A> {
+    fn default() -> Self {
+        RateLimits {
+            max_presses_per_second: vec![None; A::n_variants()],
+            recent_presses: vec![VecDeque::new(); A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> RateLimits<A> {
+    /// Limits the `action` to `max_presses_per_second`
+    pub fn set(&mut self, action: A, max_presses_per_second: u32) {
+        self.max_presses_per_second[action.index()] = Some(max_presses_per_second);
+    }
+
+    /// Removes the limit of the `action`
+    pub fn remove(&mut self, action: A) {
+        let index = action.index();
+        self.max_presses_per_second[index] = None;
+        self.recent_presses[index].clear();
+    }
+
+    /// The maximum number of presses per second of the `action`, if it is limited
+    #[must_use]
+    pub fn get(&self, action: A) -> Option<u32> {
+        self.max_presses_per_second[action.index()]
+    }
+
+    /// Suppresses the just pressed actions of the `action_state` that exceed their limit at the instant `now`,
+    /// returning the actions that were suppressed
+    ///
+    /// Suppressed actions are released without being reported as just released, and consumed,
+    /// so that they are not pressed again until their inputs are released.
+    pub fn enforce(&mut self, action_state: &mut ActionState<A>, now: Instant) -> Vec<A> {
+        let mut limited = Vec::new();
+
+        for action in A::variants() {
+            let index = action.index();
+            let Some(max_presses) = self.max_presses_per_second[index] else {
+                continue;
+            };
+
+            let recent_presses = &mut self.recent_presses[index];
+            while recent_presses
+                .front()
+                .is_some_and(|pressed_at| now.saturating_duration_since(*pressed_at) >= WINDOW)
+            {
+                recent_presses.pop_front();
+            }
+
+            if !action_state.newly_pressed(action.clone()) {
+                continue;
+            }
+
+            if recent_presses.len() < max_presses as usize {
+                recent_presses.push_back(now);
+            } else {
+                action_state.consume(action.clone());
+                // The press never happened as far as gameplay is concerned
+                action_state.action_data_mut(action.clone()).state = ButtonState::Released;
+                limited.push(action);
+            }
+        }
+
+        limited
+    }
+}
@@ -0,0 +1,320 @@
+//! A basic rebinding screen built with `bevy_ui`, usable as-is or as a reference for your own settings menu
+//!
+//! Add a [`RebindingMenu`] to an entity with a [`NodeBundle`](bevy::ui::node_bundles::NodeBundle),
+//! and the [`build_rebinding_menus`](crate::systems::build_rebinding_menus) system will fill it with one row per action:
+//! the name of the action from its [`ActionMetadata`], and a button labelled with its current bindings.
+//! Rows are ordered with [`ActionMetadata::iter_sorted`] if the [`ActionMetadata`] resource exists.
+//!
+//! Clicking a button, or activating it with [UI navigation](crate::ui_navigation), starts a [`BindingCapture`](crate::binding_capture::BindingCapture).
+//! Once a binding is captured, it replaces the first binding of the action in the target [`InputMap`],
+//! and is removed from any other action that it was [exactly bound to](crate::binding_conflicts::ConflictKind::ExactDuplicate).
+//! These are handled by the [`start_rebinding_from_menus`](crate::systems::start_rebinding_from_menus)
+//! and [`update_rebinding_menus`](crate::systems::update_rebinding_menus) systems.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::rebinding_menu::RebindingMenu;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Dash,
+//! }
+//!
+//! fn spawn_settings(mut commands: Commands) {
+//!     let player = commands
+//!         .spawn(InputManagerBundle::<Action>::default())
+//!         .id();
+//!
+//!     commands.spawn((
+//!         NodeBundle {
+//!             style: Style {
+//!                 flex_direction: FlexDirection::Column,
+//!                 ..default()
+//!             },
+//!             ..default()
+//!         },
+//!         RebindingMenu::<Action>::new(player),
+//!     ));
+//! }
+//! ```
+
+use crate::action_metadata::ActionMetadata;
+use crate::binding_conflicts::ConflictKind;
+use crate::input_map::InputMap;
+use crate::localization::DefaultNames;
+use crate::ui_navigation::Focusable;
+use crate::user_input::UserInput;
+use crate::Actionlike;
+
+use bevy::asset::Handle;
+use bevy::ecs::prelude::{Component, Entity};
+use bevy::hierarchy::{BuildChildren, ChildBuilder};
+use bevy::render::color::Color;
+use bevy::text::{Font, TextStyle};
+use bevy::ui::node_bundles::{ButtonBundle, NodeBundle, TextBundle};
+use bevy::ui::{AlignItems, BackgroundColor, JustifyContent, Style, UiRect, Val};
+
+/// Fills this `bevy_ui` node with a row per action, to rebind the actions of the `target`
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct RebindingMenu<A: Actionlike> {
+    /// The entity whose [`InputMap`] is edited, or [`None`] to edit the [`InputMap`] resource
+    pub target: Option<Entity>,
+    /// The font used for the names and bindings of actions
+    pub font: Handle<Font>,
+    /// The size of the font used for the names and bindings of actions
+    pub font_size: f32,
+    /// The color of the text
+    pub text_color: Color,
+    /// The background color of the buttons
+    pub button_color: Color,
+    /// The color of the text of the button whose binding is being captured
+    pub capturing_color: Color,
+    capturing: Option<A>,
+}
+
+impl<A: Actionlike> RebindingMenu<A> {
+    /// Edits the [`InputMap`] of the `target` entity
+    #[must_use]
+    pub fn new(target: Entity) -> Self {
+        Self {
+            target: Some(target),
+            ..Self::resource()
+        }
+    }
+
+    /// Edits the [`InputMap`] resource
+    #[must_use]
+    pub fn resource() -> Self {
+        Self {
+            target: None,
+            font: Handle::default(),
+            font_size: 20.0,
+            text_color: Color::WHITE,
+            button_color: Color::DARK_GRAY,
+            capturing_color: Color::YELLOW,
+            capturing: None,
+        }
+    }
+
+    /// Sets the `font` and `font_size` used for the names and bindings of actions
+    #[must_use]
+    pub fn with_font(mut self, font: Handle<Font>, font_size: f32) -> Self {
+        self.font = font;
+        self.font_size = font_size;
+        self
+    }
+
+    /// Sets the colors of the text, of the buttons, and of the text of the button whose binding is being captured
+    #[must_use]
+    pub fn with_colors(
+        mut self,
+        text_color: Color,
+        button_color: Color,
+        capturing_color: Color,
+    ) -> Self {
+        self.text_color = text_color;
+        self.button_color = button_color;
+        self.capturing_color = capturing_color;
+        self
+    }
+
+    /// The action whose binding is being captured by this menu, if any
+    #[must_use]
+    pub fn capturing(&self) -> Option<A> {
+        self.capturing.clone()
+    }
+
+    /// Sets the action whose binding is being captured by this menu
+    pub fn set_capturing(&mut self, action: Option<A>) {
+        self.capturing = action;
+    }
+
+    /// The text displayed on the button of the `action`, given the [`InputMap`] being edited
+    #[must_use]
+    pub fn binding_label(&self, action: A, input_map: Option<&InputMap<A>>) -> String {
+        if self
+            .capturing
+            .as_ref()
+            .is_some_and(|capturing| capturing.index() == action.index())
+        {
+            return "Press a button...".to_string();
+        }
+
+        let bindings = input_map
+            .map(|input_map| input_map.localized_names(action, &DefaultNames))
+            .unwrap_or_default();
+        if bindings.is_empty() {
+            "Unbound".to_string()
+        } else {
+            bindings.join(", ")
+        }
+    }
+
+    /// The style of the text on the button of the `action`
+    #[must_use]
+    pub fn label_style(&self, action: A) -> TextStyle {
+        let capturing = self
+            .capturing
+            .as_ref()
+            .is_some_and(|capturing| capturing.index() == action.index());
+
+        TextStyle {
+            font: self.font.clone(),
+            font_size: self.font_size,
+            color: if capturing {
+                self.capturing_color
+            } else {
+                self.text_color
+            },
+        }
+    }
+
+    /// Spawns a row for each action as children of the `menu` entity
+    ///
+    /// Action names are taken from the `metadata` if provided, and from [`Actionlike::info`] otherwise.
+    pub fn spawn_rows(
+        &self,
+        parent: &mut ChildBuilder,
+        menu: Entity,
+        metadata: Option<&ActionMetadata<A>>,
+    ) {
+        let actions: Vec<A> = match metadata {
+            Some(metadata) => metadata.iter_sorted().map(|(action, _)| action).collect(),
+            None => A::variants().collect(),
+        };
+        let text_style = TextStyle {
+            font: self.font.clone(),
+            font_size: self.font_size,
+            color: self.text_color,
+        };
+
+        for action in actions {
+            let name = match metadata {
+                Some(metadata) => metadata.localized_name(action.clone(), &DefaultNames),
+                None => action.info().name,
+            };
+
+            parent
+                .spawn(NodeBundle {
+                    style: Style {
+                        justify_content: JustifyContent::SpaceBetween,
+                        align_items: AlignItems::Center,
+                        column_gap: Val::Px(self.font_size),
+                        margin: UiRect::vertical(Val::Px(self.font_size / 4.0)),
+                        ..Default::default()
+                    },
+                    ..Default::default()
+                })
+                .with_children(|row| {
+                    row.spawn(TextBundle::from_section(name, text_style.clone()));
+                    row.spawn((
+                        ButtonBundle {
+                            style: Style {
+                                padding: UiRect::all(Val::Px(self.font_size / 4.0)),
+                                ..Default::default()
+                            },
+                            background_color: BackgroundColor(self.button_color),
+                            ..Default::default()
+                        },
+                        RebindButton {
+                            menu,
+                            action: action.clone(),
+                        },
+                        Focusable,
+                    ))
+                    .with_children(|button| {
+                        button.spawn((
+                            TextBundle::from_section(
+                                self.binding_label(action.clone(), None),
+                                text_style.clone(),
+                            ),
+                            BindingLabel { menu, action },
+                        ));
+                    });
+                });
+        }
+    }
+
+    /// Replaces the first binding of the `action` in the `input_map` with the `input`
+    ///
+    /// The `input` is removed from any other action that it was exactly bound to, so that the actions remain distinguishable.
+    pub fn apply_binding(input_map: &mut InputMap<A>, action: A, input: UserInput) {
+        for conflict in input_map.binding_conflicts(input.clone(), action.clone()) {
+            if conflict.kind == ConflictKind::ExactDuplicate {
+                input_map.remove(conflict.action, conflict.existing);
+            }
+        }
+
+        let mut bindings: Vec<UserInput> = input_map.get(action.clone()).iter().cloned().collect();
+        bindings.retain(|existing| *existing != input);
+        match bindings.first_mut() {
+            Some(first) => *first = input,
+            None => bindings.push(input),
+        }
+
+        input_map.clear_action(action.clone());
+        for binding in bindings {
+            input_map.insert(binding, action.clone());
+        }
+    }
+}
+
+/// A button of a [`RebindingMenu`], that starts capturing a binding for the `action` when pressed
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct RebindButton<A: Actionlike> {
+    /// The [`RebindingMenu`] entity that this button belongs to
+    pub menu: Entity,
+    /// The action that this button rebinds
+    pub action: A,
+}
+
+/// The text of a [`RebindButton`], displaying the current bindings of the `action`
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct BindingLabel<A: Actionlike> {
+    /// The [`RebindingMenu`] entity that this label belongs to
+    pub menu: Entity,
+    /// The action whose bindings are displayed
+    pub action: A,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::input::keyboard::KeyCode;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Jump,
+        Dash,
+    }
+
+    #[test]
+    fn bindings_are_replaced() {
+        let mut input_map = InputMap::new([
+            (KeyCode::Space, Action::Jump),
+            (KeyCode::W, Action::Jump),
+            (KeyCode::ShiftLeft, Action::Dash),
+        ]);
+
+        let mut menu = RebindingMenu::<Action>::resource();
+        assert_eq!(menu.binding_label(Action::Dash, None), "Unbound");
+        menu.set_capturing(Some(Action::Jump));
+        assert_eq!(
+            menu.binding_label(Action::Jump, Some(&input_map)),
+            "Press a button..."
+        );
+
+        // The first binding is replaced, and the duplicate binding of the other action is removed
+        RebindingMenu::apply_binding(&mut input_map, Action::Jump, KeyCode::ShiftLeft.into());
+        let jump: Vec<_> = input_map.get(Action::Jump).iter().cloned().collect();
+        assert_eq!(
+            jump,
+            [UserInput::from(KeyCode::ShiftLeft), KeyCode::W.into()]
+        );
+        assert!(input_map.get(Action::Dash).is_empty());
+    }
+}
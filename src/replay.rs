@@ -0,0 +1,309 @@
+//! Recording and playback of [`ActionState`]s, with support for seeking
+//!
+//! An [`ActionRecording`] stores the actions that were pressed and released during each frame,
+//! using the same minimal format as [`ActionDiff`](crate::action_state::ActionDiff).
+//! Add the [`record_actions`](crate::systems::record_actions) system to fill the recordings of your entities,
+//! and the [`play_back_actions`](crate::systems::play_back_actions) system to replay them using an [`ActionPlayback`].
+//!
+//! Seeking rebuilds the [`ActionState`] by fast-forwarding the recorded changes into a fresh [`ActionState`],
+//! rather than relying on the current state of the entity.
+//! This allows editors and debugging tools to scrub freely through a recorded session,
+//! even when the live state has diverged from the recording.
+//!
+//! Neither system is part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+//! Entities that are being played back should not read physical inputs:
+//! remove their [`InputMap`](crate::input_map::InputMap) or mark them as [`ExternallyControlled`](crate::action_writer::ExternallyControlled).
+
+use crate::action_state::ActionState;
+use crate::timing::ActionTimings;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Component;
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+
+/// A change to the state of a single action during a recorded frame
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ActionChange<A: Actionlike> {
+    /// The action was pressed
+    Pressed(A),
+    /// The action was released
+    Released(A),
+}
+
+impl<A: Actionlike> ActionChange<A> {
+    /// Applies this change to the `action_state`
+    pub fn apply(&self, action_state: &mut ActionState<A>) {
+        match self {
+            ActionChange::Pressed(action) => action_state.press(action.clone()),
+            ActionChange::Released(action) => action_state.release(action.clone()),
+        }
+    }
+}
+
+/// The changes made to an [`ActionState`] during each frame of a recorded session
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ActionRecording<A: Actionlike> {
+    frames: Vec<Vec<ActionChange<A>>>,
+}
+
+impl<A: Actionlike> Default for ActionRecording<A> {
+    fn default() -> Self {
+        Self { frames: Vec::new() }
+    }
+}
+
+impl<A: Actionlike> ActionRecording<A> {
+    /// Records the actions that were just pressed and just released in the `action_state` as a new frame
+    pub fn record(&mut self, action_state: &ActionState<A>) {
+        let frame = action_state
+            .get_just_pressed()
+            .into_iter()
+            .map(ActionChange::Pressed)
+            .chain(
+                action_state
+                    .get_just_released()
+                    .into_iter()
+                    .map(ActionChange::Released),
+            )
+            .collect();
+        self.frames.push(frame);
+    }
+
+    /// Appends a frame containing the provided `changes`
+    pub fn push_frame(&mut self, changes: impl IntoIterator<Item = ActionChange<A>>) {
+        self.frames.push(changes.into_iter().collect());
+    }
+
+    /// The number of recorded frames
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Does this recording contain no frames?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// The changes recorded during the provided `frame`, if it exists
+    #[must_use]
+    pub fn frame(&self, frame: usize) -> Option<&[ActionChange<A>]> {
+        self.frames.get(frame).map(Vec::as_slice)
+    }
+
+    /// Reconstructs the [`ActionState`] and its [`ActionTimings`] at the end of the provided `frame`
+    ///
+    /// The recorded changes are fast-forwarded into a fresh [`ActionState`], ticking it by `frame_duration` between each frame.
+    /// As a result, the `just_pressed` and `just_released` states reflect the changes made during `frame` itself,
+    /// and durations are measured in multiples of `frame_duration`.
+    ///
+    /// Frames past the end of the recording are clamped to the final frame.
+    #[must_use]
+    pub fn state_at(
+        &self,
+        frame: usize,
+        frame_duration: Duration,
+    ) -> (ActionState<A>, ActionTimings<A>) {
+        let mut action_state = ActionState::default();
+        let mut action_timings = ActionTimings::default();
+        let start = Instant::now();
+
+        for (index, changes) in self.frames.iter().take(frame + 1).enumerate() {
+            if index > 0 {
+                let index = index as u32;
+                action_state.tick_with_timings(
+                    &mut action_timings,
+                    start + frame_duration * index,
+                    start + frame_duration * (index - 1),
+                );
+            }
+
+            for change in changes {
+                change.apply(&mut action_state);
+            }
+        }
+
+        (action_state, action_timings)
+    }
+}
+
+/// Plays back an [`ActionRecording`] into the [`ActionState`] of the same entity
+///
+/// Each time the [`play_back_actions`](crate::systems::play_back_actions) system runs,
+/// the changes from the next frame of the recording are applied.
+#[derive(Component, Debug, Clone, PartialEq)]
+pub struct ActionPlayback<A: Actionlike> {
+    recording: ActionRecording<A>,
+    next_frame: usize,
+    seek_target: Option<usize>,
+    frame_duration: Duration,
+    paused: bool,
+}
+
+impl<A: Actionlike> ActionPlayback<A> {
+    /// Creates a playback of the `recording`, starting from its first frame
+    ///
+    /// The `frame_duration` is used to reconstruct the timing of actions when seeking.
+    #[must_use]
+    pub fn new(recording: ActionRecording<A>, frame_duration: Duration) -> Self {
+        Self {
+            recording,
+            next_frame: 0,
+            seek_target: None,
+            frame_duration,
+            paused: false,
+        }
+    }
+
+    /// The recording being played back
+    #[must_use]
+    pub fn recording(&self) -> &ActionRecording<A> {
+        &self.recording
+    }
+
+    /// The index of the frame that will be applied during the next update
+    #[must_use]
+    pub fn next_frame(&self) -> usize {
+        self.next_frame
+    }
+
+    /// Has every frame of the recording been played?
+    #[must_use]
+    pub fn is_finished(&self) -> bool {
+        self.next_frame >= self.recording.len()
+    }
+
+    /// Jumps to the provided `frame` during the next update
+    ///
+    /// The [`ActionState`] and its [`ActionTimings`] will be replaced by [`ActionRecording::state_at`] the target frame,
+    /// and playback will continue from the frame after it.
+    pub fn seek(&mut self, frame: usize) {
+        self.seek_target = Some(frame.min(self.recording.len().saturating_sub(1)));
+    }
+
+    /// Stops advancing through the recording, while still allowing seeking
+    pub fn pause(&mut self) {
+        self.paused = true;
+    }
+
+    /// Continues advancing through the recording
+    pub fn resume(&mut self) {
+        self.paused = false;
+    }
+
+    /// Is the playback paused?
+    #[must_use]
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
+    /// Applies the next frame of the recording, or the pending seek, to the `action_state`
+    ///
+    /// Seeking replaces the `action_timings` as well, if there are any.
+    pub fn advance(
+        &mut self,
+        action_state: &mut ActionState<A>,
+        action_timings: Option<&mut ActionTimings<A>>,
+    ) {
+        if let Some(frame) = self.seek_target.take() {
+            let (state, timings) = self.recording.state_at(frame, self.frame_duration);
+            *action_state = state;
+            if let Some(action_timings) = action_timings {
+                *action_timings = timings;
+            }
+            self.next_frame = frame + 1;
+            return;
+        }
+
+        if self.paused {
+            return;
+        }
+
+        if let Some(changes) = self.recording.frame(self.next_frame) {
+            for change in changes {
+                change.apply(action_state);
+            }
+            self.next_frame += 1;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+    }
+
+    const FRAME: Duration = Duration::from_millis(16);
+
+    fn recording() -> ActionRecording<Action> {
+        let mut recording = ActionRecording::default();
+        recording.push_frame([ActionChange::Pressed(Action::Run)]);
+        recording.push_frame([ActionChange::Pressed(Action::Jump)]);
+        recording.push_frame([]);
+        recording.push_frame([ActionChange::Released(Action::Jump)]);
+        recording
+    }
+
+    #[test]
+    fn state_at() {
+        let recording = recording();
+
+        let (action_state, action_timings) = recording.state_at(1, FRAME);
+        assert!(action_state.pressed(Action::Run));
+        assert!(!action_state.just_pressed(Action::Run));
+        assert!(action_state.just_pressed(Action::Jump));
+        assert_eq!(
+            action_timings.current_duration(&action_state, Action::Run),
+            FRAME
+        );
+
+        let (action_state, action_timings) = recording.state_at(3, FRAME);
+        assert!(action_state.pressed(Action::Run));
+        assert!(action_state.just_released(Action::Jump));
+
+        // Seeking past the end of the recording clamps to the final frame
+        let (clamped, clamped_timings) = recording.state_at(100, FRAME);
+        assert!(clamped.pressed(Action::Run));
+        assert!(clamped.just_released(Action::Jump));
+        assert_eq!(
+            clamped_timings.current_duration(&clamped, Action::Run),
+            action_timings.current_duration(&action_state, Action::Run)
+        );
+    }
+
+    #[test]
+    fn seeking() {
+        let mut playback = ActionPlayback::new(recording(), FRAME);
+        // The live state has diverged from the recording
+        let mut action_state = ActionState::<Action>::default();
+        action_state.press(Action::Jump);
+
+        playback.seek(0);
+        playback.advance(&mut action_state, None);
+        assert!(action_state.pressed(Action::Run));
+        assert!(action_state.released(Action::Jump));
+        assert_eq!(playback.next_frame(), 1);
+
+        playback.advance(&mut action_state, None);
+        assert!(action_state.pressed(Action::Jump));
+
+        playback.pause();
+        playback.advance(&mut action_state, None);
+        assert_eq!(playback.next_frame(), 2);
+
+        playback.resume();
+        playback.advance(&mut action_state, None);
+        playback.advance(&mut action_state, None);
+        assert!(playback.is_finished());
+        assert!(action_state.released(Action::Jump));
+    }
+}
@@ -98,7 +98,7 @@ pub enum QwertyScanCode {
     X = 45,
     /// The location of the `C` key on the QWERTY keyboard layout.
     C = 46,
-    /// The location of the `V key on the QWERTY keyboard layout.
+    /// The location of the `V` key on the QWERTY keyboard layout.
     V = 47,
     /// The location of the `B` key on the QWERTY keyboard layout.
     B = 48,
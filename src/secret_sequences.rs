@@ -0,0 +1,299 @@
+//! Detection of long input sequences, such as cheat codes, independently of any [`InputMap`](crate::input_map::InputMap)
+//!
+//! Cheats and debug unlocks are usually typed as a fixed sequence of buttons (like the Konami code),
+//! and shouldn't need actions of their own.
+//! Register each sequence by name in the [`SecretSequences`] resource,
+//! and a [`SecretSequenceMatched`] event will be sent whenever its buttons are pressed in order.
+//!
+//! Each frame, the [`detect_secret_sequences`](crate::systems::detect_secret_sequences) system records the keyboard keys,
+//! mouse buttons and gamepad buttons that were pressed. Operating system key repeats are ignored.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::secret_sequences::{SecretSequence, SecretSequenceMatched, SecretSequences};
+//!
+//! fn register_cheats(mut secret_sequences: ResMut<SecretSequences>) {
+//!     secret_sequences.register(
+//!         "konami",
+//!         SecretSequence::new([
+//!             KeyCode::Up,
+//!             KeyCode::Up,
+//!             KeyCode::Down,
+//!             KeyCode::Down,
+//!             KeyCode::Left,
+//!             KeyCode::Right,
+//!             KeyCode::Left,
+//!             KeyCode::Right,
+//!             KeyCode::B,
+//!             KeyCode::A,
+//!         ])
+//!         .with_max_gap(Duration::from_secs(1)),
+//!     );
+//! }
+//!
+//! fn unlock_cheats(mut events: EventReader<SecretSequenceMatched>) {
+//!     for event in events.iter() {
+//!         if event.name == "konami" {
+//!             info!("30 lives unlocked");
+//!         }
+//!     }
+//! }
+//! ```
+
+use crate::input_capture::CapturedInput;
+use crate::timing_forgiveness::TimingForgiveness;
+use crate::user_input::{InputKind, RawInputs, UserInput};
+
+use bevy::ecs::prelude::{Event, Resource};
+use bevy::utils::Duration;
+use std::collections::VecDeque;
+
+/// Sent when the buttons of a sequence registered in [`SecretSequences`] were pressed in order
+#[derive(Event, Debug, Clone, PartialEq, Eq)]
+pub struct SecretSequenceMatched {
+    /// The name that the sequence was registered with
+    pub name: String,
+}
+
+/// An ordered sequence of button presses
+///
+/// Only buttons can be part of a sequence: keys, key locations, modifiers, mouse buttons and gamepad buttons.
+/// Axes and other kinds of inputs never match.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SecretSequence {
+    inputs: Vec<InputKind>,
+    raw_inputs: Vec<RawInputs>,
+    max_gap: Option<Duration>,
+}
+
+impl SecretSequence {
+    /// Creates a sequence that matches when the `inputs` are pressed in order, with no other presses in between
+    #[must_use]
+    pub fn new(inputs: impl IntoIterator<Item = impl Into<InputKind>>) -> Self {
+        let inputs: Vec<InputKind> = inputs.into_iter().map(Into::into).collect();
+        let raw_inputs = inputs
+            .iter()
+            .map(|input| UserInput::Single(*input).raw_inputs())
+            .collect();
+
+        Self {
+            inputs,
+            raw_inputs,
+            max_gap: None,
+        }
+    }
+
+    /// Only matches if each press happens within `max_gap` of the previous one
+    #[must_use]
+    pub fn with_max_gap(mut self, max_gap: Duration) -> Self {
+        self.max_gap = Some(max_gap);
+        self
+    }
+
+    /// The inputs that must be pressed, in order
+    #[must_use]
+    pub fn inputs(&self) -> &[InputKind] {
+        &self.inputs
+    }
+
+    /// The longest time allowed between two consecutive presses, if any
+    #[must_use]
+    pub fn max_gap(&self) -> Option<Duration> {
+        self.max_gap
+    }
+
+    /// Do the most recent `presses` complete this sequence?
+    ///
+    /// The maximum gap is scaled by the `forgiveness`.
+    fn completed_by(
+        &self,
+        presses: &VecDeque<CapturedInput>,
+        forgiveness: &TimingForgiveness,
+    ) -> bool {
+        let len = self.raw_inputs.len();
+        if len == 0 || presses.len() < len {
+            return false;
+        }
+
+        let presses: Vec<&CapturedInput> = presses.iter().skip(presses.len() - len).collect();
+        let inputs_match = presses
+            .iter()
+            .zip(self.raw_inputs.iter())
+            .all(|(press, raw_inputs)| press.event.matches(raw_inputs, None));
+        let gaps_match = self.max_gap.map_or(true, |max_gap| {
+            let max_gap = forgiveness.scale(max_gap);
+            presses
+                .windows(2)
+                .all(|pair| pair[1].timestamp.duration_since(pair[0].timestamp) <= max_gap)
+        });
+
+        inputs_match && gaps_match
+    }
+}
+
+/// The registry of [`SecretSequence`]s, and the recent button presses that are compared to them
+///
+/// This resource is updated by the [`detect_secret_sequences`](crate::systems::detect_secret_sequences) system.
+#[derive(Resource, Debug, Default, Clone, PartialEq)]
+pub struct SecretSequences {
+    sequences: Vec<(String, SecretSequence)>,
+    recent_presses: VecDeque<CapturedInput>,
+}
+
+impl SecretSequences {
+    /// Registers the `sequence` under the provided `name`, replacing any sequence that already had this name
+    pub fn register(&mut self, name: impl Into<String>, sequence: SecretSequence) -> &mut Self {
+        let name = name.into();
+        self.unregister(&name);
+        self.sequences.push((name, sequence));
+        self
+    }
+
+    /// Removes the sequence registered under the provided `name`, returning it if it existed
+    pub fn unregister(&mut self, name: &str) -> Option<SecretSequence> {
+        let index = self
+            .sequences
+            .iter()
+            .position(|(existing, _)| existing == name)?;
+        Some(self.sequences.remove(index).1)
+    }
+
+    /// Returns the sequence registered under the provided `name`, if any
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<&SecretSequence> {
+        self.sequences
+            .iter()
+            .find(|(existing, _)| existing == name)
+            .map(|(_, sequence)| sequence)
+    }
+
+    /// Iterates over the names and sequences that are registered
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &SecretSequence)> {
+        self.sequences
+            .iter()
+            .map(|(name, sequence)| (name.as_str(), sequence))
+    }
+
+    /// Records a button `press`, returning the names of the sequences that it completed
+    ///
+    /// The maximum gap of each sequence is scaled by the `forgiveness`.
+    /// Once a sequence has been completed, the presses that made it up can't be used to complete another one.
+    pub fn record(&mut self, press: CapturedInput, forgiveness: &TimingForgiveness) -> Vec<String> {
+        let longest = self
+            .sequences
+            .iter()
+            .map(|(_, sequence)| sequence.inputs.len())
+            .max()
+            .unwrap_or(0);
+
+        self.recent_presses.push_back(press);
+        while self.recent_presses.len() > longest {
+            self.recent_presses.pop_front();
+        }
+
+        let matched: Vec<String> = self
+            .sequences
+            .iter()
+            .filter(|(_, sequence)| sequence.completed_by(&self.recent_presses, forgiveness))
+            .map(|(name, _)| name.clone())
+            .collect();
+
+        if !matched.is_empty() {
+            self.recent_presses.clear();
+        }
+
+        matched
+    }
+
+    /// Forgets all recent presses, so that partially entered sequences must be started again
+    pub fn reset(&mut self) {
+        self.recent_presses.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::input_capture::CapturedEvent;
+    use bevy::input::keyboard::KeyboardInput;
+    use bevy::input::ButtonState;
+    use bevy::prelude::{Entity, KeyCode};
+    use bevy::utils::Instant;
+
+    fn press(key_code: KeyCode, timestamp: Instant) -> CapturedInput {
+        CapturedInput {
+            event: CapturedEvent::Keyboard(KeyboardInput {
+                scan_code: 0,
+                key_code: Some(key_code),
+                state: ButtonState::Pressed,
+                window: Entity::PLACEHOLDER,
+            }),
+            timestamp,
+        }
+    }
+
+    #[test]
+    fn overlapping_prefixes() {
+        let mut secret_sequences = SecretSequences::default();
+        secret_sequences.register(
+            "up_up_down",
+            SecretSequence::new([KeyCode::Up, KeyCode::Up, KeyCode::Down]),
+        );
+
+        let forgiveness = TimingForgiveness::default();
+        let t0 = Instant::now();
+        assert!(secret_sequences
+            .record(press(KeyCode::Up, t0), &forgiveness)
+            .is_empty());
+        assert!(secret_sequences
+            .record(press(KeyCode::Up, t0), &forgiveness)
+            .is_empty());
+        // The extra press still leaves the last two presses as a valid start of the sequence
+        assert!(secret_sequences
+            .record(press(KeyCode::Up, t0), &forgiveness)
+            .is_empty());
+        assert_eq!(
+            secret_sequences.record(press(KeyCode::Down, t0), &forgiveness),
+            ["up_up_down"]
+        );
+
+        // Completed sequences start over
+        assert!(secret_sequences
+            .record(press(KeyCode::Down, t0), &forgiveness)
+            .is_empty());
+    }
+
+    #[test]
+    fn max_gap() {
+        let mut secret_sequences = SecretSequences::default();
+        secret_sequences.register(
+            "ab",
+            SecretSequence::new([KeyCode::A, KeyCode::B]).with_max_gap(Duration::from_secs(1)),
+        );
+
+        let forgiveness = TimingForgiveness::default();
+        let t0 = Instant::now();
+        secret_sequences.record(press(KeyCode::A, t0), &forgiveness);
+        assert!(secret_sequences
+            .record(press(KeyCode::B, t0 + Duration::from_secs(2)), &forgiveness)
+            .is_empty());
+
+        secret_sequences.record(press(KeyCode::A, t0 + Duration::from_secs(3)), &forgiveness);
+        assert_eq!(
+            secret_sequences.record(
+                press(KeyCode::B, t0 + Duration::from_millis(3500)),
+                &forgiveness
+            ),
+            ["ab"]
+        );
+
+        // Forgiveness lengthens the maximum gap
+        let forgiveness = TimingForgiveness::new(2.5);
+        secret_sequences.record(press(KeyCode::A, t0), &forgiveness);
+        assert_eq!(
+            secret_sequences.record(press(KeyCode::B, t0 + Duration::from_secs(2)), &forgiveness),
+            ["ab"]
+        );
+    }
+}
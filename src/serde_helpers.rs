@@ -0,0 +1,58 @@
+//! Containment module for serialization helpers shared by the configuration types
+//!
+//! Maps are written as sequences of `(key, value)` pairs,
+//! so that keys which are not strings (such as [`UserInput`](crate::user_input::UserInput)s) can be stored in any format.
+
+/// Serializes a [`HashMap`](bevy::utils::HashMap) of [`FloatOrd`](bevy::utils::FloatOrd) values as `(key, f32)` pairs
+///
+/// Use with `#[serde(with = "crate::serde_helpers::float_ord_pairs")]`.
+pub(crate) mod float_ord_pairs {
+    use bevy::utils::{FloatOrd, HashMap};
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::hash::Hash;
+
+    pub(crate) fn serialize<K: Serialize, S: Serializer>(
+        map: &HashMap<K, FloatOrd>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter().map(|(key, value)| (key, value.0)))
+    }
+
+    pub(crate) fn deserialize<'de, K, D>(deserializer: D) -> Result<HashMap<K, FloatOrd>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, f32)>::deserialize(deserializer)?;
+        Ok(pairs
+            .into_iter()
+            .map(|(key, value)| (key, FloatOrd(value)))
+            .collect())
+    }
+}
+
+/// Serializes a [`HashMap`](bevy::utils::HashMap) as `(key, value)` pairs
+///
+/// Use with `#[serde(with = "crate::serde_helpers::pairs")]`.
+pub(crate) mod pairs {
+    use bevy::utils::HashMap;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+    use std::hash::Hash;
+
+    pub(crate) fn serialize<K: Serialize, V: Serialize, S: Serializer>(
+        map: &HashMap<K, V>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(map.iter())
+    }
+
+    pub(crate) fn deserialize<'de, K, V, D>(deserializer: D) -> Result<HashMap<K, V>, D::Error>
+    where
+        K: Deserialize<'de> + Eq + Hash,
+        V: Deserialize<'de>,
+        D: Deserializer<'de>,
+    {
+        let pairs = Vec::<(K, V)>::deserialize(deserializer)?;
+        Ok(pairs.into_iter().collect())
+    }
+}
@@ -0,0 +1,127 @@
+//! Stable identifiers for the entities whose actions are sent over the network
+//!
+//! [`ActionDiff`](crate::action_state::ActionDiff)s are tagged with an identifier that both peers agree on,
+//! since [`Entity`] values are local to each [`World`].
+//! Any [`StableId`] can be used as this identifier: a `u64`, a UUID or your own type.
+//!
+//! The identifier does not need to be a component.
+//! Each peer can instead keep a [`StableIdRegistry`] resource that maps stable identifiers to its own entities,
+//! and use [`generate_registered_action_diffs`](crate::systems::generate_registered_action_diffs)
+//! and [`process_registered_action_diffs`](crate::systems::process_registered_action_diffs).
+
+use bevy::ecs::prelude::*;
+use bevy::utils::HashMap;
+use std::hash::Hash;
+
+/// An identifier for an entity that is shared by every peer, such as a client and a server
+///
+/// This trait is implemented for every type that meets its bounds.
+pub trait StableId: Clone + Eq + Hash + Send + Sync + 'static {}
+
+impl<T: Clone + Eq + Hash + Send + Sync + 'static> StableId for T {}
+
+/// A bidirectional map between the [`StableId`]s known by this peer and its local [`Entity`]s
+///
+/// Each stable identifier is mapped to at most one entity, and each entity to at most one stable identifier.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct StableIdRegistry<ID: StableId> {
+    entities: HashMap<ID, Entity>,
+    ids: HashMap<Entity, ID>,
+}
+
+impl<ID: StableId> Default for StableIdRegistry<ID> {
+    fn default() -> Self {
+        Self {
+            entities: HashMap::default(),
+            ids: HashMap::default(),
+        }
+    }
+}
+
+impl<ID: StableId> StableIdRegistry<ID> {
+    /// Maps the `id` to the `entity`
+    ///
+    /// Any previous mapping of either the `id` or the `entity` is removed.
+    pub fn insert(&mut self, id: ID, entity: Entity) {
+        self.remove_id(&id);
+        self.remove_entity(entity);
+        self.ids.insert(entity, id.clone());
+        self.entities.insert(id, entity);
+    }
+
+    /// Removes the mapping of the `id`, returning the [`Entity`] it was mapped to
+    pub fn remove_id(&mut self, id: &ID) -> Option<Entity> {
+        let entity = self.entities.remove(id)?;
+        self.ids.remove(&entity);
+        Some(entity)
+    }
+
+    /// Removes the mapping of the `entity`, returning the [`StableId`] it was mapped to
+    ///
+    /// Call this when the entity is despawned.
+    pub fn remove_entity(&mut self, entity: Entity) -> Option<ID> {
+        let id = self.ids.remove(&entity)?;
+        self.entities.remove(&id);
+        Some(id)
+    }
+
+    /// The [`Entity`] mapped to the `id`, if any
+    #[must_use]
+    pub fn entity(&self, id: &ID) -> Option<Entity> {
+        self.entities.get(id).copied()
+    }
+
+    /// The [`StableId`] mapped to the `entity`, if any
+    #[must_use]
+    pub fn id(&self, entity: Entity) -> Option<&ID> {
+        self.ids.get(&entity)
+    }
+
+    /// The number of mapped entities
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.ids.len()
+    }
+
+    /// Are no entities mapped?
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.ids.is_empty()
+    }
+
+    /// Iterates over each [`StableId`] and the [`Entity`] it is mapped to
+    pub fn iter(&self) -> impl Iterator<Item = (&ID, Entity)> {
+        self.entities.iter().map(|(id, entity)| (id, *entity))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StableIdRegistry;
+    use bevy::ecs::entity::Entity;
+
+    #[test]
+    fn insert_replaces_previous_mappings() {
+        let mut registry = StableIdRegistry::<u64>::default();
+        let first = Entity::from_raw(0);
+        let second = Entity::from_raw(1);
+
+        registry.insert(7, first);
+        assert_eq!(registry.entity(&7), Some(first));
+        assert_eq!(registry.id(first), Some(&7));
+
+        // Remapping the id frees the first entity
+        registry.insert(7, second);
+        assert_eq!(registry.entity(&7), Some(second));
+        assert_eq!(registry.id(first), None);
+
+        // Remapping the entity frees the old id
+        registry.insert(8, second);
+        assert_eq!(registry.entity(&7), None);
+        assert_eq!(registry.id(second), Some(&8));
+        assert_eq!(registry.len(), 1);
+
+        assert_eq!(registry.remove_entity(second), Some(8));
+        assert!(registry.is_empty());
+    }
+}
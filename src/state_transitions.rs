@@ -0,0 +1,194 @@
+//! Declaratively mapping action presses to transitions between Bevy [`States`]
+//!
+//! Most games switch between states such as menus and gameplay in response to a few actions.
+//! Rather than writing a system for each of these, list them in an [`ActionStateTransitions`] resource
+//! and add the [`apply_action_state_transitions`](crate::systems::apply_action_state_transitions) system for the action and state types.
+//!
+//! When an action is just pressed while in the `from` state of a transition, the [`NextState`](bevy::ecs::schedule::NextState) is set to its `to` state.
+//! The [`ActionState`](crate::action_state::ActionState) resource and every [`ActionState`](crate::action_state::ActionState) component are checked.
+//! Run the system in [`PreUpdate`](bevy::app::PreUpdate), after [`InputManagerSystem::ManualControl`](crate::plugin::InputManagerSystem::ManualControl),
+//! so that the new state is entered during the same frame.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::state_transitions::ActionStateTransitions;
+//! use leafwing_input_manager::systems::apply_action_state_transitions;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     OpenInventory,
+//!     Pause,
+//! }
+//!
+//! #[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+//! enum GameState {
+//!     #[default]
+//!     Playing,
+//!     Inventory,
+//!     Paused,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InputManagerPlugin::<Action>::default())
+//!     .add_state::<GameState>()
+//!     .insert_resource(
+//!         ActionStateTransitions::new()
+//!             .with(Action::OpenInventory, GameState::Playing, GameState::Inventory)
+//!             .with(Action::OpenInventory, GameState::Inventory, GameState::Playing)
+//!             .with(Action::Pause, GameState::Playing, GameState::Paused)
+//!             .with(Action::Pause, GameState::Paused, GameState::Playing),
+//!     )
+//!     .add_systems(
+//!         PreUpdate,
+//!         apply_action_state_transitions::<Action, GameState>
+//!             .after(InputManagerSystem::ManualControl),
+//!     );
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Resource;
+use bevy::ecs::schedule::States;
+
+/// A transition to the `to` state, taken when the `action` is just pressed while in the `from` state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionStateTransition<A: Actionlike, S: States> {
+    /// The action that triggers the transition
+    pub action: A,
+    /// The state that the transition is taken from
+    pub from: S,
+    /// The state that the transition leads to
+    pub to: S,
+}
+
+/// The transitions between the states `S` that are triggered by actions of type `A`
+///
+/// These are applied by the [`apply_action_state_transitions`](crate::systems::apply_action_state_transitions) system.
+/// See the [module documentation](crate::state_transitions) for an example.
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct ActionStateTransitions<A: Actionlike, S: States> {
+    transitions: Vec<ActionStateTransition<A, S>>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike, S: States> Default for ActionStateTransitions<A, S> {
+    fn default() -> Self {
+        Self {
+            transitions: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike, S: States> ActionStateTransitions<A, S> {
+    /// Creates an empty set of transitions
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns these transitions with a transition `from` one state `to` another when the `action` is just pressed
+    #[must_use]
+    pub fn with(mut self, action: A, from: S, to: S) -> Self {
+        self.insert(action, from, to);
+        self
+    }
+
+    /// Adds a transition `from` one state `to` another when the `action` is just pressed
+    ///
+    /// If several transitions from the same state are triggered during the same frame, the first one added is taken.
+    pub fn insert(&mut self, action: A, from: S, to: S) {
+        self.transitions
+            .push(ActionStateTransition { action, from, to });
+    }
+
+    /// Removes every transition triggered by the `action` while in the `from` state
+    pub fn remove(&mut self, action: A, from: &S) {
+        self.transitions.retain(|transition| {
+            transition.action.index() != action.index() || transition.from != *from
+        });
+    }
+
+    /// Iterates over the transitions, in the order that they were added
+    pub fn iter(&self) -> impl Iterator<Item = &ActionStateTransition<A, S>> {
+        self.transitions.iter()
+    }
+
+    /// The state that should be entered from the `current` state, if one of its actions was just pressed in the `action_state`
+    #[must_use]
+    pub fn next_state(&self, current: &S, action_state: &ActionState<A>) -> Option<&S> {
+        self.transitions
+            .iter()
+            .find(|transition| {
+                transition.from == *current && action_state.just_pressed(transition.action.clone())
+            })
+            .map(|transition| &transition.to)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        OpenInventory,
+        Pause,
+    }
+
+    #[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum GameState {
+        #[default]
+        Playing,
+        Inventory,
+        Paused,
+    }
+
+    #[test]
+    fn transitions_depend_on_the_current_state() {
+        let mut transitions = ActionStateTransitions::new()
+            .with(Action::Pause, GameState::Playing, GameState::Paused)
+            .with(Action::Pause, GameState::Paused, GameState::Playing)
+            .with(
+                Action::OpenInventory,
+                GameState::Playing,
+                GameState::Inventory,
+            );
+
+        let mut action_state = ActionState::<Action>::default();
+        assert_eq!(
+            transitions.next_state(&GameState::Playing, &action_state),
+            None
+        );
+
+        action_state.press(Action::Pause);
+        assert_eq!(
+            transitions.next_state(&GameState::Playing, &action_state),
+            Some(&GameState::Paused)
+        );
+        assert_eq!(
+            transitions.next_state(&GameState::Paused, &action_state),
+            Some(&GameState::Playing)
+        );
+        assert_eq!(
+            transitions.next_state(&GameState::Inventory, &action_state),
+            None
+        );
+
+        // The first transition added wins
+        action_state.press(Action::OpenInventory);
+        assert_eq!(
+            transitions.next_state(&GameState::Playing, &action_state),
+            Some(&GameState::Paused)
+        );
+
+        transitions.remove(Action::Pause, &GameState::Playing);
+        assert_eq!(
+            transitions.next_state(&GameState::Playing, &action_state),
+            Some(&GameState::Inventory)
+        );
+    }
+}
@@ -0,0 +1,495 @@
+//! Gestures performed with gamepad sticks, which can be bound like buttons
+//!
+//! Some mechanics care about how a stick moves, rather than where it currently points.
+//! A [`Flick`] is pressed during the frame that a stick is moved quickly from its center to its edge,
+//! which is used for flick-stick aiming and quick-turns.
+//! A [`StickRotation`] is pressed while a stick is rotated in circles, and its value is the speed of the rotation,
+//! which is used for minigames such as reeling in a fish or picking a lock.
+//!
+//! Gestures are detected from the recent positions of each stick, which are stored in the [`StickHistory`] resource
+//! by the [`record_stick_history`](crate::systems::record_stick_history) system.
+//! Only gamepad sticks are recorded: gestures bound to other axes are never pressed.
+//! Gestures cannot be pressed using [`MockInput::send_input`](crate::input_mocking::MockInput::send_input):
+//! move the stick over several frames instead.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::orientation::RotationDirection;
+//! use leafwing_input_manager::stick_gestures::{Flick, FlickDirection, StickRotation};
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Aim,
+//!     QuickTurn,
+//!     ReelIn,
+//! }
+//!
+//! let mut input_map = InputMap::new([
+//!     // The axis pair of a flick points in the direction that the stick was flicked
+//!     (Flick::new(DualAxis::right_stick()), Action::Aim),
+//!     (
+//!         Flick::new(DualAxis::left_stick())
+//!             .with_direction(FlickDirection::Down)
+//!             .with_window(Duration::from_millis(80)),
+//!         Action::QuickTurn,
+//!     ),
+//! ]);
+//! // The value of a stick rotation is its speed, in turns per second
+//! input_map.insert(
+//!     StickRotation::new(DualAxis::left_stick()).with_direction(RotationDirection::Clockwise),
+//!     Action::ReelIn,
+//! );
+//! ```
+
+use crate::axislike::{AxisType, DualAxis, SingleAxis};
+use crate::orientation::RotationDirection;
+
+use bevy::ecs::prelude::Resource;
+use bevy::input::gamepad::{Gamepad, GamepadAxis, GamepadAxisType};
+use bevy::math::Vec2;
+use bevy::utils::{Duration, FloatOrd, HashMap};
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::f32::consts::{PI, TAU};
+use std::hash::{Hash, Hasher};
+
+/// A quick movement of a stick from its center past the `threshold`, pressed during the frame that the `threshold` is crossed
+///
+/// The center of the stick is the deadzone of its axes.
+/// The axis pair of a [`Flick`] is the position of the stick when it was flicked.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct Flick {
+    /// The stick that is flicked
+    pub stick: DualAxis,
+    /// The direction that the stick must be flicked in, or [`None`] to accept any direction
+    pub direction: Option<FlickDirection>,
+    /// The distance from the center that the stick must reach, between `0.0` and `1.0`
+    pub threshold: f32,
+    /// How quickly the stick must move from its center past the `threshold`
+    pub window: Duration,
+}
+
+impl Flick {
+    /// The default distance that the stick must reach
+    pub const DEFAULT_THRESHOLD: f32 = 0.9;
+
+    /// The default time within which the stick must reach the threshold
+    pub const DEFAULT_WINDOW: Duration = Duration::from_millis(100);
+
+    /// Creates a [`Flick`] of the `stick` in any direction, with the default threshold and window
+    #[must_use]
+    pub const fn new(stick: DualAxis) -> Flick {
+        Flick {
+            stick,
+            direction: None,
+            threshold: Self::DEFAULT_THRESHOLD,
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
+
+    /// Returns this [`Flick`], only accepting flicks in the `direction`
+    #[must_use]
+    pub const fn with_direction(mut self, direction: FlickDirection) -> Flick {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Returns this [`Flick`] with the distance that the stick must reach set to `threshold`
+    #[must_use]
+    pub const fn with_threshold(mut self, threshold: f32) -> Flick {
+        self.threshold = threshold;
+        self
+    }
+
+    /// Returns this [`Flick`] with the time within which the stick must reach the threshold set to `window`
+    #[must_use]
+    pub const fn with_window(mut self, window: Duration) -> Flick {
+        self.window = window;
+        self
+    }
+
+    /// Returns the position of the stick of the `gamepad`, if it was flicked during the latest frame of the `history`
+    #[must_use]
+    pub fn detect(&self, history: &StickHistory, gamepad: Gamepad) -> Option<Vec2> {
+        let positions: Vec<(Duration, Vec2)> = history.positions(gamepad, &self.stick).collect();
+        let [.., (_, previous), (now, current)] = positions[..] else {
+            return None;
+        };
+
+        if current.length() < self.threshold || previous.length() >= self.threshold {
+            return None;
+        }
+        if self
+            .direction
+            .is_some_and(|direction| direction != FlickDirection::of(current))
+        {
+            return None;
+        }
+
+        let started_in_center = positions
+            .iter()
+            .rev()
+            .skip(1)
+            .take_while(|(time, _)| now.saturating_sub(*time) <= self.window)
+            .any(|(_, position)| in_center(&self.stick, *position));
+        started_in_center.then_some(current)
+    }
+}
+
+impl PartialEq for Flick {
+    fn eq(&self, other: &Self) -> bool {
+        self.stick == other.stick
+            && self.direction == other.direction
+            && FloatOrd(self.threshold) == FloatOrd(other.threshold)
+            && self.window == other.window
+    }
+}
+impl Eq for Flick {}
+impl Hash for Flick {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.stick.hash(state);
+        self.direction.hash(state);
+        FloatOrd(self.threshold).hash(state);
+        self.window.hash(state);
+    }
+}
+
+/// The direction of a [`Flick`], each covering a quarter of the stick's range of motion
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum FlickDirection {
+    /// Towards positive y
+    Up,
+    /// Towards negative y
+    Down,
+    /// Towards negative x
+    Left,
+    /// Towards positive x
+    Right,
+}
+
+impl FlickDirection {
+    /// Returns the direction that the `position` is closest to
+    #[must_use]
+    pub fn of(position: Vec2) -> FlickDirection {
+        if position.x.abs() > position.y.abs() {
+            if position.x > 0.0 {
+                FlickDirection::Right
+            } else {
+                FlickDirection::Left
+            }
+        } else if position.y > 0.0 {
+            FlickDirection::Up
+        } else {
+            FlickDirection::Down
+        }
+    }
+}
+
+/// A continuous circular motion of a stick, pressed while the stick has turned through at least the `angle` during the last `window`
+///
+/// Only the motion of the stick outside of its deadzone counts towards the angle,
+/// and the gesture is released as soon as the stick returns to its center.
+/// The value of a [`StickRotation`] is the speed of the rotation during the `window`, in turns per second.
+/// Counterclockwise rotations have positive speeds and clockwise rotations have negative speeds,
+/// unless a `direction` is set, in which case the speed is always positive.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize)]
+pub struct StickRotation {
+    /// The stick that is rotated
+    pub stick: DualAxis,
+    /// The direction that the stick must be rotated in, or [`None`] to accept either direction
+    pub direction: Option<RotationDirection>,
+    /// The angle that the stick must turn through during the `window`, in radians
+    pub angle: f32,
+    /// The duration over which the angle is accumulated
+    pub window: Duration,
+}
+
+impl StickRotation {
+    /// The default angle that the stick must turn through: half of a turn
+    pub const DEFAULT_ANGLE: f32 = PI;
+
+    /// The default duration over which the angle is accumulated
+    pub const DEFAULT_WINDOW: Duration = Duration::from_millis(500);
+
+    /// Creates a [`StickRotation`] of the `stick` in either direction, with the default angle and window
+    #[must_use]
+    pub const fn new(stick: DualAxis) -> StickRotation {
+        StickRotation {
+            stick,
+            direction: None,
+            angle: Self::DEFAULT_ANGLE,
+            window: Self::DEFAULT_WINDOW,
+        }
+    }
+
+    /// Returns this [`StickRotation`], only accepting rotations in the `direction`
+    #[must_use]
+    pub const fn with_direction(mut self, direction: RotationDirection) -> StickRotation {
+        self.direction = Some(direction);
+        self
+    }
+
+    /// Returns this [`StickRotation`] with the angle that the stick must turn through set to `angle`, in radians
+    #[must_use]
+    pub const fn with_angle(mut self, angle: f32) -> StickRotation {
+        self.angle = angle;
+        self
+    }
+
+    /// Returns this [`StickRotation`] with the duration over which the angle is accumulated set to `window`
+    #[must_use]
+    pub const fn with_window(mut self, window: Duration) -> StickRotation {
+        self.window = window;
+        self
+    }
+
+    /// Returns the speed of the rotation of the stick of the `gamepad` in turns per second, if it is being rotated during the latest frame of the `history`
+    #[must_use]
+    pub fn detect(&self, history: &StickHistory, gamepad: Gamepad) -> Option<f32> {
+        let positions: Vec<(Duration, Vec2)> = history.positions(gamepad, &self.stick).collect();
+        let (now, current) = *positions.last()?;
+        if in_center(&self.stick, current) {
+            return None;
+        }
+
+        let recent: Vec<Vec2> = positions
+            .iter()
+            .filter(|(time, _)| now.saturating_sub(*time) <= self.window)
+            .map(|(_, position)| *position)
+            .collect();
+        let angle: f32 = recent
+            .windows(2)
+            .filter(|pair| !in_center(&self.stick, pair[0]) && !in_center(&self.stick, pair[1]))
+            .map(|pair| pair[0].angle_between(pair[1]))
+            .sum();
+
+        let direction = if angle > 0.0 {
+            RotationDirection::CounterClockwise
+        } else {
+            RotationDirection::Clockwise
+        };
+        if angle.abs() < self.angle || self.direction.is_some_and(|wanted| wanted != direction) {
+            return None;
+        }
+
+        let speed = angle / TAU / self.window.as_secs_f32();
+        Some(if self.direction.is_some() {
+            speed.abs()
+        } else {
+            speed
+        })
+    }
+}
+
+impl PartialEq for StickRotation {
+    fn eq(&self, other: &Self) -> bool {
+        self.stick == other.stick
+            && self.direction == other.direction
+            && FloatOrd(self.angle) == FloatOrd(other.angle)
+            && self.window == other.window
+    }
+}
+impl Eq for StickRotation {}
+impl Hash for StickRotation {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.stick.hash(state);
+        self.direction.hash(state);
+        FloatOrd(self.angle).hash(state);
+        self.window.hash(state);
+    }
+}
+
+/// Is the `position` inside of the deadzone of both axes of the `stick`?
+fn in_center(stick: &DualAxis, position: Vec2) -> bool {
+    in_deadzone(&stick.x, position.x) && in_deadzone(&stick.y, position.y)
+}
+
+/// Is the `value` inside of the deadzone of the `axis`?
+fn in_deadzone(axis: &SingleAxis, value: f32) -> bool {
+    value >= axis.negative_low && value <= axis.positive_low
+}
+
+/// The recent positions of the sticks of every gamepad, used to detect stick gestures
+///
+/// This resource is updated by the [`record_stick_history`](crate::systems::record_stick_history) system,
+/// and read when updating the [`ActionState`](crate::action_state::ActionState).
+/// Positions older than the [retention](StickHistory::retention) are forgotten,
+/// so gestures with longer windows will never be detected.
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct StickHistory {
+    samples: VecDeque<StickSample>,
+    retention: Duration,
+}
+
+/// The values of the stick axes of every gamepad during a single frame
+#[derive(Debug, Clone, PartialEq)]
+struct StickSample {
+    time: Duration,
+    axes: HashMap<GamepadAxis, f32>,
+}
+
+impl Default for StickHistory {
+    fn default() -> Self {
+        Self {
+            samples: VecDeque::new(),
+            retention: Self::DEFAULT_RETENTION,
+        }
+    }
+}
+
+impl StickHistory {
+    /// The default duration for which positions are kept
+    pub const DEFAULT_RETENTION: Duration = Duration::from_secs(1);
+
+    /// The axes that are recorded for each gamepad
+    pub const STICK_AXES: [GamepadAxisType; 4] = [
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        GamepadAxisType::RightStickX,
+        GamepadAxisType::RightStickY,
+    ];
+
+    /// How long positions are kept for
+    #[must_use]
+    pub fn retention(&self) -> Duration {
+        self.retention
+    }
+
+    /// Sets how long positions are kept for, which limits the window of the gestures that can be detected
+    pub fn set_retention(&mut self, retention: Duration) {
+        self.retention = retention;
+    }
+
+    /// Records the values of the stick `axes` at the `time` since startup, forgetting positions older than the retention
+    pub fn record(&mut self, time: Duration, axes: impl IntoIterator<Item = (GamepadAxis, f32)>) {
+        self.samples.push_back(StickSample {
+            time,
+            axes: axes.into_iter().collect(),
+        });
+        while self
+            .samples
+            .front()
+            .is_some_and(|sample| time.saturating_sub(sample.time) > self.retention)
+        {
+            self.samples.pop_front();
+        }
+    }
+
+    /// Forgets every recorded position
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+
+    /// The recorded positions of the `stick` of the `gamepad` and the time that they were recorded at, from oldest to newest
+    ///
+    /// Inverted axes are taken into account, but the deadzones of the `stick` are not applied.
+    /// Sticks that are not made of gamepad axes have no recorded positions.
+    pub fn positions<'a>(
+        &'a self,
+        gamepad: Gamepad,
+        stick: &DualAxis,
+    ) -> impl Iterator<Item = (Duration, Vec2)> + 'a {
+        let axis_value = move |sample: &StickSample, axis: &SingleAxis| {
+            let AxisType::Gamepad(axis_type) = axis.axis_type else {
+                return None;
+            };
+            let value = sample
+                .axes
+                .get(&GamepadAxis { gamepad, axis_type })
+                .copied()
+                .unwrap_or_default();
+            Some(if axis.inverted { -value } else { value })
+        };
+        let (x, y) = (stick.x, stick.y);
+
+        self.samples.iter().filter_map(move |sample| {
+            Some((
+                sample.time,
+                Vec2::new(axis_value(sample, &x)?, axis_value(sample, &y)?),
+            ))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GAMEPAD: Gamepad = Gamepad { id: 0 };
+
+    fn record(history: &mut StickHistory, millis: u64, x: f32, y: f32) {
+        history.record(
+            Duration::from_millis(millis),
+            [
+                (
+                    GamepadAxis {
+                        gamepad: GAMEPAD,
+                        axis_type: GamepadAxisType::RightStickX,
+                    },
+                    x,
+                ),
+                (
+                    GamepadAxis {
+                        gamepad: GAMEPAD,
+                        axis_type: GamepadAxisType::RightStickY,
+                    },
+                    y,
+                ),
+            ],
+        );
+    }
+
+    #[test]
+    fn flicks() {
+        let flick = Flick::new(DualAxis::right_stick()).with_window(Duration::from_millis(50));
+        let up = flick.with_direction(FlickDirection::Up);
+        let mut history = StickHistory::default();
+
+        // A quick flick to the right
+        record(&mut history, 0, 0.0, 0.0);
+        record(&mut history, 16, 0.5, 0.0);
+        assert_eq!(flick.detect(&history, GAMEPAD), None);
+        record(&mut history, 32, 1.0, 0.0);
+        assert_eq!(flick.detect(&history, GAMEPAD), Some(Vec2::new(1.0, 0.0)));
+        assert_eq!(up.detect(&history, GAMEPAD), None);
+
+        // Holding the stick doesn't flick it again
+        record(&mut history, 48, 1.0, 0.0);
+        assert_eq!(flick.detect(&history, GAMEPAD), None);
+
+        // Slowly moving the stick isn't a flick
+        record(&mut history, 100, 0.0, 0.0);
+        record(&mut history, 140, 0.0, 0.5);
+        record(&mut history, 180, 0.0, 1.0);
+        assert_eq!(up.detect(&history, GAMEPAD), None);
+    }
+
+    #[test]
+    fn stick_rotations() {
+        let rotation = StickRotation::new(DualAxis::right_stick()).with_angle(3.0);
+        let clockwise = rotation.with_direction(RotationDirection::Clockwise);
+        let mut history = StickHistory::default();
+
+        // Half of a turn counterclockwise, over half of a second
+        record(&mut history, 0, 1.0, 0.0);
+        record(&mut history, 250, 0.0, 1.0);
+        assert_eq!(rotation.detect(&history, GAMEPAD), None);
+        record(&mut history, 500, -1.0, 0.0);
+        let speed = rotation.detect(&history, GAMEPAD).unwrap();
+        assert!((speed - 1.0).abs() < 1e-4);
+        assert_eq!(clockwise.detect(&history, GAMEPAD), None);
+
+        // Returning the stick to its center stops the rotation
+        record(&mut history, 600, 0.0, 0.0);
+        assert_eq!(rotation.detect(&history, GAMEPAD), None);
+
+        // Clockwise rotations have negative speeds, unless the direction is set
+        record(&mut history, 700, 0.0, 1.0);
+        record(&mut history, 800, 1.0, 0.0);
+        record(&mut history, 900, 0.0, -1.0);
+        assert!(rotation.detect(&history, GAMEPAD).unwrap() < 0.0);
+        assert!(clockwise.detect(&history, GAMEPAD).unwrap() > 0.0);
+    }
+}
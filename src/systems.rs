@@ -1,97 +1,817 @@
 //! The systems that power each [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
 
 #[cfg(feature = "ui")]
-use crate::action_state::ActionStateDriver;
+use crate::action_state::DriverTrigger;
+#[cfg(feature = "stats")]
+use crate::action_stats::ActionStats;
+#[cfg(feature = "asset")]
+use crate::control_scheme::{AppliedControlScheme, ControlScheme, SchemeMergePolicy};
+use crate::errors::{report, InputManagerError};
 use crate::{
-    action_state::{ActionDiff, ActionState},
+    action_hooks::{ActionHookContext, ActionHooks, ActionTransition},
+    action_state::{ActionData, ActionDiff, ActionState, ActionStateDriver, DriverTargetDespawned},
+    action_writer::{ActionWriter, ExternallyControlled},
+    authority::ActionAuthority,
+    auto_repeat::AutoRepeats,
+    binding_capture::{BindingCapture, BindingCaptured},
+    binding_profiles::{BindingProfileSelected, BindingProfiles, ControllerIdentity},
+    block_input::{BlockInput, InputBlockers},
     clashing_inputs::ClashStrategy,
-    input_map::InputMap,
+    custom_input::{CustomInputs, RawInputEvent},
+    diff_sequencing::{
+        DiffSequenceTracker, DiffSequencer, MissedInput, SequenceCheck, SequencedActionDiff,
+    },
+    diff_transport::{DiffReceiver, DiffSender},
+    drag::DragStates,
+    exclusive_groups::ExclusiveGroups,
+    ghosting::KeyboardMatrix,
+    hold_assist::HoldAssist,
+    hold_for::HoldRequirements,
+    idle::{AnyInputDetected, InputActivity, InputIdle},
+    ime::ImeActive,
+    input_capture::{CapturedEvent, CapturedInput, CapturedInputs, RawEventTimestamps},
+    input_map::{InputMap, InputSource, WhichPressedBuffer},
+    input_prompt::{InputPrompt, InputPromptEvent},
     input_streams::InputStreams,
+    just_persistence::PersistentJustFlags,
+    key_repeat::KeyRepeats,
+    latching::LatchedActions,
+    mirror::MirrorActionState,
+    missing_components::{
+        MissingComponent, MissingComponentKind, MissingComponentPolicies, MissingComponentPolicy,
+    },
+    navigation_deadband::NavigationDeadbands,
     plugin::ToggleActions,
     press_scheduler::PressScheduler,
+    radial_menu::{RadialMenu, RadialSelectionChanged},
+    rate_limits::{RateLimited, RateLimits},
+    replay::{ActionPlayback, ActionRecording},
+    secret_sequences::{SecretSequenceMatched, SecretSequences},
+    stable_id::{StableId, StableIdRegistry},
+    state_transitions::ActionStateTransitions,
+    stick_gestures::StickHistory,
+    timing::{ActionTimings, InputClock, Timing},
+    timing_forgiveness::TimingForgiveness,
+    user_input::InputKind,
+    value_thresholds::{ValueThresholdCrossed, ValueThresholds},
+    virtual_cursor::{VirtualCursor, VirtualCursorSettings},
+    virtual_input::VirtualInput,
+    window_events::{WindowEventInputs, WindowEventKind},
     Actionlike,
 };
 
+#[cfg(feature = "renet")]
+use crate::diff_transport::renet::{Received, RenetConnection, RenetTransport};
+#[cfg(feature = "asset")]
+use bevy::asset::{AssetEvent, Assets, Handle};
+use bevy::ecs::entity::Entities;
+use bevy::ecs::event::ManualEventReader;
+#[cfg(feature = "mouse")]
+use bevy::input::mouse::{MouseMotion, MouseWheel};
 use bevy::input::{
-    gamepad::{GamepadAxis, GamepadButton, Gamepads},
-    keyboard::KeyCode,
-    mouse::{MouseButton, MouseMotion, MouseWheel},
+    gamepad::{
+        GamepadAxis, GamepadAxisChangedEvent, GamepadButton, GamepadButtonChangedEvent,
+        GamepadConnection, GamepadConnectionEvent, Gamepads,
+    },
+    keyboard::{KeyCode, KeyboardInput},
+    mouse::{MouseButton, MouseButtonInput},
     Axis, Input,
 };
+#[cfg(feature = "trace")]
+use bevy::log::info_span;
+use bevy::log::warn;
+use bevy::math::Vec2;
+#[cfg(feature = "asset")]
+use bevy::reflect::TypeUuid;
 use bevy::time::Time;
 use bevy::utils::Instant;
+use bevy::window::{
+    FileDragAndDrop, Ime, PrimaryWindow, Window, WindowCloseRequested, WindowFocused,
+};
 use bevy::{ecs::prelude::*, prelude::ScanCode};
+#[cfg(feature = "renet")]
+use serde::{de::DeserializeOwned, Serialize};
+use std::cell::RefCell;
+use thread_local::ThreadLocal;
 
+#[cfg(feature = "ui")]
+use crate::{
+    action_metadata::ActionMetadata,
+    binding_capture::CaptureOutcome,
+    input_overlay::InputOverlay,
+    rebinding_menu::{BindingLabel, RebindButton, RebindingMenu},
+    ui_navigation::{self, Focusable, UiNavigationActions, UiNavigationEvent},
+};
+#[cfg(feature = "ui")]
+use bevy::a11y::Focus;
+#[cfg(feature = "ui")]
+use bevy::hierarchy::BuildChildren;
+#[cfg(feature = "ui")]
+use bevy::text::Text;
+#[cfg(feature = "ui")]
+use bevy::transform::components::GlobalTransform;
 #[cfg(feature = "ui")]
 use bevy::ui::Interaction;
+#[cfg(feature = "ui")]
+use bevy::utils::HashMap;
 #[cfg(feature = "egui")]
 use bevy_egui::EguiContexts;
 
+/// Latches the presses of the actions configured in [`LatchedActions`], for each [`ActionState`] with [`LatchedActions`]
+///
+/// This runs right before [`tick_action_state`], so that presses are latched before they stop being just pressed.
+pub fn latch_presses<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, &LatchedActions<A>)>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    latched_actions: Option<Res<LatchedActions<A>>>,
+) {
+    let resources = match (action_state, latched_actions) {
+        (Some(action_state), Some(latched_actions)) => {
+            Some((Mut::from(action_state), latched_actions.into_inner()))
+        }
+        _ => None,
+    };
+
+    for (mut action_state, latched_actions) in query.iter_mut().chain(resources) {
+        latched_actions.update(&mut action_state);
+    }
+}
+
 /// Advances actions timer.
 ///
+/// Time is read from the [`InputClock`], if one exists, and otherwise from Bevy's [`Time`].
 /// Clears the just-pressed and just-released values of all [`ActionState`]s.
 /// Also resets the internal `pressed_this_tick` field, used to track whether or not to release an action.
+/// [`ActionState`]s with an [`ActionTimings`] are [ticked with timings](ActionState::tick_with_timings),
+/// and those with [`PersistentJustFlags`] keep their `just_*` flags according to its [`JustPersistence`](crate::just_persistence::JustPersistence).
 pub fn tick_action_state<A: Actionlike>(
-    mut query: Query<&mut ActionState<A>>,
+    mut query: Query<(
+        &mut ActionState<A>,
+        Option<&mut ActionTimings<A>>,
+        Option<&mut PersistentJustFlags<A>>,
+    )>,
     action_state: Option<ResMut<ActionState<A>>>,
+    action_timings: Option<ResMut<ActionTimings<A>>>,
+    just_flags: Option<ResMut<PersistentJustFlags<A>>>,
     time: Res<Time>,
+    clock: Option<Res<InputClock>>,
     mut stored_previous_instant: Local<Option<Instant>>,
 ) {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("tick_action_state", action = std::any::type_name::<A>()).entered();
+
+    let clock = clock.as_deref().copied().unwrap_or_default();
     // If this is the very first tick, measure from the start of the app
-    let current_instant = time.last_update().unwrap_or_else(|| time.startup());
-    let previous_instant = stored_previous_instant.unwrap_or_else(|| time.startup());
+    let current_instant = clock.now(&time).unwrap_or_else(|| clock.startup(&time));
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| clock.startup(&time));
 
-    // Only tick the ActionState resource if it exists
-    if let Some(mut action_state) = action_state {
-        action_state.tick(current_instant, previous_instant);
-    }
+    let resource = action_state.map(|action_state| {
+        (
+            Mut::from(action_state),
+            action_timings.map(Mut::from),
+            just_flags.map(Mut::from),
+        )
+    });
 
-    // Only tick the ActionState components if they exist
-    for mut action_state in query.iter_mut() {
+    // Only tick the ActionState resource and components if they exist
+    for (mut action_state, action_timings, just_flags) in query.iter_mut().chain(resource) {
         // If `Time` has not ever been advanced, something has gone horribly wrong
         // and the user probably forgot to add the `core_plugin`.
-        action_state.tick(current_instant, previous_instant);
+        if let Some(mut action_timings) = action_timings {
+            action_timings.tick(&action_state, current_instant, previous_instant);
+        }
+        match just_flags {
+            Some(mut just_flags) => {
+                just_flags.tick(&mut action_state, current_instant, previous_instant);
+            }
+            None => action_state.tick(current_instant, previous_instant),
+        }
     }
 
     // Store the previous time in the system
-    *stored_previous_instant = time.last_update();
+    *stored_previous_instant = clock.now(&time);
 }
 
-/// Fetches all of the relevant [`Input`] resources to update [`ActionState`] according to the [`InputMap`].
+/// Counts the repeats of held actions that became due during this tick, for each [`ActionState`] with [`AutoRepeats`] and [`ActionTimings`]
 ///
-/// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed.
+/// This runs right after [`tick_action_state`], with the same instants.
+pub fn count_auto_repeats<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &ActionTimings<A>, &mut AutoRepeats<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_timings: Option<Res<ActionTimings<A>>>,
+    auto_repeats: Option<ResMut<AutoRepeats<A>>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+    mut stored_previous_instant: Local<Option<Instant>>,
+) {
+    let clock = clock.as_deref().copied().unwrap_or_default();
+    let current_instant = clock.now(&time).unwrap_or_else(|| clock.startup(&time));
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| clock.startup(&time));
+
+    let resources = match (action_state, action_timings, auto_repeats) {
+        (Some(action_state), Some(action_timings), Some(auto_repeats)) => Some((
+            action_state.into_inner(),
+            action_timings.into_inner(),
+            Mut::from(auto_repeats),
+        )),
+        _ => None,
+    };
+
+    for (action_state, action_timings, mut auto_repeats) in query.iter_mut().chain(resources) {
+        auto_repeats.tick(
+            action_state,
+            action_timings,
+            current_instant,
+            previous_instant,
+        );
+    }
+
+    *stored_previous_instant = clock.now(&time);
+}
+
+/// Records the holds that were completed during this tick, for each [`ActionState`] with [`HoldRequirements`] and [`ActionTimings`]
+///
+/// This runs right after [`tick_action_state`], with the same instants.
+pub fn record_hold_completions<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &ActionTimings<A>, &mut HoldRequirements<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_timings: Option<Res<ActionTimings<A>>>,
+    hold_requirements: Option<ResMut<HoldRequirements<A>>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+    mut stored_previous_instant: Local<Option<Instant>>,
+) {
+    let clock = clock.as_deref().copied().unwrap_or_default();
+    let current_instant = clock.now(&time).unwrap_or_else(|| clock.startup(&time));
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| clock.startup(&time));
+
+    let resources = match (action_state, action_timings, hold_requirements) {
+        (Some(action_state), Some(action_timings), Some(hold_requirements)) => Some((
+            action_state.into_inner(),
+            action_timings.into_inner(),
+            Mut::from(hold_requirements),
+        )),
+        _ => None,
+    };
+
+    for (action_state, action_timings, mut hold_requirements) in query.iter_mut().chain(resources) {
+        hold_requirements.tick(
+            action_state,
+            action_timings,
+            current_instant,
+            previous_instant,
+        );
+    }
+
+    *stored_previous_instant = clock.now(&time);
+}
+
+/// Adds an [`ActionTimings`] to each entity that is given an [`ActionState`], and alongside the [`ActionState`] resource
+///
+/// Entities that already have an [`ActionTimings`] are left untouched.
+/// This system is not added for plugins created with [`InputManagerPlugin::without_timing`](crate::plugin::InputManagerPlugin::without_timing).
+pub fn insert_action_timings<A: Actionlike>(
+    mut commands: Commands,
+    query: Query<Entity, (Added<ActionState<A>>, Without<ActionTimings<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_timings: Option<Res<ActionTimings<A>>>,
+) {
+    for entity in query.iter() {
+        commands
+            .entity(entity)
+            .insert(ActionTimings::<A>::default());
+    }
+
+    if action_state.is_some() && action_timings.is_none() {
+        commands.init_resource::<ActionTimings<A>>();
+    }
+}
+
+/// Records the window events received during this frame in the [`WindowEventInputs`] resource
+///
+/// Missing event resources will be ignored, and treated as if no events of that kind were received.
+pub fn collect_window_events(
+    mut window_events: ResMut<WindowEventInputs>,
+    close_requested: Option<Res<Events<WindowCloseRequested>>>,
+    mut close_requested_reader: Local<ManualEventReader<WindowCloseRequested>>,
+    focused: Option<Res<Events<WindowFocused>>>,
+    mut focused_reader: Local<ManualEventReader<WindowFocused>>,
+    file_drag_and_drop: Option<Res<Events<FileDragAndDrop>>>,
+    mut file_drag_and_drop_reader: Local<ManualEventReader<FileDragAndDrop>>,
+) {
+    window_events.clear();
+
+    if let Some(close_requested) = close_requested {
+        if close_requested_reader.iter(&close_requested).count() > 0 {
+            window_events.insert(WindowEventKind::CloseRequested);
+        }
+    }
+
+    if let Some(focused) = focused {
+        for event in focused_reader.iter(&focused) {
+            window_events.insert(if event.focused {
+                WindowEventKind::FocusGained
+            } else {
+                WindowEventKind::FocusLost
+            });
+        }
+    }
+
+    if let Some(file_drag_and_drop) = file_drag_and_drop {
+        for event in file_drag_and_drop_reader.iter(&file_drag_and_drop) {
+            if matches!(event, FileDragAndDrop::DroppedFile { .. }) {
+                window_events.insert(WindowEventKind::FileDropped);
+            }
+        }
+    }
+}
+
+/// Stores the latest value of each custom input sent using [`RawInputEvent`]s in the [`CustomInputs`] resource
+pub fn collect_raw_input_events(
+    mut custom_inputs: ResMut<CustomInputs>,
+    events: Option<Res<Events<RawInputEvent>>>,
+    mut reader: Local<ManualEventReader<RawInputEvent>>,
+) {
+    let Some(events) = events else {
+        return;
+    };
+
+    for event in reader.iter(&events) {
+        custom_inputs.set(event.id, event.value);
+    }
+}
+
+/// Records the position of the sticks of each connected gamepad in the [`StickHistory`] resource
+///
+/// Nothing is recorded in worlds without Bevy's gamepad resources.
+pub fn record_stick_history(
+    mut stick_history: ResMut<StickHistory>,
+    time: Res<Time>,
+    gamepads: Option<Res<Gamepads>>,
+    gamepad_axes: Option<Res<Axis<GamepadAxis>>>,
+) {
+    let (Some(gamepads), Some(gamepad_axes)) = (gamepads, gamepad_axes) else {
+        return;
+    };
+
+    let axes = gamepads.iter().flat_map(|gamepad| {
+        StickHistory::STICK_AXES.map(|axis_type| GamepadAxis { gamepad, axis_type })
+    });
+
+    stick_history.record(
+        time.elapsed(),
+        axes.map(|axis| (axis, gamepad_axes.get(axis).unwrap_or_default())),
+    );
+}
+
+/// Records whether an input method editor is composing text in the [`ImeActive`] resource
+///
+/// A composition is active from the first non-empty [`Ime::Preedit`] event until the text is committed or the IME is disabled.
+/// Keyboard suppression ends once the composition is over and no keys are pressed.
+pub fn collect_ime_events(
+    mut ime_active: ResMut<ImeActive>,
+    ime_events: Option<Res<Events<Ime>>>,
+    mut reader: Local<ManualEventReader<Ime>>,
+    keycodes: Option<Res<Input<KeyCode>>>,
+    scan_codes: Option<Res<Input<ScanCode>>>,
+) {
+    if let Some(ime_events) = ime_events {
+        for event in reader.iter(&ime_events) {
+            match event {
+                Ime::Preedit { value, .. } => ime_active.set_composing(!value.is_empty()),
+                Ime::Commit { .. } | Ime::Disabled { .. } => ime_active.set_composing(false),
+                Ime::Enabled { .. } => (),
+            }
+        }
+    }
+
+    let any_key_pressed = keycodes.is_some_and(|keycodes| keycodes.get_pressed().next().is_some())
+        || scan_codes.is_some_and(|scan_codes| scan_codes.get_pressed().next().is_some());
+    ime_active.update_suppression(any_key_pressed);
+}
+
+/// Sends a single [`AnyInputDetected`] event in each frame during which any input was pressed, whether or not it is bound to an action
+///
+/// Keyboard keys, mouse buttons and gamepad buttons are detected when they are just pressed,
+/// and gamepad axes when they move past half of their range.
+/// Missing resources are treated as if no inputs of that kind were pressed.
+pub fn detect_any_input(
+    mut events: EventWriter<AnyInputDetected>,
+    keycodes: Option<Res<Input<KeyCode>>>,
+    scan_codes: Option<Res<Input<ScanCode>>>,
+    mouse_buttons: Option<Res<Input<MouseButton>>>,
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    gamepad_axes: Option<Res<Axis<GamepadAxis>>>,
+    mut pushed_axes: Local<bevy::utils::HashSet<GamepadAxis>>,
+) {
+    let mut sources = Vec::new();
+
+    let keyboard_pressed = keycodes.is_some_and(|keycodes| keycodes.get_just_pressed().len() > 0)
+        || scan_codes.is_some_and(|scan_codes| scan_codes.get_just_pressed().len() > 0);
+    if keyboard_pressed {
+        sources.push(InputSource::Keyboard);
+    }
+
+    if mouse_buttons.is_some_and(|mouse_buttons| mouse_buttons.get_just_pressed().len() > 0) {
+        sources.push(InputSource::Mouse);
+    }
+
+    let mut gamepad_pressed =
+        gamepad_buttons.is_some_and(|gamepad_buttons| gamepad_buttons.get_just_pressed().len() > 0);
+    if let Some(gamepad_axes) = gamepad_axes {
+        pushed_axes.retain(|axis| gamepad_axes.get(*axis).is_some());
+        for &axis in gamepad_axes.devices() {
+            let pushed = gamepad_axes
+                .get(axis)
+                .is_some_and(|value| value.abs() >= 0.5);
+            if !pushed {
+                pushed_axes.remove(&axis);
+            } else if pushed_axes.insert(axis) {
+                gamepad_pressed = true;
+            }
+        }
+    }
+    if gamepad_pressed {
+        sources.push(InputSource::Gamepad);
+    }
+
+    if !sources.is_empty() {
+        events.send(AnyInputDetected { sources });
+    }
+}
+
+/// Records the buttons pressed during this frame in the [`SecretSequences`] resource,
+/// sending a [`SecretSequenceMatched`] event for each sequence that they complete
+///
+/// Operating system key repeats are ignored. Missing resources are treated as if no buttons of that kind were pressed.
 #[allow(clippy::too_many_arguments)]
-pub fn update_action_state<A: Actionlike>(
-    gamepad_buttons: Res<Input<GamepadButton>>,
-    gamepad_button_axes: Res<Axis<GamepadButton>>,
-    gamepad_axes: Res<Axis<GamepadAxis>>,
-    gamepads: Res<Gamepads>,
+pub fn detect_secret_sequences(
+    mut secret_sequences: ResMut<SecretSequences>,
+    mut matched_events: EventWriter<SecretSequenceMatched>,
     keycodes: Option<Res<Input<KeyCode>>>,
     scan_codes: Option<Res<Input<ScanCode>>>,
+    keyboard_events: Option<Res<Events<KeyboardInput>>>,
+    mut keyboard_reader: Local<ManualEventReader<KeyboardInput>>,
+    mouse_button_events: Option<Res<Events<MouseButtonInput>>>,
+    mut mouse_button_reader: Local<ManualEventReader<MouseButtonInput>>,
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    forgiveness: Option<Res<TimingForgiveness>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+) {
+    let mut presses: Vec<CapturedEvent> = Vec::new();
+
+    if let Some(keyboard_events) = keyboard_events {
+        let newly_pressed = |event: &KeyboardInput| {
+            let key_just_pressed = event.key_code.is_some_and(|key_code| {
+                keycodes
+                    .as_ref()
+                    .is_some_and(|keycodes| keycodes.just_pressed(key_code))
+            });
+            let scan_code_just_pressed = scan_codes
+                .as_ref()
+                .is_some_and(|scan_codes| scan_codes.just_pressed(ScanCode(event.scan_code)));
+            key_just_pressed || scan_code_just_pressed
+        };
+
+        presses.extend(
+            keyboard_reader
+                .iter(&keyboard_events)
+                .filter(|event| {
+                    event.state == bevy::input::ButtonState::Pressed && newly_pressed(event)
+                })
+                .map(|event| CapturedEvent::Keyboard(*event)),
+        );
+    }
+
+    if let Some(mouse_button_events) = mouse_button_events {
+        presses.extend(
+            mouse_button_reader
+                .iter(&mouse_button_events)
+                .filter(|event| event.state == bevy::input::ButtonState::Pressed)
+                .map(|event| CapturedEvent::MouseButton(*event)),
+        );
+    }
+
+    if let Some(gamepad_buttons) = gamepad_buttons {
+        presses.extend(gamepad_buttons.get_just_pressed().map(|button| {
+            CapturedEvent::GamepadButton(GamepadButtonChangedEvent {
+                gamepad: button.gamepad,
+                button_type: button.button_type,
+                value: 1.0,
+            })
+        }));
+    }
+
+    let forgiveness = forgiveness
+        .map(|forgiveness| *forgiveness)
+        .unwrap_or_default();
+    let timestamp = clock
+        .as_deref()
+        .copied()
+        .unwrap_or_default()
+        .now(&time)
+        .unwrap_or_else(Instant::now);
+    for event in presses {
+        for name in secret_sequences.record(CapturedInput { event, timestamp }, &forgiveness) {
+            matched_events.send(SecretSequenceMatched { name });
+        }
+    }
+}
+
+/// Advances the [`BindingCapture`] with the keyboard keys, mouse buttons and gamepad buttons that were pressed and released,
+/// sending a [`BindingCaptured`] event when the capture ends
+///
+/// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed.
+pub fn capture_bindings<A: Actionlike>(
+    mut capture: ResMut<BindingCapture<A>>,
+    mut captured_events: EventWriter<BindingCaptured<A>>,
+    keycodes: Option<Res<Input<KeyCode>>>,
     mouse_buttons: Option<Res<Input<MouseButton>>>,
-    mouse_wheel: Option<Res<Events<MouseWheel>>>,
-    mouse_motion: Res<Events<MouseMotion>>,
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+) {
+    if !capture.is_capturing() {
+        return;
+    }
+
+    let mut just_pressed: Vec<InputKind> = Vec::new();
+    let mut just_released: Vec<InputKind> = Vec::new();
+
+    if let Some(keycodes) = keycodes {
+        just_pressed.extend(
+            keycodes
+                .get_just_pressed()
+                .copied()
+                .map(InputKind::Keyboard),
+        );
+        just_released.extend(
+            keycodes
+                .get_just_released()
+                .copied()
+                .map(InputKind::Keyboard),
+        );
+    }
+
+    if let Some(mouse_buttons) = mouse_buttons {
+        just_pressed.extend(
+            mouse_buttons
+                .get_just_pressed()
+                .copied()
+                .map(InputKind::Mouse),
+        );
+        just_released.extend(
+            mouse_buttons
+                .get_just_released()
+                .copied()
+                .map(InputKind::Mouse),
+        );
+    }
+
+    if let Some(gamepad_buttons) = gamepad_buttons {
+        just_pressed.extend(
+            gamepad_buttons
+                .get_just_pressed()
+                .map(|button| InputKind::GamepadButton(button.button_type)),
+        );
+        just_released.extend(
+            gamepad_buttons
+                .get_just_released()
+                .map(|button| InputKind::GamepadButton(button.button_type)),
+        );
+    }
+
+    let current_instant = clock
+        .as_deref()
+        .copied()
+        .unwrap_or_default()
+        .now(&time)
+        .unwrap_or_else(Instant::now);
+    if let Some((action, outcome)) = capture.update(just_pressed, just_released, current_instant) {
+        captured_events.send(BindingCaptured { action, outcome });
+    }
+}
+
+/// Applies the [`ControlScheme`] of each entity's [`Handle`] to its [`InputMap`],
+/// once the scheme has loaded and again every time it is modified
+///
+/// Bindings that were overridden since the scheme was last applied are kept according to the entity's [`SchemeMergePolicy`],
+/// and actions whose bindings disappeared are released.
+#[cfg(feature = "asset")]
+pub fn apply_control_schemes<A: Actionlike + TypeUuid>(
+    mut commands: Commands,
+    mut asset_events: EventReader<AssetEvent<ControlScheme<A>>>,
+    control_schemes: Res<Assets<ControlScheme<A>>>,
+    mut query: Query<(
+        Entity,
+        Ref<Handle<ControlScheme<A>>>,
+        &mut InputMap<A>,
+        Option<&mut ActionState<A>>,
+        Option<&mut AppliedControlScheme<A>>,
+        Option<&SchemeMergePolicy>,
+    )>,
+) {
+    let loaded: Vec<&Handle<ControlScheme<A>>> = asset_events
+        .iter()
+        .filter_map(|event| match event {
+            AssetEvent::Created { handle } | AssetEvent::Modified { handle } => Some(handle),
+            AssetEvent::Removed { .. } => None,
+        })
+        .collect();
+
+    for (entity, handle, mut input_map, action_state, applied, policy) in query.iter_mut() {
+        if !handle.is_changed() && !loaded.contains(&&*handle) {
+            continue;
+        }
+        let Some(control_scheme) = control_schemes.get(&*handle) else {
+            continue;
+        };
+
+        let merged = control_scheme.merged_with(
+            &input_map,
+            applied.as_ref().map(|applied| applied.input_map()),
+            policy.copied().unwrap_or_default(),
+        );
+
+        if let Some(mut action_state) = action_state {
+            for action in A::variants() {
+                let bindings_disappeared = input_map
+                    .get(action.clone())
+                    .iter()
+                    .any(|input| !merged.get(action.clone()).contains(input));
+                if bindings_disappeared {
+                    action_state.release(action);
+                }
+            }
+        }
+
+        *input_map = merged;
+        let scheme_map = control_scheme.input_map.clone();
+        match applied {
+            Some(mut applied) => *applied = AppliedControlScheme::new(scheme_map),
+            None => {
+                commands
+                    .entity(entity)
+                    .insert(AppliedControlScheme::new(scheme_map));
+            }
+        }
+    }
+}
+
+/// Applies the [`BindingProfiles`] entry of each gamepad that connects to the [`InputMap`]s that use it,
+/// sending a [`BindingProfileSelected`] event for each of them
+///
+/// An [`InputMap`] uses a gamepad if it is associated with it, or if it is not associated with any gamepad.
+pub fn apply_binding_profiles<A: Actionlike>(
+    connection_events: Option<Res<Events<GamepadConnectionEvent>>>,
+    mut connection_reader: Local<ManualEventReader<GamepadConnectionEvent>>,
+    mut selected_events: EventWriter<BindingProfileSelected<A>>,
+    mut query: Query<(Entity, &mut BindingProfiles<A>, &mut InputMap<A>)>,
+    profiles_resource: Option<ResMut<BindingProfiles<A>>>,
+    input_map_resource: Option<ResMut<InputMap<A>>>,
+) {
+    let Some(connection_events) = connection_events else {
+        return;
+    };
+    let mut resources = profiles_resource.zip(input_map_resource);
+
+    for event in connection_reader.iter(&connection_events) {
+        let GamepadConnection::Connected(info) = &event.connection else {
+            continue;
+        };
+        let identity = ControllerIdentity::new(info.name.clone());
+        let uses_gamepad = |input_map: &InputMap<A>| {
+            input_map
+                .gamepad()
+                .map_or(true, |gamepad| gamepad == event.gamepad)
+        };
+
+        for (entity, mut profiles, mut input_map) in query.iter_mut() {
+            if uses_gamepad(&input_map) && profiles.apply(&identity, &mut input_map) {
+                selected_events.send(BindingProfileSelected::new(
+                    event.gamepad,
+                    identity.clone(),
+                    Some(entity),
+                ));
+            }
+        }
+
+        if let Some((profiles, input_map)) = &mut resources {
+            if uses_gamepad(input_map) && profiles.apply(&identity, input_map) {
+                selected_events.send(BindingProfileSelected::new(
+                    event.gamepad,
+                    identity.clone(),
+                    None,
+                ));
+            }
+        }
+    }
+}
+
+/// Fetches all of the relevant [`Input`] resources to update [`ActionState`] according to the [`InputMap`].
+///
+/// Missing resources will be ignored, and treated as if none of the corresponding inputs were pressed.
+/// Entities with a [`BlockInput`] component are skipped, as is everything while the [`InputBlockers`] resource is blocked.
+#[allow(clippy::too_many_arguments)]
+pub fn update_action_state<A: Actionlike>(
+    gamepad_buttons: Option<Res<Input<GamepadButton>>>,
+    gamepad_button_axes: Option<Res<Axis<GamepadButton>>>,
+    gamepad_axes: Option<Res<Axis<GamepadAxis>>>,
+    gamepads: Option<Res<Gamepads>>,
+    #[cfg(feature = "keyboard")] keycodes: Option<Res<Input<KeyCode>>>,
+    #[cfg(feature = "keyboard")] scan_codes: Option<Res<Input<ScanCode>>>,
+    #[cfg(feature = "mouse")] mouse_buttons: Option<Res<Input<MouseButton>>>,
+    #[cfg(feature = "mouse")] mouse_wheel: Option<Res<Events<MouseWheel>>>,
+    #[cfg(feature = "mouse")] mouse_motion: Option<Res<Events<MouseMotion>>>,
+    #[cfg(feature = "mouse")] windows: Query<&Window, With<PrimaryWindow>>,
     clash_strategy: Res<ClashStrategy>,
     #[cfg(feature = "ui")] interactions: Query<&Interaction>,
     #[cfg(feature = "egui")] mut maybe_egui: EguiContexts,
-    action_state: Option<ResMut<ActionState<A>>>,
-    input_map: Option<Res<InputMap<A>>>,
-    press_scheduler: Option<ResMut<PressScheduler<A>>>,
-    mut query: Query<(
-        &mut ActionState<A>,
-        &InputMap<A>,
-        Option<&mut PressScheduler<A>>,
-    )>,
+    // Grouped to stay within the maximum number of system parameters when all features are enabled
+    resources: (
+        Option<ResMut<ActionState<A>>>,
+        Option<Res<InputMap<A>>>,
+        Option<ResMut<PressScheduler<A>>>,
+        Option<ResMut<VirtualInput<A>>>,
+        Option<Res<ExternallyControlled<A>>>,
+        Option<Res<WindowEventInputs>>,
+        Option<Res<CustomInputs>>,
+        Option<Res<ImeActive>>,
+        Option<Res<HoldAssist<A>>>,
+        Option<Res<InputBlockers<A>>>,
+        Option<Res<StickHistory>>,
+        Option<Res<ActionTimings<A>>>,
+        Option<Res<NavigationDeadbands<A>>>,
+        Local<ThreadLocal<RefCell<WhichPressedBuffer>>>,
+    ),
+    mut query: Query<
+        (
+            &mut ActionState<A>,
+            &InputMap<A>,
+            Option<&mut PressScheduler<A>>,
+            Option<&mut VirtualInput<A>>,
+            Option<&ActionTimings<A>>,
+            Option<&NavigationDeadbands<A>>,
+        ),
+        (Without<ExternallyControlled<A>>, Without<BlockInput<A>>),
+    >,
 ) {
-    let gamepad_buttons = gamepad_buttons.into_inner();
-    let gamepad_button_axes = gamepad_button_axes.into_inner();
-    let gamepad_axes = gamepad_axes.into_inner();
-    let gamepads = gamepads.into_inner();
+    let (
+        action_state,
+        input_map,
+        press_scheduler,
+        virtual_input,
+        externally_controlled,
+        window_events,
+        custom_inputs,
+        ime_active,
+        hold_assist,
+        input_blockers,
+        stick_history,
+        action_timings,
+        navigation_deadbands,
+        buffers,
+    ) = resources;
+    if input_blockers.is_some_and(|input_blockers| input_blockers.is_blocked()) {
+        return;
+    }
+    let window_events = window_events.map(|window_events| window_events.into_inner());
+    let custom_inputs = custom_inputs.map(|custom_inputs| custom_inputs.into_inner());
+    let stick_history = stick_history.map(|stick_history| stick_history.into_inner());
+
+    // Worlds without Bevy's input resources, such as headless simulations, are treated as if no gamepad was connected
+    let (no_gamepad_buttons, no_gamepad_button_axes, no_gamepad_axes, no_gamepads) = (
+        Input::default(),
+        Axis::default(),
+        Axis::default(),
+        Gamepads::default(),
+    );
+    let gamepad_buttons = gamepad_buttons.map_or(&no_gamepad_buttons, Res::into_inner);
+    let gamepad_button_axes = gamepad_button_axes.map_or(&no_gamepad_button_axes, Res::into_inner);
+    let gamepad_axes = gamepad_axes.map_or(&no_gamepad_axes, Res::into_inner);
+    let gamepads = gamepads.map_or(&no_gamepads, Res::into_inner);
+
+    // Inputs from backends whose features are disabled are treated as if they were never pressed
+    #[cfg(feature = "keyboard")]
     let keycodes = keycodes.map(|keycodes| keycodes.into_inner());
+    #[cfg(feature = "keyboard")]
     let scan_codes = scan_codes.map(|scan_codes| scan_codes.into_inner());
+    #[cfg(not(feature = "keyboard"))]
+    let (keycodes, scan_codes) = (None, None);
+
+    #[cfg(feature = "mouse")]
     let mouse_buttons = mouse_buttons.map(|mouse_buttons| mouse_buttons.into_inner());
+    #[cfg(feature = "mouse")]
     let mouse_wheel = mouse_wheel.map(|mouse_wheel| mouse_wheel.into_inner());
-    let mouse_motion = mouse_motion.into_inner();
+    let no_mouse_motion = Events::default();
+    #[cfg(feature = "mouse")]
+    let mouse_motion = mouse_motion.map_or(&no_mouse_motion, Res::into_inner);
+    #[cfg(feature = "mouse")]
+    let cursor_position = windows.get_single().ok().and_then(Window::cursor_position);
+    #[cfg(not(feature = "mouse"))]
+    let (mouse_buttons, mouse_wheel, mouse_motion, cursor_position) =
+        (None, None, &no_mouse_motion, None);
 
     // If use clicks on a button, do not apply them to the game state
     #[cfg(feature = "ui")]
@@ -104,6 +824,14 @@ pub fn update_action_state<A: Actionlike>(
         (mouse_buttons, mouse_wheel)
     };
 
+    // Keys pressed while composing text with an IME are text, not commands
+    let (keycodes, scan_codes) =
+        if ime_active.is_some_and(|ime_active| ime_active.suppresses_keyboard()) {
+            (None, None)
+        } else {
+            (keycodes, scan_codes)
+        };
+
     #[cfg(feature = "egui")]
     let ctx = maybe_egui.ctx_mut();
 
@@ -124,117 +852,1176 @@ pub fn update_action_state<A: Actionlike>(
         (mouse_buttons, mouse_wheel)
     };
 
-    let resources = input_map
-        .zip(action_state)
-        .map(|(input_map, action_state)| {
-            (
-                Mut::from(action_state),
-                input_map.into_inner(),
-                press_scheduler.map(Mut::from),
-            )
-        });
+    // Shared by every entity: only the gamepad and thresholds of each input map differ
+    let input_streams = InputStreams {
+        gamepad_buttons,
+        gamepad_button_axes,
+        gamepad_axes,
+        gamepads,
+        keycodes,
+        scan_codes,
+        mouse_buttons,
+        mouse_wheel,
+        mouse_motion,
+        cursor_position,
+        button_thresholds: None,
+        associated_gamepad: None,
+        window_events,
+        custom_inputs,
+        stick_history,
+    };
+    let clash_strategy = *clash_strategy;
+    let hold_assist = hold_assist.as_deref();
 
-    for (mut action_state, input_map, press_scheduler) in query.iter_mut().chain(resources) {
-        let input_streams = InputStreams {
-            gamepad_buttons,
-            gamepad_button_axes,
-            gamepad_axes,
-            gamepads,
-            keycodes,
-            scan_codes,
-            mouse_buttons,
-            mouse_wheel,
-            mouse_motion,
-            associated_gamepad: input_map.gamepad(),
-        };
+    // Input maps only read the input streams, so entities are processed in parallel.
+    // Each thread reuses its own buffer, so that reading the pressed actions doesn't allocate.
+    let buffers = &*buffers;
+    query.par_iter_mut().for_each_mut(
+        |(
+            mut action_state,
+            input_map,
+            press_scheduler,
+            virtual_input,
+            action_timings,
+            navigation_deadbands,
+        )| {
+            update_from_input_map(
+                &input_streams,
+                &mut action_state,
+                input_map,
+                &mut buffers.get_or_default().borrow_mut(),
+                press_scheduler.map(Mut::into_inner),
+                virtual_input.map(Mut::into_inner),
+                action_timings,
+                navigation_deadbands,
+                clash_strategy,
+                hold_assist,
+            );
+        },
+    );
 
-        action_state.update(input_map.which_pressed(&input_streams, *clash_strategy));
-        if let Some(mut press_scheduler) = press_scheduler {
-            press_scheduler.apply(&mut action_state);
-        }
+    if let Some((mut action_state, input_map)) = action_state
+        .zip(input_map)
+        .filter(|_| externally_controlled.is_none())
+    {
+        update_from_input_map(
+            &input_streams,
+            &mut action_state,
+            &input_map,
+            &mut buffers.get_or_default().borrow_mut(),
+            press_scheduler.map(ResMut::into_inner),
+            virtual_input.map(ResMut::into_inner),
+            action_timings.as_deref(),
+            navigation_deadbands.as_deref(),
+            clash_strategy,
+            hold_assist,
+        );
+    }
+}
+
+/// Updates a single `action_state` from the `input_streams`, according to its `input_map`
+#[allow(clippy::too_many_arguments)]
+fn update_from_input_map<A: Actionlike>(
+    input_streams: &InputStreams,
+    action_state: &mut ActionState<A>,
+    input_map: &InputMap<A>,
+    buffer: &mut WhichPressedBuffer,
+    press_scheduler: Option<&mut PressScheduler<A>>,
+    virtual_input: Option<&mut VirtualInput<A>>,
+    action_timings: Option<&ActionTimings<A>>,
+    navigation_deadbands: Option<&NavigationDeadbands<A>>,
+    clash_strategy: ClashStrategy,
+    hold_assist: Option<&HoldAssist<A>>,
+) {
+    #[cfg(feature = "trace")]
+    let _span = info_span!("update_from_input_map", action = std::any::type_name::<A>()).entered();
+
+    let input_streams = InputStreams {
+        button_thresholds: Some(input_map.button_thresholds()),
+        associated_gamepad: input_map.gamepad(),
+        ..input_streams.clone()
+    };
+
+    input_map.which_pressed_into(&input_streams, clash_strategy, buffer);
+    input_map.apply_latest_wins(buffer, &mut action_state.binding_recency);
+    let action_data = buffer.action_data_mut();
+    let mut hot_swaps = std::mem::take(&mut action_state.hot_swaps);
+    input_map.apply_device_hot_swap(
+        action_data,
+        &input_streams,
+        action_state,
+        action_timings,
+        &mut hot_swaps,
+    );
+    action_state.hot_swaps = hot_swaps;
+    if let Some(virtual_input) = virtual_input {
+        virtual_input.merge_into(action_data);
+    }
+    if let Some(hold_assist) = hold_assist {
+        hold_assist.apply(action_data, &mut action_state.hold_latches);
+    }
+    if let Some(navigation_deadbands) = navigation_deadbands {
+        navigation_deadbands.apply(action_state, action_data);
+    }
+
+    action_state.update_from_slice(buffer.action_data());
+    if let Some(press_scheduler) = press_scheduler {
+        press_scheduler.apply(action_state);
+    }
+}
+
+/// Sets the [`ActionState`] of each [`ExternallyControlled`] entity from its [`ActionWriter`]
+///
+/// The [`ActionState`] resource is set from the [`ActionWriter`] resource if the [`ExternallyControlled`] resource exists.
+pub fn apply_action_writers<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, &ActionWriter<A>), With<ExternallyControlled<A>>>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    action_writer: Option<Res<ActionWriter<A>>>,
+    externally_controlled: Option<Res<ExternallyControlled<A>>>,
+) {
+    let resources = action_state
+        .zip(action_writer)
+        .filter(|_| externally_controlled.is_some())
+        .map(|(action_state, action_writer)| (Mut::from(action_state), action_writer.into_inner()));
+
+    for (mut action_state, action_writer) in query.iter_mut().chain(resources) {
+        action_state.update_from_slice(action_writer.action_data());
     }
 }
 
-/// When a button with a component of type `A` is clicked, press the corresponding action in the [`ActionState`]
+/// When a button with a component of type `A` is interacted with, press the corresponding action in the [`ActionState`]
 ///
-/// The action triggered is determined by the variant stored in your UI-defined button.
+/// The action triggered is determined by the variant stored in your UI-defined button,
+/// and the interactions that trigger it are determined by its [`DriverTrigger`].
 #[cfg(feature = "ui")]
 pub fn update_action_state_from_interaction<A: Actionlike>(
-    ui_query: Query<(&Interaction, &ActionStateDriver<A>)>,
+    ui_query: Query<(Entity, &Interaction, &ActionStateDriver<A>)>,
     mut action_state_query: Query<&mut ActionState<A>>,
+    mut previous_interactions: Local<HashMap<Entity, Interaction>>,
 ) {
-    for (&interaction, action_state_driver) in ui_query.iter() {
-        if interaction == Interaction::Pressed {
+    #[cfg(feature = "trace")]
+    let _span = info_span!(
+        "update_action_state_from_interaction",
+        action = std::any::type_name::<A>()
+    )
+    .entered();
+
+    for (driver_entity, &interaction, action_state_driver) in ui_query.iter() {
+        let previous = previous_interactions
+            .insert(driver_entity, interaction)
+            .unwrap_or(Interaction::None);
+
+        let triggered = match action_state_driver.trigger {
+            DriverTrigger::Pressed => interaction == Interaction::Pressed,
+            DriverTrigger::JustPressed => {
+                interaction == Interaction::Pressed && previous != Interaction::Pressed
+            }
+            DriverTrigger::Clicked => {
+                interaction == Interaction::Hovered && previous == Interaction::Pressed
+            }
+            DriverTrigger::HoverEnter => {
+                interaction == Interaction::Hovered && previous == Interaction::None
+            }
+            DriverTrigger::Hovered => interaction != Interaction::None,
+        };
+
+        if triggered {
             for entity in action_state_driver.targets.iter() {
-                let mut action_state = action_state_query
-                    .get_mut(*entity)
-                    .expect("Entity does not exist, or does not have an `ActionState` component.");
+                let Ok(mut action_state) = action_state_query.get_mut(*entity) else {
+                    report(InputManagerError::MissingActionState {
+                        entity: *entity,
+                        action_type: std::any::type_name::<A>(),
+                    });
+                    continue;
+                };
                 action_state.press(action_state_driver.action.clone());
+
+                if let Some(value) = action_state_driver.value {
+                    action_state
+                        .action_data_mut(action_state_driver.action.clone())
+                        .value = value;
+                }
             }
         }
     }
+
+    // Forget about drivers that no longer exist
+    previous_interactions.retain(|entity, _| ui_query.contains(*entity));
 }
 
-/// Generates an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`] from [`ActionState`]
-///
-/// The `ID` generic type should be a stable entity identifier,
-/// suitable to be sent across a network.
+/// Removes the targets of each [`ActionStateDriver`] that no longer exist
 ///
-/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
-pub fn generate_action_diffs<A: Actionlike, ID: Eq + Clone + Component>(
-    action_state_query: Query<(&ActionState<A>, &ID)>,
-    mut action_diffs: EventWriter<ActionDiff<A, ID>>,
+/// A [`DriverTargetDespawned`] event is sent and a warning is logged for each removed target.
+/// Drivers that are left without any targets have their [`ActionStateDriver`] component removed.
+pub fn remove_despawned_driver_targets<A: Actionlike>(
+    mut commands: Commands,
+    mut driver_query: Query<(Entity, &mut ActionStateDriver<A>)>,
+    entities: &Entities,
+    mut events: EventWriter<DriverTargetDespawned<A>>,
 ) {
-    for (action_state, id) in action_state_query.iter() {
-        for action in action_state.get_just_pressed() {
-            action_diffs.send(ActionDiff::Pressed {
-                action: action.clone(),
-                id: id.clone(),
-            });
+    for (driver, mut action_state_driver) in driver_query.iter_mut() {
+        let despawned: Vec<Entity> = action_state_driver
+            .targets
+            .iter()
+            .filter(|target| !entities.contains(**target))
+            .copied()
+            .collect();
+        if despawned.is_empty() {
+            continue;
         }
 
-        for action in action_state.get_just_released() {
-            action_diffs.send(ActionDiff::Released {
-                action: action.clone(),
-                id: id.clone(),
+        for target in despawned {
+            warn!(
+                "{target:?}, the target of the `ActionStateDriver<{}>` on {driver:?}, no longer exists",
+                std::any::type_name::<A>()
+            );
+            action_state_driver.targets.remove(target);
+            events.send(DriverTargetDespawned {
+                driver,
+                target,
+                action: action_state_driver.action.clone(),
             });
         }
+
+        if action_state_driver.targets.is_empty() {
+            commands.entity(driver).remove::<ActionStateDriver<A>>();
+        }
+    }
+}
+
+/// Records the raw input events that contributed to each action during this frame
+///
+/// Only [`ActionState`]s with [`CapturedInputs`] are affected, and the events of the previous frame are cleared.
+/// Events are recorded for each action that is pressed or was just released,
+/// when the event affects one of the inputs bound to that action.
+/// The timestamp and order of the event that pressed or released each action are recorded as well.
+#[allow(clippy::too_many_arguments)]
+pub fn capture_raw_inputs<A: Actionlike>(
+    mut query: Query<(
+        &ActionState<A>,
+        &InputMap<A>,
+        &mut CapturedInputs<A>,
+        Option<&mut ActionTimings<A>>,
+    )>,
+    action_state: Option<Res<ActionState<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    captured_inputs: Option<ResMut<CapturedInputs<A>>>,
+    action_timings: Option<ResMut<ActionTimings<A>>>,
+    keyboard_events: Option<Res<Events<KeyboardInput>>>,
+    mut keyboard_reader: Local<ManualEventReader<KeyboardInput>>,
+    mouse_button_events: Option<Res<Events<MouseButtonInput>>>,
+    mut mouse_button_reader: Local<ManualEventReader<MouseButtonInput>>,
+    gamepad_button_events: Option<Res<Events<GamepadButtonChangedEvent>>>,
+    mut gamepad_button_reader: Local<ManualEventReader<GamepadButtonChangedEvent>>,
+    gamepad_axis_events: Option<Res<Events<GamepadAxisChangedEvent>>>,
+    mut gamepad_axis_reader: Local<ManualEventReader<GamepadAxisChangedEvent>>,
+    raw_event_timestamps: Option<Res<RawEventTimestamps>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+) {
+    let frame_timestamp = clock
+        .as_deref()
+        .copied()
+        .unwrap_or_default()
+        .now(&time)
+        .unwrap_or_else(Instant::now);
+    let timestamp = |event: &CapturedEvent| {
+        raw_event_timestamps
+            .as_ref()
+            .and_then(|timestamps| timestamps.get(event))
+            .unwrap_or(frame_timestamp)
+    };
+
+    let mut events: Vec<CapturedEvent> = Vec::new();
+    if let Some(keyboard_events) = keyboard_events {
+        events.extend(
+            keyboard_reader
+                .iter(&keyboard_events)
+                .cloned()
+                .map(CapturedEvent::Keyboard),
+        );
+    }
+    if let Some(mouse_button_events) = mouse_button_events {
+        events.extend(
+            mouse_button_reader
+                .iter(&mouse_button_events)
+                .cloned()
+                .map(CapturedEvent::MouseButton),
+        );
+    }
+    if let Some(gamepad_button_events) = gamepad_button_events {
+        events.extend(
+            gamepad_button_reader
+                .iter(&gamepad_button_events)
+                .cloned()
+                .map(CapturedEvent::GamepadButton),
+        );
+    }
+    if let Some(gamepad_axis_events) = gamepad_axis_events {
+        events.extend(
+            gamepad_axis_reader
+                .iter(&gamepad_axis_events)
+                .cloned()
+                .map(CapturedEvent::GamepadAxis),
+        );
+    }
+
+    // Order the events by when they were received, keeping the order in which they were sent for ties
+    let mut events: Vec<(CapturedEvent, Instant)> = events
+        .into_iter()
+        .map(|event| {
+            let timestamp = timestamp(&event);
+            (event, timestamp)
+        })
+        .collect();
+    events.sort_by_key(|(_, timestamp)| *timestamp);
+
+    let resources = match (action_state, input_map, captured_inputs) {
+        (Some(action_state), Some(input_map), Some(captured_inputs)) => Some((
+            action_state.into_inner(),
+            input_map.into_inner(),
+            Mut::from(captured_inputs),
+            action_timings.map(Mut::from),
+        )),
+        _ => None,
+    };
+
+    for (action_state, input_map, mut captured_inputs, mut action_timings) in
+        query.iter_mut().chain(resources)
+    {
+        captured_inputs.clear();
+
+        for action in A::variants() {
+            if action_state.released(action.clone()) && !action_state.just_released(action.clone())
+            {
+                continue;
+            }
+
+            let positions =
+                input_map.matching_events(action.clone(), events.iter().map(|(event, _)| event));
+            let Some(&first_position) = positions.first() else {
+                continue;
+            };
+
+            // The earliest event is the one that changed the state of the action
+            if action_state.newly_pressed(action.clone())
+                || action_state.just_released(action.clone())
+            {
+                if let Some(action_timings) = action_timings.as_mut() {
+                    action_timings.set_event_timestamp(
+                        action_state,
+                        action.clone(),
+                        events[first_position].1,
+                    );
+                }
+                captured_inputs.set_transition_order(action.clone(), first_position);
+            }
+
+            let captured = positions
+                .into_iter()
+                .map(|position| CapturedInput {
+                    event: events[position].0.clone(),
+                    timestamp: events[position].1,
+                })
+                .collect();
+            captured_inputs.set(action, captured);
+        }
+    }
+}
+
+/// Clears the [`RawEventTimestamps`] at the end of each frame, once every action type has captured its events
+pub fn clear_raw_event_timestamps(mut raw_event_timestamps: ResMut<RawEventTimestamps>) {
+    raw_event_timestamps.clear();
+}
+
+/// Rewrites the [`Text`] of each [`InputOverlay`] to display the current state of its source [`ActionState`]
+#[cfg(feature = "ui")]
+pub fn update_input_overlays<A: Actionlike>(
+    mut overlay_query: Query<(&InputOverlay<A>, &mut Text)>,
+    source_query: Query<(&ActionState<A>, Option<&InputMap<A>>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    metadata: Option<Res<ActionMetadata<A>>>,
+) {
+    let metadata = metadata.as_deref();
+
+    for (overlay, mut text) in overlay_query.iter_mut() {
+        match overlay.source {
+            Some(source) => {
+                let Ok((action_state, input_map)) = source_query.get(source) else {
+                    report(InputManagerError::MissingActionState {
+                        entity: source,
+                        action_type: std::any::type_name::<A>(),
+                    });
+                    continue;
+                };
+                overlay.update_text(&mut text, action_state, input_map, metadata);
+            }
+            None => {
+                if let Some(action_state) = &action_state {
+                    overlay.update_text(&mut text, action_state, input_map.as_deref(), metadata);
+                }
+            }
+        }
+    }
+}
+
+/// Spawns a row for each action in each [`RebindingMenu`] that was just added
+#[cfg(feature = "ui")]
+pub fn build_rebinding_menus<A: Actionlike>(
+    mut commands: Commands,
+    menu_query: Query<(Entity, &RebindingMenu<A>), Added<RebindingMenu<A>>>,
+    metadata: Option<Res<ActionMetadata<A>>>,
+) {
+    for (entity, menu) in menu_query.iter() {
+        commands.entity(entity).with_children(|parent| {
+            menu.spawn_rows(parent, entity, metadata.as_deref());
+        });
+    }
+}
+
+/// Starts a [`BindingCapture`] when a [`RebindButton`] is clicked, or activated with a [`UiNavigationEvent`]
+#[cfg(feature = "ui")]
+pub fn start_rebinding_from_menus<A: Actionlike>(
+    button_query: Query<(Entity, &Interaction, &RebindButton<A>), Changed<Interaction>>,
+    all_buttons: Query<&RebindButton<A>>,
+    mut menu_query: Query<&mut RebindingMenu<A>>,
+    mut capture: ResMut<BindingCapture<A>>,
+    navigation_events: Option<Res<Events<UiNavigationEvent>>>,
+    mut navigation_reader: Local<ManualEventReader<UiNavigationEvent>>,
+) {
+    let mut pressed: Option<&RebindButton<A>> = button_query
+        .iter()
+        .find(|(_, interaction, _)| **interaction == Interaction::Pressed)
+        .map(|(_, _, button)| button);
+
+    if let Some(events) = navigation_events {
+        for event in navigation_reader.iter(&events) {
+            if let UiNavigationEvent::Activated(entity) = event {
+                if let Ok(button) = all_buttons.get(*entity) {
+                    pressed = Some(button);
+                }
+            }
+        }
+    }
+
+    let Some(button) = pressed else {
+        return;
+    };
+
+    capture.start(button.action.clone());
+    for mut menu in menu_query.iter_mut() {
+        menu.set_capturing(None);
+    }
+    if let Ok(mut menu) = menu_query.get_mut(button.menu) {
+        menu.set_capturing(Some(button.action.clone()));
+    }
+}
+
+/// Applies each [`BindingCaptured`] event to the [`InputMap`] of the [`RebindingMenu`] that started it,
+/// and rewrites the [`Text`] of each [`BindingLabel`] to display the current bindings of its action
+#[cfg(feature = "ui")]
+pub fn update_rebinding_menus<A: Actionlike>(
+    mut captured_events: EventReader<BindingCaptured<A>>,
+    mut menu_query: Query<&mut RebindingMenu<A>>,
+    mut label_query: Query<(&BindingLabel<A>, &mut Text)>,
+    mut input_map_query: Query<&mut InputMap<A>>,
+    mut input_map: Option<ResMut<InputMap<A>>>,
+) {
+    for event in captured_events.iter() {
+        for mut menu in menu_query.iter_mut() {
+            if menu
+                .capturing()
+                .map_or(true, |capturing| capturing.index() != event.action.index())
+            {
+                continue;
+            }
+            menu.set_capturing(None);
+
+            let CaptureOutcome::Captured(input) = &event.outcome else {
+                continue;
+            };
+            match menu.target {
+                Some(target) => {
+                    if let Ok(mut input_map) = input_map_query.get_mut(target) {
+                        RebindingMenu::apply_binding(
+                            &mut input_map,
+                            event.action.clone(),
+                            input.clone(),
+                        );
+                    }
+                }
+                None => {
+                    if let Some(input_map) = input_map.as_deref_mut() {
+                        RebindingMenu::apply_binding(
+                            input_map,
+                            event.action.clone(),
+                            input.clone(),
+                        );
+                    }
+                }
+            }
+        }
+    }
+
+    for (label, mut text) in label_query.iter_mut() {
+        let Ok(menu) = menu_query.get(label.menu) else {
+            continue;
+        };
+        let input_map = match menu.target {
+            Some(target) => input_map_query.get(target).ok(),
+            None => input_map.as_deref(),
+        };
+
+        let value = menu.binding_label(label.action.clone(), input_map);
+        let style = menu.label_style(label.action.clone());
+        // Only write when needed, to avoid triggering change detection every frame
+        if text.sections.len() != 1
+            || text.sections[0].value != value
+            || text.sections[0].style.color != style.color
+        {
+            *text = Text::from_section(value, style);
+        }
+    }
+}
+
+/// Moves the [`Focus`] between [`Focusable`] nodes, and sends [`UiNavigationEvent`]s, according to the [`UiNavigationActions`]
+///
+/// Directional actions repeat with [`KeyRepeats::just_repeated`] if the [`KeyRepeats`] resource exists,
+/// and with [`AutoRepeats::just_repeated`] if the [`AutoRepeats`] resource exists.
+/// This system is added by the [`UiNavigationPlugin`](crate::ui_navigation::UiNavigationPlugin).
+#[cfg(feature = "ui")]
+pub fn navigate_ui<A: Actionlike>(
+    action_state: Option<Res<ActionState<A>>>,
+    key_repeats: Option<Res<KeyRepeats<A>>>,
+    auto_repeats: Option<Res<AutoRepeats<A>>>,
+    actions: Res<UiNavigationActions<A>>,
+    focusables: Query<(Entity, &GlobalTransform), With<Focusable>>,
+    mut focus: ResMut<Focus>,
+    mut events: EventWriter<UiNavigationEvent>,
+) {
+    let Some(action_state) = action_state else {
+        return;
+    };
+
+    let nodes: Vec<(Entity, Vec2)> = focusables
+        .iter()
+        .map(|(entity, transform)| (entity, transform.translation().truncate()))
+        .collect();
+    // Focus that was lost, such as when the focused node was despawned, is treated as no focus
+    let focused = focus.and_then(|entity| focusables.get(entity).ok());
+
+    for (action, direction) in actions.directions() {
+        let just_pressed_or_repeated = action_state.just_pressed(action.clone())
+            || key_repeats
+                .as_ref()
+                .is_some_and(|key_repeats| key_repeats.just_repeated(action.clone()))
+            || auto_repeats
+                .as_ref()
+                .is_some_and(|auto_repeats| auto_repeats.just_repeated(action));
+        if !just_pressed_or_repeated {
+            continue;
+        }
+
+        let target = match focused {
+            Some((entity, transform)) => ui_navigation::find_neighbor(
+                transform.translation().truncate(),
+                direction,
+                nodes.iter().copied().filter(|(node, _)| *node != entity),
+            ),
+            None => ui_navigation::first_focusable(nodes.iter().copied()),
+        };
+
+        if target.is_some() {
+            **focus = target;
+        }
+        // Only a single move per frame, so that diagonal inputs don't skip over nodes
+        break;
+    }
+
+    if let Some((entity, _)) = focused {
+        if actions
+            .confirm
+            .clone()
+            .is_some_and(|confirm| action_state.just_pressed(confirm))
+        {
+            events.send(UiNavigationEvent::Activated(entity));
+        }
+    }
+
+    if actions
+        .cancel
+        .clone()
+        .is_some_and(|cancel| action_state.just_pressed(cancel))
+    {
+        events.send(UiNavigationEvent::Cancelled);
+    }
+}
+
+/// Counts the operating system key repeats of the keys bound to each pressed action during this frame
+///
+/// Only [`ActionState`]s with [`KeyRepeats`] are affected, and the repeats of the previous frame are cleared.
+/// A key press event is a repeat if the key was already held before this frame.
+#[allow(clippy::too_many_arguments)]
+pub fn surface_key_repeats<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &InputMap<A>, &mut KeyRepeats<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    key_repeats: Option<ResMut<KeyRepeats<A>>>,
+    keycodes: Option<Res<Input<KeyCode>>>,
+    scan_codes: Option<Res<Input<ScanCode>>>,
+    keyboard_events: Option<Res<Events<KeyboardInput>>>,
+    mut keyboard_reader: Local<ManualEventReader<KeyboardInput>>,
+) {
+    let held_before = |event: &KeyboardInput| {
+        let key_held = event.key_code.is_some_and(|key_code| {
+            keycodes.as_ref().is_some_and(|keycodes| {
+                keycodes.pressed(key_code) && !keycodes.just_pressed(key_code)
+            })
+        });
+        let scan_code_held = scan_codes.as_ref().is_some_and(|scan_codes| {
+            let scan_code = ScanCode(event.scan_code);
+            scan_codes.pressed(scan_code) && !scan_codes.just_pressed(scan_code)
+        });
+        key_held || scan_code_held
+    };
+
+    let repeats: Vec<CapturedEvent> = keyboard_events
+        .map(|keyboard_events| {
+            keyboard_reader
+                .iter(&keyboard_events)
+                .filter(|event| {
+                    event.state == bevy::input::ButtonState::Pressed && held_before(event)
+                })
+                .map(|event| CapturedEvent::Keyboard(*event))
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let resources = match (action_state, input_map, key_repeats) {
+        (Some(action_state), Some(input_map), Some(key_repeats)) => Some((
+            action_state.into_inner(),
+            input_map.into_inner(),
+            Mut::from(key_repeats),
+        )),
+        _ => None,
+    };
+
+    for (action_state, input_map, mut key_repeats) in query.iter_mut().chain(resources) {
+        key_repeats.clear();
+        if repeats.is_empty() {
+            continue;
+        }
+
+        for action in A::variants() {
+            if !action_state.pressed(action.clone()) {
+                continue;
+            }
+
+            let count = input_map.matching_events(action.clone(), &repeats).len() as u32;
+
+            if count > 0 {
+                key_repeats.set(action, count);
+            }
+        }
+    }
+}
+
+/// Copies the [`ActionState`] of each [`MirrorActionState`] source into the [`ActionState`] of the mirroring entity
+///
+/// The [`Timing`] of each mirrored action is copied too, if both entities have an [`ActionTimings`].
+pub fn mirror_action_states<A: Actionlike>(
+    mirror_query: Query<(Entity, &MirrorActionState<A>)>,
+    mut action_state_query: Query<(&mut ActionState<A>, Option<&mut ActionTimings<A>>)>,
+) {
+    for (entity, mirror) in mirror_query.iter() {
+        if mirror.source == entity {
+            continue;
+        }
+
+        let Ok((source, source_timings)) = action_state_query.get(mirror.source) else {
+            report(InputManagerError::MissingActionState {
+                entity: mirror.source,
+                action_type: std::any::type_name::<A>(),
+            });
+            continue;
+        };
+        let mirrored: Vec<(A, ActionData, Option<Timing>)> = mirror
+            .mirrored_actions()
+            .map(|action| {
+                let timing = source_timings.map(|timings| timings.timing(source, action.clone()));
+                (action.clone(), source.action_data(action).clone(), timing)
+            })
+            .collect();
+
+        if let Ok((mut action_state, mut action_timings)) = action_state_query.get_mut(entity) {
+            for (action, action_data, timing) in mirrored {
+                action_state.set_action_data(action.clone(), action_data);
+                if let Some((action_timings, timing)) = action_timings.as_mut().zip(timing) {
+                    action_timings.set_timing(&action_state, action, timing);
+                }
+            }
+        }
+    }
+}
+
+/// Records the changes to each [`ActionState`] into the [`ActionRecording`] of the same entity
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after [`InputManagerSystem::Update`](crate::plugin::InputManagerSystem::Update).
+pub fn record_actions<A: Actionlike>(mut query: Query<(&ActionState<A>, &mut ActionRecording<A>)>) {
+    for (action_state, mut recording) in query.iter_mut() {
+        recording.record(action_state);
+    }
+}
+
+/// Applies the next frame of each [`ActionPlayback`] to the [`ActionState`] of the same entity
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after [`InputManagerSystem::Tick`](crate::plugin::InputManagerSystem::Tick).
+pub fn play_back_actions<A: Actionlike>(
+    mut query: Query<(
+        &mut ActionState<A>,
+        Option<&mut ActionTimings<A>>,
+        &mut ActionPlayback<A>,
+    )>,
+) {
+    for (mut action_state, action_timings, mut playback) in query.iter_mut() {
+        playback.advance(&mut action_state, action_timings.map(Mut::into_inner));
     }
 }
 
 /// Generates an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`] from [`ActionState`]
 ///
 /// The `ID` generic type should be a stable entity identifier,
-/// suitable to be sent across a network.
+/// suitable to be sent across a network, and stored as a component on the same entity.
+/// If it is not a component, use [`generate_registered_action_diffs`] instead.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+pub fn generate_action_diffs<A: Actionlike, ID: StableId + Component>(
+    action_state_query: Query<(&ActionState<A>, &ID)>,
+    mut action_diffs: EventWriter<ActionDiff<A, ID>>,
+) {
+    for (action_state, id) in action_state_query.iter() {
+        for action in action_state.get_just_pressed() {
+            action_diffs.send(ActionDiff::Pressed {
+                action: action.clone(),
+                id: id.clone(),
+            });
+        }
+
+        for action in action_state.get_just_released() {
+            action_diffs.send(ActionDiff::Released {
+                action: action.clone(),
+                id: id.clone(),
+            });
+        }
+    }
+}
+
+/// Reads an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`] to update [`ActionState`]
+///
+/// The `ID` generic type should be a stable entity identifier,
+/// suitable to be sent across a network, and stored as a component on the same entity.
+/// If it is not a component, use [`process_registered_action_diffs`] instead.
+///
+/// Diffs for entities with this `ID` but no [`ActionState`] are handled according to [`MissingComponentPolicies::diff_target`],
+/// and are ignored if the [`MissingComponentPolicies`] resource does not exist.
 ///
 /// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
-pub fn process_action_diffs<A: Actionlike, ID: Eq + Component + Clone>(
+pub fn process_action_diffs<A: Actionlike, ID: StableId + Component>(
+    mut commands: Commands,
     mut action_state_query: Query<(&mut ActionState<A>, &ID)>,
+    missing_action_state_query: Query<(Entity, &ID), Without<ActionState<A>>>,
     mut action_diffs: EventReader<ActionDiff<A, ID>>,
+    policies: Option<Res<MissingComponentPolicies<A>>>,
+    mut missing_components: Option<ResMut<Events<MissingComponent<A>>>>,
 ) {
+    let policy = policies.map_or(MissingComponentPolicy::Ignore, |policies| {
+        policies.diff_target
+    });
+    let mut missing_targets = MissingDiffTargets::default();
+
     // PERF: This would probably be faster with an index, but is much more fussy
     for action_diff in action_diffs.iter() {
         for (mut action_state, id) in action_state_query.iter_mut() {
-            match action_diff {
-                ActionDiff::Pressed {
-                    action,
-                    id: event_id,
-                } => {
-                    if event_id == id {
-                        action_state.press(action.clone());
-                        continue;
+            if action_diff.id() == id {
+                apply_action_diff(&mut action_state, action_diff);
+            }
+        }
+
+        if policy != MissingComponentPolicy::Ignore {
+            for (entity, id) in missing_action_state_query.iter() {
+                if action_diff.id() == id {
+                    missing_targets.receive(entity, action_diff);
+                }
+            }
+        }
+    }
+
+    missing_targets.apply(policy, &mut commands, missing_components.as_deref_mut());
+}
+
+/// Generates an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`] from [`ActionState`],
+/// using the [`StableIdRegistry`] to find the stable identifier of each entity
+///
+/// Entities that are not in the registry are skipped.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// along with the [`StableIdRegistry<ID>`] resource.
+pub fn generate_registered_action_diffs<A: Actionlike, ID: StableId>(
+    action_state_query: Query<(Entity, &ActionState<A>)>,
+    registry: Res<StableIdRegistry<ID>>,
+    mut action_diffs: EventWriter<ActionDiff<A, ID>>,
+) {
+    for (entity, action_state) in action_state_query.iter() {
+        let Some(id) = registry.id(entity) else {
+            continue;
+        };
+
+        for action in action_state.get_just_pressed() {
+            action_diffs.send(ActionDiff::Pressed {
+                action,
+                id: id.clone(),
+            });
+        }
+
+        for action in action_state.get_just_released() {
+            action_diffs.send(ActionDiff::Released {
+                action,
+                id: id.clone(),
+            });
+        }
+    }
+}
+
+/// Reads an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`] to update [`ActionState`],
+/// using the [`StableIdRegistry`] to find the entity of each stable identifier
+///
+/// Diffs for identifiers that are not in the registry are ignored.
+/// Diffs for entities without an [`ActionState`] are handled according to [`MissingComponentPolicies::diff_target`],
+/// and are ignored if the [`MissingComponentPolicies`] resource does not exist.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// along with the [`StableIdRegistry<ID>`] resource.
+pub fn process_registered_action_diffs<A: Actionlike, ID: StableId>(
+    mut commands: Commands,
+    mut action_state_query: Query<&mut ActionState<A>>,
+    registry: Res<StableIdRegistry<ID>>,
+    mut action_diffs: EventReader<ActionDiff<A, ID>>,
+    policies: Option<Res<MissingComponentPolicies<A>>>,
+    mut missing_components: Option<ResMut<Events<MissingComponent<A>>>>,
+) {
+    let policy = policies.map_or(MissingComponentPolicy::Ignore, |policies| {
+        policies.diff_target
+    });
+    let mut missing_targets = MissingDiffTargets::default();
+
+    for action_diff in action_diffs.iter() {
+        let Some(entity) = registry.entity(action_diff.id()) else {
+            continue;
+        };
+
+        match action_state_query.get_mut(entity) {
+            Ok(mut action_state) => apply_action_diff(&mut action_state, action_diff),
+            Err(_) => {
+                if policy != MissingComponentPolicy::Ignore {
+                    missing_targets.receive(entity, action_diff);
+                }
+            }
+        }
+    }
+
+    missing_targets.apply(policy, &mut commands, missing_components.as_deref_mut());
+}
+
+/// Presses or releases the action of the `action_diff`
+fn apply_action_diff<A: Actionlike, ID: StableId>(
+    action_state: &mut ActionState<A>,
+    action_diff: &ActionDiff<A, ID>,
+) {
+    match action_diff {
+        ActionDiff::Pressed { action, .. } => action_state.press(action.clone()),
+        ActionDiff::Released { action, .. } => action_state.release(action.clone()),
+    }
+}
+
+/// The [`ActionDiff`]s received during a single run of a system for entities without an [`ActionState`]
+///
+/// Since the new [`ActionState`]s are only inserted once commands are applied,
+/// each entity's diffs are collected into a single [`ActionState`], in the order they were received.
+struct MissingDiffTargets<A: Actionlike> {
+    action_states: Vec<(Entity, ActionState<A>)>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for MissingDiffTargets<A> {
+    fn default() -> Self {
+        Self {
+            action_states: Vec::new(),
+        }
+    }
+}
+
+impl<A: Actionlike> MissingDiffTargets<A> {
+    /// Records the `action_diff` received for the `entity`
+    fn receive<ID: StableId>(&mut self, entity: Entity, action_diff: &ActionDiff<A, ID>) {
+        let index = match self
+            .action_states
+            .iter()
+            .position(|(existing, _)| *existing == entity)
+        {
+            Some(index) => index,
+            None => {
+                self.action_states.push((entity, ActionState::default()));
+                self.action_states.len() - 1
+            }
+        };
+        apply_action_diff(&mut self.action_states[index].1, action_diff);
+    }
+
+    /// Applies the `policy` to each entity that diffs were received for
+    fn apply(
+        self,
+        policy: MissingComponentPolicy,
+        commands: &mut Commands,
+        mut missing_components: Option<&mut Events<MissingComponent<A>>>,
+    ) {
+        for (entity, action_state) in self.action_states {
+            match policy {
+                MissingComponentPolicy::Ignore => (),
+                MissingComponentPolicy::Warn => {
+                    warn!(
+                        "{entity:?} has no `ActionState<{}>` component, so the action diffs received for it were dropped",
+                        std::any::type_name::<A>()
+                    );
+                    if let Some(events) = missing_components.as_deref_mut() {
+                        events.send(MissingComponent::new(
+                            entity,
+                            MissingComponentKind::ActionState,
+                        ));
                     }
                 }
-                ActionDiff::Released {
-                    action,
-                    id: event_id,
-                } => {
-                    if event_id == id {
-                        action_state.release(action.clone());
-                        continue;
+                MissingComponentPolicy::Insert => {
+                    // The entity may have been despawned since the diff was sent
+                    if let Some(mut entity_commands) = commands.get_entity(entity) {
+                        entity_commands.insert(action_state);
                     }
                 }
-            };
+            }
+        }
+    }
+}
+
+/// Wraps each [`ActionDiff`] event in a [`SequencedActionDiff`] event, to be sent over an unreliable transport
+///
+/// Each diff is numbered and stamped with the current tick by the [`DiffSequencer`],
+/// which advances to the next tick every time this system runs.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// along with the [`DiffSequencer<A, ID>`] resource.
+/// It should run after the system that generates the [`ActionDiff`]s.
+pub fn sequence_action_diffs<A: Actionlike, ID: StableId>(
+    mut action_diffs: EventReader<ActionDiff<A, ID>>,
+    mut sequencer: ResMut<DiffSequencer<A, ID>>,
+    mut sequenced_diffs: EventWriter<SequencedActionDiff<A, ID>>,
+) {
+    for action_diff in action_diffs.iter() {
+        sequenced_diffs.send(sequencer.stamp(action_diff.clone()));
+    }
+    sequencer.advance_tick();
+}
+
+/// Unwraps the received [`SequencedActionDiff`] events into [`ActionDiff`] events, in the order they were sent
+///
+/// Duplicated diffs and diffs that arrive after a later one are dropped.
+/// When diffs were skipped, a [`MissedInput`] event is sent before the diff that revealed the gap is passed on.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually,
+/// along with the [`DiffSequenceTracker<A, ID>`] resource.
+/// It should run before the system that processes the [`ActionDiff`]s.
+pub fn unsequence_action_diffs<A: Actionlike, ID: StableId>(
+    mut sequenced_diffs: EventReader<SequencedActionDiff<A, ID>>,
+    mut tracker: ResMut<DiffSequenceTracker<A, ID>>,
+    mut action_diffs: EventWriter<ActionDiff<A, ID>>,
+    mut missed_inputs: EventWriter<MissedInput<A, ID>>,
+) {
+    // Unreliable transports may reorder diffs that arrive in the same frame.
+    // Sequence numbers increase for each identifier, so a stable sort restores the order of each one.
+    let mut received: Vec<&SequencedActionDiff<A, ID>> = sequenced_diffs.iter().collect();
+    received.sort_by_key(|sequenced_diff| sequenced_diff.sequence);
+
+    for sequenced_diff in received {
+        let id = sequenced_diff.diff.id();
+        match tracker.receive(id, sequenced_diff.sequence) {
+            SequenceCheck::InOrder => {}
+            SequenceCheck::Gap(missed) => {
+                missed_inputs.send(MissedInput::new(id.clone(), missed, sequenced_diff.tick));
+            }
+            SequenceCheck::Stale => continue,
+        }
+        action_diffs.send(sequenced_diff.diff.clone());
+    }
+}
+
+/// Sends each event of type `E` to another peer using the [`DiffSender`] resource `S`
+///
+/// Typically, `E` is an [`ActionDiff`] or a [`SequencedActionDiff`].
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after the system that generates the events.
+pub fn send_diffs<E: Event + Clone, S: DiffSender<E>>(
+    mut events: EventReader<E>,
+    mut sender: ResMut<S>,
+) {
+    for event in events.iter() {
+        sender.send(event.clone());
+    }
+}
+
+/// Sends an event of type `E` for each message received from another peer by the [`DiffReceiver`] resource `R`
+///
+/// Typically, `E` is an [`ActionDiff`] or a [`SequencedActionDiff`].
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run before the system that processes the events.
+pub fn receive_diffs<E: Event, R: DiffReceiver<E>>(
+    mut receiver: ResMut<R>,
+    mut events: EventWriter<E>,
+) {
+    while let Some(event) = receiver.receive() {
+        events.send(event);
+    }
+}
+
+/// Sends each event of type `E` to another peer using its [`RenetTransport`] over the [`RenetConnection`] `C`
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after the system that generates the events.
+#[cfg(feature = "renet")]
+pub fn send_renet_messages<E: Event + Serialize, C: RenetConnection>(
+    mut events: EventReader<E>,
+    transport: Res<RenetTransport<E, C>>,
+    mut connection: ResMut<C::Connection>,
+) {
+    for event in events.iter() {
+        transport.send(&mut connection, event);
+    }
+}
+
+/// Sends a [`Received`] event for each message of type `E` received by its [`RenetTransport`] over the [`RenetConnection`] `C`
+///
+/// Each event records the peer that sent the message, so that servers can reject diffs for identifiers that the sender does not own.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run before the system that processes the events.
+#[cfg(feature = "renet")]
+pub fn receive_renet_messages<E: DeserializeOwned + Send + Sync + 'static, C: RenetConnection>(
+    transport: Res<RenetTransport<E, C>>,
+    mut connection: ResMut<C::Connection>,
+    mut events: EventWriter<Received<E, C::PeerId>>,
+) {
+    while let Some(received) = transport.receive(&mut connection) {
+        events.send(received);
+    }
+}
+
+/// Reads an [`Events`](bevy::ecs::event::Events) stream of [`ActionDiff`]s confirmed by the server to update [`ActionAuthority::confirmed`]
+///
+/// The confirmed states are ticked first, so that actions confirmed during this frame are reported as just pressed or just released.
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run before [`reconcile_action_states`].
+pub fn process_confirmed_action_diffs<A: Actionlike, ID: StableId + Component>(
+    mut authority_query: Query<(&mut ActionAuthority<A>, &ID)>,
+    mut action_diffs: EventReader<ActionDiff<A, ID>>,
+    time: Res<Time>,
+    mut stored_previous_instant: Local<Option<Instant>>,
+) {
+    let current_instant = time.last_update().unwrap_or_else(|| time.startup());
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| time.startup());
+    for (mut authority, _) in authority_query.iter_mut() {
+        let authority = &mut *authority;
+        authority.confirmed.tick_with_timings(
+            &mut authority.confirmed_timings,
+            current_instant,
+            previous_instant,
+        );
+    }
+    *stored_previous_instant = time.last_update();
+
+    for action_diff in action_diffs.iter() {
+        for (mut authority, id) in authority_query.iter_mut() {
+            if action_diff.id() != id {
+                continue;
+            }
+
+            match action_diff {
+                ActionDiff::Pressed { action, .. } => authority.confirmed.press(action.clone()),
+                ActionDiff::Released { action, .. } => authority.confirmed.release(action.clone()),
+            }
+        }
+    }
+}
+
+/// Stores the local inputs of each [`ActionState`] in [`ActionAuthority::predicted`],
+/// then replaces the actions that require confirmation with their [`ActionAuthority::confirmed`] state
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after [`InputManagerSystem::ManualControl`](crate::plugin::InputManagerSystem::ManualControl),
+/// and [`restore_predicted_action_states`] must run later in the same frame.
+///
+/// The [`Timing`] of each confirmed action is replaced too, if the entity has an [`ActionTimings`].
+pub fn reconcile_action_states<A: Actionlike>(
+    mut query: Query<(
+        &mut ActionState<A>,
+        Option<&mut ActionTimings<A>>,
+        &mut ActionAuthority<A>,
+    )>,
+) {
+    for (mut action_state, mut action_timings, mut authority) in query.iter_mut() {
+        let authority = &mut *authority;
+        authority.predicted = action_state.clone();
+        if let Some(action_timings) = &action_timings {
+            authority.predicted_timings = ActionTimings::clone(action_timings);
+        }
+
+        for action in authority.confirmed_actions() {
+            let confirmed_data = authority.confirmed.action_data(action.clone()).clone();
+            action_state.set_action_data(action.clone(), confirmed_data);
+            if let Some(action_timings) = action_timings.as_mut() {
+                let timing = authority
+                    .confirmed_timings
+                    .timing(&authority.confirmed, action.clone());
+                action_timings.set_timing(&action_state, action, timing);
+            }
+        }
+    }
+}
+
+/// Restores the local inputs stored in [`ActionAuthority::predicted`] for the actions that require confirmation
+///
+/// This system is not part of the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) and must be added manually.
+/// It should run after [`reconcile_action_states`] and before the [`ActionDiff`]s sent to the server are generated.
+///
+/// The [`Timing`] of each confirmed action is restored too, if the entity has an [`ActionTimings`].
+pub fn restore_predicted_action_states<A: Actionlike>(
+    mut query: Query<(
+        &mut ActionState<A>,
+        Option<&mut ActionTimings<A>>,
+        &ActionAuthority<A>,
+    )>,
+) {
+    for (mut action_state, mut action_timings, authority) in query.iter_mut() {
+        for action in authority.confirmed_actions() {
+            let predicted_data = authority.predicted.action_data(action.clone()).clone();
+            action_state.set_action_data(action.clone(), predicted_data);
+            if let Some(action_timings) = action_timings.as_mut() {
+                let timing = authority
+                    .predicted_timings
+                    .timing(&authority.predicted, action.clone());
+                action_timings.set_timing(&action_state, action, timing);
+            }
         }
     }
 }
@@ -255,6 +2042,28 @@ pub fn release_on_disable<A: Actionlike>(
     }
 }
 
+/// Releases all actions of [`ActionState`]s whose inputs were just blocked
+///
+/// This applies to entities that were just given a [`BlockInput`] component,
+/// and to every [`ActionState`] when a reason is pushed onto the [`InputBlockers`] resource.
+pub fn release_blocked_actions<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, Option<Ref<BlockInput<A>>>)>,
+    resource: Option<ResMut<ActionState<A>>>,
+    input_blockers: Option<Res<InputBlockers<A>>>,
+) {
+    let globally_blocked = input_blockers
+        .is_some_and(|input_blockers| input_blockers.is_changed() && input_blockers.is_blocked());
+
+    for (mut action_state, block_input) in query.iter_mut() {
+        if globally_blocked || block_input.is_some_and(|block_input| block_input.is_added()) {
+            action_state.release_all();
+        }
+    }
+    if let Some(mut action_state) = resource.filter(|_| globally_blocked) {
+        action_state.release_all();
+    }
+}
+
 /// Release all inputs when an [`InputMap<A>`] is removed to prevent them from being held forever.
 ///
 /// By default, [`InputManagerPlugin<A>`](crate::plugin::InputManagerPlugin) will run this on [`PostUpdate`](bevy::prelude::PostUpdate).
@@ -292,3 +2101,415 @@ pub fn release_on_input_map_removed<A: Actionlike>(
 pub fn run_if_enabled<A: Actionlike>(toggle_actions: Res<ToggleActions<A>>) -> bool {
     toggle_actions.enabled
 }
+
+/// Sends a [`ValueThresholdCrossed`] event whenever the value of an action crosses a threshold registered in its [`ValueThresholds`]
+pub fn generate_value_threshold_events<A: Actionlike>(
+    mut query: Query<(Entity, &ActionState<A>, &mut ValueThresholds<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    value_thresholds: Option<ResMut<ValueThresholds<A>>>,
+    mut events: EventWriter<ValueThresholdCrossed<A>>,
+) {
+    let mut send_events = |entity: Option<Entity>,
+                           action_state: &ActionState<A>,
+                           value_thresholds: &mut ValueThresholds<A>| {
+        for (action, threshold, direction) in value_thresholds.update(action_state) {
+            events.send(ValueThresholdCrossed {
+                value: action_state.value(action.clone()),
+                action,
+                threshold,
+                direction,
+                entity,
+            });
+        }
+    };
+
+    for (entity, action_state, mut value_thresholds) in query.iter_mut() {
+        send_events(Some(entity), action_state, &mut value_thresholds);
+    }
+
+    if let (Some(action_state), Some(mut value_thresholds)) = (action_state, value_thresholds) {
+        send_events(None, &action_state, &mut value_thresholds);
+    }
+}
+
+/// Records the activity of every [`ActionState`] in the [`InputActivity`], sending an [`InputIdle`] event whenever one of its thresholds is reached
+pub fn generate_input_idle_events<A: Actionlike>(
+    query: Query<(Entity, &ActionState<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    mut activity: ResMut<InputActivity<A>>,
+    mut events: EventWriter<InputIdle<A>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+    mut stored_previous_instant: Local<Option<Instant>>,
+) {
+    let clock = clock.as_deref().copied().unwrap_or_default();
+    let current_instant = clock.now(&time).unwrap_or_else(|| clock.startup(&time));
+    let previous_instant = stored_previous_instant.unwrap_or_else(|| clock.startup(&time));
+
+    let action_states = query
+        .iter()
+        .map(|(entity, action_state)| (Some(entity), action_state))
+        .chain(
+            action_state
+                .as_deref()
+                .map(|action_state| (None, action_state)),
+        );
+    events.send_batch(activity.update(action_states, current_instant, previous_instant));
+
+    *stored_previous_instant = clock.now(&time);
+}
+
+/// Records the usage of each action in its [`ActionStats`]
+#[cfg(feature = "stats")]
+pub fn record_action_stats<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &mut ActionStats<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    stats: Option<ResMut<ActionStats<A>>>,
+    time: Res<Time>,
+) {
+    for (action_state, mut stats) in query.iter_mut() {
+        stats.record(action_state, time.delta());
+    }
+
+    if let (Some(action_state), Some(mut stats)) = (action_state, stats) {
+        stats.record(&action_state, time.delta());
+    }
+}
+
+/// Applies the [`MissingComponentPolicies`] to entities that were given only one of an [`InputMap`] and an [`ActionState`]
+///
+/// Only entities whose [`InputMap`] or [`ActionState`] was added since this system last ran are checked.
+pub fn check_missing_components<A: Actionlike>(
+    mut commands: Commands,
+    policies: Res<MissingComponentPolicies<A>>,
+    missing_action_state: Query<Entity, (Added<InputMap<A>>, Without<ActionState<A>>)>,
+    missing_input_map: Query<Entity, (Added<ActionState<A>>, Without<InputMap<A>>)>,
+    mut events: EventWriter<MissingComponent<A>>,
+) {
+    let checks = [
+        (
+            policies.action_state,
+            MissingComponentKind::ActionState,
+            missing_action_state.iter().collect::<Vec<_>>(),
+        ),
+        (
+            policies.input_map,
+            MissingComponentKind::InputMap,
+            missing_input_map.iter().collect::<Vec<_>>(),
+        ),
+    ];
+
+    for (policy, missing, entities) in checks {
+        for entity in entities {
+            match policy {
+                MissingComponentPolicy::Ignore => (),
+                MissingComponentPolicy::Warn => {
+                    warn!(
+                        "{entity:?} has no `{}<{}>` component, so its inputs will not be processed",
+                        match missing {
+                            MissingComponentKind::ActionState => "ActionState",
+                            MissingComponentKind::InputMap => "InputMap",
+                        },
+                        std::any::type_name::<A>()
+                    );
+                    events.send(MissingComponent::new(entity, missing));
+                }
+                MissingComponentPolicy::Insert => match missing {
+                    MissingComponentKind::ActionState => {
+                        commands.entity(entity).insert(ActionState::<A>::default());
+                    }
+                    MissingComponentKind::InputMap => {
+                        commands.entity(entity).insert(InputMap::<A>::default());
+                    }
+                },
+            }
+        }
+    }
+}
+
+/// Sets the [`NextState`] when an action in the [`ActionStateTransitions`] is just pressed while in the state that it transitions from
+///
+/// Both the [`ActionState`] resource and every [`ActionState`] component are checked.
+/// This system is not added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), as it is generic over the state type `S`.
+pub fn apply_action_state_transitions<A: Actionlike, S: States>(
+    transitions: Res<ActionStateTransitions<A, S>>,
+    state: Res<State<S>>,
+    mut next_state: ResMut<NextState<S>>,
+    action_state: Option<Res<ActionState<A>>>,
+    query: Query<&ActionState<A>>,
+) {
+    let next = action_state
+        .as_deref()
+        .into_iter()
+        .chain(query.iter())
+        .find_map(|action_state| transitions.next_state(state.get(), action_state));
+
+    if let Some(next) = next {
+        next_state.set(next.clone());
+    }
+}
+
+/// Suppresses the presses that exceed the limits set in each [`RateLimits`], sending a [`RateLimited`] event for each of them
+pub fn enforce_rate_limits<A: Actionlike>(
+    mut query: Query<(Entity, &mut ActionState<A>, &mut RateLimits<A>)>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    rate_limits: Option<ResMut<RateLimits<A>>>,
+    time: Res<Time>,
+    clock: Option<Res<InputClock>>,
+    mut events: EventWriter<RateLimited<A>>,
+) {
+    let now = clock
+        .as_deref()
+        .copied()
+        .unwrap_or_default()
+        .now(&time)
+        .unwrap_or_else(Instant::now);
+    let mut send_events = |entity: Option<Entity>,
+                           action_state: &mut ActionState<A>,
+                           rate_limits: &mut RateLimits<A>| {
+        for action in rate_limits.enforce(action_state, now) {
+            events.send(RateLimited { action, entity });
+        }
+    };
+
+    for (entity, mut action_state, mut rate_limits) in query.iter_mut() {
+        send_events(Some(entity), &mut action_state, &mut rate_limits);
+    }
+
+    if let (Some(mut action_state), Some(mut rate_limits)) = (action_state, rate_limits) {
+        send_events(None, &mut action_state, &mut rate_limits);
+    }
+}
+
+/// Advances the [`DragStates`] of each [`ActionState`], using the cursor position of the primary window
+pub fn update_drag_states<A: Actionlike>(
+    mut query: Query<(&ActionState<A>, &mut DragStates<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    drag_states: Option<ResMut<DragStates<A>>>,
+    windows: Query<&Window, With<PrimaryWindow>>,
+) {
+    let cursor = windows.get_single().ok().and_then(Window::cursor_position);
+
+    for (action_state, mut drag_states) in query.iter_mut() {
+        drag_states.update(action_state, cursor);
+    }
+
+    if let (Some(action_state), Some(mut drag_states)) = (action_state, drag_states) {
+        drag_states.update(&action_state, cursor);
+    }
+}
+
+/// Updates the selection of each [`RadialMenu`], sending a [`RadialSelectionChanged`] event whenever it changes
+pub fn update_radial_menus<A: Actionlike>(
+    mut query: Query<(Entity, &ActionState<A>, &mut RadialMenu<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    radial_menu: Option<ResMut<RadialMenu<A>>>,
+    mut events: EventWriter<RadialSelectionChanged<A>>,
+) {
+    let mut send_events =
+        |entity: Option<Entity>, action_state: &ActionState<A>, radial_menu: &mut RadialMenu<A>| {
+            let previous = radial_menu.selected();
+            if let Some(selected) = radial_menu.update(action_state) {
+                events.send(RadialSelectionChanged {
+                    action: radial_menu.action(),
+                    previous,
+                    selected,
+                    entity,
+                });
+            }
+        };
+
+    for (entity, action_state, mut radial_menu) in query.iter_mut() {
+        send_events(Some(entity), action_state, &mut radial_menu);
+    }
+
+    if let (Some(action_state), Some(mut radial_menu)) = (action_state, radial_menu) {
+        send_events(None, &action_state, &mut radial_menu);
+    }
+}
+
+/// Applies the [`ExclusiveGroups`] of each [`ActionState`], consuming the other members of groups whose member was just pressed
+pub fn apply_exclusive_groups<A: Actionlike>(
+    mut query: Query<(&mut ActionState<A>, &mut ExclusiveGroups<A>)>,
+    action_state: Option<ResMut<ActionState<A>>>,
+    exclusive_groups: Option<ResMut<ExclusiveGroups<A>>>,
+) {
+    for (mut action_state, mut exclusive_groups) in query.iter_mut() {
+        exclusive_groups.update(&mut action_state);
+    }
+
+    if let (Some(mut action_state), Some(mut exclusive_groups)) = (action_state, exclusive_groups) {
+        exclusive_groups.update(&mut action_state);
+    }
+}
+
+/// Runs the [`ActionHooks`] of each action that was just pressed or just released
+pub fn run_action_hooks<A: Actionlike>(
+    query: Query<(Entity, &ActionState<A>, &ActionHooks<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    action_hooks: Option<Res<ActionHooks<A>>>,
+    mut commands: Commands,
+) {
+    let resources = action_state
+        .zip(action_hooks)
+        .map(|(action_state, action_hooks)| {
+            (None, action_state.into_inner(), action_hooks.into_inner())
+        });
+
+    for (entity, action_state, action_hooks) in query
+        .iter()
+        .map(|(entity, action_state, action_hooks)| (Some(entity), action_state, action_hooks))
+        .chain(resources)
+    {
+        for (action, transition, hook) in action_hooks.iter() {
+            let triggered = match transition {
+                ActionTransition::Pressed => action_state.newly_pressed(action.clone()),
+                ActionTransition::Released => action_state.just_released(action.clone()),
+            };
+
+            if triggered {
+                let context = ActionHookContext {
+                    action: action.clone(),
+                    transition,
+                    entity,
+                };
+                hook(&context, &mut commands);
+            }
+        }
+    }
+}
+
+/// Advances each [`InputPrompt`], sending an [`InputPromptEvent`] for everything that happens to it
+pub fn update_input_prompts<A: Actionlike>(
+    mut query: Query<(Entity, &ActionState<A>, &mut InputPrompt<A>)>,
+    action_state: Option<Res<ActionState<A>>>,
+    input_prompt: Option<ResMut<InputPrompt<A>>>,
+    forgiveness: Option<Res<TimingForgiveness>>,
+    time: Res<Time>,
+    mut events: EventWriter<InputPromptEvent<A>>,
+) {
+    // Slowing down the prompt's clock is equivalent to lengthening its timeout
+    let delta = forgiveness
+        .map(|forgiveness| forgiveness.slow_down(time.delta()))
+        .unwrap_or_else(|| time.delta());
+
+    let mut send_events = |entity: Option<Entity>,
+                           action_state: &ActionState<A>,
+                           input_prompt: &mut InputPrompt<A>| {
+        for outcome in input_prompt.update(action_state, delta) {
+            events.send(InputPromptEvent { outcome, entity });
+        }
+    };
+
+    for (entity, action_state, mut input_prompt) in query.iter_mut() {
+        send_events(Some(entity), action_state, &mut input_prompt);
+    }
+
+    if let (Some(action_state), Some(mut input_prompt)) = (action_state, input_prompt) {
+        send_events(None, &action_state, &mut input_prompt);
+    }
+}
+
+/// Moves the [`VirtualCursor`] using the actions named in the [`VirtualCursorSettings`], read from the [`ActionState`] resource
+///
+/// If [`VirtualCursorSettings::drive_window_cursor`] is enabled, the cursor of the primary window is moved as well,
+/// and clicks are sent as left [`MouseButtonInput`] events.
+///
+/// This system is not added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+/// See the [`virtual_cursor`](crate::virtual_cursor) module for how to add it.
+pub fn update_virtual_cursor<A: Actionlike>(
+    action_state: Option<Res<ActionState<A>>>,
+    settings: Res<VirtualCursorSettings<A>>,
+    mut cursor: ResMut<VirtualCursor>,
+    mut windows: Query<(Entity, &mut Window), With<PrimaryWindow>>,
+    mut mouse_button_events: EventWriter<MouseButtonInput>,
+    time: Res<Time>,
+) {
+    let Some(action_state) = action_state else {
+        return;
+    };
+
+    let stick = action_state
+        .axis_pair(settings.movement.clone())
+        .map(|axis_pair| axis_pair.xy())
+        .unwrap_or_default();
+    let clicking = settings
+        .click
+        .clone()
+        .is_some_and(|click| action_state.pressed(click));
+
+    let mut window = windows.get_single_mut().ok();
+    let bounds = window
+        .as_ref()
+        .map(|(_, window)| Vec2::new(window.width(), window.height()));
+    cursor.advance(stick, clicking, &settings, time.delta_seconds(), bounds);
+
+    if !settings.drive_window_cursor {
+        return;
+    }
+    let Some((window_entity, window)) = window.as_mut() else {
+        return;
+    };
+
+    if stick != Vec2::ZERO {
+        window.set_cursor_position(cursor.position);
+    }
+
+    let state = if cursor.just_clicked() {
+        bevy::input::ButtonState::Pressed
+    } else if cursor.just_released() {
+        bevy::input::ButtonState::Released
+    } else {
+        return;
+    };
+    mouse_button_events.send(MouseButtonInput {
+        button: MouseButton::Left,
+        state,
+        window: *window_entity,
+    });
+}
+
+/// Logs a warning for each keyboard chord that is likely to be impossible to press due to keyboard ghosting
+///
+/// Inputs are checked against the [`KeyboardMatrix`] resource if it exists, or [`KeyboardMatrix::default`] otherwise.
+/// Only [`InputMap`]s that have changed are checked.
+///
+/// This diagnostic is not added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+/// Add it to your app during development to help design default bindings that work on all keyboards.
+pub fn warn_about_ghosting<A: Actionlike + std::fmt::Debug>(
+    query: Query<(Entity, &InputMap<A>), Changed<InputMap<A>>>,
+    input_map: Option<Res<InputMap<A>>>,
+    keyboard_matrix: Option<Res<KeyboardMatrix>>,
+) {
+    let default_matrix;
+    let keyboard_matrix = match &keyboard_matrix {
+        Some(keyboard_matrix) => keyboard_matrix.as_ref(),
+        None => {
+            default_matrix = KeyboardMatrix::default();
+            &default_matrix
+        }
+    };
+
+    let resource = input_map
+        .as_ref()
+        .filter(|input_map| input_map.is_changed())
+        .map(|input_map| (None, input_map.as_ref()));
+
+    for (entity, input_map) in query
+        .iter()
+        .map(|(entity, input_map)| (Some(entity), input_map))
+        .chain(resource)
+    {
+        for warning in input_map.ghosting_warnings(keyboard_matrix) {
+            warn!(
+                "The input {} bound to {:?}{} may be impossible to press on some keyboards: {:?}",
+                warning.input,
+                warning.action,
+                entity
+                    .map(|entity| format!(" on {entity:?}"))
+                    .unwrap_or_default(),
+                warning.risk
+            );
+        }
+    }
+}
@@ -0,0 +1,481 @@
+//! Headless utilities for testing control schemes built on this crate
+//!
+//! The [`InputHarness`] builds a minimal [`App`] with the [`InputManagerPlugin`] and a single entity with an [`InputMap`],
+//! so that downstream crates can test their bindings without a window:
+//! inject inputs, advance frames, then assert on the resulting [`ActionState`].
+//!
+//! Each frame advances time by a fixed [frame duration](InputHarness::with_frame_duration),
+//! so that durations measured by the [`ActionTimings`] of the entity are deterministic.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::testing::InputHarness;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Crouch,
+//! }
+//!
+//! let mut harness = InputHarness::new(InputMap::new([
+//!     (KeyCode::Space, Action::Jump),
+//!     (KeyCode::ControlLeft, Action::Crouch),
+//! ]));
+//!
+//! harness
+//!     .press_input(KeyCode::Space)
+//!     .update()
+//!     .assert_just_pressed(Action::Jump)
+//!     .assert_released(Action::Crouch);
+//!
+//! harness.advance_frames(10).assert_pressed(Action::Jump);
+//!
+//! harness
+//!     .release_input(KeyCode::Space)
+//!     .update()
+//!     .assert_just_released(Action::Jump);
+//! ```
+//!
+//! The [`NetplayHarness`] does the same for networked inputs, with a host and a client [`App`] in a single process.
+//! The [`ActionDiff`]s generated by the client are sent to the host over a [`loopback_channel`] and applied there,
+//! so that the replication of actions can be tested end to end without a real socket.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::testing::NetplayHarness;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//! }
+//!
+//! let mut netplay = NetplayHarness::new(InputMap::new([(KeyCode::Space, Action::Jump)]));
+//!
+//! netplay.press_input(KeyCode::Space).update();
+//! assert!(netplay.host_action_state().pressed(Action::Jump));
+//!
+//! netplay.release_input(KeyCode::Space).update().assert_in_sync();
+//! ```
+
+use crate::action_state::{ActionDiff, ActionState};
+use crate::diff_transport::{loopback_channel, LoopbackReceiver, LoopbackSender};
+use crate::input_map::InputMap;
+use crate::input_mocking::MockInput;
+use crate::plugin::{InputManagerPlugin, InputManagerSystem};
+use crate::stable_id::StableIdRegistry;
+use crate::systems::{
+    generate_registered_action_diffs, process_registered_action_diffs, receive_diffs, send_diffs,
+};
+use crate::timing::ActionTimings;
+use crate::user_input::UserInput;
+use crate::{Actionlike, InputManagerBundle};
+
+use bevy::app::{App, PostUpdate, PreUpdate};
+use bevy::ecs::entity::Entity;
+use bevy::ecs::schedule::IntoSystemConfigs;
+use bevy::input::InputPlugin;
+use bevy::prelude::MinimalPlugins;
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::Duration;
+use std::fmt::Debug;
+use std::marker::PhantomData;
+
+/// A minimal headless [`App`] for testing how inputs are mapped to the actions of type `A`
+///
+/// See the [module documentation](crate::testing) for an example.
+pub struct InputHarness<A: Actionlike> {
+    app: App,
+    entity: Entity,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> InputHarness<A> {
+    /// The duration of each frame, unless changed with [`InputHarness::with_frame_duration`]
+    pub const DEFAULT_FRAME_DURATION: Duration = Duration::from_nanos(1_000_000_000 / 60);
+
+    /// Creates an [`App`] with the [`InputManagerPlugin`] and a single entity with the `input_map`
+    ///
+    /// No frames are run until [`InputHarness::update`] is called.
+    #[must_use]
+    pub fn new(input_map: InputMap<A>) -> Self {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_plugins(InputPlugin)
+            .add_plugins(InputManagerPlugin::<A>::default())
+            .insert_resource(TimeUpdateStrategy::ManualDuration(
+                Self::DEFAULT_FRAME_DURATION,
+            ));
+
+        let entity = app
+            .world
+            .spawn(InputManagerBundle::<A> {
+                input_map,
+                ..Default::default()
+            })
+            .id();
+
+        Self {
+            app,
+            entity,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Advances time by the `frame_duration` in each frame
+    #[must_use]
+    pub fn with_frame_duration(mut self, frame_duration: Duration) -> Self {
+        self.app
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame_duration));
+        self
+    }
+
+    /// The [`App`] under test, to inspect other resources and components
+    #[must_use]
+    pub fn app(&self) -> &App {
+        &self.app
+    }
+
+    /// The [`App`] under test, to add the systems and resources of the game
+    pub fn app_mut(&mut self) -> &mut App {
+        &mut self.app
+    }
+
+    /// The entity with the [`InputMap`] and [`ActionState`]
+    #[must_use]
+    pub fn entity(&self) -> Entity {
+        self.entity
+    }
+
+    /// The [`ActionState`] of the [`InputHarness::entity`]
+    #[must_use]
+    pub fn action_state(&self) -> &ActionState<A> {
+        self.app
+            .world
+            .get::<ActionState<A>>(self.entity)
+            .expect("The ActionState of the InputHarness entity was removed")
+    }
+
+    /// The [`ActionTimings`] of the [`InputHarness::entity`], which track how long its actions have been pressed or released
+    #[must_use]
+    pub fn action_timings(&self) -> &ActionTimings<A> {
+        self.app
+            .world
+            .get::<ActionTimings<A>>(self.entity)
+            .expect("The ActionTimings of the InputHarness entity was removed")
+    }
+
+    /// Presses the `input`, which is read during the next [`InputHarness::update`]
+    pub fn press_input(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        self.app.send_input(input);
+        self
+    }
+
+    /// Releases the `input`, which is read during the next [`InputHarness::update`]
+    pub fn release_input(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        self.app.release_input(input);
+        self
+    }
+
+    /// Releases all inputs
+    pub fn reset_inputs(&mut self) -> &mut Self {
+        self.app.reset_inputs();
+        self
+    }
+
+    /// Runs a single frame
+    pub fn update(&mut self) -> &mut Self {
+        self.app.update();
+        self
+    }
+
+    /// Runs the number of `frames`
+    pub fn advance_frames(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.app.update();
+        }
+        self
+    }
+}
+
+impl<A: Actionlike + Debug> InputHarness<A> {
+    /// Asserts that the `action` is pressed
+    #[track_caller]
+    pub fn assert_pressed(&self, action: A) -> &Self {
+        assert!(
+            self.action_state().pressed(action.clone()),
+            "{action:?} should be pressed"
+        );
+        self
+    }
+
+    /// Asserts that the `action` is released
+    #[track_caller]
+    pub fn assert_released(&self, action: A) -> &Self {
+        assert!(
+            self.action_state().released(action.clone()),
+            "{action:?} should be released"
+        );
+        self
+    }
+
+    /// Asserts that the `action` was pressed during the last frame
+    #[track_caller]
+    pub fn assert_just_pressed(&self, action: A) -> &Self {
+        assert!(
+            self.action_state().just_pressed(action.clone()),
+            "{action:?} should have just been pressed"
+        );
+        self
+    }
+
+    /// Asserts that the `action` was released during the last frame
+    #[track_caller]
+    pub fn assert_just_released(&self, action: A) -> &Self {
+        assert!(
+            self.action_state().just_released(action.clone()),
+            "{action:?} should have just been released"
+        );
+        self
+    }
+}
+
+/// A host and a client [`App`] in a single process, connected by a [`loopback_channel`] of [`ActionDiff`]s
+///
+/// The client reads inputs into the [`ActionState`] of an entity with an [`InputMap`].
+/// Its diffs are sent to the host, which applies them to the [`ActionState`] of its own entity for the same player.
+/// Both entities are registered in a [`StableIdRegistry`] with the [`NetplayHarness::PLAYER_ID`].
+///
+/// See the [module documentation](crate::testing) for an example.
+pub struct NetplayHarness<A: Actionlike> {
+    host: App,
+    client: App,
+    host_entity: Entity,
+    client_entity: Entity,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> NetplayHarness<A> {
+    /// The stable identifier of the player, shared by the host and the client
+    pub const PLAYER_ID: u64 = 0;
+
+    /// Creates a client [`App`] with a single entity with the `input_map`, and a host [`App`] that replicates its actions
+    ///
+    /// Each frame advances time by [`InputHarness::DEFAULT_FRAME_DURATION`] in both apps.
+    /// No frames are run until [`NetplayHarness::update`] is called.
+    #[must_use]
+    pub fn new(input_map: InputMap<A>) -> Self {
+        let (sender, receiver) = loopback_channel::<ActionDiff<A, u64>>();
+
+        let mut client = App::new();
+        client
+            .add_plugins(MinimalPlugins)
+            .add_plugins(InputPlugin)
+            .add_plugins(InputManagerPlugin::<A>::default())
+            .add_event::<ActionDiff<A, u64>>()
+            .insert_resource(sender)
+            .add_systems(
+                PostUpdate,
+                (
+                    generate_registered_action_diffs::<A, u64>,
+                    send_diffs::<ActionDiff<A, u64>, LoopbackSender<ActionDiff<A, u64>>>,
+                )
+                    .chain(),
+            );
+        let client_entity = client
+            .world
+            .spawn(InputManagerBundle::<A> {
+                input_map,
+                ..Default::default()
+            })
+            .id();
+
+        let mut host = App::new();
+        host.add_plugins(MinimalPlugins)
+            .add_plugins(InputManagerPlugin::<A>::server())
+            .add_event::<ActionDiff<A, u64>>()
+            .insert_resource(receiver)
+            .add_systems(
+                PreUpdate,
+                (
+                    receive_diffs::<ActionDiff<A, u64>, LoopbackReceiver<ActionDiff<A, u64>>>,
+                    process_registered_action_diffs::<A, u64>,
+                )
+                    .chain()
+                    .after(InputManagerSystem::Tick),
+            );
+        let host_entity = host.world.spawn(ActionState::<A>::default()).id();
+
+        for (app, entity) in [(&mut client, client_entity), (&mut host, host_entity)] {
+            let mut registry = StableIdRegistry::<u64>::default();
+            registry.insert(Self::PLAYER_ID, entity);
+            app.insert_resource(registry)
+                .insert_resource(TimeUpdateStrategy::ManualDuration(
+                    InputHarness::<A>::DEFAULT_FRAME_DURATION,
+                ));
+        }
+
+        Self {
+            host,
+            client,
+            host_entity,
+            client_entity,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Advances time by the `frame_duration` in each frame, in both apps
+    #[must_use]
+    pub fn with_frame_duration(mut self, frame_duration: Duration) -> Self {
+        for app in [&mut self.host, &mut self.client] {
+            app.insert_resource(TimeUpdateStrategy::ManualDuration(frame_duration));
+        }
+        self
+    }
+
+    /// The [`App`] that receives the actions of the client
+    #[must_use]
+    pub fn host(&self) -> &App {
+        &self.host
+    }
+
+    /// The [`App`] that receives the actions of the client, to add the systems and resources of the game
+    pub fn host_mut(&mut self) -> &mut App {
+        &mut self.host
+    }
+
+    /// The [`App`] that reads inputs and sends its actions to the host
+    #[must_use]
+    pub fn client(&self) -> &App {
+        &self.client
+    }
+
+    /// The [`App`] that reads inputs and sends its actions to the host, to add the systems and resources of the game
+    pub fn client_mut(&mut self) -> &mut App {
+        &mut self.client
+    }
+
+    /// The entity of the player in the host [`App`]
+    #[must_use]
+    pub fn host_entity(&self) -> Entity {
+        self.host_entity
+    }
+
+    /// The entity of the player in the client [`App`], with the [`InputMap`]
+    #[must_use]
+    pub fn client_entity(&self) -> Entity {
+        self.client_entity
+    }
+
+    /// The [`ActionState`] of the player in the host [`App`]
+    #[must_use]
+    pub fn host_action_state(&self) -> &ActionState<A> {
+        self.host
+            .world
+            .get::<ActionState<A>>(self.host_entity)
+            .expect("The ActionState of the NetplayHarness host entity was removed")
+    }
+
+    /// The [`ActionState`] of the player in the client [`App`]
+    #[must_use]
+    pub fn client_action_state(&self) -> &ActionState<A> {
+        self.client
+            .world
+            .get::<ActionState<A>>(self.client_entity)
+            .expect("The ActionState of the NetplayHarness client entity was removed")
+    }
+
+    /// Presses the `input` on the client, which is read during the next [`NetplayHarness::update`]
+    pub fn press_input(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        self.client.send_input(input);
+        self
+    }
+
+    /// Releases the `input` on the client, which is read during the next [`NetplayHarness::update`]
+    pub fn release_input(&mut self, input: impl Into<UserInput>) -> &mut Self {
+        self.client.release_input(input);
+        self
+    }
+
+    /// Releases all inputs on the client
+    pub fn reset_inputs(&mut self) -> &mut Self {
+        self.client.reset_inputs();
+        self
+    }
+
+    /// Runs a single frame of the client, then a single frame of the host
+    ///
+    /// The diffs sent by the client during its frame are applied by the host during the same call.
+    pub fn update(&mut self) -> &mut Self {
+        self.client.update();
+        self.host.update();
+        self
+    }
+
+    /// Runs the number of `frames` in both apps
+    pub fn advance_frames(&mut self, frames: usize) -> &mut Self {
+        for _ in 0..frames {
+            self.update();
+        }
+        self
+    }
+
+    /// Asserts that every action is pressed on the host if and only if it is pressed on the client
+    #[track_caller]
+    pub fn assert_in_sync(&self) -> &Self {
+        assert!(
+            self.host_action_state()
+                .eq_pressed_state(self.client_action_state()),
+            "the host should have the same pressed actions as the client"
+        );
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::{KeyCode, Reflect};
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+    }
+
+    #[test]
+    fn frames_advance_by_the_frame_duration() {
+        let mut harness = InputHarness::new(InputMap::new([(KeyCode::ShiftLeft, Action::Run)]))
+            .with_frame_duration(Duration::from_millis(100));
+
+        harness.press_input(KeyCode::ShiftLeft).update();
+        harness.advance_frames(3).assert_pressed(Action::Run);
+        assert_eq!(
+            harness
+                .action_timings()
+                .current_duration(harness.action_state(), Action::Run),
+            Duration::from_millis(300)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "Run should be pressed")]
+    fn failed_assertions_panic() {
+        let mut harness = InputHarness::new(InputMap::new([(KeyCode::ShiftLeft, Action::Run)]));
+        harness.update().assert_pressed(Action::Run);
+    }
+
+    #[test]
+    fn actions_are_replicated_to_the_host() {
+        let mut netplay = NetplayHarness::new(InputMap::new([(KeyCode::ShiftLeft, Action::Run)]));
+
+        netplay.press_input(KeyCode::ShiftLeft).update();
+        assert!(netplay.host_action_state().just_pressed(Action::Run));
+        netplay.advance_frames(2).assert_in_sync();
+        assert!(netplay.host_action_state().pressed(Action::Run));
+
+        netplay.reset_inputs().update().assert_in_sync();
+        assert!(netplay.host_action_state().just_released(Action::Run));
+    }
+}
@@ -0,0 +1,699 @@
+//! Tracking how long actions have been pressed or released
+//!
+//! How long each action has been pressed or released is stored in an optional [`ActionTimings`], alongside its [`ActionState`].
+//! The time that actions are ticked with is read from the [`InputClock`] resource.
+//! By default, this follows Bevy's [`Time`], but it can be replaced by a manual clock
+//! so that holds, buffers and cooldowns can be tested deterministically, without sleeping.
+//!
+//! ```rust
+//! use bevy::input::InputPlugin;
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::timing::{ActionTimings, InputClock};
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Charge,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(MinimalPlugins)
+//!     .add_plugins(InputPlugin)
+//!     .add_plugins(InputManagerPlugin::<Action>::default())
+//!     .init_resource::<ActionState<Action>>()
+//!     .insert_resource(InputClock::manual());
+//! app.update();
+//!
+//! app.world.resource_mut::<ActionState<Action>>().press(Action::Charge);
+//! app.world.resource_mut::<InputClock>().advance(Duration::from_secs(2));
+//! app.update();
+//!
+//! let action_state = app.world.resource::<ActionState<Action>>();
+//! let timings = app.world.resource::<ActionTimings<Action>>();
+//! assert_eq!(timings.current_duration(action_state, Action::Charge), Duration::from_secs(2));
+//! ```
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use bevy::reflect::Reflect;
+use bevy::time::Time;
+use bevy::utils::{Duration, Instant};
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+
+/// Stores information about when an action was pressed or released
+///
+/// The [`Timing`] of each action is stored in an [`ActionTimings`], alongside the [`ActionState`].
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize, Reflect)]
+pub struct Timing {
+    /// The [`Instant`] at which the button was pressed or released
+    /// Recorded as the [`Time`](bevy::time::Time) at the start of the tick after the state last changed.
+    /// If this is none, [`Timing::tick`] has not been called yet.
+    #[serde(skip)]
+    pub instant_started: Option<Instant>,
+    /// The [`Instant`] of the raw input event that last pressed or released the button, if it is known
+    ///
+    /// Unlike `instant_started`, this is not delayed until the next tick, so it can be used to score precisely timed inputs.
+    /// It is only recorded for [`ActionState`]s that [capture raw inputs](crate::input_capture),
+    /// using the timestamps in the [`RawEventTimestamps`](crate::input_capture::RawEventTimestamps) resource where they are available.
+    #[serde(skip)]
+    pub event_timestamp: Option<Instant>,
+    /// The [`Duration`] for which the button has been pressed or released.
+    ///
+    /// This begins at [`Duration::ZERO`] when [`ActionState::update`](crate::action_state::ActionState::update) is called.
+    pub current_duration: Duration,
+    /// The [`Duration`] for which the button was pressed or released before the state last changed.
+    pub previous_duration: Duration,
+}
+
+impl PartialOrd for Timing {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        self.current_duration.partial_cmp(&other.current_duration)
+    }
+}
+
+impl Timing {
+    /// Advances the `current_duration` of this timer
+    ///
+    /// If the `instant_started` is None, it will be set to the current time.
+    /// This design allows us to ensure that the timing is always synchronized with the start of each frame.
+    pub fn tick(&mut self, current_instant: Instant, previous_instant: Instant) {
+        if let Some(instant_started) = self.instant_started {
+            self.current_duration = current_instant - instant_started;
+        } else {
+            self.current_duration = current_instant - previous_instant;
+            self.instant_started = Some(previous_instant);
+        }
+    }
+
+    /// Flips the metaphorical hourglass, storing `current_duration` in `previous_duration` and resetting `instant_started` and `event_timestamp`
+    ///
+    /// This method is called whenever actions are pressed or released
+    pub fn flip(&mut self) {
+        self.previous_duration = self.current_duration;
+        self.current_duration = Duration::ZERO;
+        self.instant_started = None;
+        self.event_timestamp = None;
+    }
+}
+
+/// How long each action of an [`ActionState`] has been pressed or released
+///
+/// Timing is optional, and stored separately from the [`ActionState`], so that entities which never query durations don't pay for it.
+/// The [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) adds an [`ActionTimings`] to each entity with an [`ActionState`],
+/// and alongside the [`ActionState`] resource, unless it was created with [`InputManagerPlugin::without_timing`](crate::plugin::InputManagerPlugin::without_timing).
+/// Both are then advanced together by [`ActionState::tick_with_timings`].
+///
+/// Presses and releases are picked up lazily, by comparing the [`ActionState`] with the state that the timings were last flipped for.
+/// As a result, every method reads the matching [`ActionState`], and reports actions that were just pressed or released as if they had been flipped.
+/// [Auto repeats](crate::auto_repeat) and [holds](crate::hold_for) are measured with these durations, and never fire for entities without timings.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use bevy::utils::{Duration, Instant};
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::timing::ActionTimings;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Charge,
+/// }
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// let mut timings = ActionTimings::<Action>::default();
+/// let t0 = Instant::now();
+///
+/// action_state.press(Action::Charge);
+/// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(500), t0);
+/// assert_eq!(timings.current_duration(&action_state, Action::Charge), Duration::from_millis(500));
+/// assert!(timings.pressed_for(&action_state, Action::Charge, Duration::from_millis(500)));
+/// assert!(!timings.pressed_for(&action_state, Action::Charge, Duration::from_secs(1)));
+///
+/// action_state.release(Action::Charge);
+/// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(800), t0 + Duration::from_millis(500));
+/// assert!(timings.released_for(&action_state, Action::Charge, Duration::from_millis(300)));
+/// assert_eq!(timings.previous_duration(&action_state, Action::Charge), Duration::from_millis(500));
+/// ```
+#[derive(Resource, Component, Clone, Debug, PartialEq, Serialize, Deserialize, Reflect)]
+pub struct ActionTimings<A: Actionlike> {
+    /// The [`Timing`] of each action
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    timings: Vec<Timing>,
+    /// Whether each action was pressed when its [`Timing`] was last flipped
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    timed_pressed: Vec<bool>,
+    /// How long each action was held the last time it was pressed and then released
+    ///
+    /// The position in this vector corresponds to [`Actionlike::index`].
+    last_hold_durations: Vec<Duration>,
+    #[reflect(ignore)]
+    _phantom: PhantomData<A>,
+}
+
+// Implement manually to not require [`Default`] for `A`
+impl<A: Actionlike> Default for ActionTimings<A> {
+    fn default() -> Self {
+        ActionTimings {
+            timings: vec![Timing::default(); A::n_variants()],
+            timed_pressed: vec![false; A::n_variants()],
+            last_hold_durations: vec![Duration::ZERO; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ActionTimings<A> {
+    /// Advances the [`Timing`] of each action that isn't consumed
+    ///
+    /// Actions that were pressed or released since the last tick have their [`Timing`] flipped first,
+    /// so that their durations are measured from the start of this tick.
+    /// Prefer [`ActionState::tick_with_timings`], which also ticks the `action_state`.
+    pub fn tick(
+        &mut self,
+        action_state: &ActionState<A>,
+        current_instant: Instant,
+        previous_instant: Instant,
+    ) {
+        for action in A::variants() {
+            self.flip_if_changed(action_state, &action);
+            // Durations should not advance while actions are consumed
+            if !action_state.action_data(action.clone()).consumed {
+                self.timings[action.index()].tick(current_instant, previous_instant);
+            }
+        }
+    }
+
+    /// The [`Timing`] of the `action`, as of the current state of the `action_state`
+    #[must_use]
+    pub fn timing(&self, action_state: &ActionState<A>, action: A) -> Timing {
+        let mut timing = self.timings[action.index()].clone();
+        if self.changed(action_state, &action) {
+            timing.flip();
+        }
+        timing
+    }
+
+    /// Sets the [`Timing`] of the `action`, which describes its current state in the `action_state`
+    ///
+    /// This is useful to transfer timing information along with the [`ActionData`](crate::action_state::ActionData) of an action.
+    pub fn set_timing(&mut self, action_state: &ActionState<A>, action: A, timing: Timing) {
+        let index = action.index();
+        self.timings[index] = timing;
+        self.timed_pressed[index] = action_state.pressed(action);
+    }
+
+    /// Records the [`Instant`] of the raw input event that last pressed or released the `action`, see [`Timing::event_timestamp`]
+    pub fn set_event_timestamp(
+        &mut self,
+        action_state: &ActionState<A>,
+        action: A,
+        event_timestamp: Instant,
+    ) {
+        self.flip_if_changed(action_state, &action);
+        self.timings[action.index()].event_timestamp = Some(event_timestamp);
+    }
+
+    /// Swaps the timing of actions `a` and `b`, to match [`ActionState::swap`]
+    pub fn swap(&mut self, a: A, b: A) {
+        let (a, b) = (a.index(), b.index());
+        self.timings.swap(a, b);
+        self.timed_pressed.swap(a, b);
+        self.last_hold_durations.swap(a, b);
+    }
+
+    /// Clears the timing of the `action`, to match [`ActionState::reset`]
+    pub fn reset(&mut self, action: A) {
+        let index = action.index();
+        self.timings[index] = Timing::default();
+        self.timed_pressed[index] = false;
+        self.last_hold_durations[index] = Duration::ZERO;
+    }
+
+    /// Clears the timing of all actions, to match [`ActionState::reset_all`]
+    pub fn reset_all(&mut self) {
+        *self = ActionTimings::default();
+    }
+
+    /// The [`Instant`] that the action was last pressed or released
+    ///
+    /// If the action was pressed or released since the last tick, the value will be [`None`].
+    /// This ensures that all of our actions are assigned a timing and duration
+    /// that corresponds exactly to the start of a frame, rather than relying on idiosyncratic timing.
+    #[must_use]
+    pub fn instant_started(&self, action_state: &ActionState<A>, action: A) -> Option<Instant> {
+        let index = action.index();
+        if self.changed(action_state, &action) {
+            None
+        } else {
+            self.timings[index].instant_started
+        }
+    }
+
+    /// The [`Duration`] for which the action has been held or released
+    #[must_use]
+    pub fn current_duration(&self, action_state: &ActionState<A>, action: A) -> Duration {
+        let index = action.index();
+        if self.changed(action_state, &action) {
+            Duration::ZERO
+        } else {
+            self.timings[index].current_duration
+        }
+    }
+
+    /// The [`Duration`] for which the action was last held or released
+    ///
+    /// This is a snapshot of the [`ActionTimings::current_duration`] at the time
+    /// the action was last pressed or released.
+    #[must_use]
+    pub fn previous_duration(&self, action_state: &ActionState<A>, action: A) -> Duration {
+        let index = action.index();
+        if self.changed(action_state, &action) {
+            self.timings[index].current_duration
+        } else {
+            self.timings[index].previous_duration
+        }
+    }
+
+    /// Has the `action` been pressed for at least `duration`?
+    #[must_use]
+    pub fn pressed_for(
+        &self,
+        action_state: &ActionState<A>,
+        action: A,
+        duration: Duration,
+    ) -> bool {
+        action_state.pressed(action.clone())
+            && self.current_duration(action_state, action) >= duration
+    }
+
+    /// Has the `action` been released for at least `duration`?
+    #[must_use]
+    pub fn released_for(
+        &self,
+        action_state: &ActionState<A>,
+        action: A,
+        duration: Duration,
+    ) -> bool {
+        action_state.released(action.clone())
+            && self.current_duration(action_state, action) >= duration
+    }
+
+    /// Are both actions pressed, with their presses starting no more than `window` apart?
+    ///
+    /// This is useful for mechanics where pressing two actions at (nearly) the same time has a special meaning,
+    /// such as pressing jump and dash together to perform a super jump.
+    /// Use [`ActionTimings::just_pressed_together`] to respond only once, when the combination is first formed.
+    /// To respect the player's accessibility settings, scale the `window` with [`TimingForgiveness::scale`](crate::timing_forgiveness::TimingForgiveness::scale).
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::timing::ActionTimings;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Jump,
+    ///     Dash,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let mut timings = ActionTimings::<Action>::default();
+    /// let t0 = Instant::now();
+    ///
+    /// action_state.press(Action::Jump);
+    /// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(50), t0);
+    ///
+    /// // Dash is pressed 50 ms after Jump
+    /// action_state.press(Action::Dash);
+    /// assert!(timings.just_pressed_together(&action_state, Action::Jump, Action::Dash, Duration::from_millis(60)));
+    /// assert!(!timings.pressed_together(&action_state, Action::Jump, Action::Dash, Duration::from_millis(40)));
+    /// ```
+    #[must_use]
+    pub fn pressed_together(
+        &self,
+        action_state: &ActionState<A>,
+        a: A,
+        b: A,
+        window: Duration,
+    ) -> bool {
+        if !action_state.pressed(a.clone()) || !action_state.pressed(b.clone()) {
+            return false;
+        }
+
+        let (a, b) = (
+            self.current_duration(action_state, a),
+            self.current_duration(action_state, b),
+        );
+        a.max(b) - a.min(b) <= window
+    }
+
+    /// Did pressing one of the actions just complete a [`pressed_together`](ActionTimings::pressed_together) combination?
+    ///
+    /// This is only true during the tick in which the second of the two actions was pressed.
+    #[must_use]
+    pub fn just_pressed_together(
+        &self,
+        action_state: &ActionState<A>,
+        a: A,
+        b: A,
+        window: Duration,
+    ) -> bool {
+        (action_state.just_pressed(a.clone()) || action_state.just_pressed(b.clone()))
+            && self.pressed_together(action_state, a, b, window)
+    }
+
+    /// The currently pressed action that has been held for the longest, if any
+    ///
+    /// This is useful to prioritize between charged actions, or to find inputs that are stuck down.
+    /// Ties are broken in favor of the action defined first.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::Reflect;
+    /// use bevy::utils::{Duration, Instant};
+    /// use leafwing_input_manager::prelude::*;
+    /// use leafwing_input_manager::timing::ActionTimings;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Fireball,
+    ///     Shield,
+    /// }
+    ///
+    /// let mut action_state = ActionState::<Action>::default();
+    /// let mut timings = ActionTimings::<Action>::default();
+    /// let t0 = Instant::now();
+    /// assert_eq!(timings.longest_held(&action_state), None);
+    ///
+    /// action_state.press(Action::Shield);
+    /// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(300), t0);
+    /// action_state.press(Action::Fireball);
+    /// action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(400), t0 + Duration::from_millis(300));
+    /// assert_eq!(timings.longest_held(&action_state), Some(Action::Shield));
+    /// ```
+    #[must_use]
+    pub fn longest_held(&self, action_state: &ActionState<A>) -> Option<A> {
+        A::variants()
+            .filter(|action| action_state.pressed(action.clone()))
+            .fold(None, |longest: Option<(A, Duration)>, action| {
+                let duration = self.current_duration(action_state, action.clone());
+                match longest {
+                    Some((_, longest_duration)) if longest_duration >= duration => longest,
+                    _ => Some((action, duration)),
+                }
+            })
+            .map(|(action, _)| action)
+    }
+
+    /// The [`Duration`] for which the `action` was held the last time that it was released
+    ///
+    /// Unlike [`ActionTimings::previous_duration`], this is kept while the action is pressed again.
+    /// This is [`Duration::ZERO`] if the action has never been released after being pressed.
+    #[must_use]
+    pub fn last_hold_duration(&self, action_state: &ActionState<A>, action: A) -> Duration {
+        let index = action.index();
+        if self.changed(action_state, &action) && self.timed_pressed[index] {
+            self.timings[index].current_duration
+        } else {
+            self.last_hold_durations[index]
+        }
+    }
+
+    /// Is the `action` pressed, and has it been held for longer than the last time it was pressed?
+    ///
+    /// See [`ActionTimings::last_hold_duration`] for the length of the previous press.
+    #[must_use]
+    pub fn held_longer_than_previous(&self, action_state: &ActionState<A>, action: A) -> bool {
+        action_state.pressed(action.clone())
+            && self.current_duration(action_state, action.clone())
+                > self.last_hold_duration(action_state, action)
+    }
+
+    /// Was the `action` pressed or released since its [`Timing`] was last flipped?
+    fn changed(&self, action_state: &ActionState<A>, action: &A) -> bool {
+        self.timed_pressed[action.index()] != action_state.pressed(action.clone())
+    }
+
+    /// Flips the [`Timing`] of the `action` if it was pressed or released, recording how long it was held
+    fn flip_if_changed(&mut self, action_state: &ActionState<A>, action: &A) {
+        if !self.changed(action_state, action) {
+            return;
+        }
+
+        let index = action.index();
+        if self.timed_pressed[index] {
+            self.last_hold_durations[index] = self.timings[index].current_duration;
+        }
+        self.timings[index].flip();
+        self.timed_pressed[index] = !self.timed_pressed[index];
+    }
+}
+
+/// The source of the time that [`ActionState`]s are ticked with
+///
+/// This resource is inserted by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin), following Bevy's [`Time`].
+/// Replace it with [`InputClock::manual`] to control the passage of time yourself.
+#[derive(Resource, Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputClock {
+    /// Reads the time from Bevy's [`Time`] resource
+    #[default]
+    Real,
+    /// Only moves forward when [`InputClock::advance`] is called
+    Manual {
+        /// The [`Instant`] at which the clock was created
+        startup: Instant,
+        /// The current [`Instant`] of the clock
+        now: Instant,
+    },
+}
+
+impl InputClock {
+    /// Creates a manual clock, stopped at the current instant
+    #[must_use]
+    pub fn manual() -> Self {
+        let now = Instant::now();
+        InputClock::Manual { startup: now, now }
+    }
+
+    /// Moves a manual clock forward by the `delta`
+    ///
+    /// This has no effect on an [`InputClock::Real`] clock.
+    pub fn advance(&mut self, delta: Duration) {
+        if let InputClock::Manual { now, .. } = self {
+            *now += delta;
+        }
+    }
+
+    /// The current [`Instant`], or [`None`] if the [`Time`] has not been updated yet
+    #[must_use]
+    pub fn now(&self, time: &Time) -> Option<Instant> {
+        match self {
+            InputClock::Real => time.last_update(),
+            InputClock::Manual { now, .. } => Some(*now),
+        }
+    }
+
+    /// The [`Instant`] at which the clock started
+    #[must_use]
+    pub fn startup(&self, time: &Time) -> Instant {
+        match self {
+            InputClock::Real => time.startup(),
+            InputClock::Manual { startup, .. } => *startup,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate as leafwing_input_manager;
+    use crate::action_state::ActionState;
+    use crate::timing::ActionTimings;
+    use bevy::prelude::Reflect;
+    use bevy::utils::{Duration, Instant};
+    use leafwing_input_manager_macros::Actionlike;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Hide,
+    }
+
+    #[test]
+    fn durations() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+
+        // Actions start released
+        assert!(action_state.released(Action::Jump));
+        assert_eq!(timings.instant_started(&action_state, Action::Jump), None);
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+        assert_eq!(
+            timings.previous_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+
+        // Pressing a button swaps the state
+        action_state.press(Action::Jump);
+        assert!(action_state.pressed(Action::Jump));
+        assert_eq!(timings.instant_started(&action_state, Action::Jump), None);
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+        assert_eq!(
+            timings.previous_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+
+        // Ticking time sets the instant for the new state
+        let t0 = Instant::now();
+        let t1 = t0 + Duration::new(1, 0);
+
+        action_state.tick_with_timings(&mut timings, t1, t0);
+        assert_eq!(
+            timings.instant_started(&action_state, Action::Jump),
+            Some(t0)
+        );
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Jump),
+            t1 - t0
+        );
+        assert_eq!(
+            timings.previous_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+
+        // Time passes
+        let t2 = t1 + Duration::new(5, 0);
+
+        // The duration is updated
+        action_state.tick_with_timings(&mut timings, t2, t1);
+        assert_eq!(
+            timings.instant_started(&action_state, Action::Jump),
+            Some(t0)
+        );
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Jump),
+            t2 - t0
+        );
+        assert_eq!(
+            timings.previous_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+
+        // Releasing again, swapping the current duration to the previous one
+        action_state.release(Action::Jump);
+        assert_eq!(timings.instant_started(&action_state, Action::Jump), None);
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Jump),
+            Duration::ZERO
+        );
+        assert_eq!(
+            timings.previous_duration(&action_state, Action::Jump),
+            t2 - t0
+        );
+    }
+
+    #[test]
+    fn hold_durations() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let t0 = Instant::now();
+        let ms = Duration::from_millis;
+
+        action_state.press(Action::Run);
+        action_state.tick_with_timings(&mut timings, t0 + ms(300), t0);
+        assert!(timings.held_longer_than_previous(&action_state, Action::Run));
+        action_state.release(Action::Run);
+        assert_eq!(
+            timings.last_hold_duration(&action_state, Action::Run),
+            ms(300)
+        );
+
+        action_state.tick_with_timings(&mut timings, t0 + ms(400), t0 + ms(300));
+        action_state.press(Action::Run);
+        action_state.tick_with_timings(&mut timings, t0 + ms(600), t0 + ms(400));
+        assert!(timings.pressed_for(&action_state, Action::Run, ms(200)));
+        assert!(!timings.released_for(&action_state, Action::Run, ms(200)));
+        assert!(!timings.held_longer_than_previous(&action_state, Action::Run));
+
+        action_state.tick_with_timings(&mut timings, t0 + ms(800), t0 + ms(600));
+        assert!(timings.held_longer_than_previous(&action_state, Action::Run));
+
+        // Consuming an action also ends its hold
+        action_state.consume(Action::Run);
+        assert_eq!(
+            timings.last_hold_duration(&action_state, Action::Run),
+            ms(400)
+        );
+    }
+
+    #[test]
+    fn pressed_together() {
+        let window = Duration::from_millis(100);
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let t0 = Instant::now();
+
+        action_state.press(Action::Run);
+        action_state.press(Action::Jump);
+        assert!(timings.just_pressed_together(&action_state, Action::Run, Action::Jump, window));
+
+        // The combination remains held, but is no longer just formed
+        action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(50), t0);
+        assert!(timings.pressed_together(&action_state, Action::Run, Action::Jump, window));
+        assert!(!timings.just_pressed_together(&action_state, Action::Run, Action::Jump, window));
+
+        // Pressing the second action too late does not count
+        action_state.tick_with_timings(
+            &mut timings,
+            t0 + Duration::from_millis(200),
+            t0 + Duration::from_millis(50),
+        );
+        action_state.press(Action::Hide);
+        assert!(!timings.pressed_together(&action_state, Action::Run, Action::Hide, window));
+
+        action_state.release(Action::Jump);
+        assert!(!timings.pressed_together(&action_state, Action::Run, Action::Jump, window));
+    }
+
+    #[test]
+    fn swap_and_reset() {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let t0 = Instant::now();
+
+        action_state.press(Action::Run);
+        action_state.tick_with_timings(&mut timings, t0 + Duration::from_millis(200), t0);
+
+        // Swapping the timings alongside the actions keeps them in sync
+        action_state.swap(Action::Run, Action::Hide);
+        timings.swap(Action::Run, Action::Hide);
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Hide),
+            Duration::from_millis(200)
+        );
+        // Run takes over how long Hide had been released for
+        assert!(timings.released_for(&action_state, Action::Run, Duration::from_millis(200)));
+
+        action_state.reset_all();
+        timings.reset_all();
+        assert_eq!(
+            timings.current_duration(&action_state, Action::Hide),
+            Duration::ZERO
+        );
+        assert_eq!(timings.instant_started(&action_state, Action::Hide), None);
+    }
+}
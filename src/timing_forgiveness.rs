@@ -0,0 +1,136 @@
+//! A single accessibility setting that loosens every timing window at once
+//!
+//! Many mechanics only accept inputs that happen close enough together in time,
+//! which can make them hard or impossible to perform for some players.
+//! Insert a [`TimingForgiveness`] resource to scale these windows globally, for example from an accessibility menu.
+//!
+//! The following windows are scaled automatically:
+//! - the timeout of each [`InputPrompt`](crate::input_prompt::InputPrompt)
+//! - the maximum gap between the presses of each [`SecretSequence`](crate::secret_sequences::SecretSequence)
+//!
+//! Windows that are passed in by the caller, such as the one used by [`ActionTimings::pressed_together`](crate::timing::ActionTimings::pressed_together),
+//! should be scaled using [`TimingForgiveness::scale`].
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use bevy::utils::Duration;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::timing::ActionTimings;
+//! use leafwing_input_manager::timing_forgiveness::TimingForgiveness;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum Action {
+//!     Jump,
+//!     Dash,
+//! }
+//!
+//! const SUPER_JUMP_WINDOW: Duration = Duration::from_millis(80);
+//!
+//! fn super_jump(
+//!     query: Query<(&ActionState<Action>, &ActionTimings<Action>)>,
+//!     forgiveness: Option<Res<TimingForgiveness>>,
+//! ) {
+//!     let forgiveness = forgiveness.map(|forgiveness| *forgiveness).unwrap_or_default();
+//!     let window = forgiveness.scale(SUPER_JUMP_WINDOW);
+//!
+//!     for (action_state, timings) in query.iter() {
+//!         if timings.just_pressed_together(action_state, Action::Jump, Action::Dash, window) {
+//!             info!("Super jump!");
+//!         }
+//!     }
+//! }
+//!
+//! let mut app = App::new();
+//! // Timing windows are twice as long as usual
+//! app.insert_resource(TimingForgiveness::new(2.0))
+//!     .add_systems(Update, super_jump);
+//! ```
+
+use bevy::ecs::prelude::Resource;
+use bevy::utils::Duration;
+
+/// The factor that every timing window is multiplied by
+///
+/// A multiplier of `1.0` (the default) leaves timing windows unchanged,
+/// while higher multipliers make them more forgiving.
+#[derive(Resource, Debug, Clone, Copy, PartialEq)]
+pub struct TimingForgiveness {
+    multiplier: f32,
+}
+
+impl Default for TimingForgiveness {
+    fn default() -> Self {
+        Self { multiplier: 1.0 }
+    }
+}
+
+impl TimingForgiveness {
+    /// The smallest multiplier allowed
+    pub const MIN_MULTIPLIER: f32 = 0.01;
+
+    /// Creates a [`TimingForgiveness`] that multiplies every timing window by the `multiplier`
+    ///
+    /// The `multiplier` is raised to [`TimingForgiveness::MIN_MULTIPLIER`] if it is smaller, or if it is not a number.
+    #[must_use]
+    pub fn new(multiplier: f32) -> Self {
+        let mut forgiveness = Self::default();
+        forgiveness.set_multiplier(multiplier);
+        forgiveness
+    }
+
+    /// The factor that every timing window is multiplied by
+    #[must_use]
+    pub fn multiplier(&self) -> f32 {
+        self.multiplier
+    }
+
+    /// Sets the factor that every timing window is multiplied by
+    ///
+    /// The `multiplier` is raised to [`TimingForgiveness::MIN_MULTIPLIER`] if it is smaller, or if it is not a number.
+    pub fn set_multiplier(&mut self, multiplier: f32) -> &mut Self {
+        self.multiplier = multiplier.max(Self::MIN_MULTIPLIER);
+        self
+    }
+
+    /// Scales the timing `window` by the multiplier
+    #[must_use]
+    pub fn scale(&self, window: Duration) -> Duration {
+        window.mul_f64(f64::from(self.multiplier))
+    }
+
+    /// Scales the `elapsed` time by the inverse of the multiplier,
+    /// which is equivalent to scaling the timeout that it is compared to
+    #[must_use]
+    pub(crate) fn slow_down(&self, elapsed: Duration) -> Duration {
+        elapsed.div_f64(f64::from(self.multiplier))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaling() {
+        let window = Duration::from_millis(100);
+        assert_eq!(TimingForgiveness::default().scale(window), window);
+        assert_eq!(
+            TimingForgiveness::new(1.5).scale(window),
+            Duration::from_millis(150)
+        );
+        assert_eq!(
+            TimingForgiveness::new(2.0).slow_down(window),
+            Duration::from_millis(50)
+        );
+
+        // Windows can't shrink to nothing
+        assert_eq!(
+            TimingForgiveness::new(-1.0).multiplier(),
+            TimingForgiveness::MIN_MULTIPLIER
+        );
+        assert_eq!(
+            TimingForgiveness::new(f32::NAN).multiplier(),
+            TimingForgiveness::MIN_MULTIPLIER
+        );
+    }
+}
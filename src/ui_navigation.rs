@@ -0,0 +1,221 @@
+//! Moving the focus between `bevy_ui` nodes using actions, for gamepad and keyboard menu navigation
+//!
+//! Add a [`UiNavigationPlugin`] with the actions of your existing [`Actionlike`] enum that should navigate menus,
+//! and mark each node that can be focused with the [`Focusable`] component.
+//!
+//! The focused node is stored in the [`Focus`] resource, which is shared with Bevy's accessibility integration.
+//! Each frame, the [`navigate_ui`](crate::systems::navigate_ui) system reads the [`ActionState`](crate::action_state::ActionState) resource:
+//! - the directional actions move the focus to the nearest [`Focusable`] node in that direction,
+//!   repeating with [key repeats](crate::key_repeat) if the [`KeyRepeats`](crate::key_repeat::KeyRepeats) resource exists,
+//!   and with [auto repeats](crate::auto_repeat) if the [`AutoRepeats`](crate::auto_repeat::AutoRepeats) resource exists
+//!   (actions bound to analog sticks should also be given a [`NavigationDeadband`](crate::navigation_deadband::NavigationDeadband) in the [`NavigationDeadbands`](crate::navigation_deadband::NavigationDeadbands) resource)
+//! - the confirm action sends a [`UiNavigationEvent::Activated`] event for the focused node
+//! - the cancel action sends a [`UiNavigationEvent::Cancelled`] event
+//!
+//! If nothing is focused, any directional action focuses the top-left [`Focusable`] node.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::ui_navigation::{
+//!     Focusable, UiNavigationActions, UiNavigationEvent, UiNavigationPlugin,
+//! };
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     Up,
+//!     Down,
+//!     Select,
+//!     Back,
+//! }
+//!
+//! fn spawn_menu(mut commands: Commands) {
+//!     for label in ["Play", "Options", "Quit"] {
+//!         commands.spawn((ButtonBundle::default(), Focusable, Name::new(label)));
+//!     }
+//! }
+//!
+//! fn press_buttons(mut events: EventReader<UiNavigationEvent>, names: Query<&Name>) {
+//!     for event in events.iter() {
+//!         if let UiNavigationEvent::Activated(entity) = event {
+//!             info!("Pressed {}", names.get(*entity).unwrap());
+//!         }
+//!     }
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InputManagerPlugin::<MenuAction>::default())
+//!     .add_plugins(UiNavigationPlugin::new(UiNavigationActions {
+//!         up: Some(MenuAction::Up),
+//!         down: Some(MenuAction::Down),
+//!         confirm: Some(MenuAction::Select),
+//!         cancel: Some(MenuAction::Back),
+//!         ..Default::default()
+//!     }))
+//!     .add_systems(Startup, spawn_menu)
+//!     .add_systems(Update, press_buttons);
+//! ```
+
+use crate::plugin::InputManagerSystem;
+use crate::Actionlike;
+
+use bevy::a11y::Focus;
+use bevy::app::{App, Plugin};
+use bevy::ecs::prelude::*;
+use bevy::math::Vec2;
+use bevy::prelude::PreUpdate;
+
+/// Marks a `bevy_ui` node that can receive the [`Focus`] during [UI navigation](crate::ui_navigation)
+#[derive(Component, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Focusable;
+
+/// Sent by the [`navigate_ui`](crate::systems::navigate_ui) system when a navigation action that doesn't move the focus is pressed
+#[derive(Event, Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiNavigationEvent {
+    /// The confirm action was pressed while this [`Focusable`] node was focused
+    Activated(Entity),
+    /// The cancel action was pressed
+    Cancelled,
+}
+
+/// The actions of type `A` that navigate between [`Focusable`] nodes
+///
+/// Inserted as a resource by the [`UiNavigationPlugin`], and can be changed at runtime.
+/// Navigation is skipped for fields that are [`None`].
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct UiNavigationActions<A: Actionlike> {
+    /// Moves the focus to the nearest node above
+    pub up: Option<A>,
+    /// Moves the focus to the nearest node below
+    pub down: Option<A>,
+    /// Moves the focus to the nearest node to the left
+    pub left: Option<A>,
+    /// Moves the focus to the nearest node to the right
+    pub right: Option<A>,
+    /// Activates the focused node
+    pub confirm: Option<A>,
+    /// Leaves the current menu
+    pub cancel: Option<A>,
+}
+
+// Deriving default induces an undesired bound on the generic
+impl<A: Actionlike> Default for UiNavigationActions<A> {
+    fn default() -> Self {
+        Self {
+            up: None,
+            down: None,
+            left: None,
+            right: None,
+            confirm: None,
+            cancel: None,
+        }
+    }
+}
+
+impl<A: Actionlike> UiNavigationActions<A> {
+    /// The directional actions, paired with the direction that they move the focus in
+    ///
+    /// Directions are in UI coordinates, where `+y` points down.
+    pub(crate) fn directions(&self) -> impl Iterator<Item = (A, Vec2)> + '_ {
+        [
+            (&self.up, Vec2::NEG_Y),
+            (&self.down, Vec2::Y),
+            (&self.left, Vec2::NEG_X),
+            (&self.right, Vec2::X),
+        ]
+        .into_iter()
+        .filter_map(|(action, direction)| action.clone().map(|action| (action, direction)))
+    }
+}
+
+/// A [`Plugin`] that moves the [`Focus`] between [`Focusable`] `bevy_ui` nodes using actions of type `A`
+///
+/// Requires the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin) of the same action type,
+/// and reads the [`ActionState`](crate::action_state::ActionState) resource.
+/// See the [module documentation](crate::ui_navigation) for more details.
+pub struct UiNavigationPlugin<A: Actionlike> {
+    actions: UiNavigationActions<A>,
+}
+
+impl<A: Actionlike> UiNavigationPlugin<A> {
+    /// Creates a plugin that navigates using the provided `actions`
+    #[must_use]
+    pub fn new(actions: UiNavigationActions<A>) -> Self {
+        Self { actions }
+    }
+}
+
+impl<A: Actionlike> Plugin for UiNavigationPlugin<A> {
+    fn build(&self, app: &mut App) {
+        use crate::systems::{navigate_ui, run_if_enabled};
+
+        app.insert_resource(self.actions.clone())
+            .init_resource::<Focus>()
+            .add_event::<UiNavigationEvent>()
+            .add_systems(
+                PreUpdate,
+                navigate_ui::<A>
+                    .run_if(run_if_enabled::<A>)
+                    .after(InputManagerSystem::ReleaseOnDisable)
+                    .after(InputManagerSystem::ManualControl),
+            );
+    }
+}
+
+/// Finds the node that the focus should move to from `origin` when navigating in the `direction`
+///
+/// Nodes that lie ahead of the `origin` are preferred when they are close, and when they are well aligned with the `direction`.
+pub(crate) fn find_neighbor(
+    origin: Vec2,
+    direction: Vec2,
+    candidates: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .filter_map(|(entity, position)| {
+            let offset = position - origin;
+            let along = offset.dot(direction);
+            if along <= 0.0 {
+                return None;
+            }
+            // Sideways offsets count double, so that aligned nodes win over nearer diagonal ones
+            let across = offset.perp_dot(direction).abs();
+            Some((entity, along + 2.0 * across))
+        })
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(entity, _)| entity)
+}
+
+/// Finds the node that should be focused first: the top-most one, then the left-most
+pub(crate) fn first_focusable(
+    candidates: impl IntoIterator<Item = (Entity, Vec2)>,
+) -> Option<Entity> {
+    candidates
+        .into_iter()
+        .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+        .map(|(entity, _)| entity)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn neighbors() {
+        let mut world = World::new();
+        let [center, above, far_above, diagonal, left] = [(); 5].map(|_| world.spawn_empty().id());
+        let nodes = [
+            (center, Vec2::ZERO),
+            (above, Vec2::new(0.0, -100.0)),
+            (far_above, Vec2::new(0.0, -200.0)),
+            (diagonal, Vec2::new(60.0, -60.0)),
+            (left, Vec2::new(-50.0, 0.0)),
+        ];
+
+        assert_eq!(find_neighbor(Vec2::ZERO, Vec2::NEG_Y, nodes), Some(above));
+        assert_eq!(find_neighbor(Vec2::ZERO, Vec2::NEG_X, nodes), Some(left));
+        assert_eq!(find_neighbor(Vec2::ZERO, Vec2::X, nodes), Some(diagonal));
+        assert_eq!(find_neighbor(Vec2::ZERO, Vec2::Y, nodes), None);
+        assert_eq!(first_focusable(nodes), Some(far_above));
+    }
+}
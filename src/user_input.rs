@@ -5,12 +5,16 @@ use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode, mouse::MouseBut
 use bevy::prelude::ScanCode;
 use bevy::utils::HashSet;
 use petitset::PetitSet;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
 
 use crate::axislike::VirtualAxis;
 use crate::scan_codes::QwertyScanCode;
+use crate::stick_gestures::{Flick, StickRotation};
+use crate::window_events::WindowEventKind;
 use crate::{
-    axislike::{AxisType, DualAxis, SingleAxis, VirtualDPad},
+    axislike::{AxisType, DualAxis, MouseDrag, SingleAxis, VirtualDPad},
     buttonlike::{MouseMotionDirection, MouseWheelDirection},
 };
 
@@ -19,18 +23,53 @@ use crate::{
 /// For example, this may store mouse, keyboard or gamepad input, including cross-device chords!
 ///
 /// Suitable for use in an [`InputMap`](crate::input_map::InputMap)
-#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+// Chords are stored inline to keep bindings cheap to clone and compare
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum UserInput {
     /// A single button
     Single(InputKind),
     /// A combination of buttons, pressed simultaneously
     ///
     /// Up to 8 (!!) buttons can be chorded together at once.
-    Chord(PetitSet<InputKind, 8>),
-    /// A virtual DPad that you can get an [`DualAxis`] from
+    ///
+    /// Chords are equal regardless of the order of their buttons.
+    /// Chords created by [`UserInput::chord`], [`UserInput::modified`], an [`InputMap`](crate::input_map::InputMap) or deserialization
+    /// are stored in their canonical order, so that they are serialized and displayed identically:
+    /// see [`UserInput::canonicalize`].
+    Chord(#[serde(deserialize_with = "deserialize_chord")] PetitSet<InputKind, 8>),
+    /// A virtual `DPad` that you can get an [`DualAxis`] from
     VirtualDPad(VirtualDPad),
     /// A virtual axis that you can get a [`SingleAxis`] from
     VirtualAxis(VirtualAxis),
+    /// A window or application event, which is pressed during the frame that it was received
+    ///
+    /// See the [`window_events`](crate::window_events) module for more details.
+    WindowEvent(WindowEventKind),
+    /// An input from a custom device, identified by the `id` of the [`RawInputEvent`](crate::custom_input::RawInputEvent)s that update it
+    ///
+    /// See the [`custom_input`](crate::custom_input) module for more details.
+    Custom(u32),
+    /// Pressed while all of the inputs are pressed
+    ///
+    /// Unlike a [`UserInput::Chord`], the inputs can be any [`UserInput`], including other [`All`](UserInput::All) and [`Any`](UserInput::Any) expressions.
+    /// This allows complex bindings such as `(Shift AND Click) OR RightTrigger` to be expressed as a single binding:
+    /// see [`UserInput::all`] and [`UserInput::any`].
+    All(Vec<UserInput>),
+    /// Pressed while any of the inputs are pressed
+    ///
+    /// See [`UserInput::All`] for more details.
+    Any(Vec<UserInput>),
+    /// Pressed while the `input` is pressed, unless the `blocker` is held
+    ///
+    /// This states explicitly which of two overlapping bindings should win, rather than relying on the [`ClashStrategy`](crate::clashing_inputs::ClashStrategy):
+    /// see [`UserInput::unless`].
+    Unless {
+        /// The input that presses the action
+        input: Box<UserInput>,
+        /// The input that prevents the action from being pressed while it is held
+        blocker: Box<UserInput>,
+    },
 }
 
 impl UserInput {
@@ -40,11 +79,7 @@ impl UserInput {
     pub fn modified(modifier: Modifier, input: impl Into<InputKind>) -> UserInput {
         let modifier: InputKind = modifier.into();
         let input: InputKind = input.into();
-        let mut set: PetitSet<InputKind, 8> = PetitSet::default();
-        set.insert(modifier);
-        set.insert(input);
-
-        UserInput::Chord(set)
+        UserInput::Chord(canonical_chord([modifier, input]))
     }
 
     /// Creates a [`UserInput::Chord`] from an iterator of inputs of the same type that can be converted into an [`InputKind`]s
@@ -62,7 +97,106 @@ impl UserInput {
 
         match length {
             1 => UserInput::Single(set.into_iter().next().unwrap()),
-            _ => UserInput::Chord(set),
+            _ => UserInput::Chord(canonical_chord(set)),
+        }
+    }
+
+    /// Creates a [`UserInput::All`] expression, which is pressed while all of the `inputs` are pressed
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::{gamepad::GamepadButtonType, keyboard::KeyCode, mouse::MouseButton};
+    /// use leafwing_input_manager::user_input::UserInput;
+    ///
+    /// // (Shift AND Click) OR RightTrigger
+    /// let input = UserInput::any([
+    ///     UserInput::all([KeyCode::ShiftLeft.into(), MouseButton::Left.into()]),
+    ///     GamepadButtonType::RightTrigger.into(),
+    /// ]);
+    /// assert_eq!(input.to_string(), "(ShiftLeft AND Left) OR RightTrigger");
+    /// ```
+    #[must_use]
+    pub fn all(inputs: impl IntoIterator<Item = UserInput>) -> Self {
+        UserInput::All(inputs.into_iter().collect())
+    }
+
+    /// Creates a [`UserInput::Any`] expression, which is pressed while any of the `inputs` are pressed
+    ///
+    /// See [`UserInput::all`] for an example.
+    #[must_use]
+    pub fn any(inputs: impl IntoIterator<Item = UserInput>) -> Self {
+        UserInput::Any(inputs.into_iter().collect())
+    }
+
+    /// Creates a [`UserInput::Unless`] binding, which is pressed while `self` is pressed but the `blocker` is not
+    ///
+    /// Inputs that require the `blocker` to be held never clash with this binding.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::prelude::*;
+    /// use leafwing_input_manager::prelude::*;
+    ///
+    /// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    /// enum Action {
+    ///     Select,
+    ///     MultiSelect,
+    /// }
+    ///
+    /// let mut input_map = InputMap::default();
+    /// // Clicking selects, unless Shift is held, which multi-selects instead
+    /// input_map.insert(
+    ///     UserInput::from(MouseButton::Left).unless(KeyCode::ShiftLeft),
+    ///     Action::Select,
+    /// );
+    /// input_map.insert_chord([InputKind::from(KeyCode::ShiftLeft), MouseButton::Left.into()], Action::MultiSelect);
+    /// ```
+    #[must_use]
+    pub fn unless(self, blocker: impl Into<UserInput>) -> Self {
+        UserInput::Unless {
+            input: Box::new(self),
+            blocker: Box::new(blocker.into()),
+        }
+    }
+
+    /// Sorts the buttons of a [`UserInput::Chord`] into their canonical order
+    ///
+    /// Keyboard modifiers come first, in the order `Control`, `Shift`, `Alt` and `Win`,
+    /// followed by the remaining buttons in a stable order that does not depend on how the chord was created.
+    /// The inputs of [`UserInput::All`] and [`UserInput::Any`] expressions are canonicalized recursively,
+    /// and other kinds of [`UserInput`] are left unchanged.
+    ///
+    /// # Example
+    /// ```rust
+    /// use bevy::input::keyboard::KeyCode;
+    /// use leafwing_input_manager::user_input::{InputKind, Modifier, UserInput};
+    /// use petitset::PetitSet;
+    ///
+    /// let mut shift_ctrl_s = UserInput::Chord(PetitSet::from_iter([
+    ///     InputKind::Keyboard(KeyCode::S),
+    ///     InputKind::Modifier(Modifier::Shift),
+    ///     InputKind::Modifier(Modifier::Control),
+    /// ]));
+    /// shift_ctrl_s.canonicalize();
+    ///
+    /// let ctrl_shift_s = UserInput::chord([
+    ///     InputKind::Modifier(Modifier::Control),
+    ///     InputKind::Modifier(Modifier::Shift),
+    ///     InputKind::Keyboard(KeyCode::S),
+    /// ]);
+    /// assert_eq!(shift_ctrl_s.to_string(), ctrl_shift_s.to_string());
+    /// ```
+    pub fn canonicalize(&mut self) {
+        match self {
+            UserInput::Chord(chord) => *chord = canonical_chord(chord.iter().copied()),
+            UserInput::All(inputs) | UserInput::Any(inputs) => {
+                inputs.iter_mut().for_each(UserInput::canonicalize);
+            }
+            UserInput::Unless { input, blocker } => {
+                input.canonicalize();
+                blocker.canonicalize();
+            }
+            _ => (),
         }
     }
 
@@ -71,12 +205,22 @@ impl UserInput {
     /// - A [`Single`][UserInput::Single] input returns 1
     /// - A [`Chord`][UserInput::Chord] returns the number of buttons in the chord
     /// - A [`VirtualDPad`][UserInput::VirtualDPad] returns 1
+    /// - A [`WindowEvent`][UserInput::WindowEvent] returns 1
+    /// - A [`Custom`][UserInput::Custom] input returns 1
+    /// - An [`All`][UserInput::All] expression returns the total length of its inputs
+    /// - An [`Any`][UserInput::Any] expression returns the greatest length of its inputs
+    /// - An [`Unless`][UserInput::Unless] binding returns the length of its input
     pub fn len(&self) -> usize {
         match self {
             UserInput::Single(_) => 1,
             UserInput::Chord(button_set) => button_set.len(),
             UserInput::VirtualDPad { .. } => 1,
             UserInput::VirtualAxis { .. } => 1,
+            UserInput::WindowEvent(_) => 1,
+            UserInput::Custom(_) => 1,
+            UserInput::All(inputs) => inputs.iter().map(UserInput::len).sum(),
+            UserInput::Any(inputs) => inputs.iter().map(UserInput::len).max().unwrap_or_default(),
+            UserInput::Unless { input, .. } => input.len(),
         }
     }
 
@@ -144,10 +288,21 @@ impl UserInput {
 
                 n_matching
             }
+            UserInput::WindowEvent(_) | UserInput::Custom(_) => 0,
+            UserInput::All(inputs) => inputs.iter().map(|input| input.n_matching(buttons)).sum(),
+            UserInput::Any(inputs) => inputs
+                .iter()
+                .map(|input| input.n_matching(buttons))
+                .max()
+                .unwrap_or_default(),
+            UserInput::Unless { input, .. } => input.n_matching(buttons),
         }
     }
 
     /// Returns the raw inputs that make up this [`UserInput`]
+    ///
+    /// [`UserInput::WindowEvent`] and [`UserInput::Custom`] inputs are not made up of any raw inputs.
+    /// The blocker of a [`UserInput::Unless`] binding is not included, as pressing it would release the binding.
     pub fn raw_inputs(&self) -> RawInputs {
         let mut raw_inputs = RawInputs::default();
 
@@ -175,6 +330,11 @@ impl UserInput {
                 InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
                 InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
                 InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
+                InputKind::MouseDrag(drag) => raw_inputs.mouse_buttons.push(drag.button),
+                InputKind::Flick(Flick { stick, .. })
+                | InputKind::StickRotation(StickRotation { stick, .. }) => raw_inputs
+                    .axis_data
+                    .extend([(stick.x.axis_type, None), (stick.y.axis_type, None)]),
             },
             UserInput::Chord(button_set) => {
                 for button in button_set.iter() {
@@ -201,6 +361,11 @@ impl UserInput {
                         InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
                         InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
                         InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
+                        InputKind::MouseDrag(drag) => raw_inputs.mouse_buttons.push(drag.button),
+                        InputKind::Flick(Flick { stick, .. })
+                        | InputKind::StickRotation(StickRotation { stick, .. }) => raw_inputs
+                            .axis_data
+                            .extend([(stick.x.axis_type, None), (stick.y.axis_type, None)]),
                     }
                 }
             }
@@ -234,6 +399,11 @@ impl UserInput {
                         InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
                         InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
                         InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
+                        InputKind::MouseDrag(drag) => raw_inputs.mouse_buttons.push(drag.button),
+                        InputKind::Flick(Flick { stick, .. })
+                        | InputKind::StickRotation(StickRotation { stick, .. }) => raw_inputs
+                            .axis_data
+                            .extend([(stick.x.axis_type, None), (stick.y.axis_type, None)]),
                     }
                 }
             }
@@ -263,27 +433,208 @@ impl UserInput {
                         InputKind::Mouse(button) => raw_inputs.mouse_buttons.push(button),
                         InputKind::MouseWheel(button) => raw_inputs.mouse_wheel.push(button),
                         InputKind::MouseMotion(button) => raw_inputs.mouse_motion.push(button),
+                        InputKind::MouseDrag(drag) => raw_inputs.mouse_buttons.push(drag.button),
+                        InputKind::Flick(Flick { stick, .. })
+                        | InputKind::StickRotation(StickRotation { stick, .. }) => raw_inputs
+                            .axis_data
+                            .extend([(stick.x.axis_type, None), (stick.y.axis_type, None)]),
                     }
                 }
             }
+            UserInput::WindowEvent(_) | UserInput::Custom(_) => (),
+            UserInput::All(inputs) | UserInput::Any(inputs) => {
+                for input in inputs {
+                    raw_inputs.merge(input.raw_inputs());
+                }
+            }
+            UserInput::Unless { input, .. } => raw_inputs = input.raw_inputs(),
         };
 
         raw_inputs
     }
 }
 
+impl Hash for UserInput {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            UserInput::Single(input) => input.hash(state),
+            // Chords are equal regardless of the order of their buttons, so they must hash identically too
+            UserInput::Chord(chord) => {
+                let combined = chord
+                    .iter()
+                    .map(|input| {
+                        let mut hasher = DefaultHasher::new();
+                        input.hash(&mut hasher);
+                        hasher.finish()
+                    })
+                    .fold(0, u64::wrapping_add);
+                chord.len().hash(state);
+                combined.hash(state);
+            }
+            UserInput::VirtualDPad(dpad) => dpad.hash(state),
+            UserInput::VirtualAxis(axis) => axis.hash(state),
+            UserInput::WindowEvent(event) => event.hash(state),
+            UserInput::Custom(id) => id.hash(state),
+            UserInput::All(inputs) | UserInput::Any(inputs) => inputs.hash(state),
+            UserInput::Unless { input, blocker } => {
+                input.hash(state);
+                blocker.hash(state);
+            }
+        }
+    }
+}
+
+/// The position of the `input` in a canonical chord, used by [`UserInput::canonicalize`]
+///
+/// Modifiers come first, then inputs are grouped by their [`InputKind`] variant and ordered by the code of the key, button or axis they read.
+fn chord_order(input: &InputKind) -> (u8, u8, u32) {
+    let modifier = match *input {
+        InputKind::Modifier(modifier) => Some(modifier),
+        InputKind::Keyboard(KeyCode::ControlLeft | KeyCode::ControlRight) => {
+            Some(Modifier::Control)
+        }
+        InputKind::Keyboard(KeyCode::ShiftLeft | KeyCode::ShiftRight) => Some(Modifier::Shift),
+        InputKind::Keyboard(KeyCode::AltLeft | KeyCode::AltRight) => Some(Modifier::Alt),
+        InputKind::Keyboard(KeyCode::SuperLeft | KeyCode::SuperRight) => Some(Modifier::Win),
+        _ => None,
+    };
+    let group = match modifier {
+        Some(Modifier::Control) => 0,
+        Some(Modifier::Shift) => 1,
+        Some(Modifier::Alt) => 2,
+        Some(Modifier::Win) => 3,
+        None => 4,
+    };
+
+    let (variant, code) = match *input {
+        InputKind::GamepadButton(button) => (0, gamepad_button_code(button)),
+        InputKind::SingleAxis(axis) => (1, axis_type_code(axis.axis_type)),
+        InputKind::DualAxis(axis) => (2, dual_axis_code(axis)),
+        InputKind::Keyboard(key_code) => (3, key_code as u32),
+        InputKind::KeyLocation(ScanCode(scan_code)) => (4, scan_code),
+        InputKind::Modifier(modifier) => (5, modifier as u32),
+        InputKind::Mouse(button) => (6, mouse_button_code(button)),
+        InputKind::MouseWheel(direction) => (7, direction as u32),
+        InputKind::MouseMotion(direction) => (8, direction as u32),
+        InputKind::MouseDrag(drag) => (9, mouse_button_code(drag.button)),
+        InputKind::Flick(flick) => (10, dual_axis_code(flick.stick)),
+        InputKind::StickRotation(rotation) => (11, dual_axis_code(rotation.stick)),
+    };
+
+    (group, variant, code)
+}
+
+/// A code that orders gamepad buttons by their declaration, followed by the [`GamepadButtonType::Other`] buttons
+fn gamepad_button_code(button: GamepadButtonType) -> u32 {
+    use GamepadButtonType::*;
+    match button {
+        South => 0,
+        East => 1,
+        North => 2,
+        West => 3,
+        C => 4,
+        Z => 5,
+        LeftTrigger => 6,
+        LeftTrigger2 => 7,
+        RightTrigger => 8,
+        RightTrigger2 => 9,
+        Select => 10,
+        Start => 11,
+        Mode => 12,
+        LeftThumb => 13,
+        RightThumb => 14,
+        DPadUp => 15,
+        DPadDown => 16,
+        DPadLeft => 17,
+        DPadRight => 18,
+        Other(index) => 19 + u32::from(index),
+    }
+}
+
+/// A code that orders mouse buttons by their declaration, followed by the [`MouseButton::Other`] buttons
+fn mouse_button_code(button: MouseButton) -> u32 {
+    match button {
+        MouseButton::Left => 0,
+        MouseButton::Right => 1,
+        MouseButton::Middle => 2,
+        MouseButton::Other(index) => 3 + u32::from(index),
+    }
+}
+
+/// A code below `0x4000` that orders gamepad axes before mouse wheel and mouse motion axes
+fn axis_type_code(axis_type: AxisType) -> u32 {
+    use bevy::input::gamepad::GamepadAxisType::*;
+    match axis_type {
+        AxisType::Gamepad(axis) => match axis {
+            LeftStickX => 0,
+            LeftStickY => 1,
+            LeftZ => 2,
+            RightStickX => 3,
+            RightStickY => 4,
+            RightZ => 5,
+            Other(index) => 6 + u32::from(index),
+        },
+        AxisType::MouseWheel(axis) => 0x1000 + axis as u32,
+        AxisType::MouseMotion(axis) => 0x2000 + axis as u32,
+    }
+}
+
+/// Packs the codes of both axes of the `axis`, ordering by the `x` axis first
+fn dual_axis_code(axis: DualAxis) -> u32 {
+    (axis_type_code(axis.x.axis_type) << 16) | axis_type_code(axis.y.axis_type)
+}
+
+/// Collects the `inputs` into a chord, sorted into their canonical order
+fn canonical_chord(inputs: impl IntoIterator<Item = InputKind>) -> PetitSet<InputKind, 8> {
+    let mut inputs: Vec<InputKind> = inputs.into_iter().collect();
+    inputs.sort_by_key(chord_order);
+    inputs.into_iter().collect()
+}
+
+fn deserialize_chord<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<PetitSet<InputKind, 8>, D::Error> {
+    let chord = PetitSet::<InputKind, 8>::deserialize(deserializer)?;
+    Ok(canonical_chord(chord))
+}
+
 impl From<InputKind> for UserInput {
     fn from(input: InputKind) -> Self {
         UserInput::Single(input)
     }
 }
 
+impl From<WindowEventKind> for UserInput {
+    fn from(event: WindowEventKind) -> Self {
+        UserInput::WindowEvent(event)
+    }
+}
+
 impl From<DualAxis> for UserInput {
     fn from(input: DualAxis) -> Self {
         UserInput::Single(InputKind::DualAxis(input))
     }
 }
 
+impl From<StickRotation> for UserInput {
+    fn from(input: StickRotation) -> Self {
+        UserInput::Single(InputKind::StickRotation(input))
+    }
+}
+
+impl From<Flick> for UserInput {
+    fn from(input: Flick) -> Self {
+        UserInput::Single(InputKind::Flick(input))
+    }
+}
+
+impl From<MouseDrag> for UserInput {
+    fn from(input: MouseDrag) -> Self {
+        UserInput::Single(InputKind::MouseDrag(input))
+    }
+}
+
 impl From<SingleAxis> for UserInput {
     fn from(input: SingleAxis) -> Self {
         UserInput::Single(InputKind::SingleAxis(input))
@@ -387,6 +738,16 @@ pub enum InputKind {
     MouseWheel(MouseWheelDirection),
     /// A discretized mouse movement
     MouseMotion(MouseMotionDirection),
+    /// Mouse movement while a mouse button is held down
+    MouseDrag(MouseDrag),
+    /// A quick movement of a gamepad stick from its center to its edge
+    ///
+    /// See the [`stick_gestures`](crate::stick_gestures) module for more details.
+    Flick(Flick),
+    /// A continuous circular motion of a gamepad stick
+    ///
+    /// See the [`stick_gestures`](crate::stick_gestures) module for more details.
+    StickRotation(StickRotation),
 }
 
 impl From<DualAxis> for InputKind {
@@ -395,6 +756,24 @@ impl From<DualAxis> for InputKind {
     }
 }
 
+impl From<StickRotation> for InputKind {
+    fn from(input: StickRotation) -> Self {
+        InputKind::StickRotation(input)
+    }
+}
+
+impl From<Flick> for InputKind {
+    fn from(input: Flick) -> Self {
+        InputKind::Flick(input)
+    }
+}
+
+impl From<MouseDrag> for InputKind {
+    fn from(input: MouseDrag) -> Self {
+        InputKind::MouseDrag(input)
+    }
+}
+
 impl From<SingleAxis> for InputKind {
     fn from(input: SingleAxis) -> Self {
         InputKind::SingleAxis(input)
@@ -503,6 +882,19 @@ pub struct RawInputs {
     pub axis_data: Vec<(AxisType, Option<f32>)>,
 }
 
+impl RawInputs {
+    /// Appends the raw inputs of `other` to these
+    fn merge(&mut self, other: RawInputs) {
+        self.keycodes.extend(other.keycodes);
+        self.scan_codes.extend(other.scan_codes);
+        self.mouse_buttons.extend(other.mouse_buttons);
+        self.mouse_wheel.extend(other.mouse_wheel);
+        self.mouse_motion.extend(other.mouse_motion);
+        self.gamepad_buttons.extend(other.gamepad_buttons);
+        self.axis_data.extend(other.axis_data);
+    }
+}
+
 #[cfg(test)]
 impl RawInputs {
     fn from_keycode(keycode: KeyCode) -> RawInputs {
@@ -570,9 +962,10 @@ mod raw_input_tests {
         use bevy::input::gamepad::GamepadButtonType;
 
         let buttons = vec![GamepadButtonType::Start, GamepadButtonType::Select];
-        let raw_inputs = UserInput::chord(buttons.clone()).raw_inputs();
+        let raw_inputs = UserInput::chord(buttons).raw_inputs();
+        // Chords are stored in their canonical order
         let expected = RawInputs {
-            gamepad_buttons: buttons,
+            gamepad_buttons: vec![GamepadButtonType::Select, GamepadButtonType::Start],
             ..Default::default()
         };
 
@@ -745,3 +1138,52 @@ mod raw_input_tests {
         }
     }
 }
+
+#[cfg(test)]
+mod chord_tests {
+    use super::{InputKind, Modifier, UserInput};
+    use bevy::input::keyboard::KeyCode;
+    use bevy::utils::HashSet;
+    use petitset::PetitSet;
+
+    #[test]
+    fn chords_are_order_independent() {
+        let ctrl_shift_s = UserInput::chord([
+            InputKind::Modifier(Modifier::Control),
+            InputKind::Modifier(Modifier::Shift),
+            InputKind::Keyboard(KeyCode::S),
+        ]);
+        let shift_ctrl_s = UserInput::chord([
+            InputKind::Keyboard(KeyCode::S),
+            InputKind::Modifier(Modifier::Shift),
+            InputKind::Modifier(Modifier::Control),
+        ]);
+        // Constructed directly, so it is not canonicalized
+        let s_shift_ctrl = UserInput::Chord(PetitSet::from_iter([
+            InputKind::Keyboard(KeyCode::S),
+            InputKind::Modifier(Modifier::Shift),
+            InputKind::Modifier(Modifier::Control),
+        ]));
+
+        assert_eq!(ctrl_shift_s, shift_ctrl_s);
+        assert_eq!(ctrl_shift_s, s_shift_ctrl);
+        assert_eq!(ctrl_shift_s.to_string(), shift_ctrl_s.to_string());
+
+        let set = HashSet::from_iter([ctrl_shift_s.clone()]);
+        assert!(set.contains(&shift_ctrl_s));
+        assert!(set.contains(&s_shift_ctrl));
+
+        let UserInput::Chord(chord) = ctrl_shift_s else {
+            unreachable!()
+        };
+        let order: Vec<InputKind> = chord.iter().copied().collect();
+        assert_eq!(
+            order,
+            [
+                InputKind::Modifier(Modifier::Control),
+                InputKind::Modifier(Modifier::Shift),
+                InputKind::Keyboard(KeyCode::S),
+            ]
+        );
+    }
+}
@@ -0,0 +1,191 @@
+//! Events for reacting to analog action values crossing user-registered thresholds
+//!
+//! Add a [`ValueThresholds`] resource or component alongside an [`ActionState`],
+//! and a [`ValueThresholdCrossed`] event will be sent whenever the value of an action crosses one of its thresholds.
+//! This allows gameplay or audio systems to react to analog milestones (such as a throttle passing 90%)
+//! without polling and comparing the value every frame.
+
+use crate::action_state::ActionState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Entity, Event, Resource};
+use std::marker::PhantomData;
+
+/// The direction in which a value crossed a threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CrossingDirection {
+    /// The value rose from below the threshold to at or above it
+    Rising,
+    /// The value fell from at or above the threshold to below it
+    Falling,
+}
+
+/// Sent when the value of an action crosses one of the thresholds registered in its [`ValueThresholds`]
+///
+/// These events are generated by the [`generate_value_threshold_events`](crate::systems::generate_value_threshold_events) system.
+#[derive(Event, Debug, Clone, PartialEq)]
+pub struct ValueThresholdCrossed<A: Actionlike> {
+    /// The action whose value crossed the threshold
+    pub action: A,
+    /// The threshold that was crossed
+    pub threshold: f32,
+    /// Whether the value rose above or fell below the threshold
+    pub direction: CrossingDirection,
+    /// The new value of the action
+    pub value: f32,
+    /// The entity whose [`ActionState`] changed, or [`None`] if the [`ActionState`] is a resource
+    pub entity: Option<Entity>,
+}
+
+/// The value thresholds registered for each action of type `A`
+///
+/// Can be used as either a resource or as a [`Component`], alongside the [`ActionState`] that it watches.
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::Reflect;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::value_thresholds::{CrossingDirection, ValueThresholds};
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Throttle,
+/// }
+///
+/// let mut thresholds = ValueThresholds::<Action>::default();
+/// thresholds.add(Action::Throttle, 0.9);
+///
+/// let mut action_state = ActionState::<Action>::default();
+/// action_state.action_data_mut(Action::Throttle).value = 0.95;
+///
+/// let crossings = thresholds.update(&action_state);
+/// assert_eq!(crossings, [(Action::Throttle, 0.9, CrossingDirection::Rising)]);
+/// ```
+#[derive(Resource, Component, Debug, Clone, PartialEq)]
+pub struct ValueThresholds<A: Actionlike> {
+    /// The thresholds of each action, indexed by [`Actionlike::index`]
+    thresholds: Vec<Vec<f32>>,
+    /// The value of each action when [`ValueThresholds::update`] was last called
+    previous_values: Vec<f32>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for ValueThresholds<A> {
+    fn default() -> Self {
+        ValueThresholds {
+            thresholds: vec![Vec::new(); A::n_variants()],
+            previous_values: vec![0.0; A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> ValueThresholds<A> {
+    /// Registers a `threshold` for the `action`
+    ///
+    /// Registering the same threshold twice has no effect.
+    pub fn add(&mut self, action: A, threshold: f32) -> &mut Self {
+        let thresholds = &mut self.thresholds[action.index()];
+        if !thresholds.contains(&threshold) {
+            thresholds.push(threshold);
+        }
+        self
+    }
+
+    /// Removes a previously registered `threshold` from the `action`
+    pub fn remove(&mut self, action: A, threshold: f32) -> &mut Self {
+        self.thresholds[action.index()].retain(|&existing| existing != threshold);
+        self
+    }
+
+    /// Removes all thresholds registered for the `action`
+    pub fn clear(&mut self, action: A) -> &mut Self {
+        self.thresholds[action.index()].clear();
+        self
+    }
+
+    /// Returns the thresholds registered for the `action`
+    #[must_use]
+    pub fn thresholds(&self, action: A) -> &[f32] {
+        &self.thresholds[action.index()]
+    }
+
+    /// Compares the values in the `action_state` to the values seen during the previous call,
+    /// returning each `(action, threshold, direction)` that was crossed
+    ///
+    /// A threshold is crossed when the value moves from below it to at or above it, or the reverse.
+    pub fn update(&mut self, action_state: &ActionState<A>) -> Vec<(A, f32, CrossingDirection)> {
+        let mut crossings = Vec::new();
+
+        for action in A::variants() {
+            let index = action.index();
+            let previous = self.previous_values[index];
+            let current = action_state.value(action.clone());
+
+            for &threshold in self.thresholds[index].iter() {
+                let direction = match (previous >= threshold, current >= threshold) {
+                    (false, true) => CrossingDirection::Rising,
+                    (true, false) => CrossingDirection::Falling,
+                    _ => continue,
+                };
+                crossings.push((action.clone(), threshold, direction));
+            }
+
+            self.previous_values[index] = current;
+        }
+
+        crossings
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Throttle,
+        Brake,
+    }
+
+    #[test]
+    fn crossings() {
+        let mut thresholds = ValueThresholds::<Action>::default();
+        thresholds
+            .add(Action::Throttle, 0.5)
+            .add(Action::Throttle, 0.9);
+        let mut action_state = ActionState::<Action>::default();
+
+        action_state.action_data_mut(Action::Throttle).value = 0.6;
+        assert_eq!(
+            thresholds.update(&action_state),
+            [(Action::Throttle, 0.5, CrossingDirection::Rising)]
+        );
+
+        // Values that stay on the same side of every threshold are not reported
+        action_state.action_data_mut(Action::Throttle).value = 0.7;
+        assert!(thresholds.update(&action_state).is_empty());
+
+        action_state.action_data_mut(Action::Throttle).value = 0.95;
+        assert_eq!(
+            thresholds.update(&action_state),
+            [(Action::Throttle, 0.9, CrossingDirection::Rising)]
+        );
+
+        // Several thresholds can be crossed at once
+        action_state.action_data_mut(Action::Throttle).value = 0.1;
+        assert_eq!(
+            thresholds.update(&action_state),
+            [
+                (Action::Throttle, 0.5, CrossingDirection::Falling),
+                (Action::Throttle, 0.9, CrossingDirection::Falling)
+            ]
+        );
+
+        // Actions without thresholds are ignored
+        action_state.action_data_mut(Action::Brake).value = 1.0;
+        assert!(thresholds.update(&action_state).is_empty());
+    }
+}
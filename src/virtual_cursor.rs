@@ -0,0 +1,197 @@
+//! A cursor that is moved by a dual-axis action, so that gamepads can operate pointer-driven interfaces
+//!
+//! Insert a [`VirtualCursorSettings`] resource naming the action that moves the cursor (usually a stick)
+//! and the action that clicks, initialize the [`VirtualCursor`] resource,
+//! and add the [`update_virtual_cursor`](crate::systems::update_virtual_cursor) system to your app.
+//! This system is not added by the [`InputManagerPlugin`](crate::plugin::InputManagerPlugin).
+//!
+//! The [`VirtualCursor`] is kept within the bounds of the primary window.
+//! If [`VirtualCursorSettings::drive_window_cursor`] is enabled, the real window cursor is moved along with it,
+//! and clicks are sent as left [`MouseButtonInput`](bevy::input::mouse::MouseButtonInput) events,
+//! so that existing pointer interfaces such as `bevy_ui` buttons respond to the gamepad.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::plugin::InputManagerSystem;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::systems::update_virtual_cursor;
+//! use leafwing_input_manager::virtual_cursor::{VirtualCursor, VirtualCursorSettings};
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     Point,
+//!     Click,
+//! }
+//!
+//! let mut app = App::new();
+//! app.add_plugins(InputManagerPlugin::<MenuAction>::default())
+//!     .init_resource::<VirtualCursor>()
+//!     .insert_resource(VirtualCursorSettings {
+//!         drive_window_cursor: true,
+//!         ..VirtualCursorSettings::new(MenuAction::Point, Some(MenuAction::Click))
+//!     })
+//!     .add_systems(
+//!         PreUpdate,
+//!         update_virtual_cursor::<MenuAction>.after(InputManagerSystem::ManualControl),
+//!     );
+//! ```
+
+use crate::Actionlike;
+
+use bevy::ecs::prelude::Resource;
+use bevy::math::Vec2;
+
+/// The position and click state of the cursor moved by the [`update_virtual_cursor`](crate::systems::update_virtual_cursor) system
+///
+/// Positions are in window coordinates, like [`Window::cursor_position`](bevy::window::Window::cursor_position).
+#[derive(Resource, Debug, Default, Clone, Copy, PartialEq)]
+pub struct VirtualCursor {
+    /// The position of the cursor, or [`None`] if it has not been placed yet
+    ///
+    /// The cursor is placed at the center of the primary window the first time that it is updated.
+    pub position: Option<Vec2>,
+    /// The current speed of the cursor, in logical pixels per second
+    speed: f32,
+    clicking: bool,
+    just_clicked: bool,
+    just_released: bool,
+}
+
+impl VirtualCursor {
+    /// Is the click action pressed?
+    #[must_use]
+    pub fn clicking(&self) -> bool {
+        self.clicking
+    }
+
+    /// Was the click action pressed during the last update?
+    #[must_use]
+    pub fn just_clicked(&self) -> bool {
+        self.just_clicked
+    }
+
+    /// Was the click action released during the last update?
+    #[must_use]
+    pub fn just_released(&self) -> bool {
+        self.just_released
+    }
+
+    /// The current speed of the cursor, in logical pixels per second
+    ///
+    /// This accelerates from [`VirtualCursorSettings::speed`] to [`VirtualCursorSettings::max_speed`]
+    /// while the cursor keeps moving, and resets once the stick is released.
+    #[must_use]
+    pub fn speed(&self) -> f32 {
+        self.speed
+    }
+
+    /// Moves the cursor by the `stick` direction over `delta_seconds`, and records whether the click action is `clicking`
+    ///
+    /// The position is clamped to the `bounds` of the window, and starts at their center.
+    /// The stick follows the usual convention of `+y` pointing up, while window coordinates point down.
+    pub(crate) fn advance<A: Actionlike>(
+        &mut self,
+        stick: Vec2,
+        clicking: bool,
+        settings: &VirtualCursorSettings<A>,
+        delta_seconds: f32,
+        bounds: Option<Vec2>,
+    ) {
+        let mut position = self
+            .position
+            .or(bounds.map(|bounds| bounds / 2.0))
+            .unwrap_or_default();
+
+        if stick == Vec2::ZERO {
+            self.speed = settings.speed;
+        } else {
+            self.speed = (self.speed.max(settings.speed) + settings.acceleration * delta_seconds)
+                .min(settings.max_speed.max(settings.speed));
+            let stick = stick.clamp_length_max(1.0);
+            position += Vec2::new(stick.x, -stick.y) * self.speed * delta_seconds;
+        }
+
+        if let Some(bounds) = bounds {
+            position = position.clamp(Vec2::ZERO, bounds);
+        }
+        self.position = Some(position);
+
+        self.just_clicked = clicking && !self.clicking;
+        self.just_released = !clicking && self.clicking;
+        self.clicking = clicking;
+    }
+}
+
+/// Configures how the [`VirtualCursor`] is driven by actions of type `A`
+#[derive(Resource, Debug, Clone, PartialEq)]
+pub struct VirtualCursorSettings<A: Actionlike> {
+    /// The dual-axis action that moves the cursor
+    pub movement: A,
+    /// The button action that clicks, if any
+    pub click: Option<A>,
+    /// The speed of the cursor when the stick is fully tilted, in logical pixels per second
+    pub speed: f32,
+    /// The speed that the cursor accelerates up to while it keeps moving, in logical pixels per second
+    pub max_speed: f32,
+    /// How quickly the cursor accelerates while it keeps moving, in logical pixels per second squared
+    pub acceleration: f32,
+    /// Should the real window cursor follow the [`VirtualCursor`], and clicks be sent as mouse button events?
+    pub drive_window_cursor: bool,
+}
+
+impl<A: Actionlike> VirtualCursorSettings<A> {
+    /// Creates settings that move the cursor with the `movement` action and click with the `click` action
+    ///
+    /// The cursor accelerates from 400 to 1200 logical pixels per second over a second,
+    /// and does not drive the window cursor.
+    #[must_use]
+    pub fn new(movement: A, click: Option<A>) -> Self {
+        Self {
+            movement,
+            click,
+            speed: 400.0,
+            max_speed: 1200.0,
+            acceleration: 800.0,
+            drive_window_cursor: false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Point,
+        Click,
+    }
+
+    #[test]
+    fn movement() {
+        let settings = VirtualCursorSettings::new(Action::Point, Some(Action::Click));
+        let bounds = Some(Vec2::new(800.0, 600.0));
+        let mut cursor = VirtualCursor::default();
+
+        cursor.advance(Vec2::ZERO, false, &settings, 0.5, bounds);
+        assert_eq!(cursor.position, Some(Vec2::new(400.0, 300.0)));
+
+        // Pushing the stick up moves the cursor up the window, accelerating as it goes
+        cursor.advance(Vec2::Y, true, &settings, 0.25, bounds);
+        assert_eq!(cursor.speed(), 600.0);
+        assert_eq!(cursor.position, Some(Vec2::new(400.0, 150.0)));
+        assert!(cursor.just_clicked());
+
+        // The cursor stays inside the window
+        cursor.advance(Vec2::Y, true, &settings, 0.25, bounds);
+        assert_eq!(cursor.position, Some(Vec2::new(400.0, 0.0)));
+        assert!(cursor.clicking());
+        assert!(!cursor.just_clicked());
+
+        cursor.advance(Vec2::ZERO, false, &settings, 0.25, bounds);
+        assert_eq!(cursor.speed(), settings.speed);
+        assert!(cursor.just_released());
+    }
+}
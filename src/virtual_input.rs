@@ -0,0 +1,178 @@
+//! This module contains [`VirtualInput`] and its supporting methods and impls.
+//!
+//! The [`VirtualInput`] is an optional addition to an [`InputManagerBundle`](crate::InputManagerBundle),
+//! which allows gameplay systems to push synthetic presses, values and axis pairs into the action pipeline.
+//! These are merged with the physical inputs read by the [`InputMap`](crate::input_map::InputMap)
+//! the next time [`update_action_state`](crate::systems::update_action_state) runs,
+//! allowing AI-controlled characters and assist features to drive the same actions as players.
+
+use crate::action_state::ActionData;
+use crate::axislike::DualAxisData;
+use crate::buttonlike::ButtonState;
+use crate::Actionlike;
+
+use bevy::ecs::prelude::{Component, Resource};
+use std::marker::PhantomData;
+
+/// The synthetic input pushed for a single action
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+struct VirtualActionInput {
+    pressed: bool,
+    value: f32,
+    axis_pair: Option<DualAxisData>,
+}
+
+/// Synthetic inputs that are merged with physical inputs during the next update
+///
+/// Can be used as either a resource or as a [`Component`], alongside an [`ActionState`](crate::action_state::ActionState) and [`InputMap`](crate::input_map::InputMap).
+/// Pushed inputs only last for a single update: push them again each frame to hold an action.
+///
+/// Virtual inputs are merged after clashes between physical inputs have been resolved,
+/// so they are never removed by the [`ClashStrategy`](crate::clashing_inputs::ClashStrategy).
+///
+/// # Example
+/// ```rust
+/// use bevy::prelude::*;
+/// use leafwing_input_manager::axislike::DualAxisData;
+/// use leafwing_input_manager::prelude::*;
+/// use leafwing_input_manager::virtual_input::VirtualInput;
+///
+/// #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+/// enum Action {
+///     Move,
+///     Attack,
+/// }
+///
+/// #[derive(Component)]
+/// struct Enemy;
+///
+/// fn enemy_ai(mut query: Query<&mut VirtualInput<Action>, With<Enemy>>) {
+///     for mut virtual_input in query.iter_mut() {
+///         virtual_input.set_axis_pair(Action::Move, DualAxisData::new(1.0, 0.0));
+///         virtual_input.press(Action::Attack);
+///     }
+/// }
+/// ```
+#[derive(Component, Resource, Debug, Clone, PartialEq)]
+pub struct VirtualInput<A: Actionlike> {
+    inputs: Vec<VirtualActionInput>,
+    _phantom: PhantomData<A>,
+}
+
+impl<A: Actionlike> Default for VirtualInput<A> {
+    fn default() -> Self {
+        Self {
+            inputs: vec![VirtualActionInput::default(); A::n_variants()],
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<A: Actionlike> VirtualInput<A> {
+    /// Presses the `action` during the next update, with a value of `1.0`
+    pub fn press(&mut self, action: A) -> &mut Self {
+        let input = &mut self.inputs[action.index()];
+        input.pressed = true;
+        input.value = 1.0;
+        self
+    }
+
+    /// Sets the value of the `action` during the next update
+    ///
+    /// Like an analog input, the action is pressed if the `value` is non-zero.
+    pub fn set_value(&mut self, action: A, value: f32) -> &mut Self {
+        let input = &mut self.inputs[action.index()];
+        input.pressed = value != 0.0;
+        input.value = value;
+        self
+    }
+
+    /// Sets the axis pair of the `action` during the next update
+    ///
+    /// Like an analog stick, the action is pressed if the `axis_pair` is non-zero,
+    /// and its value is set to the length of the `axis_pair`.
+    pub fn set_axis_pair(&mut self, action: A, axis_pair: DualAxisData) -> &mut Self {
+        let input = &mut self.inputs[action.index()];
+        input.pressed = axis_pair.length() != 0.0;
+        input.value = axis_pair.length();
+        input.axis_pair = Some(axis_pair);
+        self
+    }
+
+    /// Removes any input pushed for the `action`
+    pub fn release(&mut self, action: A) -> &mut Self {
+        self.inputs[action.index()] = VirtualActionInput::default();
+        self
+    }
+
+    /// Removes all pushed inputs
+    pub fn clear(&mut self) {
+        self.inputs.fill(VirtualActionInput::default());
+    }
+
+    /// Was the `action` pushed as pressed?
+    #[must_use]
+    pub fn pressed(&self, action: A) -> bool {
+        self.inputs[action.index()].pressed
+    }
+
+    /// Merges the pushed inputs into the `action_data` produced by [`InputMap::which_pressed`](crate::input_map::InputMap::which_pressed),
+    /// then clears them
+    ///
+    /// Values are added to the values of the physical inputs, and axis pairs are combined using [`DualAxisData::merged_with`].
+    pub fn merge_into(&mut self, action_data: &mut [ActionData]) {
+        for (data, input) in action_data.iter_mut().zip(self.inputs.iter()) {
+            if input.pressed {
+                data.state = ButtonState::JustPressed;
+            }
+
+            data.value += input.value;
+
+            if let Some(axis_pair) = input.axis_pair {
+                data.axis_pair = Some(match data.axis_pair {
+                    Some(current_axis_pair) => current_axis_pair.merged_with(axis_pair),
+                    None => axis_pair,
+                });
+            }
+        }
+
+        self.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate as leafwing_input_manager;
+    use bevy::prelude::Reflect;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+    enum Action {
+        Run,
+        Jump,
+        Move,
+    }
+
+    #[test]
+    fn merging() {
+        let mut virtual_input = VirtualInput::<Action>::default();
+        virtual_input
+            .press(Action::Jump)
+            .set_value(Action::Run, 0.0)
+            .set_axis_pair(Action::Move, DualAxisData::new(0.0, 1.0));
+
+        let mut action_data = vec![ActionData::default(); 3];
+        action_data[2].state = ButtonState::JustPressed;
+        action_data[2].axis_pair = Some(DualAxisData::new(1.0, 0.0));
+        virtual_input.merge_into(&mut action_data);
+
+        // A value of zero does not press the action
+        assert_eq!(action_data[0].state, ButtonState::Released);
+        assert_eq!(action_data[1].state, ButtonState::JustPressed);
+        assert_eq!(action_data[1].value, 1.0);
+        assert_eq!(action_data[2].axis_pair, Some(DualAxisData::new(1.0, 1.0)));
+
+        // Inputs only last for a single update
+        assert!(!virtual_input.pressed(Action::Jump));
+    }
+}
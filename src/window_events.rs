@@ -0,0 +1,80 @@
+//! Binding window and application events to actions
+//!
+//! Some commands don't come from a keyboard, mouse or gamepad at all:
+//! the player might close the window, switch to another application or drop a file onto the game.
+//! Binding these to actions with [`UserInput::WindowEvent`](crate::user_input::UserInput::WindowEvent)
+//! lets every command in a game flow through the same action system.
+//!
+//! Each frame, the [`collect_window_events`](crate::systems::collect_window_events) system records the window events
+//! that were received in the [`WindowEventInputs`] resource.
+//! A binding to a [`WindowEventKind`] is pressed during the frame that the event was received, and released on the next.
+//! These bindings cannot be pressed using [`MockInput`](crate::input_mocking::MockInput): send the corresponding window event instead.
+//!
+//! ```rust
+//! use bevy::prelude::*;
+//! use leafwing_input_manager::prelude::*;
+//! use leafwing_input_manager::window_events::WindowEventKind;
+//!
+//! #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+//! enum MenuAction {
+//!     OpenQuitDialog,
+//!     Pause,
+//! }
+//!
+//! let input_map = InputMap::new([
+//!     (UserInput::WindowEvent(WindowEventKind::CloseRequested), MenuAction::OpenQuitDialog),
+//!     (UserInput::WindowEvent(WindowEventKind::FocusLost), MenuAction::Pause),
+//!     (KeyCode::Escape.into(), MenuAction::Pause),
+//! ]);
+//! ```
+
+use bevy::ecs::prelude::Resource;
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+use std::fmt::Display;
+
+/// A window or application event that can be bound to an action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum WindowEventKind {
+    /// The user asked to close a window, as sent by [`WindowCloseRequested`](bevy::window::WindowCloseRequested)
+    CloseRequested,
+    /// A window gained focus, as sent by [`WindowFocused`](bevy::window::WindowFocused)
+    FocusGained,
+    /// A window lost focus, as sent by [`WindowFocused`](bevy::window::WindowFocused)
+    FocusLost,
+    /// A file was dropped onto a window, as sent by [`FileDragAndDrop::DroppedFile`](bevy::window::FileDragAndDrop::DroppedFile)
+    FileDropped,
+}
+
+impl Display for WindowEventKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{self:?}")
+    }
+}
+
+/// The window events received during the current frame
+///
+/// This resource is updated by the [`collect_window_events`](crate::systems::collect_window_events) system,
+/// and read when updating the [`ActionState`](crate::action_state::ActionState).
+#[derive(Resource, Debug, Default, Clone, PartialEq, Eq)]
+pub struct WindowEventInputs {
+    received: HashSet<WindowEventKind>,
+}
+
+impl WindowEventInputs {
+    /// Was the `event` received during the current frame?
+    #[must_use]
+    pub fn received(&self, event: WindowEventKind) -> bool {
+        self.received.contains(&event)
+    }
+
+    /// Records that the `event` was received during the current frame
+    pub fn insert(&mut self, event: WindowEventKind) {
+        self.received.insert(event);
+    }
+
+    /// Forgets every received event
+    pub fn clear(&mut self) {
+        self.received.clear();
+    }
+}
@@ -1,6 +1,10 @@
 //! When debugging this file, `cargo expand` is invaluable.
 //! See: https://github.com/dtolnay/cargo-expand
 //! use `cargo expand --test actionlike_derive`
+
+// Several of these enums only exist to check that the derive compiles
+#![allow(dead_code)]
+
 use bevy::prelude::Reflect;
 use leafwing_input_manager::Actionlike;
 
@@ -56,3 +60,75 @@ fn index() {
     assert_eq!(SimpleAction::One.index(), 1);
     assert_eq!(SimpleAction::Two.index(), 2);
 }
+
+#[derive(Actionlike, Debug, Hash, PartialEq, Eq, Clone, Copy, Reflect)]
+enum DescribedAction {
+    #[actionlike(category = "Movement", order = 1)]
+    Run,
+    #[actionlike(
+        name = "Leap",
+        description = "Jump into the air",
+        category = "Movement"
+    )]
+    Jump,
+    Pause,
+    #[actionlike(category = "Combat", order = -1)]
+    Block {
+        direction: usize,
+    },
+}
+
+#[test]
+fn action_info() {
+    use leafwing_input_manager::action_metadata::ActionInfo;
+
+    assert_eq!(
+        DescribedAction::Run.info(),
+        ActionInfo::new("Run")
+            .with_category("Movement")
+            .with_sort_order(1)
+    );
+    assert_eq!(
+        DescribedAction::Jump.info(),
+        ActionInfo::new("Leap")
+            .with_description("Jump into the air")
+            .with_category("Movement")
+    );
+    assert_eq!(DescribedAction::Pause.info(), ActionInfo::new("Pause"));
+    assert_eq!(
+        DescribedAction::Block { direction: 3 }.info().sort_order,
+        -1
+    );
+}
+
+#[test]
+fn action_metadata_sorting() {
+    use leafwing_input_manager::action_metadata::ActionMetadata;
+
+    let mut metadata = ActionMetadata::<DescribedAction>::default();
+    let sorted: Vec<DescribedAction> = metadata.iter_sorted().map(|(action, _)| action).collect();
+    assert_eq!(
+        sorted,
+        [
+            DescribedAction::Block { direction: 0 },
+            DescribedAction::Jump,
+            DescribedAction::Run,
+            DescribedAction::Pause,
+        ]
+    );
+    assert_eq!(metadata.categories(), ["Combat", "Movement"]);
+
+    metadata.get_mut(DescribedAction::Pause).category = Some("Movement".to_string());
+    let movement: Vec<DescribedAction> = metadata
+        .iter_category("Movement")
+        .map(|(action, _)| action)
+        .collect();
+    assert_eq!(
+        movement,
+        [
+            DescribedAction::Jump,
+            DescribedAction::Pause,
+            DescribedAction::Run
+        ]
+    );
+}
@@ -1,5 +1,6 @@
 use bevy::input::gamepad::{
-    GamepadAxisChangedEvent, GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    GamepadAxisChangedEvent, GamepadButtonChangedEvent, GamepadConnection, GamepadConnectionEvent,
+    GamepadEvent, GamepadInfo,
 };
 use bevy::input::InputPlugin;
 use bevy::prelude::*;
@@ -79,6 +80,8 @@ fn game_pad_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
 
@@ -99,6 +102,8 @@ fn game_pad_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
         y: SingleAxis {
@@ -106,6 +111,8 @@ fn game_pad_dual_axis_mocking() {
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
     };
@@ -135,6 +142,8 @@ fn game_pad_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -148,6 +157,8 @@ fn game_pad_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -161,6 +172,8 @@ fn game_pad_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -174,6 +187,8 @@ fn game_pad_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -188,6 +203,8 @@ fn game_pad_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -201,6 +218,8 @@ fn game_pad_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -294,3 +313,510 @@ fn game_pad_virtualdpad() {
         DualAxisData::new(-1.0, 0.0)
     );
 }
+
+#[test]
+fn dual_stage_trigger() {
+    let mut app = test_app();
+    // Up stands in for aiming, and Down for firing
+    app.insert_resource(InputMap::new([
+        (
+            SingleAxis::positive_range(GamepadAxisType::RightZ, 0.2, 1.0),
+            ButtonlikeTestAction::Up,
+        ),
+        (
+            SingleAxis::positive_range(GamepadAxisType::RightZ, 0.9, 1.0),
+            ButtonlikeTestAction::Down,
+        ),
+        // An exclusive stage, which is released once the trigger is fully pulled
+        (
+            SingleAxis::positive_range(GamepadAxisType::RightZ, 0.2, 0.9),
+            ButtonlikeTestAction::Left,
+        ),
+    ]));
+
+    // Half-pull
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.just_pressed(ButtonlikeTestAction::Up));
+    assert!(action_state.released(ButtonlikeTestAction::Down));
+    assert!(action_state.pressed(ButtonlikeTestAction::Left));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Up), 0.5);
+    assert_eq!(action_state.value(ButtonlikeTestAction::Down), 0.0);
+
+    // Full pull
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 1.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    // The first stage stays pressed, rather than being re-triggered
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+    assert!(!action_state.just_pressed(ButtonlikeTestAction::Up));
+    assert!(action_state.just_pressed(ButtonlikeTestAction::Down));
+    assert!(action_state.just_released(ButtonlikeTestAction::Left));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Left), 0.0);
+
+    // Release
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.just_released(ButtonlikeTestAction::Up));
+    assert!(action_state.just_released(ButtonlikeTestAction::Down));
+}
+
+#[test]
+fn dual_stage_trigger_ordering() {
+    let mut app = test_app();
+    // Up stands in for aiming, and Down for firing
+    app.insert_resource(InputMap::new([
+        (
+            SingleAxis::positive_range(GamepadAxisType::RightZ, 0.2, 1.0),
+            ButtonlikeTestAction::Up,
+        ),
+        (
+            SingleAxis::positive_range(GamepadAxisType::RightZ, 0.9, 1.0),
+            ButtonlikeTestAction::Down,
+        ),
+    ]));
+
+    for clash_strategy in ClashStrategy::variants() {
+        app.insert_resource(*clash_strategy);
+
+        // Skipping past the half-pull in a single frame presses both stages at once
+        app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 1.0));
+        app.update();
+        let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+        assert!(action_state.just_pressed(ButtonlikeTestAction::Up));
+        assert!(action_state.just_pressed(ButtonlikeTestAction::Down));
+
+        // Easing off the trigger releases the later stage first
+        app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.5));
+        app.update();
+        let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+        assert!(action_state.pressed(ButtonlikeTestAction::Up));
+        assert!(action_state.just_released(ButtonlikeTestAction::Down));
+
+        // Letting go entirely releases the earlier stage too
+        app.send_input(SingleAxis::from_value(GamepadAxisType::RightZ, 0.0));
+        app.update();
+        let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+        assert!(action_state.just_released(ButtonlikeTestAction::Up));
+        assert!(action_state.released(ButtonlikeTestAction::Down));
+    }
+}
+
+#[test]
+fn split_axis() {
+    let mut app = test_app();
+    let (forward, backward) = SingleAxis::split(GamepadAxisType::LeftStickY, 0.1, 0.3);
+    app.insert_resource(InputMap::new([
+        (forward, ButtonlikeTestAction::Up),
+        (backward, ButtonlikeTestAction::Down),
+    ]));
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickY, 0.5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+    assert!(action_state.released(ButtonlikeTestAction::Down));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Up), 0.5);
+
+    // Below the threshold of the negative half
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickY, -0.2));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(ButtonlikeTestAction::Up));
+    assert!(action_state.released(ButtonlikeTestAction::Down));
+
+    // The negative half reports positive values
+    app.send_input(SingleAxis::from_value(GamepadAxisType::LeftStickY, -0.8));
+    app.update();
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.released(ButtonlikeTestAction::Up));
+    assert!(action_state.pressed(ButtonlikeTestAction::Down));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Down), 0.8);
+}
+
+#[test]
+fn per_input_thresholds() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([
+        (
+            UserInput::from(GamepadButtonType::RightTrigger2),
+            ButtonlikeTestAction::Up,
+        ),
+        (
+            GamepadButtonType::LeftTrigger2.into(),
+            ButtonlikeTestAction::Down,
+        ),
+        (
+            SingleAxis::symmetric(GamepadAxisType::LeftStickX, 0.1).into(),
+            ButtonlikeTestAction::Left,
+        ),
+    ]);
+    input_map
+        .set_button_threshold(GamepadButtonType::RightTrigger2, 0.3)
+        .set_axis_threshold(GamepadAxisType::LeftStickX, 0.5);
+    app.insert_resource(input_map);
+
+    let gamepad = Gamepad { id: 1 };
+    let mut events = app.world.resource_mut::<Events<GamepadEvent>>();
+    for button_type in [
+        GamepadButtonType::RightTrigger2,
+        GamepadButtonType::LeftTrigger2,
+    ] {
+        events.send(GamepadEvent::Button(GamepadButtonChangedEvent {
+            gamepad,
+            button_type,
+            value: 0.5,
+        }));
+    }
+    events.send(GamepadEvent::Axis(GamepadAxisChangedEvent {
+        gamepad,
+        axis_type: GamepadAxisType::LeftStickX,
+        value: 0.3,
+    }));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    // Only the overridden trigger is pressed at half pull
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+    assert!(action_state.released(ButtonlikeTestAction::Down));
+    // The axis override replaces the deadzone of the binding
+    assert!(action_state.released(ButtonlikeTestAction::Left));
+
+    let mut events = app.world.resource_mut::<Events<GamepadEvent>>();
+    events.send(GamepadEvent::Axis(GamepadAxisChangedEvent {
+        gamepad,
+        axis_type: GamepadAxisType::LeftStickX,
+        value: 0.6,
+    }));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Left));
+}
+
+#[test]
+fn value_threshold_events() {
+    use leafwing_input_manager::value_thresholds::{
+        CrossingDirection, ValueThresholdCrossed, ValueThresholds,
+    };
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        SingleAxis::symmetric(GamepadAxisType::RightZ, 0.0),
+        AxislikeTestAction::X,
+    )]));
+    let mut value_thresholds = ValueThresholds::<AxislikeTestAction>::default();
+    value_thresholds.add(AxislikeTestAction::X, 0.9);
+    app.insert_resource(value_thresholds);
+
+    let send_axis = |app: &mut App, value: f32| {
+        let mut events = app.world.resource_mut::<Events<GamepadEvent>>();
+        events.send(GamepadEvent::Axis(GamepadAxisChangedEvent {
+            gamepad: Gamepad { id: 1 },
+            axis_type: GamepadAxisType::RightZ,
+            value,
+        }));
+        app.update();
+        app.world
+            .resource_mut::<Events<ValueThresholdCrossed<AxislikeTestAction>>>()
+            .drain()
+            .collect::<Vec<_>>()
+    };
+
+    assert!(send_axis(&mut app, 0.5).is_empty());
+
+    let crossed = send_axis(&mut app, 0.95);
+    assert_eq!(crossed.len(), 1);
+    assert_eq!(crossed[0].threshold, 0.9);
+    assert_eq!(crossed[0].direction, CrossingDirection::Rising);
+    assert_eq!(crossed[0].entity, None);
+
+    // Holding the value above the threshold does not send more events
+    assert!(send_axis(&mut app, 1.0).is_empty());
+
+    let crossed = send_axis(&mut app, 0.2);
+    assert_eq!(crossed.len(), 1);
+    assert_eq!(crossed[0].direction, CrossingDirection::Falling);
+}
+
+#[test]
+fn pressure_sensitive_buttons() {
+    let mut app = test_app();
+    let mut input_map =
+        InputMap::new([(GamepadButtonType::RightTrigger2, ButtonlikeTestAction::Up)]);
+    input_map.insert_chord(
+        [
+            GamepadButtonType::LeftTrigger,
+            GamepadButtonType::RightTrigger2,
+        ],
+        ButtonlikeTestAction::Down,
+    );
+    input_map.set_button_threshold(GamepadButtonType::RightTrigger2, 0.1);
+    app.insert_resource(input_map);
+
+    let gamepad = Gamepad { id: 1 };
+    let mut events = app.world.resource_mut::<Events<GamepadEvent>>();
+    events.send(GamepadEvent::Button(GamepadButtonChangedEvent {
+        gamepad,
+        button_type: GamepadButtonType::RightTrigger2,
+        value: 0.4,
+    }));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Up));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Up), 0.4);
+    assert_eq!(action_state.value(ButtonlikeTestAction::Down), 0.0);
+
+    // The pressure of the trigger is used as the value of the chord
+    let mut events = app.world.resource_mut::<Events<GamepadEvent>>();
+    events.send(GamepadEvent::Button(GamepadButtonChangedEvent {
+        gamepad,
+        button_type: GamepadButtonType::LeftTrigger,
+        value: 1.0,
+    }));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<ButtonlikeTestAction>>();
+    assert!(action_state.pressed(ButtonlikeTestAction::Down));
+    assert_eq!(action_state.value(ButtonlikeTestAction::Down), 0.4);
+}
+
+#[test]
+fn axis_pair_processors() {
+    use leafwing_input_manager::axislike::AxisPairProcessor;
+    use leafwing_input_manager::orientation::Rotation;
+
+    let mut app = test_app();
+    let mut input_map = InputMap::new([(DualAxis::left_stick(), AxislikeTestAction::XY)]);
+    input_map.add_axis_pair_processor(
+        DualAxis::left_stick(),
+        AxisPairProcessor::Rotate(Rotation::from_degrees(90.0)),
+    );
+    app.insert_resource(input_map);
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.8,
+        0.0,
+    ));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    let axis_pair = action_state.axis_pair(AxislikeTestAction::XY).unwrap();
+    assert!((axis_pair.xy() - Vec2::new(0.0, 0.8)).length() < 0.001);
+
+    // Processors can be replaced, for example to follow a moving camera
+    app.world
+        .resource_mut::<InputMap<AxislikeTestAction>>()
+        .set_axis_pair_processors(
+            DualAxis::left_stick(),
+            [AxisPairProcessor::camera_relative(Vec2::new(0.0, -1.0))],
+        );
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    let axis_pair = action_state.axis_pair(AxislikeTestAction::XY).unwrap();
+    assert!((axis_pair.xy() - Vec2::new(-0.8, 0.0)).length() < 0.001);
+}
+
+#[test]
+fn dead_zone_shapes() {
+    use leafwing_input_manager::axislike::DeadZoneShape;
+
+    let mut app = test_app();
+    let stick = DualAxis::left_stick().with_deadzone(0.0);
+    let mut input_map = InputMap::new([(stick, AxislikeTestAction::XY)]);
+    input_map.add_axis_pair_processor(stick, DeadZoneShape::radial(0.2).into());
+    app.insert_resource(input_map);
+
+    // Both axes are within an axial dead zone of the same size, but the stick is outside the radial dead zone
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.15,
+        0.15,
+    ));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.15, 0.15)
+    );
+
+    app.world
+        .resource_mut::<InputMap<AxislikeTestAction>>()
+        .set_axis_pair_processors(stick, [DeadZoneShape::axial(0.2).into()]);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 0.0)
+    );
+}
+
+#[test]
+fn aggregation_policies() {
+    use leafwing_input_manager::input_map::AggregationPolicy;
+
+    let mut app = test_app();
+    let mut input_map = InputMap::new([
+        (
+            UserInput::from(DualAxis::left_stick()),
+            AxislikeTestAction::XY,
+        ),
+        (VirtualDPad::arrow_keys().into(), AxislikeTestAction::XY),
+    ]);
+    input_map.set_aggregation_policy(AxislikeTestAction::XY, AggregationPolicy::SumClamped);
+    app.insert_resource(input_map);
+
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.0,
+        0.8,
+    ));
+    app.update();
+
+    app.send_input(KeyCode::Up);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 1.0)
+    );
+
+    // The most recently pressed binding wins, even if it has a smaller magnitude
+    app.world
+        .resource_mut::<InputMap<AxislikeTestAction>>()
+        .set_aggregation_policy(AxislikeTestAction::XY, AggregationPolicy::LatestWins);
+    app.release_input(KeyCode::Up);
+    app.update();
+    app.send_input(KeyCode::Up);
+    app.send_input(KeyCode::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(-1.0, 1.0)
+    );
+
+    app.release_input(KeyCode::Up);
+    app.release_input(KeyCode::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 0.8)
+    );
+}
+
+#[test]
+fn source_priority() {
+    use leafwing_input_manager::input_map::{InputSource, SourcePriority};
+
+    let mut app = test_app();
+    let mut input_map = InputMap::new([
+        (
+            UserInput::from(DualAxis::left_stick()),
+            AxislikeTestAction::XY,
+        ),
+        (VirtualDPad::arrow_keys().into(), AxislikeTestAction::XY),
+    ]);
+    input_map.set_source_priority(
+        AxislikeTestAction::XY,
+        SourcePriority::new([InputSource::Gamepad, InputSource::Keyboard]),
+    );
+    app.insert_resource(input_map);
+
+    app.send_input(KeyCode::Up);
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.0, 1.0)
+    );
+
+    // The gamepad takes over while it is active
+    app.send_input(DualAxis::from_value(
+        GamepadAxisType::LeftStickX,
+        GamepadAxisType::LeftStickY,
+        0.5,
+        0.0,
+    ));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(0.5, 0.0)
+    );
+    assert_eq!(action_state.value(AxislikeTestAction::XY), 0.5);
+}
+
+#[test]
+fn stick_flick() {
+    use leafwing_input_manager::stick_gestures::{Flick, FlickDirection};
+
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([
+        (Flick::new(DualAxis::right_stick()), AxislikeTestAction::XY),
+        (
+            Flick::new(DualAxis::right_stick()).with_direction(FlickDirection::Up),
+            AxislikeTestAction::Y,
+        ),
+    ]));
+    app.update();
+
+    app.send_input(SingleAxis::from_value(GamepadAxisType::RightStickX, 1.0));
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_pressed(AxislikeTestAction::XY));
+    assert!(action_state.released(AxislikeTestAction::Y));
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(1.0, 0.0)
+    );
+
+    // Holding the stick at its edge is not another flick
+    app.update();
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.just_released(AxislikeTestAction::XY));
+}
+
+#[test]
+fn stick_rotation() {
+    use leafwing_input_manager::orientation::RotationDirection;
+    use leafwing_input_manager::stick_gestures::StickRotation;
+
+    let mut app = test_app();
+    let rotation = StickRotation::new(DualAxis::left_stick()).with_angle(3.0);
+    app.insert_resource(InputMap::new([
+        (rotation, AxislikeTestAction::X),
+        (
+            rotation.with_direction(RotationDirection::Clockwise),
+            AxislikeTestAction::Y,
+        ),
+    ]));
+
+    // Half of a turn counterclockwise
+    for (x, y) in [(1.0, 0.0), (0.0, 1.0), (-1.0, 0.0)] {
+        app.send_input(DualAxis::from_value(
+            GamepadAxisType::LeftStickX,
+            GamepadAxisType::LeftStickY,
+            x,
+            y,
+        ));
+        app.update();
+    }
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::X));
+    assert!(action_state.value(AxislikeTestAction::X) > 0.0);
+    assert!(action_state.released(AxislikeTestAction::Y));
+}
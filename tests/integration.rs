@@ -1,6 +1,7 @@
 use bevy::prelude::*;
 use leafwing_input_manager::prelude::*;
 use leafwing_input_manager::press_scheduler::PressScheduler;
+use leafwing_input_manager::timing::ActionTimings;
 
 #[derive(Actionlike, Clone, Copy, Debug, Reflect)]
 enum Action {
@@ -66,13 +67,15 @@ fn do_nothing() {
 
     app.update();
     let action_state = app.world.resource::<ActionState<Action>>();
-    let t0 = action_state.instant_started(Action::PayRespects);
+    let timings = app.world.resource::<ActionTimings<Action>>();
+    let t0 = timings.instant_started(action_state, Action::PayRespects);
     assert!(t0.is_some());
     let mut duration_last_update = Duration::ZERO;
 
     for _ in 0..3 {
         app.update();
         let action_state = app.world.resource::<ActionState<Action>>();
+        let timings = app.world.resource::<ActionTimings<Action>>();
 
         // Sanity checking state to catch wonkiness
         assert!(!action_state.pressed(Action::PayRespects));
@@ -80,14 +83,17 @@ fn do_nothing() {
         assert!(action_state.released(Action::PayRespects));
         assert!(!action_state.just_released(Action::PayRespects));
 
-        assert_eq!(action_state.instant_started(Action::PayRespects), t0);
         assert_eq!(
-            action_state.previous_duration(Action::PayRespects),
+            timings.instant_started(action_state, Action::PayRespects),
+            t0
+        );
+        assert_eq!(
+            timings.previous_duration(action_state, Action::PayRespects),
             Duration::ZERO
         );
-        assert!(action_state.current_duration(Action::PayRespects) > duration_last_update);
+        assert!(timings.current_duration(action_state, Action::PayRespects) > duration_last_update);
 
-        duration_last_update = action_state.current_duration(Action::PayRespects);
+        duration_last_update = timings.current_duration(action_state, Action::PayRespects);
         dbg!(duration_last_update);
     }
 }
@@ -132,6 +138,57 @@ fn disable_input() {
     assert_eq!(*respect, Respect(false));
 }
 
+#[test]
+fn block_input() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::block_input::{BlockInput, InputBlockers};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+
+    let pressed = |app: &mut App| {
+        app.update();
+        let mut query = app
+            .world
+            .query_filtered::<&ActionState<Action>, With<Player>>();
+        query.single(&app.world).pressed(Action::PayRespects)
+    };
+
+    app.send_input(KeyCode::F);
+    assert!(pressed(&mut app));
+
+    // Blocked entities release their actions and ignore their inputs
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    app.world
+        .entity_mut(player)
+        .insert(BlockInput::<Action>::default());
+    assert!(!pressed(&mut app));
+    assert!(!pressed(&mut app));
+
+    app.world.entity_mut(player).remove::<BlockInput<Action>>();
+    assert!(pressed(&mut app));
+
+    // Inputs stay blocked until every reason has been removed
+    let mut input_blockers = app.world.resource_mut::<InputBlockers<Action>>();
+    input_blockers.push("dialog");
+    input_blockers.push("cutscene");
+    assert!(!pressed(&mut app));
+
+    app.world
+        .resource_mut::<InputBlockers<Action>>()
+        .remove("cutscene");
+    assert!(!pressed(&mut app));
+
+    app.world.resource_mut::<InputBlockers<Action>>().pop();
+    assert!(pressed(&mut app));
+}
+
 #[test]
 fn release_when_input_map_removed() {
     use bevy::input::InputPlugin;
@@ -199,6 +256,8 @@ fn action_state_driver() {
             .insert(ActionStateDriver::<Action> {
                 action: Action::PayRespects,
                 targets: player_entity.into(),
+                trigger: DriverTrigger::Pressed,
+                value: None,
             });
     }
 
@@ -243,6 +302,182 @@ fn action_state_driver() {
     assert_eq!(*respect, Respect(false));
 }
 
+#[test]
+#[cfg(feature = "ui")]
+fn action_state_driver_triggers() {
+    use bevy::input::InputPlugin;
+    use bevy::ui::Interaction;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_plugins(InputPlugin);
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle::<Action>::default())
+        .id();
+    let button = app
+        .world
+        .spawn((
+            Interaction::None,
+            ActionStateDriver::new(Action::PayRespects, player)
+                .with_trigger(DriverTrigger::Clicked),
+        ))
+        .id();
+
+    let interact = |app: &mut App, interaction: Interaction| {
+        *app.world.get_mut::<Interaction>(button).unwrap() = interaction;
+        app.update();
+        app.world
+            .get::<ActionState<Action>>(player)
+            .unwrap()
+            .clone()
+    };
+
+    // Clicks are only registered once the press is released over the button
+    assert!(interact(&mut app, Interaction::Pressed).released(Action::PayRespects));
+    assert!(interact(&mut app, Interaction::Hovered).just_pressed(Action::PayRespects));
+    assert!(interact(&mut app, Interaction::Hovered).released(Action::PayRespects));
+
+    app.world.entity_mut(button).insert(
+        ActionStateDriver::new(Action::PayRespects, player)
+            .with_trigger(DriverTrigger::HoverEnter)
+            .with_value(0.5),
+    );
+    interact(&mut app, Interaction::None);
+    let action_state = interact(&mut app, Interaction::Hovered);
+    assert!(action_state.just_pressed(Action::PayRespects));
+    assert_eq!(action_state.value(Action::PayRespects), 0.5);
+    assert!(interact(&mut app, Interaction::Hovered).released(Action::PayRespects));
+}
+
+#[test]
+fn despawned_driver_targets_are_removed() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_state::{ActionStateDriverTarget, DriverTargetDespawned};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_plugins(InputPlugin);
+
+    let first = app
+        .world
+        .spawn(InputManagerBundle::<Action>::default())
+        .id();
+    let second = app
+        .world
+        .spawn(InputManagerBundle::<Action>::default())
+        .id();
+    let driver = app
+        .world
+        .spawn(ActionStateDriver::new(
+            Action::PayRespects,
+            [first, second].iter().collect::<ActionStateDriverTarget>(),
+        ))
+        .id();
+
+    app.update();
+    assert_eq!(
+        app.world
+            .get::<ActionStateDriver<Action>>(driver)
+            .unwrap()
+            .targets
+            .len(),
+        2
+    );
+
+    let despawned_targets = |app: &mut App| {
+        let mut events = app
+            .world
+            .resource_mut::<Events<DriverTargetDespawned<Action>>>();
+        events
+            .drain()
+            .map(|event| {
+                assert_eq!(event.driver, driver);
+                event.target
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Despawned targets are removed from the driver
+    app.world.despawn(first);
+    app.update();
+    assert_eq!(despawned_targets(&mut app), vec![first]);
+    let action_state_driver = app.world.get::<ActionStateDriver<Action>>(driver).unwrap();
+    assert_eq!(
+        action_state_driver.targets.iter().collect::<Vec<_>>(),
+        vec![&second]
+    );
+
+    // Drivers without any targets left are removed
+    app.world.despawn(second);
+    app.update();
+    assert_eq!(despawned_targets(&mut app), vec![second]);
+    assert!(app.world.get::<ActionStateDriver<Action>>(driver).is_none());
+}
+
+#[test]
+fn action_state_transitions() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::state_transitions::ActionStateTransitions;
+    use leafwing_input_manager::systems::apply_action_state_transitions;
+
+    #[derive(States, Default, Clone, Copy, PartialEq, Eq, Hash, Debug)]
+    enum GameState {
+        #[default]
+        Playing,
+        Respecting,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_state::<GameState>()
+        .insert_resource(
+            ActionStateTransitions::new()
+                .with(
+                    Action::PayRespects,
+                    GameState::Playing,
+                    GameState::Respecting,
+                )
+                .with(
+                    Action::PayRespects,
+                    GameState::Respecting,
+                    GameState::Playing,
+                ),
+        )
+        .add_systems(Startup, spawn_player)
+        .add_systems(
+            PreUpdate,
+            apply_action_state_transitions::<Action, GameState>
+                .after(InputManagerSystem::ManualControl),
+        );
+
+    let state = |app: &App| *app.world.resource::<State<GameState>>().get();
+
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+
+    // The state changes during the frame in which the action is pressed
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(state(&app), GameState::Respecting);
+
+    // Holding the action does not transition again
+    app.update();
+    assert_eq!(state(&app), GameState::Respecting);
+
+    app.release_input(KeyCode::F);
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(state(&app), GameState::Playing);
+}
+
 #[test]
 fn duration() {
     use bevy::input::InputPlugin;
@@ -252,11 +487,12 @@ fn duration() {
 
     fn hold_f_to_pay_respects(
         action_state: Res<ActionState<Action>>,
+        timings: Res<ActionTimings<Action>>,
         mut respect: ResMut<Respect>,
     ) {
         if action_state.pressed(Action::PayRespects)
             // Unrealistically disrespectful, but makes the tests faster
-            && action_state.current_duration(Action::PayRespects) > RESPECTFUL_DURATION
+            && timings.current_duration(&action_state, Action::PayRespects) > RESPECTFUL_DURATION
         {
             respect.0 = true;
         }
@@ -329,3 +565,1809 @@ fn schedule_presses() {
         .resource::<ActionState<Action>>()
         .just_released(Action::PayRespects));
 }
+
+#[test]
+fn virtual_input() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::virtual_input::VirtualInput;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    app.world
+        .entity_mut(player)
+        .insert(VirtualInput::<Action>::default());
+
+    app.world
+        .get_mut::<VirtualInput<Action>>(player)
+        .unwrap()
+        .set_value(Action::PayRespects, 0.5);
+    app.update();
+
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+    assert_eq!(action_state.value(Action::PayRespects), 0.5);
+
+    // Virtual inputs are merged with physical inputs
+    app.world
+        .get_mut::<VirtualInput<Action>>(player)
+        .unwrap()
+        .set_value(Action::PayRespects, 0.5);
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert_eq!(action_state.value(Action::PayRespects), 1.5);
+
+    // Virtual inputs only last for a single update
+    app.reset_inputs();
+    app.update();
+
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_released(Action::PayRespects));
+}
+
+#[test]
+fn externally_controlled() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_writer::{ActionWriter, ExternallyControlled};
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    app.world.entity_mut(player).insert((
+        ActionWriter::<Action>::default(),
+        ExternallyControlled::<Action>::default(),
+    ));
+
+    // Physical inputs are ignored
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(Action::PayRespects));
+
+    app.world
+        .get_mut::<ActionWriter<Action>>(player)
+        .unwrap()
+        .write(|frame| {
+            frame.press(Action::PayRespects);
+        });
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+
+    // The written state is held until it is replaced
+    app.reset_inputs();
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!action_state.just_pressed(Action::PayRespects));
+
+    app.world
+        .get_mut::<ActionWriter<Action>>(player)
+        .unwrap()
+        .release_all();
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_released(Action::PayRespects));
+}
+
+#[test]
+fn cursor_position_recorded_on_press() {
+    use bevy::input::InputPlugin;
+    use bevy::window::PrimaryWindow;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(
+            MouseButton::Left,
+            Action::PayRespects,
+        )]));
+
+    let mut window = Window::default();
+    window.set_cursor_position(Some(Vec2::new(10.0, 20.0)));
+    let window_entity = app.world.spawn((window, PrimaryWindow)).id();
+
+    // Recording is opt-in
+    app.send_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::PayRespects));
+    assert_eq!(action_state.cursor_position(Action::PayRespects), None);
+
+    app.release_input(MouseButton::Left);
+    app.world
+        .resource_mut::<InputMap<Action>>()
+        .set_record_cursor_position(true);
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert_eq!(action_state.cursor_position(Action::PayRespects), None);
+
+    app.send_input(MouseButton::Left);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::PayRespects));
+    assert_eq!(
+        action_state.cursor_position(Action::PayRespects),
+        Some(Vec2::new(10.0, 20.0))
+    );
+
+    // Moving the cursor while the action is held does not change the recorded position
+    let mut window = app.world.get_mut::<Window>(window_entity).unwrap();
+    window.set_cursor_position(Some(Vec2::new(30.0, 40.0)));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert_eq!(
+        action_state.cursor_position(Action::PayRespects),
+        Some(Vec2::new(10.0, 20.0))
+    );
+}
+
+#[test]
+fn raw_input_capture() {
+    use bevy::input::keyboard::KeyboardInput;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::input_capture::{CapturedEvent, CapturedInputs};
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]));
+
+    // Capturing is disabled without a `CapturedInputs`
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+
+    app.init_resource::<CapturedInputs<Action>>();
+    app.reset_inputs();
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let captured_inputs = app.world.resource::<CapturedInputs<Action>>();
+    let captured = captured_inputs.get(Action::PayRespects);
+    assert_eq!(captured.len(), 1);
+    assert!(matches!(
+        captured[0].event,
+        CapturedEvent::Keyboard(KeyboardInput {
+            key_code: Some(KeyCode::F),
+            ..
+        })
+    ));
+
+    // Captured inputs only last for a single frame
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    let captured_inputs = app.world.resource::<CapturedInputs<Action>>();
+    assert!(captured_inputs.get(Action::PayRespects).is_empty());
+}
+
+#[test]
+fn raw_event_timestamps() {
+    use bevy::input::keyboard::KeyboardInput;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::utils::{Duration, Instant};
+    use leafwing_input_manager::input_capture::{
+        CapturedEvent, CapturedInputs, RawEventTimestamps,
+    };
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .init_resource::<CapturedInputs<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]));
+    app.update();
+
+    // Supplied by the platform, before the frame during which the event is processed
+    let pressed = CapturedEvent::Keyboard(KeyboardInput {
+        scan_code: u32::MAX,
+        key_code: Some(KeyCode::F),
+        state: ButtonState::Pressed,
+        window: Entity::PLACEHOLDER,
+    });
+    let received = Instant::now() - Duration::from_millis(7);
+    app.world
+        .resource_mut::<RawEventTimestamps>()
+        .record(pressed.clone(), received);
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let captured_inputs = app.world.resource::<CapturedInputs<Action>>();
+    assert_eq!(
+        captured_inputs.get(Action::PayRespects)[0].timestamp,
+        received
+    );
+    let action_state = app.world.resource::<ActionState<Action>>();
+    let timings = app.world.resource::<ActionTimings<Action>>();
+    let timing = timings.timing(action_state, Action::PayRespects);
+    assert_eq!(timing.event_timestamp, Some(received));
+
+    // Without a supplied timestamp, the event is timestamped with the start of the frame
+    app.release_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    let timings = app.world.resource::<ActionTimings<Action>>();
+    let timing = timings.timing(action_state, Action::PayRespects);
+    assert!(timing.event_timestamp.unwrap() > received);
+    // Supplied timestamps only last for a single frame
+    assert_eq!(
+        app.world.resource::<RawEventTimestamps>().get(&pressed),
+        None
+    );
+}
+
+#[test]
+fn transition_order() {
+    use bevy::input::mouse::MouseButtonInput;
+    use bevy::input::{ButtonState, InputPlugin};
+    use bevy::utils::{Duration, Instant};
+    use leafwing_input_manager::input_capture::{
+        CapturedEvent, CapturedInputs, RawEventTimestamps,
+    };
+
+    #[derive(Actionlike, Clone, Copy, Debug, PartialEq, Reflect)]
+    enum Fighter {
+        Punch,
+        Kick,
+        Block,
+    }
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Fighter>::default())
+        .init_resource::<ActionState<Fighter>>()
+        .init_resource::<CapturedInputs<Fighter>>()
+        .insert_resource(InputMap::<Fighter>::new([
+            (UserInput::from(MouseButton::Left), Fighter::Punch),
+            (UserInput::from(KeyCode::K), Fighter::Kick),
+        ]));
+    app.update();
+
+    // Without timestamps, keyboard events come before mouse events
+    app.send_input(MouseButton::Left);
+    app.send_input(KeyCode::K);
+    app.world
+        .resource_mut::<ActionState<Fighter>>()
+        .press(Fighter::Block);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Fighter>>();
+    let captured_inputs = app.world.resource::<CapturedInputs<Fighter>>();
+    assert_eq!(captured_inputs.transition_order(Fighter::Kick), Some(0));
+    assert_eq!(captured_inputs.transition_order(Fighter::Punch), Some(1));
+    assert_eq!(captured_inputs.transition_order(Fighter::Block), None);
+    assert_eq!(
+        captured_inputs.ordered_transitions(action_state),
+        [Fighter::Kick, Fighter::Punch, Fighter::Block]
+    );
+
+    // Held actions have no transitions
+    app.update();
+    let action_state = app.world.resource::<ActionState<Fighter>>();
+    let captured_inputs = app.world.resource::<CapturedInputs<Fighter>>();
+    assert!(captured_inputs.ordered_transitions(action_state).is_empty());
+
+    // Supplied timestamps take priority over the kind of device
+    app.reset_inputs();
+    app.update();
+    let now = Instant::now();
+    let mut timestamps = app.world.resource_mut::<RawEventTimestamps>();
+    timestamps.record(
+        CapturedEvent::MouseButton(MouseButtonInput {
+            button: MouseButton::Left,
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        }),
+        now - Duration::from_millis(2),
+    );
+    timestamps.record(
+        CapturedEvent::Keyboard(bevy::input::keyboard::KeyboardInput {
+            scan_code: u32::MAX,
+            key_code: Some(KeyCode::K),
+            state: ButtonState::Pressed,
+            window: Entity::PLACEHOLDER,
+        }),
+        now - Duration::from_millis(1),
+    );
+    app.send_input(MouseButton::Left);
+    app.send_input(KeyCode::K);
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<Fighter>>();
+    let captured_inputs = app.world.resource::<CapturedInputs<Fighter>>();
+    assert_eq!(
+        captured_inputs.ordered_transitions(action_state),
+        [Fighter::Punch, Fighter::Kick]
+    );
+}
+
+#[test]
+fn action_hooks() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_hooks::ActionHooks;
+
+    let mut app = App::new();
+
+    let mut hooks = ActionHooks::<Action>::default();
+    hooks
+        .on_pressed(Action::PayRespects, |context, commands| {
+            assert_eq!(context.entity, None);
+            commands.insert_resource(Respect(true));
+        })
+        .on_released(Action::PayRespects, |_, commands| {
+            commands.insert_resource(Respect(false));
+        });
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]))
+        .insert_resource(hooks)
+        .init_resource::<Respect>();
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert_eq!(*app.world.resource::<Respect>(), Respect(true));
+
+    // Hooks only run on transitions, so later changes are not overwritten while the action is held
+    app.world.resource_mut::<Respect>().0 = false;
+    app.update();
+    assert_eq!(*app.world.resource::<Respect>(), Respect(false));
+
+    app.world.resource_mut::<Respect>().0 = true;
+    app.reset_inputs();
+    app.update();
+    assert_eq!(*app.world.resource::<Respect>(), Respect(false));
+}
+
+#[test]
+fn mirror_action_state() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::mirror::MirrorActionState;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    let spectator = app
+        .world
+        .spawn((
+            ActionState::<Action>::default(),
+            MirrorActionState::<Action>::new(player),
+        ))
+        .id();
+    let filtered_spectator = app
+        .world
+        .spawn((
+            ActionState::<Action>::default(),
+            MirrorActionState::<Action>::new(player).with_actions([]),
+        ))
+        .id();
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(spectator).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+    let action_state = app
+        .world
+        .get::<ActionState<Action>>(filtered_spectator)
+        .unwrap();
+    assert!(action_state.released(Action::PayRespects));
+
+    // Timing is mirrored along with the state of each action
+    app.update();
+    let timing = |entity| {
+        let action_state = app.world.get::<ActionState<Action>>(entity).unwrap();
+        let timings = app.world.get::<ActionTimings<Action>>(entity).unwrap();
+        timings.timing(action_state, Action::PayRespects)
+    };
+    assert!(timing(player).instant_started.is_some());
+    assert_eq!(timing(spectator), timing(player));
+    assert_ne!(timing(filtered_spectator), timing(player));
+
+    app.reset_inputs();
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(spectator).unwrap();
+    assert!(action_state.just_released(Action::PayRespects));
+
+    // Mirrored states are left unchanged once the source is despawned
+    app.world.despawn(player);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(spectator).unwrap();
+    assert!(action_state.released(Action::PayRespects));
+}
+
+#[test]
+fn key_repeats() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::key_repeat::KeyRepeats;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]));
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::PayRespects));
+
+    // Repeated press events from the operating system never re-trigger `just_pressed`
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!action_state.just_pressed(Action::PayRespects));
+
+    // With a `KeyRepeats`, repeats are surfaced separately
+    app.init_resource::<KeyRepeats<Action>>();
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    let key_repeats = app.world.resource::<KeyRepeats<Action>>();
+    assert!(!action_state.just_pressed(Action::PayRespects));
+    assert!(key_repeats.just_repeated(Action::PayRespects));
+    assert!(key_repeats.just_pressed_or_repeated(action_state, Action::PayRespects));
+    assert_eq!(key_repeats.repeats(Action::PayRespects), 1);
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    let key_repeats = app.world.resource::<KeyRepeats<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!key_repeats.just_repeated(Action::PayRespects));
+}
+
+#[test]
+fn window_events() {
+    use bevy::input::InputPlugin;
+    use bevy::window::WindowCloseRequested;
+    use leafwing_input_manager::window_events::WindowEventKind;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_event::<WindowCloseRequested>()
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(
+            UserInput::WindowEvent(WindowEventKind::CloseRequested),
+            Action::PayRespects,
+        )]));
+
+    app.world.send_event(WindowCloseRequested {
+        window: Entity::PLACEHOLDER,
+    });
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::PayRespects));
+
+    // Window events only press their actions during the frame in which they were received
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(Action::PayRespects));
+}
+
+#[test]
+fn custom_inputs() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::custom_input::RawInputEvent;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(
+            UserInput::Custom(7),
+            Action::PayRespects,
+        )]));
+
+    app.world.send_event(RawInputEvent::new(7, 0.5));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_pressed(Action::PayRespects));
+    assert_eq!(action_state.value(Action::PayRespects), 0.5);
+
+    // Custom inputs are held until they are released
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!action_state.just_pressed(Action::PayRespects));
+
+    // Events for other inputs are ignored
+    app.world.send_event(RawInputEvent::released(8));
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::PayRespects));
+
+    app.world.send_event(RawInputEvent::released(7));
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.just_released(Action::PayRespects));
+}
+
+#[test]
+fn ime_composition_suppresses_keyboard() {
+    use bevy::input::InputPlugin;
+    use bevy::window::Ime;
+    use leafwing_input_manager::ime::ImeActive;
+
+    let mut app = App::new();
+
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_event::<Ime>()
+        .init_resource::<ActionState<Action>>()
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]));
+
+    app.world.send_event(Ime::Preedit {
+        window: Entity::PLACEHOLDER,
+        value: "ㅎ".to_string(),
+        cursor: None,
+    });
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app.world.resource::<ImeActive>().composing());
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::PayRespects));
+
+    // The key used to confirm the text does not fire its action once the composition ends
+    app.world.send_event(Ime::Commit {
+        window: Entity::PLACEHOLDER,
+        value: "한".to_string(),
+    });
+    app.update();
+    assert!(!app.world.resource::<ImeActive>().composing());
+    assert!(!app
+        .world
+        .resource::<ActionState<Action>>()
+        .pressed(Action::PayRespects));
+
+    app.release_input(KeyCode::F);
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .just_pressed(Action::PayRespects));
+}
+
+#[test]
+#[cfg(feature = "ui")]
+fn ui_navigation() {
+    use bevy::a11y::Focus;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::ui_navigation::{
+        Focusable, UiNavigationActions, UiNavigationEvent, UiNavigationPlugin,
+    };
+
+    #[derive(Actionlike, Clone, Copy, Debug, Reflect)]
+    enum MenuAction {
+        Down,
+        Select,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<MenuAction>::default())
+        .add_plugins(UiNavigationPlugin::new(UiNavigationActions {
+            down: Some(MenuAction::Down),
+            confirm: Some(MenuAction::Select),
+            ..Default::default()
+        }))
+        .init_resource::<ActionState<MenuAction>>()
+        .insert_resource(InputMap::<MenuAction>::new([
+            (KeyCode::Down, MenuAction::Down),
+            (KeyCode::Return, MenuAction::Select),
+        ]));
+
+    let bottom = app
+        .world
+        .spawn((Focusable, GlobalTransform::from_xyz(0.0, 100.0, 0.0)))
+        .id();
+    let top = app
+        .world
+        .spawn((Focusable, GlobalTransform::from_xyz(0.0, 0.0, 0.0)))
+        .id();
+
+    // Without a focus, navigating focuses the top-left node
+    app.send_input(KeyCode::Down);
+    app.update();
+    assert_eq!(**app.world.resource::<Focus>(), Some(top));
+
+    app.release_input(KeyCode::Down);
+    app.update();
+    app.send_input(KeyCode::Down);
+    app.update();
+    assert_eq!(**app.world.resource::<Focus>(), Some(bottom));
+
+    app.send_input(KeyCode::Return);
+    app.update();
+    let events = app.world.resource::<Events<UiNavigationEvent>>();
+    let activated: Vec<UiNavigationEvent> = events.get_reader().iter(events).copied().collect();
+    assert_eq!(activated, [UiNavigationEvent::Activated(bottom)]);
+}
+
+#[test]
+fn many_entities() {
+    use bevy::input::InputPlugin;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    // Half of the entities pay respects with F, the other half with G
+    for i in 0..1000 {
+        let key = if i % 2 == 0 { KeyCode::F } else { KeyCode::G };
+        app.world.spawn(InputManagerBundle::<Action> {
+            input_map: InputMap::new([(key, Action::PayRespects)]),
+            ..Default::default()
+        });
+    }
+
+    app.send_input(KeyCode::F);
+    app.update();
+
+    let mut query = app
+        .world
+        .query::<(&ActionState<Action>, &InputMap<Action>)>();
+    for (action_state, input_map) in query.iter(&app.world) {
+        let bound_to_f = input_map
+            .get(Action::PayRespects)
+            .iter()
+            .any(|input| *input == UserInput::from(KeyCode::F));
+        assert_eq!(action_state.pressed(Action::PayRespects), bound_to_f);
+    }
+}
+
+#[test]
+fn registered_action_diffs() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_state::ActionDiff;
+    use leafwing_input_manager::stable_id::StableIdRegistry;
+    use leafwing_input_manager::systems::{
+        generate_registered_action_diffs, process_registered_action_diffs,
+    };
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_event::<ActionDiff<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .add_systems(Startup, spawn_player)
+        .add_systems(PostUpdate, generate_registered_action_diffs::<Action, u64>);
+    client_app.update();
+    let client_player = client_app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, client_player);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::server())
+        .add_event::<ActionDiff<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .add_systems(PreUpdate, process_registered_action_diffs::<Action, u64>);
+    let server_player = server_app
+        .world
+        .spawn(ActionState::<Action>::default())
+        .id();
+    server_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, server_player);
+
+    client_app.send_input(KeyCode::F);
+    client_app.update();
+
+    let diffs: Vec<_> = client_app
+        .world
+        .resource_mut::<Events<ActionDiff<Action, u64>>>()
+        .drain()
+        .collect();
+    assert_eq!(diffs.len(), 1);
+    server_app
+        .world
+        .resource_mut::<Events<ActionDiff<Action, u64>>>()
+        .extend(diffs);
+    server_app.update();
+
+    let action_state = server_app
+        .world
+        .get::<ActionState<Action>>(server_player)
+        .unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+}
+
+#[test]
+fn action_diffs_for_late_joining_entities() {
+    use leafwing_input_manager::action_state::ActionDiff;
+    use leafwing_input_manager::missing_components::MissingComponentPolicy;
+    use leafwing_input_manager::stable_id::StableIdRegistry;
+    use leafwing_input_manager::systems::process_registered_action_diffs;
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(
+            InputManagerPlugin::<Action>::server()
+                .with_missing_diff_targets(MissingComponentPolicy::Insert),
+        )
+        .add_event::<ActionDiff<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .add_systems(PreUpdate, process_registered_action_diffs::<Action, u64>);
+    // This entity has not been given an `ActionState` yet
+    let late_player = server_app.world.spawn_empty().id();
+    server_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, late_player);
+
+    server_app
+        .world
+        .send_event(ActionDiff::<Action, u64>::Pressed {
+            action: Action::PayRespects,
+            id: 7,
+        });
+    server_app.update();
+
+    let action_state = server_app
+        .world
+        .get::<ActionState<Action>>(late_player)
+        .unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+}
+
+#[test]
+fn missed_sequenced_action_diffs() {
+    use bevy::ecs::event::Events;
+    use leafwing_input_manager::action_state::ActionDiff;
+    use leafwing_input_manager::diff_sequencing::{
+        DiffSequenceTracker, DiffSequencer, MissedInput, SequencedActionDiff,
+    };
+    use leafwing_input_manager::stable_id::StableIdRegistry;
+    use leafwing_input_manager::systems::{
+        process_registered_action_diffs, unsequence_action_diffs,
+    };
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::server())
+        .add_event::<ActionDiff<Action, u64>>()
+        .add_event::<SequencedActionDiff<Action, u64>>()
+        .add_event::<MissedInput<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .init_resource::<DiffSequenceTracker<Action, u64>>()
+        .add_systems(
+            PreUpdate,
+            (
+                unsequence_action_diffs::<Action, u64>,
+                process_registered_action_diffs::<Action, u64>,
+            )
+                .chain(),
+        );
+    let player = server_app
+        .world
+        .spawn(ActionState::<Action>::default())
+        .id();
+    server_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, player);
+
+    let mut sequencer = DiffSequencer::<Action, u64>::default();
+    let pressed = sequencer.stamp(ActionDiff::Pressed {
+        action: Action::PayRespects,
+        id: 7,
+    });
+    let released = sequencer.stamp(ActionDiff::Released {
+        action: Action::PayRespects,
+        id: 7,
+    });
+    let pressed_again = sequencer.stamp(ActionDiff::Pressed {
+        action: Action::PayRespects,
+        id: 7,
+    });
+
+    // The release is lost, and the other diffs arrive out of order and duplicated
+    server_app
+        .world
+        .resource_mut::<Events<SequencedActionDiff<Action, u64>>>()
+        .extend([pressed_again, pressed.clone(), pressed]);
+    server_app.update();
+
+    let missed: Vec<_> = server_app
+        .world
+        .resource_mut::<Events<MissedInput<Action, u64>>>()
+        .drain()
+        .collect();
+    assert_eq!(missed.len(), 1);
+    assert_eq!(missed[0].id, 7);
+    assert_eq!(missed[0].missed, 1..2);
+
+    let action_state = server_app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+
+    // The lost diff is stale once it finally arrives
+    server_app
+        .world
+        .resource_mut::<Events<SequencedActionDiff<Action, u64>>>()
+        .send(released);
+    server_app.update();
+    let action_state = server_app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+}
+
+#[test]
+fn loopback_diff_transport() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_state::ActionDiff;
+    use leafwing_input_manager::diff_transport::{
+        loopback_channel, LoopbackReceiver, LoopbackSender,
+    };
+    use leafwing_input_manager::stable_id::StableIdRegistry;
+    use leafwing_input_manager::systems::{
+        generate_registered_action_diffs, process_registered_action_diffs, receive_diffs,
+        send_diffs,
+    };
+
+    let (sender, receiver) = loopback_channel::<ActionDiff<Action, u64>>();
+
+    let mut client_app = App::new();
+    client_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_event::<ActionDiff<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .insert_resource(sender)
+        .add_systems(Startup, spawn_player)
+        .add_systems(
+            PostUpdate,
+            (
+                generate_registered_action_diffs::<Action, u64>,
+                send_diffs::<ActionDiff<Action, u64>, LoopbackSender<ActionDiff<Action, u64>>>,
+            )
+                .chain(),
+        );
+    client_app.update();
+    let client_player = client_app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&client_app.world);
+    client_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, client_player);
+
+    let mut server_app = App::new();
+    server_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::server())
+        .add_event::<ActionDiff<Action, u64>>()
+        .init_resource::<StableIdRegistry<u64>>()
+        .insert_resource(receiver)
+        .add_systems(
+            PreUpdate,
+            (
+                receive_diffs::<ActionDiff<Action, u64>, LoopbackReceiver<ActionDiff<Action, u64>>>,
+                process_registered_action_diffs::<Action, u64>,
+            )
+                .chain(),
+        );
+    let server_player = server_app
+        .world
+        .spawn(ActionState::<Action>::default())
+        .id();
+    server_app
+        .world
+        .resource_mut::<StableIdRegistry<u64>>()
+        .insert(7, server_player);
+
+    client_app.send_input(KeyCode::F);
+    client_app.update();
+    server_app.update();
+    let action_state = server_app
+        .world
+        .get::<ActionState<Action>>(server_player)
+        .unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+
+    client_app.reset_inputs();
+    client_app.update();
+    server_app.update();
+    let action_state = server_app
+        .world
+        .get::<ActionState<Action>>(server_player)
+        .unwrap();
+    assert!(action_state.released(Action::PayRespects));
+}
+
+#[test]
+fn confirmed_actions_wait_for_the_server() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::action_state::ActionDiff;
+    use leafwing_input_manager::authority::{ActionAuthority, AuthorityLevel};
+    use leafwing_input_manager::plugin::InputManagerSystem;
+    use leafwing_input_manager::systems::{
+        process_confirmed_action_diffs, reconcile_action_states, restore_predicted_action_states,
+    };
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum Shop {
+        Move,
+        Buy,
+    }
+
+    #[derive(Component, Clone, PartialEq, Eq, Hash, Debug)]
+    struct PlayerId(u64);
+
+    // What gameplay systems see during `Update`
+    #[derive(Resource, Default)]
+    struct Seen {
+        moving: bool,
+        buying: bool,
+        confirmed_timing: bool,
+    }
+
+    fn observe(
+        query: Query<(
+            &ActionState<Shop>,
+            &ActionTimings<Shop>,
+            &ActionAuthority<Shop>,
+        )>,
+        mut seen: ResMut<Seen>,
+    ) {
+        let (action_state, timings, authority) = query.single();
+        seen.moving = action_state.pressed(Shop::Move);
+        seen.buying = action_state.pressed(Shop::Buy);
+        seen.confirmed_timing = timings.timing(action_state, Shop::Buy)
+            == authority
+                .confirmed_timings
+                .timing(&authority.confirmed, Shop::Buy);
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Shop>::default())
+        .add_event::<ActionDiff<Shop, PlayerId>>()
+        .init_resource::<Seen>()
+        .add_systems(
+            PreUpdate,
+            (
+                process_confirmed_action_diffs::<Shop, PlayerId>,
+                reconcile_action_states::<Shop>,
+            )
+                .chain()
+                .after(InputManagerSystem::ManualControl),
+        )
+        .add_systems(Update, observe)
+        .add_systems(PostUpdate, restore_predicted_action_states::<Shop>);
+
+    let player = app
+        .world
+        .spawn((
+            InputManagerBundle::<Shop> {
+                input_map: InputMap::new([(KeyCode::W, Shop::Move), (KeyCode::B, Shop::Buy)]),
+                ..Default::default()
+            },
+            ActionAuthority::default().with_level(Shop::Buy, AuthorityLevel::Confirmed),
+            PlayerId(1),
+        ))
+        .id();
+
+    app.send_input(KeyCode::W);
+    app.send_input(KeyCode::B);
+    app.update();
+
+    // Movement is predicted, but the purchase waits for the server
+    let seen = app.world.resource::<Seen>();
+    assert!(seen.moving);
+    assert!(!seen.buying);
+    assert!(seen.confirmed_timing);
+    // The local press is restored along with its timing, so that it can be sent to the server
+    let action_state = app.world.get::<ActionState<Shop>>(player).unwrap();
+    assert!(action_state.pressed(Shop::Buy));
+    let authority = app.world.get::<ActionAuthority<Shop>>(player).unwrap();
+    assert!(authority.mispredicted(Shop::Buy));
+    let timings = app.world.get::<ActionTimings<Shop>>(player).unwrap();
+    assert_eq!(
+        timings.timing(action_state, Shop::Buy),
+        authority
+            .predicted_timings
+            .timing(&authority.predicted, Shop::Buy)
+    );
+
+    app.world
+        .resource_mut::<Events<ActionDiff<Shop, PlayerId>>>()
+        .send(ActionDiff::Pressed {
+            action: Shop::Buy,
+            id: PlayerId(1),
+        });
+    app.update();
+
+    let seen = app.world.resource::<Seen>();
+    assert!(seen.moving);
+    assert!(seen.buying);
+    assert!(seen.confirmed_timing);
+    let authority = app.world.get::<ActionAuthority<Shop>>(player).unwrap();
+    assert!(!authority.mispredicted(Shop::Buy));
+}
+
+#[test]
+fn rate_limits() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::rate_limits::{RateLimited, RateLimits};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    let mut rate_limits = RateLimits::<Action>::default();
+    rate_limits.set(Action::PayRespects, 1);
+    app.world.entity_mut(player).insert(rate_limits);
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+
+    app.release_input(KeyCode::F);
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+
+    // The second press within a second is suppressed
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(Action::PayRespects));
+    assert!(!action_state.just_released(Action::PayRespects));
+    let events = app.world.resource::<Events<RateLimited<Action>>>();
+    let mut reader = events.get_reader();
+    let limited: Vec<_> = reader.iter(events).cloned().collect();
+    assert_eq!(limited.len(), 1);
+    assert_eq!(limited[0].entity, Some(player));
+
+    // Holding the input does not press the action again
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.released(Action::PayRespects));
+}
+
+#[test]
+fn input_idle() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::idle::{InputActivity, InputIdle};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player);
+    app.world
+        .resource_mut::<InputActivity<Action>>()
+        .add_threshold(Duration::from_nanos(1));
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    app.send_input(KeyCode::F);
+    app.update();
+    app.world
+        .resource_mut::<Events<InputIdle<Action>>>()
+        .clear();
+
+    // The player is idle again from the first update after the release
+    std::thread::sleep(Duration::from_millis(1));
+    app.release_input(KeyCode::F);
+    app.update();
+
+    let activity = app.world.resource::<InputActivity<Action>>();
+    assert!(activity.entity_last_activity(player).is_some());
+    assert!(activity.entity_idle_for(player) > Some(Duration::ZERO));
+    assert_eq!(
+        activity.last_activity(),
+        activity.entity_last_activity(player)
+    );
+
+    let events = app.world.resource::<Events<InputIdle<Action>>>();
+    let mut reader = events.get_reader();
+    let idle: Vec<_> = reader
+        .iter(events)
+        .map(|event| (event.threshold, event.entity))
+        .collect();
+    assert_eq!(
+        idle,
+        [
+            (Duration::from_nanos(1), Some(player)),
+            (Duration::from_nanos(1), None)
+        ]
+    );
+}
+
+#[test]
+fn manual_input_clock() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+    use leafwing_input_manager::rate_limits::RateLimits;
+    use leafwing_input_manager::timing::InputClock;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_systems(Startup, spawn_player)
+        .insert_resource(InputClock::manual());
+    app.update();
+
+    let player = app
+        .world
+        .query_filtered::<Entity, With<Player>>()
+        .single(&app.world);
+    let mut rate_limits = RateLimits::<Action>::default();
+    rate_limits.set(Action::PayRespects, 1);
+    app.world.entity_mut(player).insert(rate_limits);
+
+    app.send_input(KeyCode::F);
+    app.update();
+    app.world
+        .resource_mut::<InputClock>()
+        .advance(Duration::from_millis(1500));
+    app.update();
+
+    // Durations are measured by the manual clock, however long the frames actually took
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    let timings = app.world.get::<ActionTimings<Action>>(player).unwrap();
+    assert_eq!(
+        timings.current_duration(action_state, Action::PayRespects),
+        Duration::from_millis(1500)
+    );
+
+    // The cooldown of the rate limit has passed according to the manual clock
+    app.release_input(KeyCode::F);
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+}
+
+#[test]
+fn missing_components() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::missing_components::{
+        MissingComponent, MissingComponentKind, MissingComponentPolicy,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(
+            InputManagerPlugin::<Action>::default().with_missing_components(
+                MissingComponentPolicy::Warn,
+                MissingComponentPolicy::Warn,
+            ),
+        );
+
+    let input_map_only = app
+        .world
+        .spawn(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]))
+        .id();
+    let action_state_only = app.world.spawn(ActionState::<Action>::default()).id();
+    app.update();
+
+    let events = app.world.resource::<Events<MissingComponent<Action>>>();
+    let mut reader = events.get_reader();
+    let mut missing: Vec<_> = reader
+        .iter(events)
+        .map(|event| (event.entity, event.missing))
+        .collect();
+    missing.sort_by_key(|(entity, _)| *entity);
+    assert_eq!(
+        missing,
+        [
+            (input_map_only, MissingComponentKind::ActionState),
+            (action_state_only, MissingComponentKind::InputMap)
+        ]
+    );
+    assert!(app
+        .world
+        .get::<ActionState<Action>>(input_map_only)
+        .is_none());
+
+    // Each entity is only checked when its component is added
+    app.update();
+    let events = app.world.resource::<Events<MissingComponent<Action>>>();
+    assert_eq!(reader.iter(events).count(), 0);
+
+    // Missing components can be inserted instead
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(
+            InputManagerPlugin::<Action>::default().with_missing_components(
+                MissingComponentPolicy::Insert,
+                MissingComponentPolicy::Insert,
+            ),
+        );
+
+    let input_map_only = app
+        .world
+        .spawn(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]))
+        .id();
+    let action_state_only = app.world.spawn(ActionState::<Action>::default()).id();
+    app.update();
+
+    assert!(app
+        .world
+        .get::<ActionState<Action>>(input_map_only)
+        .is_some());
+    assert!(app
+        .world
+        .get::<InputMap<Action>>(action_state_only)
+        .is_some());
+
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app
+        .world
+        .get::<ActionState<Action>>(input_map_only)
+        .unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+}
+
+#[test]
+fn action_states_are_inserted_for_input_maps() {
+    use bevy::input::InputPlugin;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    let player = app
+        .world
+        .spawn(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]))
+        .id();
+    let action_state_only = app.world.spawn(ActionState::<Action>::default()).id();
+
+    // The inserted action state is updated during the same frame
+    app.send_input(KeyCode::F);
+    app.update();
+    let action_state = app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+
+    // Action states without an input map are left alone by default
+    assert!(app
+        .world
+        .get::<InputMap<Action>>(action_state_only)
+        .is_none());
+}
+
+#[test]
+fn device_hot_swap() {
+    use bevy::input::InputPlugin;
+    use bevy::utils::Duration;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    let mut input_map = InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]);
+    input_map.insert(MouseButton::Left, Action::PayRespects);
+    input_map.set_device_hot_swap(Duration::from_secs(60));
+    let player = app.world.spawn(input_map).id();
+    let action_state = |app: &App| {
+        app.world
+            .get::<ActionState<Action>>(player)
+            .unwrap()
+            .clone()
+    };
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(action_state(&app).just_pressed(Action::PayRespects));
+
+    // The press is kept while switching from the keyboard to the mouse
+    app.release_input(KeyCode::F);
+    app.update();
+    app.send_input(MouseButton::Left);
+    app.update();
+    let state = action_state(&app);
+    assert!(state.pressed(Action::PayRespects));
+    assert!(!state.just_pressed(Action::PayRespects));
+    assert!(!state.just_released(Action::PayRespects));
+
+    // Pressing the same device again is a new press
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).pressed(Action::PayRespects));
+    app.send_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).just_released(Action::PayRespects));
+    app.update();
+    assert!(action_state(&app).just_pressed(Action::PayRespects));
+
+    // Without a window, releases go through immediately
+    app.world
+        .get_mut::<InputMap<Action>>(player)
+        .unwrap()
+        .clear_device_hot_swap();
+    app.release_input(MouseButton::Left);
+    app.update();
+    assert!(action_state(&app).just_released(Action::PayRespects));
+}
+
+#[test]
+fn binding_capture() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::binding_capture::{
+        BindingCapture, BindingCaptured, CaptureOutcome,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+    app.update();
+
+    app.world
+        .resource_mut::<BindingCapture<Action>>()
+        .start(Action::PayRespects);
+    app.send_input(KeyCode::ShiftRight);
+    app.update();
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app
+        .world
+        .resource::<BindingCapture<Action>>()
+        .is_capturing());
+
+    // The chord is captured once any of its keys is released
+    app.release_input(KeyCode::F);
+    app.update();
+    assert!(!app
+        .world
+        .resource::<BindingCapture<Action>>()
+        .is_capturing());
+
+    let events = app.world.resource::<Events<BindingCaptured<Action>>>();
+    let mut reader = events.get_reader();
+    let outcomes: Vec<_> = reader
+        .iter(events)
+        .map(|event| event.outcome.clone())
+        .collect();
+    assert_eq!(
+        outcomes,
+        [CaptureOutcome::Captured(UserInput::modified(
+            Modifier::Shift,
+            KeyCode::F
+        ))]
+    );
+}
+
+#[test]
+#[cfg(feature = "asset")]
+fn control_schemes_are_applied() {
+    use bevy::input::InputPlugin;
+    use bevy::reflect::TypeUuid;
+    use leafwing_input_manager::control_scheme::{ControlScheme, ControlSchemePlugin};
+    use serde::{Deserialize, Serialize};
+
+    #[derive(
+        Actionlike,
+        Clone,
+        Copy,
+        PartialEq,
+        Eq,
+        PartialOrd,
+        Ord,
+        Hash,
+        Debug,
+        Reflect,
+        TypeUuid,
+        Serialize,
+        Deserialize,
+    )]
+    #[uuid = "3C5A7F1E-2B4D-4E8A-9F61-7D0B2C8E4A13"]
+    enum Scheme {
+        Jump,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(AssetPlugin::default())
+        .add_plugins(InputManagerPlugin::<Scheme>::default())
+        .add_plugins(ControlSchemePlugin::<Scheme>::default());
+
+    let handle = app
+        .world
+        .resource_mut::<Assets<ControlScheme<Scheme>>>()
+        .add(ControlScheme {
+            name: "Default".to_string(),
+            description: String::new(),
+            input_map: InputMap::new([(KeyCode::Space, Scheme::Jump)]),
+        });
+    let player = app
+        .world
+        .spawn((InputManagerBundle::<Scheme>::default(), handle.clone()))
+        .id();
+    app.update();
+
+    let input_map = app.world.get::<InputMap<Scheme>>(player).unwrap();
+    assert_eq!(input_map.get(Scheme::Jump).len(), 1);
+
+    // Modifying the scheme, such as when its file is hot-reloaded, updates the input map
+    app.world
+        .resource_mut::<Assets<ControlScheme<Scheme>>>()
+        .get_mut(&handle)
+        .unwrap()
+        .input_map
+        .insert(KeyCode::Return, Scheme::Jump);
+    app.update();
+
+    let input_map = app.world.get::<InputMap<Scheme>>(player).unwrap();
+    assert_eq!(input_map.get(Scheme::Jump).len(), 2);
+
+    // Bindings that disappear release their actions
+    app.world
+        .get_mut::<ActionState<Scheme>>(player)
+        .unwrap()
+        .press(Scheme::Jump);
+    let mut control_schemes = app.world.resource_mut::<Assets<ControlScheme<Scheme>>>();
+    let control_scheme = control_schemes.get_mut(&handle).unwrap();
+    control_scheme.input_map.clear_action(Scheme::Jump);
+    control_scheme.input_map.insert(KeyCode::J, Scheme::Jump);
+    app.update();
+
+    let input_map = app.world.get::<InputMap<Scheme>>(player).unwrap();
+    assert_eq!(input_map.get(Scheme::Jump).len(), 1);
+    let action_state = app.world.get::<ActionState<Scheme>>(player).unwrap();
+    assert!(action_state.released(Scheme::Jump));
+
+    // The bindings that the player overrode are preserved
+    app.world
+        .get_mut::<InputMap<Scheme>>(player)
+        .unwrap()
+        .insert(KeyCode::K, Scheme::Jump);
+    app.world
+        .resource_mut::<Assets<ControlScheme<Scheme>>>()
+        .get_mut(&handle)
+        .unwrap()
+        .input_map
+        .insert(KeyCode::L, Scheme::Jump);
+    app.update();
+
+    let input_map = app.world.get::<InputMap<Scheme>>(player).unwrap();
+    assert!(input_map
+        .get(Scheme::Jump)
+        .contains(&UserInput::from(KeyCode::K)));
+    assert!(!input_map
+        .get(Scheme::Jump)
+        .contains(&UserInput::from(KeyCode::L)));
+}
+
+#[test]
+fn binding_profiles() {
+    use bevy::ecs::event::Events;
+    use bevy::input::gamepad::{
+        GamepadConnection, GamepadConnectionEvent, GamepadEvent, GamepadInfo,
+    };
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::binding_profiles::{
+        BindingProfileSelected, BindingProfiles, ControllerIdentity,
+    };
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    let mut profiles = BindingProfiles::<Action>::default();
+    profiles.insert(
+        ControllerIdentity::new("Xbox Wireless Controller"),
+        InputMap::new([(GamepadButtonType::South, Action::PayRespects)]),
+    );
+    let player = app
+        .world
+        .spawn((InputManagerBundle::<Action>::default(), profiles, Player))
+        .id();
+    app.update();
+
+    let gamepad = Gamepad { id: 1 };
+    app.world
+        .resource_mut::<Events<GamepadEvent>>()
+        .send(GamepadEvent::Connection(GamepadConnectionEvent {
+            gamepad,
+            connection: GamepadConnection::Connected(GamepadInfo {
+                name: "Xbox Series X Controller".into(),
+            }),
+        }));
+    app.update();
+
+    // The profile of another Xbox controller is used
+    let input_map = app.world.get::<InputMap<Action>>(player).unwrap();
+    assert!(input_map
+        .get(Action::PayRespects)
+        .contains(&GamepadButtonType::South.into()));
+
+    let events = app
+        .world
+        .resource::<Events<BindingProfileSelected<Action>>>();
+    let mut reader = events.get_reader();
+    let selected: Vec<_> = reader
+        .iter(events)
+        .map(|event| {
+            (
+                event.gamepad,
+                event.identity.name().to_string(),
+                event.entity,
+            )
+        })
+        .collect();
+    assert_eq!(
+        selected,
+        [(
+            gamepad,
+            "Xbox Series X Controller".to_string(),
+            Some(player)
+        )]
+    );
+}
+
+#[test]
+#[cfg(feature = "ui")]
+fn rebinding_menus() {
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::binding_capture::BindingCapture;
+    use leafwing_input_manager::rebinding_menu::{BindingLabel, RebindButton, RebindingMenu};
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+
+    let player = app
+        .world
+        .spawn(InputManagerBundle::<Action> {
+            input_map: InputMap::new([(KeyCode::F, Action::PayRespects)]),
+            ..Default::default()
+        })
+        .insert(Player)
+        .id();
+    app.world
+        .spawn((NodeBundle::default(), RebindingMenu::<Action>::new(player)));
+    app.update();
+
+    let label = |app: &mut App| {
+        let mut label_query = app
+            .world
+            .query_filtered::<&Text, With<BindingLabel<Action>>>();
+        label_query.single(&app.world).sections[0].value.clone()
+    };
+    assert_eq!(label(&mut app), "F");
+
+    app.click_button::<RebindButton<Action>>();
+    app.update();
+    assert!(app
+        .world
+        .resource::<BindingCapture<Action>>()
+        .is_capturing());
+    assert_eq!(label(&mut app), "Press a button...");
+
+    app.reset_inputs();
+    app.send_input(KeyCode::G);
+    app.update();
+    app.release_input(KeyCode::G);
+    app.update();
+
+    let input_map = app.world.get::<InputMap<Action>>(player).unwrap();
+    let bindings: Vec<_> = input_map.get(Action::PayRespects).iter().cloned().collect();
+    assert_eq!(bindings, [UserInput::from(KeyCode::G)]);
+    assert_eq!(label(&mut app), "G");
+}
+
+#[test]
+fn actions_without_timing() {
+    use bevy::input::InputPlugin;
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default().without_timing())
+        .insert_resource(InputMap::<Action>::new([(KeyCode::F, Action::PayRespects)]))
+        .init_resource::<ActionState<Action>>();
+    app.update();
+
+    app.send_input(KeyCode::F);
+    app.update();
+    assert!(app
+        .world
+        .resource::<ActionState<Action>>()
+        .just_pressed(Action::PayRespects));
+
+    app.update();
+    let action_state = app.world.resource::<ActionState<Action>>();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!action_state.just_pressed(Action::PayRespects));
+    // No timing is stored or advanced
+    assert!(app.world.get_resource::<ActionTimings<Action>>().is_none());
+}
+
+#[test]
+fn custom_worlds() {
+    use bevy::ecs::schedule::Schedule;
+    use bevy::input::Input;
+    use leafwing_input_manager::systems::{tick_action_state, update_action_state};
+
+    let mut world = World::new();
+    InputManagerPlugin::<Action>::default().init_resources(&mut world);
+    world.init_resource::<Time>();
+
+    let mut keycodes = Input::<KeyCode>::default();
+    keycodes.press(KeyCode::F);
+    world.insert_resource(keycodes);
+    let player = world
+        .spawn(InputManagerBundle::<Action> {
+            input_map: InputMap::new([(KeyCode::F, Action::PayRespects)]),
+            ..Default::default()
+        })
+        .id();
+
+    // No gamepad resources are needed
+    let mut schedule = Schedule::new();
+    schedule.add_systems((tick_action_state::<Action>, update_action_state::<Action>).chain());
+    schedule.run(&mut world);
+
+    let action_state = world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.just_pressed(Action::PayRespects));
+}
+
+#[test]
+fn headless_sub_apps() {
+    let mut sub_app = App::new();
+    sub_app
+        .add_plugins(MinimalPlugins)
+        .add_plugins(InputManagerPlugin::<Action>::default());
+    // Simulated entities have no input map, and are driven by the simulation
+    let player = sub_app.world.spawn(ActionState::<Action>::default()).id();
+
+    // Bevy's input resources are not needed
+    sub_app.update();
+    sub_app
+        .world
+        .get_mut::<ActionState<Action>>(player)
+        .unwrap()
+        .press(Action::PayRespects);
+    sub_app.update();
+
+    let action_state = sub_app.world.get::<ActionState<Action>>(player).unwrap();
+    assert!(action_state.pressed(Action::PayRespects));
+    assert!(!action_state.just_pressed(Action::PayRespects));
+}
+
+#[test]
+fn unbound_inputs_are_detected() {
+    use bevy::ecs::event::Events;
+    use bevy::input::InputPlugin;
+    use leafwing_input_manager::idle::AnyInputDetected;
+    use leafwing_input_manager::input_map::InputSource;
+
+    #[derive(Actionlike, Clone, Copy, PartialEq, Eq, Hash, Debug, Reflect)]
+    enum MenuAction {
+        Confirm,
+    }
+
+    let mut app = App::new();
+    app.add_plugins(MinimalPlugins)
+        .add_plugins(InputPlugin)
+        .add_plugins(InputManagerPlugin::<Action>::default())
+        .add_plugins(InputManagerPlugin::<MenuAction>::default())
+        .add_systems(Startup, spawn_player);
+    app.update();
+    app.world.resource_mut::<Events<AnyInputDetected>>().clear();
+
+    // None of these inputs are bound, and they are coalesced into a single event for every action type
+    app.send_input(KeyCode::Q);
+    app.send_input(KeyCode::E);
+    app.send_input(MouseButton::Right);
+    app.update();
+    let detected: Vec<AnyInputDetected> = app
+        .world
+        .resource_mut::<Events<AnyInputDetected>>()
+        .drain()
+        .collect();
+    assert_eq!(
+        detected,
+        [AnyInputDetected {
+            sources: vec![InputSource::Keyboard, InputSource::Mouse]
+        }]
+    );
+
+    // Holding the keys does not send more events
+    app.update();
+    assert!(app.world.resource::<Events<AnyInputDetected>>().is_empty());
+}
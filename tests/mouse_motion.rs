@@ -74,6 +74,8 @@ fn mouse_motion_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
 
@@ -94,6 +96,8 @@ fn mouse_motion_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
         y: SingleAxis {
@@ -101,6 +105,8 @@ fn mouse_motion_dual_axis_mocking() {
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
     };
@@ -169,6 +175,8 @@ fn mouse_motion_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -182,6 +190,8 @@ fn mouse_motion_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -195,6 +205,8 @@ fn mouse_motion_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -208,6 +220,8 @@ fn mouse_motion_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -222,6 +236,8 @@ fn mouse_motion_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -235,6 +251,8 @@ fn mouse_motion_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -331,3 +349,102 @@ fn mouse_drag() {
         Some(DualAxisData::new(5.0, 0.0))
     );
 }
+
+#[test]
+fn mouse_drag_input() {
+    let mut app = test_app();
+    app.insert_resource(InputMap::new([(
+        MouseDrag::new(MouseButton::Middle),
+        AxislikeTestAction::XY,
+    )]));
+
+    // Motion without the button held is ignored
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        5.0,
+        0.0,
+    ));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.released(AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY),
+        Some(DualAxisData::new(0.0, 0.0))
+    );
+
+    app.send_input(MouseButton::Middle);
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        -4.0,
+    ));
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::XY));
+    assert_eq!(action_state.value(AxislikeTestAction::XY), 5.0);
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY),
+        Some(DualAxisData::new(3.0, -4.0))
+    );
+
+    // Holding the button without moving reports no motion
+    app.update();
+
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert!(action_state.pressed(AxislikeTestAction::XY));
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY),
+        Some(DualAxisData::new(0.0, 0.0))
+    );
+}
+
+#[test]
+fn mouse_motion_sensitivity_group() {
+    let mut app = test_app();
+    let mut input_map = InputMap::new([
+        (DualAxis::mouse_motion(), AxislikeTestAction::XY),
+        (DualAxis::mouse_motion(), AxislikeTestAction::X),
+    ]);
+    input_map.assign_sensitivity_group(DualAxis::mouse_motion(), "look");
+    app.insert_resource(input_map);
+
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    // Groups without a sensitivity do not scale their bindings
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    assert_eq!(
+        action_state.axis_pair(AxislikeTestAction::XY).unwrap(),
+        DualAxisData::new(3.0, 4.0)
+    );
+
+    app.world
+        .resource_mut::<InputMap<AxislikeTestAction>>()
+        .set_sensitivity("look", 2.0);
+    app.send_input(DualAxis::from_value(
+        MouseMotionAxisType::X,
+        MouseMotionAxisType::Y,
+        3.0,
+        4.0,
+    ));
+    app.update();
+
+    // Every binding in the group is scaled
+    let action_state = app.world.resource::<ActionState<AxislikeTestAction>>();
+    for action in [AxislikeTestAction::XY, AxislikeTestAction::X] {
+        assert_eq!(
+            action_state.axis_pair(action).unwrap(),
+            DualAxisData::new(6.0, 8.0)
+        );
+        assert_eq!(action_state.value(action), 10.0);
+    }
+}
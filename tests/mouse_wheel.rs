@@ -75,6 +75,8 @@ fn mouse_wheel_single_axis_mocking() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
 
@@ -95,6 +97,8 @@ fn mouse_wheel_dual_axis_mocking() {
             value: Some(1.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
         y: SingleAxis {
@@ -102,6 +106,8 @@ fn mouse_wheel_dual_axis_mocking() {
             value: Some(0.),
             positive_low: 0.0,
             negative_low: 0.0,
+            positive_high: f32::MAX,
+            negative_high: f32::MIN,
             inverted: false,
         },
     };
@@ -170,6 +176,8 @@ fn mouse_wheel_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -183,6 +191,8 @@ fn mouse_wheel_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -196,6 +206,8 @@ fn mouse_wheel_single_axis() {
         value: Some(1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -209,6 +221,8 @@ fn mouse_wheel_single_axis() {
         value: Some(-1.),
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -223,6 +237,8 @@ fn mouse_wheel_single_axis() {
         // Usually a small deadzone threshold will be set
         positive_low: 0.1,
         negative_low: 0.1,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -236,6 +252,8 @@ fn mouse_wheel_single_axis() {
         value: None,
         positive_low: 0.0,
         negative_low: 0.0,
+        positive_high: f32::MAX,
+        negative_high: f32::MIN,
         inverted: false,
     };
     app.send_input(input);
@@ -0,0 +1,157 @@
+//! Property-based tests of the invariants that the [`ActionState`] state machine upholds,
+//! whatever sequence of operations it is driven by
+
+use bevy::prelude::Reflect;
+use bevy::utils::{Duration, Instant};
+use leafwing_input_manager::action_state::ActionData;
+use leafwing_input_manager::buttonlike::ButtonState;
+use leafwing_input_manager::hold_for::{HoldFor, HoldRequirements};
+use leafwing_input_manager::prelude::*;
+use leafwing_input_manager::timing::ActionTimings;
+use proptest::prelude::*;
+
+#[derive(Actionlike, Clone, Copy, PartialEq, Eq, Debug, Reflect)]
+enum Action {
+    Run,
+    Jump,
+    Hide,
+}
+
+/// A single operation applied to an [`ActionState`]
+#[derive(Debug, Clone)]
+enum Operation {
+    Press(Action),
+    Release(Action),
+    Consume(Action),
+    Reset(Action),
+    Disable(Action),
+    Enable(Action),
+    /// Updates every action from inputs, pressing those whose flag is set
+    Update([bool; 3]),
+    /// Advances time by the number of milliseconds
+    Tick(u64),
+}
+
+fn action() -> impl Strategy<Value = Action> {
+    prop_oneof![Just(Action::Run), Just(Action::Jump), Just(Action::Hide)]
+}
+
+fn operation() -> impl Strategy<Value = Operation> {
+    prop_oneof![
+        action().prop_map(Operation::Press),
+        action().prop_map(Operation::Release),
+        action().prop_map(Operation::Consume),
+        action().prop_map(Operation::Reset),
+        action().prop_map(Operation::Disable),
+        action().prop_map(Operation::Enable),
+        any::<[bool; 3]>().prop_map(Operation::Update),
+        (0..500_u64).prop_map(Operation::Tick),
+    ]
+}
+
+/// Asserts the invariants that must hold for every action after any operation
+fn assert_invariants(
+    action_state: &ActionState<Action>,
+    timings: &ActionTimings<Action>,
+    holds: &HoldRequirements<Action>,
+) {
+    for action in Action::variants() {
+        let action_data = action_state.action_data(action);
+
+        assert_ne!(
+            action_state.pressed(action),
+            action_state.released(action),
+            "{action:?} must be either pressed or released"
+        );
+        if action_state.just_pressed(action) {
+            assert!(action_state.pressed(action), "{action:?} was just pressed");
+        }
+        if action_state.just_released(action) {
+            assert!(
+                action_state.released(action),
+                "{action:?} was just released"
+            );
+        }
+        if action_data.consumed {
+            assert!(action_state.released(action), "{action:?} is consumed");
+        }
+        if action_data.disabled {
+            assert!(action_state.released(action), "{action:?} is disabled");
+            assert_eq!(action_state.value(action), 0.0);
+        }
+
+        let hold_progress = holds.progress(action_state, timings, action);
+        assert!((0.0..=1.0).contains(&hold_progress));
+    }
+}
+
+proptest! {
+    #[test]
+    fn invariants_hold_for_any_operations(operations in prop::collection::vec(operation(), 0..64)) {
+        let mut action_state = ActionState::<Action>::default();
+        let mut timings = ActionTimings::<Action>::default();
+        let mut holds = HoldRequirements::<Action>::default();
+        holds.set(Action::Jump, Some(HoldFor::new(Duration::from_millis(200))));
+
+        let mut now = Instant::now();
+        for operation in operations {
+            match operation {
+                Operation::Press(action) => action_state.press(action),
+                Operation::Release(action) => action_state.release(action),
+                Operation::Consume(action) => action_state.consume(action),
+                Operation::Reset(action) => {
+                    action_state.reset(action);
+                    timings.reset(action);
+                }
+                Operation::Disable(action) => action_state.disable(action),
+                Operation::Enable(action) => action_state.enable(action),
+                Operation::Update(pressed) => {
+                    let action_data = pressed
+                        .into_iter()
+                        .map(|pressed| ActionData {
+                            state: if pressed {
+                                ButtonState::JustPressed
+                            } else {
+                                ButtonState::Released
+                            },
+                            value: if pressed { 1.0 } else { 0.0 },
+                            ..Default::default()
+                        })
+                        .collect();
+                    action_state.update(action_data);
+                }
+                Operation::Tick(millis) => {
+                    let previous = now;
+                    now += Duration::from_millis(millis);
+
+                    let before: Vec<(bool, Duration, bool)> = Action::variants()
+                        .map(|action| {
+                            (
+                                action_state.pressed(action),
+                                timings.current_duration(&action_state, action),
+                                holds.completed(&action_state, &timings, action),
+                            )
+                        })
+                        .collect();
+                    action_state.tick_with_timings(&mut timings, now, previous);
+                    holds.tick(&action_state, &timings, now, previous);
+
+                    // Ticking never presses or releases actions, and their durations only grow between ticks
+                    for (action, (was_pressed, duration, was_completed)) in Action::variants().zip(before) {
+                        prop_assert_eq!(action_state.pressed(action), was_pressed);
+                        prop_assert!(timings.current_duration(&action_state, action) >= duration);
+                        prop_assert!(!action_state.just_pressed(action));
+                        prop_assert!(!action_state.just_released(action));
+                        // Holds complete exactly once, during the tick that reaches their duration
+                        prop_assert_eq!(
+                            holds.just_completed(action),
+                            holds.completed(&action_state, &timings, action) && !was_completed
+                        );
+                    }
+                }
+            }
+
+            assert_invariants(&action_state, &timings, &holds);
+        }
+    }
+}
@@ -76,9 +76,12 @@ fn main() {
 
     if what_to_run.contains(Check::TEST) {
         // Run tests (except doc tests and without building examples)
-        cmd!(sh, "cargo test --workspace --lib --bins --tests --benches")
-            .run()
-            .expect("Please fix failing tests in output above.");
+        cmd!(
+            sh,
+            "cargo test --workspace --lib --bins --tests --benches --features bench,stats,asset"
+        )
+        .run()
+        .expect("Please fix failing tests in output above.");
     }
 
     if what_to_run.contains(Check::DOC_TEST) {